@@ -0,0 +1,295 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::translate::*;
+use smallvec::SmallVec;
+
+use crate::{AudioChannelMixerFlags, AudioChannelPosition, ffi};
+
+#[derive(Debug)]
+#[doc(alias = "GstAudioChannelMixer")]
+pub struct AudioChannelMixer {
+    ptr: ptr::NonNull<ffi::GstAudioChannelMixer>,
+    flags: AudioChannelMixerFlags,
+    format: crate::AudioFormat,
+    in_channels: u32,
+    out_channels: u32,
+}
+
+impl Drop for AudioChannelMixer {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_audio_channel_mixer_free(self.ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for AudioChannelMixer {}
+unsafe impl Sync for AudioChannelMixer {}
+
+impl AudioChannelMixer {
+    fn check_format(format: crate::AudioFormat) -> Result<(), glib::BoolError> {
+        if [
+            crate::AUDIO_FORMAT_S16,
+            crate::AUDIO_FORMAT_S32,
+            crate::AUDIO_FORMAT_F32,
+            crate::AUDIO_FORMAT_F64,
+        ]
+        .contains(&format)
+        {
+            Ok(())
+        } else {
+            Err(glib::bool_error!("Unsupported format {}", format))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a mixer that converts between the given channel layouts.
+    #[doc(alias = "gst_audio_channel_mixer_new")]
+    pub fn new(
+        flags: AudioChannelMixerFlags,
+        format: crate::AudioFormat,
+        in_positions: &[AudioChannelPosition],
+        out_positions: &[AudioChannelPosition],
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        Self::check_format(format)?;
+        if in_positions.is_empty()
+            || in_positions.len() > 64
+            || out_positions.is_empty()
+            || out_positions.len() > 64
+        {
+            return Err(glib::bool_error!("Invalid number of channels"));
+        }
+
+        unsafe {
+            let ptr = ffi::gst_audio_channel_mixer_new(
+                flags.into_glib(),
+                format.into_glib(),
+                in_positions.len() as i32,
+                in_positions.as_ptr() as *mut _,
+                out_positions.len() as i32,
+                out_positions.as_ptr() as *mut _,
+            );
+            if ptr.is_null() {
+                Err(glib::bool_error!("Failed to create audio channel mixer"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    flags,
+                    format,
+                    in_channels: in_positions.len() as u32,
+                    out_channels: out_positions.len() as u32,
+                })
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a mixer from an explicit matrix.
+    ///
+    /// `matrix` has one row per input channel with one coefficient per output
+    /// channel.
+    #[doc(alias = "gst_audio_channel_mixer_new_with_matrix")]
+    pub fn with_matrix(
+        flags: AudioChannelMixerFlags,
+        format: crate::AudioFormat,
+        matrix: &[impl AsRef<[f32]>],
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        Self::check_format(format)?;
+        let in_channels = matrix.len();
+        let out_channels = matrix.first().map(|row| row.as_ref().len()).unwrap_or(0);
+        if in_channels == 0 || in_channels > 64 || out_channels == 0 || out_channels > 64 {
+            return Err(glib::bool_error!("Invalid number of channels"));
+        }
+        if matrix.iter().any(|row| row.as_ref().len() != out_channels) {
+            return Err(glib::bool_error!(
+                "All matrix rows must have the same length"
+            ));
+        }
+
+        unsafe {
+            // The mixer takes ownership of the matrix and frees it with g_free()
+            let rows = glib::ffi::g_malloc_n(in_channels, std::mem::size_of::<*mut f32>())
+                as *mut *mut f32;
+            for (i, row) in matrix.iter().enumerate() {
+                let row = row.as_ref();
+                let ptr =
+                    glib::ffi::g_malloc_n(out_channels, std::mem::size_of::<f32>()) as *mut f32;
+                ptr::copy_nonoverlapping(row.as_ptr(), ptr, out_channels);
+                *rows.add(i) = ptr;
+            }
+
+            let ptr = ffi::gst_audio_channel_mixer_new_with_matrix(
+                flags.into_glib(),
+                format.into_glib(),
+                in_channels as i32,
+                out_channels as i32,
+                rows,
+            );
+            if ptr.is_null() {
+                // Ownership of the matrix was not taken over
+                for i in 0..in_channels {
+                    glib::ffi::g_free(*rows.add(i) as glib::ffi::gpointer);
+                }
+                glib::ffi::g_free(rows as glib::ffi::gpointer);
+
+                Err(glib::bool_error!("Failed to create audio channel mixer"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    flags,
+                    format,
+                    in_channels: in_channels as u32,
+                    out_channels: out_channels as u32,
+                })
+            }
+        }
+    }
+
+    #[doc(alias = "gst_audio_channel_mixer_is_passthrough")]
+    pub fn is_passthrough(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_audio_channel_mixer_is_passthrough(
+                self.ptr.as_ptr(),
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Mixes all frames of `input` into `output`.
+    ///
+    /// Interleaved data is passed as a single plane, non-interleaved data as
+    /// one plane per channel. Both must contain the same number of frames.
+    /// Returns the number of frames that were mixed.
+    #[doc(alias = "gst_audio_channel_mixer_samples")]
+    pub fn samples(
+        &self,
+        input: &[impl AsRef<[u8]>],
+        output: &mut [impl AsMut<[u8]>],
+    ) -> Result<usize, glib::BoolError> {
+        let in_frames = crate::audio_resampler::n_frames(
+            input,
+            self.format,
+            self.in_channels,
+            self.flags
+                .contains(AudioChannelMixerFlags::NON_INTERLEAVED_IN),
+        )?;
+
+        let mut out_planes = output
+            .iter_mut()
+            .map(|plane| plane.as_mut())
+            .collect::<SmallVec<[&mut [u8]; 8]>>();
+        let out_frames = crate::audio_resampler::n_frames(
+            &out_planes,
+            self.format,
+            self.out_channels,
+            self.flags
+                .contains(AudioChannelMixerFlags::NON_INTERLEAVED_OUT),
+        )?;
+
+        if in_frames != out_frames {
+            return Err(glib::bool_error!(
+                "Input and output have different number of frames: {} != {}",
+                in_frames,
+                out_frames
+            ));
+        }
+        if in_frames > i32::MAX as usize {
+            return Err(glib::bool_error!("Too many frames"));
+        }
+
+        let in_ptrs = input
+            .iter()
+            .map(|plane| plane.as_ref().as_ptr() as glib::ffi::gconstpointer)
+            .collect::<SmallVec<[_; 8]>>();
+        let mut out_ptrs = out_planes
+            .iter_mut()
+            .map(|plane| plane.as_mut_ptr() as glib::ffi::gpointer)
+            .collect::<SmallVec<[_; 8]>>();
+
+        unsafe {
+            ffi::gst_audio_channel_mixer_samples(
+                self.ptr.as_ptr(),
+                in_ptrs.as_ptr() as *const glib::ffi::gpointer,
+                out_ptrs.as_mut_ptr(),
+                in_frames as i32,
+            );
+        }
+
+        Ok(in_frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples_f32(data: &[u8]) -> Vec<f32> {
+        data.chunks_exact(4)
+            .map(|v| f32::from_ne_bytes(v.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_matrix() {
+        gst::init().unwrap();
+
+        let mixer = AudioChannelMixer::with_matrix(
+            AudioChannelMixerFlags::UNPOSITIONED_IN | AudioChannelMixerFlags::UNPOSITIONED_OUT,
+            crate::AUDIO_FORMAT_F32,
+            &[[0.5f32], [0.25]],
+        )
+        .unwrap();
+        assert!(!mixer.is_passthrough());
+
+        let input = [1.0f32, 1.0, 0.5, -1.0]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect::<Vec<_>>();
+        let mut output = [vec![0u8; 8]];
+        assert_eq!(mixer.samples(&[&input], &mut output).unwrap(), 2);
+        assert_eq!(samples_f32(&output[0]), [0.75, 0.0]);
+
+        let mut output = [vec![0u8; 12]];
+        assert!(mixer.samples(&[&input], &mut output).is_err());
+    }
+
+    #[test]
+    fn test_positions() {
+        gst::init().unwrap();
+
+        let stereo = [
+            AudioChannelPosition::FrontLeft,
+            AudioChannelPosition::FrontRight,
+        ];
+        let mixer = AudioChannelMixer::new(
+            AudioChannelMixerFlags::empty(),
+            crate::AUDIO_FORMAT_F32,
+            &stereo,
+            &stereo,
+        )
+        .unwrap();
+        assert!(mixer.is_passthrough());
+
+        let mixer = AudioChannelMixer::new(
+            AudioChannelMixerFlags::NON_INTERLEAVED_OUT,
+            crate::AUDIO_FORMAT_F32,
+            &[AudioChannelPosition::Mono],
+            &stereo,
+        )
+        .unwrap();
+        assert!(!mixer.is_passthrough());
+
+        let input = 1.0f32.to_ne_bytes();
+        let mut output = [vec![0u8; 4], vec![0u8; 4]];
+        assert_eq!(mixer.samples(&[&input], &mut output).unwrap(), 1);
+        assert!(samples_f32(&output[0])[0] > 0.0);
+        assert_eq!(samples_f32(&output[0]), samples_f32(&output[1]));
+    }
+}
@@ -6,6 +6,7 @@ use crate::ffi;
 use glib::{Type, prelude::*, translate::*, value::FromValue};
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 #[doc(alias = "GstAudioChannelPosition")]
 #[repr(i32)]
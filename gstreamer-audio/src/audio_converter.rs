@@ -1,8 +1,308 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::ops;
+use std::{ops, ptr, slice};
 
-use glib::prelude::*;
+use glib::{prelude::*, translate::*};
+
+use crate::{AudioConverterFlags, ffi};
+
+#[derive(Debug)]
+#[doc(alias = "GstAudioConverter")]
+pub struct AudioConverter {
+    ptr: ptr::NonNull<ffi::GstAudioConverter>,
+    in_info: crate::AudioInfo,
+    out_info: crate::AudioInfo,
+}
+
+impl Drop for AudioConverter {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_audio_converter_free(self.ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for AudioConverter {}
+unsafe impl Sync for AudioConverter {}
+
+impl AudioConverter {
+    #[doc(alias = "gst_audio_converter_new")]
+    pub fn new(
+        flags: AudioConverterFlags,
+        in_info: &crate::AudioInfo,
+        out_info: &crate::AudioInfo,
+        config: Option<AudioConverterConfig>,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        unsafe {
+            let ptr = ffi::gst_audio_converter_new(
+                flags.into_glib(),
+                in_info.to_glib_none().0 as *mut _,
+                out_info.to_glib_none().0 as *mut _,
+                config
+                    .map(|s| s.0.into_glib_ptr())
+                    .unwrap_or(ptr::null_mut()),
+            );
+            if ptr.is_null() {
+                Err(glib::bool_error!("Failed to create audio converter"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    in_info: in_info.clone(),
+                    out_info: out_info.clone(),
+                })
+            }
+        }
+    }
+
+    pub fn in_info(&self) -> &crate::AudioInfo {
+        &self.in_info
+    }
+
+    pub fn out_info(&self) -> &crate::AudioInfo {
+        &self.out_info
+    }
+
+    #[doc(alias = "get_config")]
+    #[doc(alias = "gst_audio_converter_get_config")]
+    pub fn config(&self) -> AudioConverterConfig {
+        unsafe {
+            AudioConverterConfig(
+                gst::StructureRef::from_glib_borrow(ffi::gst_audio_converter_get_config(
+                    self.ptr.as_ptr(),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                ))
+                .to_owned(),
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the current input and output sample rate.
+    #[doc(alias = "gst_audio_converter_get_config")]
+    pub fn rates(&self) -> (u32, u32) {
+        unsafe {
+            let mut in_rate = 0;
+            let mut out_rate = 0;
+            ffi::gst_audio_converter_get_config(self.ptr.as_ptr(), &mut in_rate, &mut out_rate);
+            (in_rate as u32, out_rate as u32)
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Changes the sample rates and/or configuration.
+    ///
+    /// A rate of 0 keeps the current rate. Changing the rates is only possible
+    /// if the converter was created with [`AudioConverterFlags::VARIABLE_RATE`].
+    #[doc(alias = "gst_audio_converter_update_config")]
+    pub fn update_config(
+        &mut self,
+        in_rate: u32,
+        out_rate: u32,
+        config: Option<AudioConverterConfig>,
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_converter_update_config(
+                    self.ptr.as_ptr(),
+                    in_rate as i32,
+                    out_rate as i32,
+                    config
+                        .map(|s| s.0.into_glib_ptr())
+                        .unwrap_or(ptr::null_mut()),
+                ),
+                "Failed to update audio converter configuration"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_converter_reset")]
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::gst_audio_converter_reset(self.ptr.as_ptr());
+        }
+    }
+
+    #[doc(alias = "get_in_frames")]
+    #[doc(alias = "gst_audio_converter_get_in_frames")]
+    pub fn in_frames(&self, out_frames: usize) -> usize {
+        unsafe { ffi::gst_audio_converter_get_in_frames(self.ptr.as_ptr(), out_frames) }
+    }
+
+    #[doc(alias = "get_out_frames")]
+    #[doc(alias = "gst_audio_converter_get_out_frames")]
+    pub fn out_frames(&self, in_frames: usize) -> usize {
+        unsafe { ffi::gst_audio_converter_get_out_frames(self.ptr.as_ptr(), in_frames) }
+    }
+
+    #[doc(alias = "get_max_latency")]
+    #[doc(alias = "gst_audio_converter_get_max_latency")]
+    pub fn max_latency(&self) -> usize {
+        unsafe { ffi::gst_audio_converter_get_max_latency(self.ptr.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_audio_converter_supports_inplace")]
+    pub fn supports_inplace(&self) -> bool {
+        unsafe { from_glib(ffi::gst_audio_converter_supports_inplace(self.ptr.as_ptr())) }
+    }
+
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_audio_converter_is_passthrough")]
+    pub fn is_passthrough(&self) -> bool {
+        unsafe { from_glib(ffi::gst_audio_converter_is_passthrough(self.ptr.as_ptr())) }
+    }
+
+    // The rate can change with `update_config()` so it's not compared here
+    fn check_info(
+        expected: &crate::AudioInfo,
+        info: &crate::AudioInfo,
+    ) -> Result<(), glib::BoolError> {
+        if expected.format() != info.format()
+            || expected.channels() != info.channels()
+            || expected.layout() != info.layout()
+        {
+            return Err(glib::bool_error!(
+                "Audio buffer does not match the converter configuration"
+            ));
+        }
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts all samples of `in_buffer` into `out_buffer`.
+    ///
+    /// `out_buffer` must have space for at least [`Self::out_frames`] of
+    /// the number of input samples. Returns the number of samples written
+    /// to `out_buffer`.
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_audio_converter_samples")]
+    pub fn samples<T>(
+        &mut self,
+        flags: AudioConverterFlags,
+        in_buffer: &crate::AudioBuffer<T>,
+        out_buffer: &mut crate::AudioBuffer<crate::audio_buffer::Writable>,
+    ) -> Result<usize, glib::BoolError> {
+        Self::check_info(&self.in_info, in_buffer.info())?;
+        Self::check_info(&self.out_info, out_buffer.info())?;
+
+        let in_frames = in_buffer.n_samples();
+        let out_frames = self.out_frames(in_frames);
+        if out_buffer.n_samples() < out_frames {
+            return Err(glib::bool_error!(
+                "Output buffer too small: {} < {} samples",
+                out_buffer.n_samples(),
+                out_frames
+            ));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_converter_samples(
+                    self.ptr.as_ptr(),
+                    // The input is not writable
+                    (flags - AudioConverterFlags::IN_WRITABLE).into_glib(),
+                    (*in_buffer.as_ptr()).planes,
+                    in_frames,
+                    (*out_buffer.as_mut_ptr()).planes,
+                    out_frames,
+                ),
+                "Failed to convert samples"
+            )?;
+        }
+
+        Ok(out_frames)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts the samples of `buffer` in place.
+    ///
+    /// This is only possible if [`Self::supports_inplace`] returns `true`.
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_audio_converter_samples")]
+    pub fn samples_inplace(
+        &mut self,
+        flags: AudioConverterFlags,
+        buffer: &mut crate::AudioBuffer<crate::audio_buffer::Writable>,
+    ) -> Result<(), glib::BoolError> {
+        if !self.supports_inplace() {
+            return Err(glib::bool_error!(
+                "Audio converter does not support in-place conversion"
+            ));
+        }
+        Self::check_info(&self.in_info, buffer.info())?;
+
+        let frames = buffer.n_samples();
+        unsafe {
+            let planes = (*buffer.as_mut_ptr()).planes;
+            glib::result_from_gboolean!(
+                ffi::gst_audio_converter_samples(
+                    self.ptr.as_ptr(),
+                    (flags | AudioConverterFlags::IN_WRITABLE).into_glib(),
+                    planes,
+                    frames,
+                    planes,
+                    frames,
+                ),
+                "Failed to convert samples"
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts interleaved samples from `input` into a newly allocated
+    /// output.
+    #[doc(alias = "gst_audio_converter_convert")]
+    pub fn convert(
+        &mut self,
+        flags: AudioConverterFlags,
+        input: &[u8],
+    ) -> Result<Vec<u8>, glib::BoolError> {
+        if self.in_info.layout() != crate::AudioLayout::Interleaved
+            || self.out_info.layout() != crate::AudioLayout::Interleaved
+        {
+            return Err(glib::bool_error!("Only interleaved audio can be converted"));
+        }
+        if input.len() % self.in_info.bpf() as usize != 0 {
+            return Err(glib::bool_error!(
+                "Input is not a multiple of the frame size"
+            ));
+        }
+
+        unsafe {
+            let mut out = ptr::null_mut::<u8>();
+            // FIXME: Workaround for gstreamer-audio-sys having the wrong type for this parameter
+            let out_ptr = &mut out as *mut *mut u8 as *mut u8;
+            let mut out_size = 0;
+            glib::result_from_gboolean!(
+                ffi::gst_audio_converter_convert(
+                    self.ptr.as_ptr(),
+                    (flags - AudioConverterFlags::IN_WRITABLE).into_glib(),
+                    mut_override(input.as_ptr()) as glib::ffi::gpointer,
+                    input.len(),
+                    out_ptr,
+                    &mut out_size,
+                ),
+                "Failed to convert samples"
+            )?;
+
+            let output = if out_size == 0 {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(out, out_size).to_vec()
+            };
+            glib::ffi::g_free(out as glib::ffi::gpointer);
+
+            Ok(output)
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AudioConverterConfig(gst::Structure);
@@ -223,4 +523,93 @@ mod tests {
 
         config.set_mix_matrix(&matrix);
     }
+
+    #[test]
+    fn test_convert() {
+        gst::init().unwrap();
+
+        let in_info = crate::AudioInfo::builder(crate::AudioFormat::S16le, 48000, 2)
+            .build()
+            .unwrap();
+        let out_info = crate::AudioInfo::builder(crate::AudioFormat::F32le, 48000, 1)
+            .build()
+            .unwrap();
+        let mut config = AudioConverterConfig::new();
+        config.set_mix_matrix(&[[0.5f32, 0.5]]);
+
+        let mut converter = AudioConverter::new(
+            AudioConverterFlags::empty(),
+            &in_info,
+            &out_info,
+            Some(config),
+        )
+        .unwrap();
+        assert!(!converter.supports_inplace());
+        assert_eq!(converter.out_frames(100), 100);
+        assert_eq!(converter.rates(), (48000, 48000));
+
+        let input = [i16::MAX, 0, 0, i16::MIN]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+        let output = converter
+            .convert(AudioConverterFlags::empty(), &input)
+            .unwrap();
+        assert_eq!(output.len(), 8);
+        let samples = output
+            .chunks_exact(4)
+            .map(|v| f32::from_le_bytes(v.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert!((samples[0] - 0.5).abs() < 0.01);
+        assert!((samples[1] + 0.5).abs() < 0.01);
+    }
+
+    #[cfg(feature = "v1_16")]
+    #[test]
+    fn test_samples_resample() {
+        gst::init().unwrap();
+
+        let in_info = crate::AudioInfo::builder(crate::AudioFormat::F32le, 48000, 2)
+            .layout(crate::AudioLayout::NonInterleaved)
+            .build()
+            .unwrap();
+        let out_info = crate::AudioInfo::builder(crate::AudioFormat::S16le, 24000, 2)
+            .build()
+            .unwrap();
+
+        let mut converter =
+            AudioConverter::new(AudioConverterFlags::empty(), &in_info, &out_info, None).unwrap();
+        let out_frames = converter.out_frames(480);
+        assert!(out_frames <= 240);
+
+        let in_buffer = crate::AudioBuffer::from_buffer_readable(
+            gst::Buffer::with_size(480 * in_info.bpf() as usize).unwrap(),
+            &in_info,
+        )
+        .unwrap();
+        let mut out_buffer = crate::AudioBuffer::from_buffer_writable(
+            gst::Buffer::with_size(240 * out_info.bpf() as usize).unwrap(),
+            &out_info,
+        )
+        .unwrap();
+
+        assert_eq!(
+            converter
+                .samples(AudioConverterFlags::empty(), &in_buffer, &mut out_buffer)
+                .unwrap(),
+            out_frames
+        );
+
+        // Output buffer with the input format
+        let mut wrong_buffer = crate::AudioBuffer::from_buffer_writable(
+            gst::Buffer::with_size(240 * in_info.bpf() as usize).unwrap(),
+            &in_info,
+        )
+        .unwrap();
+        assert!(
+            converter
+                .samples(AudioConverterFlags::empty(), &in_buffer, &mut wrong_buffer)
+                .is_err()
+        );
+    }
 }
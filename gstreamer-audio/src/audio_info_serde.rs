@@ -0,0 +1,140 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{AudioChannelPosition, AudioFlags, AudioFormat, AudioInfo, AudioLayout};
+
+// The format and layout use the same strings as in the caps so that an `AudioInfo` serialized
+// from caps converts back to the very same caps.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct AudioInfoSerde {
+    format: String,
+    rate: u32,
+    channels: u32,
+    layout: String,
+    flags: AudioFlags,
+    positions: Option<Vec<AudioChannelPosition>>,
+}
+
+impl Serialize for AudioInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AudioInfoSerde {
+            format: self.format().to_str().to_string(),
+            rate: self.rate(),
+            channels: self.channels(),
+            layout: match self.layout() {
+                AudioLayout::NonInterleaved => "non-interleaved",
+                _ => "interleaved",
+            }
+            .to_owned(),
+            flags: self.flags(),
+            positions: self.positions().map(ToOwned::to_owned),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AudioInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let info = AudioInfoSerde::deserialize(deserializer)?;
+
+        let format = info
+            .format
+            .parse::<AudioFormat>()
+            .map_err(de::Error::custom)?;
+        let layout = match info.layout.as_str() {
+            "interleaved" => AudioLayout::Interleaved,
+            "non-interleaved" => AudioLayout::NonInterleaved,
+            layout => {
+                return Err(de::Error::custom(format!("invalid audio layout {layout}")));
+            }
+        };
+
+        AudioInfo::builder(format, info.rate, info.channels)
+            .positions_if_some(info.positions.as_deref())
+            .flags(info.flags)
+            .layout(layout)
+            .build()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AudioChannelPosition, AudioFlags, AudioFormat, AudioInfo, AudioLayout};
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let info = AudioInfo::builder(AudioFormat::S16le, 48000, 2)
+            .positions(&[
+                AudioChannelPosition::FrontLeft,
+                AudioChannelPosition::FrontRight,
+            ])
+            .build()
+            .unwrap();
+
+        let res = serde_json::to_string(&info).unwrap();
+        assert_eq!(
+            res,
+            concat!(
+                "{",
+                "\"format\":\"S16LE\",",
+                "\"rate\":48000,",
+                "\"channels\":2,",
+                "\"layout\":\"interleaved\",",
+                "\"flags\":\"\",",
+                "\"positions\":[\"FrontLeft\",\"FrontRight\"]",
+                "}"
+            )
+        );
+
+        let info_de: AudioInfo = serde_json::from_str(&res).unwrap();
+        assert_eq!(info_de, info);
+    }
+
+    #[test]
+    fn test_caps_roundtrip() {
+        gst::init().unwrap();
+
+        let positions = [
+            AudioChannelPosition::FrontLeft,
+            AudioChannelPosition::FrontRight,
+            AudioChannelPosition::FrontCenter,
+            AudioChannelPosition::Lfe1,
+            AudioChannelPosition::RearLeft,
+            AudioChannelPosition::RearRight,
+        ];
+        let info = AudioInfo::builder(AudioFormat::F32le, 44100, 6)
+            .positions(&positions)
+            .layout(AudioLayout::NonInterleaved)
+            .build()
+            .unwrap();
+        let caps = info.to_caps().unwrap();
+
+        let res = serde_json::to_string(&AudioInfo::from_caps(&caps).unwrap()).unwrap();
+        let info_de: AudioInfo = serde_json::from_str(&res).unwrap();
+        assert_eq!(info_de.positions(), Some(&positions[..]));
+        assert_eq!(info_de.to_caps().unwrap(), caps);
+
+        let caps = gst::Caps::builder("audio/x-raw")
+            .field("format", "S32LE")
+            .field("rate", 8000i32)
+            .field("channels", 4i32)
+            .field("layout", "interleaved")
+            .field("channel-mask", gst::Bitmask::new(0))
+            .build();
+        let info = AudioInfo::from_caps(&caps).unwrap();
+        assert!(info.flags().contains(AudioFlags::UNPOSITIONED));
+
+        let res = serde_json::to_string(&info).unwrap();
+        let info_de: AudioInfo = serde_json::from_str(&res).unwrap();
+        assert!(info_de.positions().is_none());
+        assert_eq!(info_de.to_caps().unwrap(), info.to_caps().unwrap());
+    }
+}
@@ -0,0 +1,497 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ops, ptr};
+
+use glib::{prelude::*, translate::*};
+use smallvec::SmallVec;
+
+use crate::{AudioResamplerFlags, ffi};
+
+#[derive(Debug)]
+#[doc(alias = "GstAudioResampler")]
+pub struct AudioResampler {
+    ptr: ptr::NonNull<ffi::GstAudioResampler>,
+    flags: AudioResamplerFlags,
+    format: crate::AudioFormat,
+    channels: u32,
+}
+
+impl Drop for AudioResampler {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_audio_resampler_free(self.ptr.as_ptr());
+        }
+    }
+}
+
+unsafe impl Send for AudioResampler {}
+unsafe impl Sync for AudioResampler {}
+
+// Returns the number of frames in the given planes after checking that they
+// match the expected layout.
+pub(crate) fn n_frames<T: AsRef<[u8]>>(
+    planes: &[T],
+    format: crate::AudioFormat,
+    channels: u32,
+    non_interleaved: bool,
+) -> Result<usize, glib::BoolError> {
+    let bps = (crate::AudioFormatInfo::from_format(format).width() / 8) as usize;
+    let (n_planes, bpf) = if non_interleaved {
+        (channels as usize, bps)
+    } else {
+        (1, bps * channels as usize)
+    };
+
+    if planes.len() != n_planes {
+        return Err(glib::bool_error!(
+            "Expected {} planes but got {}",
+            n_planes,
+            planes.len()
+        ));
+    }
+
+    let size = planes[0].as_ref().len();
+    if planes.iter().any(|plane| plane.as_ref().len() != size) {
+        return Err(glib::bool_error!("All planes must have the same size"));
+    }
+    if !size.is_multiple_of(bpf) {
+        return Err(glib::bool_error!("Incomplete number of samples in plane"));
+    }
+
+    Ok(size / bpf)
+}
+
+impl AudioResampler {
+    #[doc(alias = "gst_audio_resampler_new")]
+    pub fn new(
+        method: crate::AudioResamplerMethod,
+        flags: AudioResamplerFlags,
+        format: crate::AudioFormat,
+        channels: u32,
+        in_rate: u32,
+        out_rate: u32,
+        options: Option<AudioResamplerOptions>,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        if ![
+            crate::AUDIO_FORMAT_S16,
+            crate::AUDIO_FORMAT_S32,
+            crate::AUDIO_FORMAT_F32,
+            crate::AUDIO_FORMAT_F64,
+        ]
+        .contains(&format)
+        {
+            return Err(glib::bool_error!("Unsupported format {}", format));
+        }
+        if channels == 0 || in_rate == 0 || out_rate == 0 {
+            return Err(glib::bool_error!("Invalid number of channels or rate"));
+        }
+
+        unsafe {
+            let ptr = ffi::gst_audio_resampler_new(
+                method.into_glib(),
+                flags.into_glib(),
+                format.into_glib(),
+                channels as i32,
+                in_rate as i32,
+                out_rate as i32,
+                // The options are copied by the resampler
+                options
+                    .as_ref()
+                    .map(|s| s.0.to_glib_none().0)
+                    .unwrap_or(ptr::null_mut()),
+            );
+            if ptr.is_null() {
+                Err(glib::bool_error!("Failed to create audio resampler"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    flags,
+                    format,
+                    channels,
+                })
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Changes the rates and/or options of the resampler.
+    ///
+    /// A rate of 0 keeps the current rate. Changing the rates without
+    /// glitches requires [`AudioResamplerFlags::VARIABLE_RATE`].
+    #[doc(alias = "gst_audio_resampler_update")]
+    pub fn update(
+        &mut self,
+        in_rate: u32,
+        out_rate: u32,
+        options: Option<AudioResamplerOptions>,
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_resampler_update(
+                    self.ptr.as_ptr(),
+                    in_rate as i32,
+                    out_rate as i32,
+                    options
+                        .as_ref()
+                        .map(|s| s.0.to_glib_none().0)
+                        .unwrap_or(ptr::null_mut()),
+                ),
+                "Failed to update audio resampler"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_resampler_reset")]
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::gst_audio_resampler_reset(self.ptr.as_ptr());
+        }
+    }
+
+    #[doc(alias = "get_in_frames")]
+    #[doc(alias = "gst_audio_resampler_get_in_frames")]
+    pub fn in_frames(&self, out_frames: usize) -> usize {
+        unsafe { ffi::gst_audio_resampler_get_in_frames(self.ptr.as_ptr(), out_frames) }
+    }
+
+    #[doc(alias = "get_out_frames")]
+    #[doc(alias = "gst_audio_resampler_get_out_frames")]
+    pub fn out_frames(&self, in_frames: usize) -> usize {
+        unsafe { ffi::gst_audio_resampler_get_out_frames(self.ptr.as_ptr(), in_frames) }
+    }
+
+    #[doc(alias = "get_max_latency")]
+    #[doc(alias = "gst_audio_resampler_get_max_latency")]
+    pub fn max_latency(&self) -> usize {
+        unsafe { ffi::gst_audio_resampler_get_max_latency(self.ptr.as_ptr()) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Resamples all frames of `input` into `output`.
+    ///
+    /// Interleaved data is passed as a single plane, non-interleaved data as
+    /// one plane per channel. `output` must have space for at least
+    /// [`Self::out_frames`] of the number of input frames. Returns the number
+    /// of frames written to `output`.
+    #[doc(alias = "gst_audio_resampler_resample")]
+    pub fn resample(
+        &mut self,
+        input: &[impl AsRef<[u8]>],
+        output: &mut [impl AsMut<[u8]>],
+    ) -> Result<usize, glib::BoolError> {
+        let in_frames = n_frames(
+            input,
+            self.format,
+            self.channels,
+            self.flags.contains(AudioResamplerFlags::NON_INTERLEAVED_IN),
+        )?;
+
+        let mut out_planes = output
+            .iter_mut()
+            .map(|plane| plane.as_mut())
+            .collect::<SmallVec<[&mut [u8]; 8]>>();
+        let available = n_frames(
+            &out_planes,
+            self.format,
+            self.channels,
+            self.flags
+                .contains(AudioResamplerFlags::NON_INTERLEAVED_OUT),
+        )?;
+
+        let out_frames = self.out_frames(in_frames);
+        if available < out_frames {
+            return Err(glib::bool_error!(
+                "Output too small: {} < {} frames",
+                available,
+                out_frames
+            ));
+        }
+
+        let mut in_ptrs = input
+            .iter()
+            .map(|plane| plane.as_ref().as_ptr() as glib::ffi::gpointer)
+            .collect::<SmallVec<[_; 8]>>();
+        let mut out_ptrs = out_planes
+            .iter_mut()
+            .map(|plane| plane.as_mut_ptr() as glib::ffi::gpointer)
+            .collect::<SmallVec<[_; 8]>>();
+
+        unsafe {
+            ffi::gst_audio_resampler_resample(
+                self.ptr.as_ptr(),
+                in_ptrs.as_mut_ptr(),
+                in_frames,
+                out_ptrs.as_mut_ptr(),
+                out_frames,
+            );
+        }
+
+        Ok(out_frames)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioResamplerOptions(gst::Structure);
+
+impl ops::Deref for AudioResamplerOptions {
+    type Target = gst::StructureRef;
+
+    #[inline]
+    fn deref(&self) -> &gst::StructureRef {
+        self.0.deref()
+    }
+}
+
+impl ops::DerefMut for AudioResamplerOptions {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut gst::StructureRef {
+        self.0.deref_mut()
+    }
+}
+
+impl AsRef<gst::StructureRef> for AudioResamplerOptions {
+    #[inline]
+    fn as_ref(&self) -> &gst::StructureRef {
+        self.0.as_ref()
+    }
+}
+
+impl AsMut<gst::StructureRef> for AudioResamplerOptions {
+    #[inline]
+    fn as_mut(&mut self) -> &mut gst::StructureRef {
+        self.0.as_mut()
+    }
+}
+
+impl Default for AudioResamplerOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<gst::Structure> for AudioResamplerOptions {
+    type Error = glib::BoolError;
+
+    fn try_from(v: gst::Structure) -> Result<Self, Self::Error> {
+        skip_assert_initialized!();
+        if v.name() == "GstAudioResampler" {
+            Ok(Self(v))
+        } else {
+            Err(glib::bool_error!("Structure is no AudioResamplerOptions"))
+        }
+    }
+}
+
+impl From<AudioResamplerOptions> for gst::Structure {
+    fn from(v: AudioResamplerOptions) -> Self {
+        skip_assert_initialized!();
+        v.0
+    }
+}
+
+impl AudioResamplerOptions {
+    pub fn new() -> Self {
+        Self(gst::Structure::new_empty("GstAudioResampler"))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the options for `method` to the given `quality` between 0 and 10.
+    #[doc(alias = "gst_audio_resampler_options_set_quality")]
+    pub fn set_quality(
+        &mut self,
+        method: crate::AudioResamplerMethod,
+        quality: u32,
+        in_rate: u32,
+        out_rate: u32,
+    ) {
+        assert!(quality <= 10, "quality must be between 0 and 10");
+        unsafe {
+            ffi::gst_audio_resampler_options_set_quality(
+                method.into_glib(),
+                quality,
+                in_rate as i32,
+                out_rate as i32,
+                self.0.as_mut_ptr(),
+            );
+        }
+    }
+
+    pub fn set_filter_mode(&mut self, v: crate::AudioResamplerFilterMode) {
+        self.0.set(glib::gstr!("GstAudioResampler.filter-mode"), v);
+    }
+
+    #[doc(alias = "get_filter_mode")]
+    pub fn filter_mode(&self) -> crate::AudioResamplerFilterMode {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.filter-mode"))
+            .expect("Wrong type")
+            .unwrap_or(crate::AudioResamplerFilterMode::Auto)
+    }
+
+    pub fn set_filter_mode_threshold(&mut self, v: u32) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.filter-mode-threshold"), v);
+    }
+
+    #[doc(alias = "get_filter_mode_threshold")]
+    pub fn filter_mode_threshold(&self) -> u32 {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.filter-mode-threshold"))
+            .expect("Wrong type")
+            .unwrap_or(1_048_576)
+    }
+
+    pub fn set_filter_interpolation(&mut self, v: crate::AudioResamplerFilterInterpolation) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.filter-interpolation"), v);
+    }
+
+    #[doc(alias = "get_filter_interpolation")]
+    pub fn filter_interpolation(&self) -> crate::AudioResamplerFilterInterpolation {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.filter-interpolation"))
+            .expect("Wrong type")
+            .unwrap_or(crate::AudioResamplerFilterInterpolation::Cubic)
+    }
+
+    pub fn set_filter_oversample(&mut self, v: u32) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.filter-oversample"), v);
+    }
+
+    #[doc(alias = "get_filter_oversample")]
+    pub fn filter_oversample(&self) -> u32 {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.filter-oversample"))
+            .expect("Wrong type")
+            .unwrap_or(8)
+    }
+
+    pub fn set_n_taps(&mut self, v: i32) {
+        self.0.set(glib::gstr!("GstAudioResampler.n-taps"), v);
+    }
+
+    #[doc(alias = "get_n_taps")]
+    pub fn n_taps(&self) -> Option<i32> {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.n-taps"))
+            .expect("Wrong type")
+    }
+
+    pub fn set_cutoff(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstAudioResampler.cutoff"), v);
+    }
+
+    #[doc(alias = "get_cutoff")]
+    pub fn cutoff(&self) -> Option<f64> {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.cutoff"))
+            .expect("Wrong type")
+    }
+
+    pub fn set_stop_attenuation(&mut self, v: f64) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.stop-attenutation"), v);
+    }
+
+    #[doc(alias = "get_stop_attenuation")]
+    pub fn stop_attenuation(&self) -> Option<f64> {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.stop-attenutation"))
+            .expect("Wrong type")
+    }
+
+    pub fn set_transition_bandwidth(&mut self, v: f64) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.transition-bandwidth"), v);
+    }
+
+    #[doc(alias = "get_transition_bandwidth")]
+    pub fn transition_bandwidth(&self) -> Option<f64> {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.transition-bandwidth"))
+            .expect("Wrong type")
+    }
+
+    pub fn set_cubic_b(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstAudioResampler.cubic-b"), v);
+    }
+
+    #[doc(alias = "get_cubic_b")]
+    pub fn cubic_b(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.cubic-b"))
+            .expect("Wrong type")
+            .unwrap_or(1.0)
+    }
+
+    pub fn set_cubic_c(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstAudioResampler.cubic-c"), v);
+    }
+
+    #[doc(alias = "get_cubic_c")]
+    pub fn cubic_c(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.cubic-c"))
+            .expect("Wrong type")
+            .unwrap_or(0.0)
+    }
+
+    pub fn set_max_phase_error(&mut self, v: f64) {
+        self.0
+            .set(glib::gstr!("GstAudioResampler.max-phase-error"), v);
+    }
+
+    #[doc(alias = "get_max_phase_error")]
+    pub fn max_phase_error(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstAudioResampler.max-phase-error"))
+            .expect("Wrong type")
+            .unwrap_or(0.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample() {
+        gst::init().unwrap();
+
+        let mut options = AudioResamplerOptions::new();
+        options.set_quality(crate::AudioResamplerMethod::Kaiser, 4, 48000, 16000);
+        options.set_filter_mode(crate::AudioResamplerFilterMode::Full);
+        assert_eq!(options.filter_mode(), crate::AudioResamplerFilterMode::Full);
+
+        let mut resampler = AudioResampler::new(
+            crate::AudioResamplerMethod::Kaiser,
+            AudioResamplerFlags::NON_INTERLEAVED_IN | AudioResamplerFlags::NON_INTERLEAVED_OUT,
+            crate::AUDIO_FORMAT_F32,
+            2,
+            48000,
+            16000,
+            Some(options),
+        )
+        .unwrap();
+        assert!(resampler.max_latency() > 0);
+
+        let input = [vec![0u8; 480 * 4], vec![0u8; 480 * 4]];
+        let out_frames = resampler.out_frames(480);
+        assert!(out_frames <= 160);
+        let mut output = [vec![0xffu8; 160 * 4], vec![0xffu8; 160 * 4]];
+        assert_eq!(resampler.resample(&input, &mut output).unwrap(), out_frames);
+        assert!(output[0][..out_frames * 4].iter().all(|&b| b == 0));
+
+        // Interleaved data as single plane is rejected
+        let mut output = [vec![0u8; 160 * 8]];
+        assert!(resampler.resample(&input, &mut output).is_err());
+
+        resampler.update(48000, 8000, None).unwrap();
+        assert!(resampler.out_frames(480) <= 80);
+    }
+}
@@ -0,0 +1,294 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    mem, ptr, slice,
+    sync::atomic::{AtomicI32, Ordering},
+};
+
+use glib::translate::*;
+use gst::format::{FormattedValue, SpecificFormattedValueFullRange};
+use gst::prelude::*;
+
+use crate::{
+    AudioChannelPosition, AudioRingBuffer, AudioRingBufferSpec, AudioRingBufferState, ffi,
+};
+
+fn bpf(rbuf: &AudioRingBuffer) -> usize {
+    unsafe { (*rbuf.as_ptr()).spec.info.bpf as usize }
+}
+
+pub trait AudioRingBufferExtManual: IsA<AudioRingBuffer> + 'static {
+    // rustdoc-stripper-ignore-next
+    /// Acquires the ring buffer with `spec`.
+    ///
+    /// `spec` becomes the spec of the ring buffer and is updated with the values chosen by
+    /// the implementation, e.g. the segment size.
+    ///
+    /// Fails if the ring buffer is already acquired.
+    #[doc(alias = "gst_audio_ring_buffer_acquire")]
+    fn acquire(&self, spec: &mut AudioRingBufferSpec) -> Result<(), glib::BoolError> {
+        unsafe {
+            let ptr = self.as_ref().as_ptr();
+
+            // The implementation and the C code operate on the ring buffer's own spec. It must
+            // not be replaced while acquired as it describes the layout of the allocated memory.
+            {
+                let _guard = self.as_ref().object_lock();
+                if (*ptr).acquired != glib::ffi::GFALSE {
+                    return Err(glib::bool_error!("Ring buffer is already acquired"));
+                }
+
+                let new_spec = mem::ManuallyDrop::new(spec.clone());
+                let old_spec = mem::replace(&mut (*ptr).spec, new_spec.0);
+                if !old_spec.caps.is_null() {
+                    gst::ffi::gst_mini_object_unref(old_spec.caps as *mut gst::ffi::GstMiniObject);
+                }
+            }
+
+            let res = glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_acquire(ptr, ptr::addr_of_mut!((*ptr).spec)),
+                "Failed to acquire ring buffer"
+            );
+
+            if res.is_ok() {
+                let _guard = self.as_ref().object_lock();
+                *spec = (*(ptr::addr_of!((*ptr).spec) as *const AudioRingBufferSpec)).clone();
+            }
+
+            res
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes `in_samples` samples from `data` into the ring buffer, starting at `sample`.
+    ///
+    /// `sample` and `accum` are updated for the next call. Returns the number of samples
+    /// that were written.
+    #[doc(alias = "gst_audio_ring_buffer_commit")]
+    fn commit(
+        &self,
+        sample: &mut u64,
+        data: &[u8],
+        in_samples: i32,
+        out_samples: i32,
+        accum: &mut i32,
+    ) -> u32 {
+        let bpf = bpf(self.as_ref());
+        assert!(in_samples >= 0);
+        assert!(data.len() >= in_samples as usize * bpf);
+
+        unsafe {
+            ffi::gst_audio_ring_buffer_commit(
+                self.as_ref().to_glib_none().0,
+                sample,
+                mut_override(data.as_ptr()),
+                in_samples,
+                out_samples,
+                accum,
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_convert")]
+    fn convert<U: SpecificFormattedValueFullRange>(
+        &self,
+        src_val: impl FormattedValue,
+    ) -> Option<U> {
+        unsafe {
+            let mut dest_val = mem::MaybeUninit::uninit();
+            let ret = from_glib(ffi::gst_audio_ring_buffer_convert(
+                self.as_ref().to_glib_none().0,
+                src_val.format().into_glib(),
+                src_val.into_raw_value(),
+                U::default_format().into_glib(),
+                dest_val.as_mut_ptr(),
+            ));
+            if ret {
+                Some(U::from_raw(U::default_format(), dest_val.assume_init()))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_convert")]
+    fn convert_generic(
+        &self,
+        src_val: impl FormattedValue,
+        dest_format: gst::Format,
+    ) -> Option<gst::GenericFormattedValue> {
+        unsafe {
+            let mut dest_val = mem::MaybeUninit::uninit();
+            let ret = from_glib(ffi::gst_audio_ring_buffer_convert(
+                self.as_ref().to_glib_none().0,
+                src_val.format().into_glib(),
+                src_val.into_raw_value(),
+                dest_format.into_glib(),
+                dest_val.as_mut_ptr(),
+            ));
+            if ret {
+                Some(gst::GenericFormattedValue::new(
+                    dest_format,
+                    dest_val.assume_init(),
+                ))
+            } else {
+                None
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Calls `func` with the index and the memory of the segment that the device has to
+    /// process next.
+    ///
+    /// Playback devices read the segment, capture devices fill it. Afterwards the segment
+    /// is handed back with [`advance()`](crate::prelude::AudioRingBufferExt::advance).
+    ///
+    /// Returns `None` if the ring buffer is not started.
+    #[doc(alias = "gst_audio_ring_buffer_prepare_read")]
+    fn prepare_read<R, F: FnOnce(i32, &mut [u8]) -> R>(&self, func: F) -> Option<R> {
+        unsafe {
+            let mut segment = mem::MaybeUninit::uninit();
+            let mut readptr = ptr::null_mut();
+            let mut len = mem::MaybeUninit::uninit();
+            let ret: bool = from_glib(ffi::gst_audio_ring_buffer_prepare_read(
+                self.as_ref().to_glib_none().0,
+                segment.as_mut_ptr(),
+                &mut readptr,
+                len.as_mut_ptr(),
+            ));
+            if !ret {
+                return None;
+            }
+
+            let len = len.assume_init();
+            let data = if readptr.is_null() || len <= 0 {
+                &mut [][..]
+            } else {
+                slice::from_raw_parts_mut(readptr, len as usize)
+            };
+
+            Some(func(segment.assume_init(), data))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reads samples starting at `sample` into `data`.
+    ///
+    /// Returns the number of samples that were read and the timestamp of the first of
+    /// them, if known.
+    #[doc(alias = "gst_audio_ring_buffer_read")]
+    fn read(&self, sample: u64, data: &mut [u8]) -> (u32, Option<gst::ClockTime>) {
+        let bpf = bpf(self.as_ref());
+        assert!(bpf > 0);
+
+        unsafe {
+            // Only written if the ring buffer keeps track of timestamps
+            let mut timestamp = gst::ffi::GST_CLOCK_TIME_NONE;
+            let ret = ffi::gst_audio_ring_buffer_read(
+                self.as_ref().to_glib_none().0,
+                sample,
+                data.as_mut_ptr(),
+                (data.len() / bpf) as u32,
+                &mut timestamp,
+            );
+            (ret, from_glib(timestamp))
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_callback_full")]
+    #[doc(alias = "gst_audio_ring_buffer_set_callback")]
+    fn set_callback<F: Fn(&AudioRingBuffer, &mut [u8]) + Send + Sync + 'static>(&self, func: F) {
+        unsafe extern "C" fn trampoline<
+            F: Fn(&AudioRingBuffer, &mut [u8]) + Send + Sync + 'static,
+        >(
+            rbuf: *mut ffi::GstAudioRingBuffer,
+            data: *mut u8,
+            len: u32,
+            user_data: glib::ffi::gpointer,
+        ) {
+            unsafe {
+                let func = &*(user_data as *const F);
+                let data = if data.is_null() || len == 0 {
+                    &mut [][..]
+                } else {
+                    slice::from_raw_parts_mut(data, len as usize)
+                };
+                func(&from_glib_borrow(rbuf), data);
+            }
+        }
+
+        unsafe extern "C" fn destroy<F>(user_data: glib::ffi::gpointer) {
+            unsafe {
+                let _ = Box::from_raw(user_data as *mut F);
+            }
+        }
+
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_callback_full(
+                self.as_ref().to_glib_none().0,
+                Some(trampoline::<F>),
+                Box::into_raw(Box::new(func)) as glib::ffi::gpointer,
+                Some(destroy::<F>),
+            );
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_callback")]
+    fn unset_callback(&self) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_callback_full(
+                self.as_ref().to_glib_none().0,
+                None,
+                ptr::null_mut(),
+                None,
+            );
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_channel_positions")]
+    fn set_channel_positions(&self, positions: &[AudioChannelPosition]) {
+        let channels = unsafe { (*self.as_ref().as_ptr()).spec.info.channels };
+        assert_eq!(positions.len(), channels as usize);
+
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_channel_positions(
+                self.as_ref().to_glib_none().0,
+                positions.as_ptr() as *const _,
+            );
+        }
+    }
+
+    #[doc(alias = "get_state")]
+    fn state(&self) -> AudioRingBufferState {
+        unsafe {
+            let state = &*(ptr::addr_of!((*self.as_ref().as_ptr()).state) as *const AtomicI32);
+            from_glib(state.load(Ordering::SeqCst))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of samples per segment.
+    ///
+    /// This takes the object lock of the ring buffer and must not be called from
+    /// [`AudioRingBufferImpl`](crate::subclass::prelude::AudioRingBufferImpl) virtual methods
+    /// that are called with the lock held, e.g. `acquire()`. Use
+    /// [`Self::samples_per_segment_unlocked()`] there instead.
+    #[doc(alias = "samples_per_seg")]
+    fn samples_per_segment(&self) -> u32 {
+        let _guard = self.as_ref().object_lock();
+        self.samples_per_segment_unlocked()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of samples per segment without taking the object lock.
+    ///
+    /// The caller must hold the object lock, e.g. from inside
+    /// [`AudioRingBufferImpl::acquire()`](crate::subclass::prelude::AudioRingBufferImpl::acquire).
+    #[doc(alias = "samples_per_seg")]
+    fn samples_per_segment_unlocked(&self) -> u32 {
+        unsafe { (*self.as_ref().as_ptr()).samples_per_seg as u32 }
+    }
+}
+
+impl<O: IsA<AudioRingBuffer>> AudioRingBufferExtManual for O {}
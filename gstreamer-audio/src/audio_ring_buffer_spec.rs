@@ -1,6 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::fmt;
+use std::{fmt, mem};
 
 use glib::translate::*;
 use gst::Caps;
@@ -12,6 +12,37 @@ use crate::{AudioInfo, AudioRingBufferFormatType, ffi};
 pub struct AudioRingBufferSpec(pub(crate) ffi::GstAudioRingBufferSpec);
 
 impl AudioRingBufferSpec {
+    // rustdoc-stripper-ignore-next
+    /// Creates a spec for `caps` with the given buffer and latency time in microseconds.
+    ///
+    /// The segment size and number of segments are derived from the two times.
+    #[doc(alias = "gst_audio_ring_buffer_parse_caps")]
+    pub fn from_caps(
+        caps: &gst::CapsRef,
+        buffer_time: u64,
+        latency_time: u64,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        unsafe {
+            let mut spec = mem::zeroed::<ffi::GstAudioRingBufferSpec>();
+            spec.buffer_time = buffer_time;
+            spec.latency_time = latency_time;
+            let res: bool = from_glib(ffi::gst_audio_ring_buffer_parse_caps(
+                &mut spec,
+                caps.as_mut_ptr(),
+            ));
+            if res && !spec.caps.is_null() {
+                Ok(Self(spec))
+            } else {
+                if !spec.caps.is_null() {
+                    gst::ffi::gst_mini_object_unref(spec.caps as *mut gst::ffi::GstMiniObject);
+                }
+                Err(glib::bool_error!("Failed to parse caps"))
+            }
+        }
+    }
+
     #[doc(alias = "get_type")]
     #[inline]
     pub fn type_(&self) -> AudioRingBufferFormatType {
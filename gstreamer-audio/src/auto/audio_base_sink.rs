@@ -28,11 +28,6 @@ unsafe impl Send for AudioBaseSink {}
 unsafe impl Sync for AudioBaseSink {}
 
 pub trait AudioBaseSinkExt: IsA<AudioBaseSink> + 'static {
-    //#[doc(alias = "gst_audio_base_sink_create_ringbuffer")]
-    //fn create_ringbuffer(&self) -> /*Ignored*/Option<AudioRingBuffer> {
-    //    unsafe { TODO: call ffi:gst_audio_base_sink_create_ringbuffer() }
-    //}
-
     #[doc(alias = "gst_audio_base_sink_get_alignment_threshold")]
     #[doc(alias = "get_alignment_threshold")]
     #[doc(alias = "alignment-threshold")]
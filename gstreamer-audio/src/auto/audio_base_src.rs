@@ -28,11 +28,6 @@ unsafe impl Send for AudioBaseSrc {}
 unsafe impl Sync for AudioBaseSrc {}
 
 pub trait AudioBaseSrcExt: IsA<AudioBaseSrc> + 'static {
-    //#[doc(alias = "gst_audio_base_src_create_ringbuffer")]
-    //fn create_ringbuffer(&self) -> /*Ignored*/Option<AudioRingBuffer> {
-    //    unsafe { TODO: call ffi:gst_audio_base_src_create_ringbuffer() }
-    //}
-
     #[doc(alias = "gst_audio_base_src_get_provide_clock")]
     #[doc(alias = "get_provide_clock")]
     #[doc(alias = "provide-clock")]
@@ -0,0 +1,239 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// from gst-gir-files (https://gitlab.freedesktop.org/gstreamer/gir-files-rs.git)
+// DO NOT EDIT
+
+use crate::ffi;
+use glib::{prelude::*, translate::*};
+
+glib::wrapper! {
+    #[doc(alias = "GstAudioRingBuffer")]
+    pub struct AudioRingBuffer(Object<ffi::GstAudioRingBuffer, ffi::GstAudioRingBufferClass>) @extends gst::Object;
+
+    match fn {
+        type_ => || ffi::gst_audio_ring_buffer_get_type(),
+    }
+}
+
+impl AudioRingBuffer {
+    pub const NONE: Option<&'static AudioRingBuffer> = None;
+}
+
+unsafe impl Send for AudioRingBuffer {}
+unsafe impl Sync for AudioRingBuffer {}
+
+pub trait AudioRingBufferExt: IsA<AudioRingBuffer> + 'static {
+    #[doc(alias = "gst_audio_ring_buffer_activate")]
+    fn activate(&self, active: bool) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_activate(
+                    self.as_ref().to_glib_none().0,
+                    active.into_glib()
+                ),
+                "Failed to activate ring buffer"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_advance")]
+    fn advance(&self, advance: u32) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_advance(self.as_ref().to_glib_none().0, advance);
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_clear")]
+    fn clear(&self, segment: i32) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_clear(self.as_ref().to_glib_none().0, segment);
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_clear_all")]
+    fn clear_all(&self) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_clear_all(self.as_ref().to_glib_none().0);
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_close_device")]
+    fn close_device(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_close_device(self.as_ref().to_glib_none().0),
+                "Failed to close device"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_delay")]
+    fn delay(&self) -> u32 {
+        unsafe { ffi::gst_audio_ring_buffer_delay(self.as_ref().to_glib_none().0) }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_device_is_open")]
+    fn device_is_open(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_audio_ring_buffer_device_is_open(
+                self.as_ref().to_glib_none().0,
+            ))
+        }
+    }
+
+    #[cfg(feature = "v1_26")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_26")))]
+    #[doc(alias = "gst_audio_ring_buffer_get_segbase")]
+    #[doc(alias = "get_segbase")]
+    fn segbase(&self) -> u64 {
+        unsafe { ffi::gst_audio_ring_buffer_get_segbase(self.as_ref().to_glib_none().0) }
+    }
+
+    #[cfg(feature = "v1_26")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_26")))]
+    #[doc(alias = "gst_audio_ring_buffer_get_segdone")]
+    #[doc(alias = "get_segdone")]
+    fn segdone(&self) -> u64 {
+        unsafe { ffi::gst_audio_ring_buffer_get_segdone(self.as_ref().to_glib_none().0) }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_is_acquired")]
+    fn is_acquired(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_audio_ring_buffer_is_acquired(
+                self.as_ref().to_glib_none().0,
+            ))
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_is_active")]
+    fn is_active(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_audio_ring_buffer_is_active(
+                self.as_ref().to_glib_none().0,
+            ))
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_is_flushing")]
+    fn is_flushing(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_audio_ring_buffer_is_flushing(
+                self.as_ref().to_glib_none().0,
+            ))
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_may_start")]
+    fn may_start(&self, allowed: bool) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_may_start(
+                self.as_ref().to_glib_none().0,
+                allowed.into_glib(),
+            );
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_open_device")]
+    fn open_device(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_open_device(self.as_ref().to_glib_none().0),
+                "Failed to open device"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_pause")]
+    fn pause(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_pause(self.as_ref().to_glib_none().0),
+                "Failed to pause ring buffer"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_release")]
+    fn release(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_release(self.as_ref().to_glib_none().0),
+                "Failed to release ring buffer"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_samples_done")]
+    fn samples_done(&self) -> u64 {
+        unsafe { ffi::gst_audio_ring_buffer_samples_done(self.as_ref().to_glib_none().0) }
+    }
+
+    #[cfg(feature = "v1_24")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_24")))]
+    #[doc(alias = "gst_audio_ring_buffer_set_errored")]
+    fn set_errored(&self) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_errored(self.as_ref().to_glib_none().0);
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_flushing")]
+    fn set_flushing(&self, flushing: bool) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_flushing(
+                self.as_ref().to_glib_none().0,
+                flushing.into_glib(),
+            );
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_sample")]
+    fn set_sample(&self, sample: u64) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_sample(self.as_ref().to_glib_none().0, sample);
+        }
+    }
+
+    #[cfg(feature = "v1_26")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_26")))]
+    #[doc(alias = "gst_audio_ring_buffer_set_segdone")]
+    fn set_segdone(&self, segdone: u64) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_segdone(self.as_ref().to_glib_none().0, segdone);
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_set_timestamp")]
+    fn set_timestamp(&self, readseg: i32, timestamp: gst::ClockTime) {
+        unsafe {
+            ffi::gst_audio_ring_buffer_set_timestamp(
+                self.as_ref().to_glib_none().0,
+                readseg,
+                timestamp.into_glib(),
+            );
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_start")]
+    fn start(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_start(self.as_ref().to_glib_none().0),
+                "Failed to start ring buffer"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_audio_ring_buffer_stop")]
+    fn stop(&self) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_audio_ring_buffer_stop(self.as_ref().to_glib_none().0),
+                "Failed to stop ring buffer"
+            )
+        }
+    }
+}
+
+impl<O: IsA<AudioRingBuffer>> AudioRingBufferExt for O {}
@@ -575,6 +575,206 @@ impl From<AudioNoiseShapingMethod> for glib::Value {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstAudioResamplerFilterInterpolation")]
+pub enum AudioResamplerFilterInterpolation {
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_NONE")]
+    None,
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_LINEAR")]
+    Linear,
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_CUBIC")]
+    Cubic,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioResamplerFilterInterpolation {
+    type GlibType = ffi::GstAudioResamplerFilterInterpolation;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioResamplerFilterInterpolation {
+        match self {
+            Self::None => ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_NONE,
+            Self::Linear => ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_LINEAR,
+            Self::Cubic => ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_CUBIC,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioResamplerFilterInterpolation> for AudioResamplerFilterInterpolation {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioResamplerFilterInterpolation) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_NONE => Self::None,
+            ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_LINEAR => Self::Linear,
+            ffi::GST_AUDIO_RESAMPLER_FILTER_INTERPOLATION_CUBIC => Self::Cubic,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+impl StaticType for AudioResamplerFilterInterpolation {
+    #[inline]
+    #[doc(alias = "gst_audio_resampler_filter_interpolation_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_resampler_filter_interpolation_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioResamplerFilterInterpolation {
+    type ParamSpec = glib::ParamSpecEnum;
+    type SetValue = Self;
+    type BuilderFn = fn(&str, Self) -> glib::ParamSpecEnumBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder_with_default
+    }
+}
+
+impl glib::value::ValueType for AudioResamplerFilterInterpolation {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioResamplerFilterInterpolation {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_enum(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioResamplerFilterInterpolation {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_enum(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioResamplerFilterInterpolation> for glib::Value {
+    #[inline]
+    fn from(v: AudioResamplerFilterInterpolation) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstAudioResamplerFilterMode")]
+pub enum AudioResamplerFilterMode {
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_MODE_INTERPOLATED")]
+    Interpolated,
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_MODE_FULL")]
+    Full,
+    #[doc(alias = "GST_AUDIO_RESAMPLER_FILTER_MODE_AUTO")]
+    Auto,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioResamplerFilterMode {
+    type GlibType = ffi::GstAudioResamplerFilterMode;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioResamplerFilterMode {
+        match self {
+            Self::Interpolated => ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_INTERPOLATED,
+            Self::Full => ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_FULL,
+            Self::Auto => ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_AUTO,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioResamplerFilterMode> for AudioResamplerFilterMode {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioResamplerFilterMode) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_INTERPOLATED => Self::Interpolated,
+            ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_FULL => Self::Full,
+            ffi::GST_AUDIO_RESAMPLER_FILTER_MODE_AUTO => Self::Auto,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+impl StaticType for AudioResamplerFilterMode {
+    #[inline]
+    #[doc(alias = "gst_audio_resampler_filter_mode_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_resampler_filter_mode_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioResamplerFilterMode {
+    type ParamSpec = glib::ParamSpecEnum;
+    type SetValue = Self;
+    type BuilderFn = fn(&str, Self) -> glib::ParamSpecEnumBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder_with_default
+    }
+}
+
+impl glib::value::ValueType for AudioResamplerFilterMode {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioResamplerFilterMode {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_enum(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioResamplerFilterMode {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_enum(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioResamplerFilterMode> for glib::Value {
+    #[inline]
+    fn from(v: AudioResamplerFilterMode) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstAudioResamplerMethod")]
@@ -837,6 +1037,110 @@ impl From<AudioRingBufferFormatType> for glib::Value {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstAudioRingBufferState")]
+pub enum AudioRingBufferState {
+    #[doc(alias = "GST_AUDIO_RING_BUFFER_STATE_STOPPED")]
+    Stopped,
+    #[doc(alias = "GST_AUDIO_RING_BUFFER_STATE_PAUSED")]
+    Paused,
+    #[doc(alias = "GST_AUDIO_RING_BUFFER_STATE_STARTED")]
+    Started,
+    #[doc(alias = "GST_AUDIO_RING_BUFFER_STATE_ERROR")]
+    Error,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioRingBufferState {
+    type GlibType = ffi::GstAudioRingBufferState;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioRingBufferState {
+        match self {
+            Self::Stopped => ffi::GST_AUDIO_RING_BUFFER_STATE_STOPPED,
+            Self::Paused => ffi::GST_AUDIO_RING_BUFFER_STATE_PAUSED,
+            Self::Started => ffi::GST_AUDIO_RING_BUFFER_STATE_STARTED,
+            Self::Error => ffi::GST_AUDIO_RING_BUFFER_STATE_ERROR,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioRingBufferState> for AudioRingBufferState {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioRingBufferState) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_AUDIO_RING_BUFFER_STATE_STOPPED => Self::Stopped,
+            ffi::GST_AUDIO_RING_BUFFER_STATE_PAUSED => Self::Paused,
+            ffi::GST_AUDIO_RING_BUFFER_STATE_STARTED => Self::Started,
+            ffi::GST_AUDIO_RING_BUFFER_STATE_ERROR => Self::Error,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+impl StaticType for AudioRingBufferState {
+    #[inline]
+    #[doc(alias = "gst_audio_ring_buffer_state_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_ring_buffer_state_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioRingBufferState {
+    type ParamSpec = glib::ParamSpecEnum;
+    type SetValue = Self;
+    type BuilderFn = fn(&str, Self) -> glib::ParamSpecEnumBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder_with_default
+    }
+}
+
+impl glib::value::ValueType for AudioRingBufferState {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioRingBufferState {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_enum(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioRingBufferState {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_enum(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioRingBufferState> for glib::Value {
+    #[inline]
+    fn from(v: AudioRingBufferState) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstStreamVolumeFormat")]
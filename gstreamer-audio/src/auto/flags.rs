@@ -6,6 +6,182 @@
 use crate::ffi;
 use glib::{bitflags::bitflags, prelude::*, translate::*};
 
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[doc(alias = "GstAudioChannelMixerFlags")]
+    pub struct AudioChannelMixerFlags: u32 {
+        #[doc(alias = "GST_AUDIO_CHANNEL_MIXER_FLAGS_NON_INTERLEAVED_IN")]
+        const NON_INTERLEAVED_IN = ffi::GST_AUDIO_CHANNEL_MIXER_FLAGS_NON_INTERLEAVED_IN as _;
+        #[doc(alias = "GST_AUDIO_CHANNEL_MIXER_FLAGS_NON_INTERLEAVED_OUT")]
+        const NON_INTERLEAVED_OUT = ffi::GST_AUDIO_CHANNEL_MIXER_FLAGS_NON_INTERLEAVED_OUT as _;
+        #[doc(alias = "GST_AUDIO_CHANNEL_MIXER_FLAGS_UNPOSITIONED_IN")]
+        const UNPOSITIONED_IN = ffi::GST_AUDIO_CHANNEL_MIXER_FLAGS_UNPOSITIONED_IN as _;
+        #[doc(alias = "GST_AUDIO_CHANNEL_MIXER_FLAGS_UNPOSITIONED_OUT")]
+        const UNPOSITIONED_OUT = ffi::GST_AUDIO_CHANNEL_MIXER_FLAGS_UNPOSITIONED_OUT as _;
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioChannelMixerFlags {
+    type GlibType = ffi::GstAudioChannelMixerFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioChannelMixerFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioChannelMixerFlags> for AudioChannelMixerFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioChannelMixerFlags) -> Self {
+        skip_assert_initialized!();
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for AudioChannelMixerFlags {
+    #[inline]
+    #[doc(alias = "gst_audio_channel_mixer_flags_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_channel_mixer_flags_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioChannelMixerFlags {
+    type ParamSpec = glib::ParamSpecFlags;
+    type SetValue = Self;
+    type BuilderFn = fn(&str) -> glib::ParamSpecFlagsBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder
+    }
+}
+
+impl glib::value::ValueType for AudioChannelMixerFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioChannelMixerFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioChannelMixerFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioChannelMixerFlags> for glib::Value {
+    #[inline]
+    fn from(v: AudioChannelMixerFlags) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[doc(alias = "GstAudioConverterFlags")]
+    pub struct AudioConverterFlags: u32 {
+        #[doc(alias = "GST_AUDIO_CONVERTER_FLAG_IN_WRITABLE")]
+        const IN_WRITABLE = ffi::GST_AUDIO_CONVERTER_FLAG_IN_WRITABLE as _;
+        #[doc(alias = "GST_AUDIO_CONVERTER_FLAG_VARIABLE_RATE")]
+        const VARIABLE_RATE = ffi::GST_AUDIO_CONVERTER_FLAG_VARIABLE_RATE as _;
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioConverterFlags {
+    type GlibType = ffi::GstAudioConverterFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioConverterFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioConverterFlags> for AudioConverterFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioConverterFlags) -> Self {
+        skip_assert_initialized!();
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for AudioConverterFlags {
+    #[inline]
+    #[doc(alias = "gst_audio_converter_flags_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_converter_flags_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioConverterFlags {
+    type ParamSpec = glib::ParamSpecFlags;
+    type SetValue = Self;
+    type BuilderFn = fn(&str) -> glib::ParamSpecFlagsBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder
+    }
+}
+
+impl glib::value::ValueType for AudioConverterFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioConverterFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioConverterFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioConverterFlags> for glib::Value {
+    #[inline]
+    fn from(v: AudioConverterFlags) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
 bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[doc(alias = "GstAudioFlags")]
@@ -265,3 +441,91 @@ impl From<AudioPackFlags> for glib::Value {
         ToValue::to_value(&v)
     }
 }
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[doc(alias = "GstAudioResamplerFlags")]
+    pub struct AudioResamplerFlags: u32 {
+        #[doc(alias = "GST_AUDIO_RESAMPLER_FLAG_NON_INTERLEAVED_IN")]
+        const NON_INTERLEAVED_IN = ffi::GST_AUDIO_RESAMPLER_FLAG_NON_INTERLEAVED_IN as _;
+        #[doc(alias = "GST_AUDIO_RESAMPLER_FLAG_NON_INTERLEAVED_OUT")]
+        const NON_INTERLEAVED_OUT = ffi::GST_AUDIO_RESAMPLER_FLAG_NON_INTERLEAVED_OUT as _;
+        #[doc(alias = "GST_AUDIO_RESAMPLER_FLAG_VARIABLE_RATE")]
+        const VARIABLE_RATE = ffi::GST_AUDIO_RESAMPLER_FLAG_VARIABLE_RATE as _;
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for AudioResamplerFlags {
+    type GlibType = ffi::GstAudioResamplerFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstAudioResamplerFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstAudioResamplerFlags> for AudioResamplerFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstAudioResamplerFlags) -> Self {
+        skip_assert_initialized!();
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for AudioResamplerFlags {
+    #[inline]
+    #[doc(alias = "gst_audio_resampler_flags_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_audio_resampler_flags_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for AudioResamplerFlags {
+    type ParamSpec = glib::ParamSpecFlags;
+    type SetValue = Self;
+    type BuilderFn = fn(&str) -> glib::ParamSpecFlagsBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder
+    }
+}
+
+impl glib::value::ValueType for AudioResamplerFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for AudioResamplerFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for AudioResamplerFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<AudioResamplerFlags> for glib::Value {
+    #[inline]
+    fn from(v: AudioResamplerFlags) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
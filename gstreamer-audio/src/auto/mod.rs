@@ -27,6 +27,9 @@ pub use self::audio_encoder::AudioEncoder;
 mod audio_filter;
 pub use self::audio_filter::AudioFilter;
 
+mod audio_ring_buffer;
+pub use self::audio_ring_buffer::AudioRingBuffer;
+
 mod audio_sink;
 pub use self::audio_sink::AudioSink;
 
@@ -44,14 +47,20 @@ pub use self::enums::AudioDitherMethod;
 pub use self::enums::AudioFormat;
 pub use self::enums::AudioLayout;
 pub use self::enums::AudioNoiseShapingMethod;
+pub use self::enums::AudioResamplerFilterInterpolation;
+pub use self::enums::AudioResamplerFilterMode;
 pub use self::enums::AudioResamplerMethod;
 pub use self::enums::AudioRingBufferFormatType;
+pub use self::enums::AudioRingBufferState;
 pub use self::enums::StreamVolumeFormat;
 
 mod flags;
+pub use self::flags::AudioChannelMixerFlags;
+pub use self::flags::AudioConverterFlags;
 pub use self::flags::AudioFlags;
 pub use self::flags::AudioFormatFlags;
 pub use self::flags::AudioPackFlags;
+pub use self::flags::AudioResamplerFlags;
 
 pub(crate) mod traits {
     pub use super::audio_aggregator::AudioAggregatorExt;
@@ -61,5 +70,6 @@ pub(crate) mod traits {
     pub use super::audio_base_src::AudioBaseSrcExt;
     pub use super::audio_decoder::AudioDecoderExt;
     pub use super::audio_encoder::AudioEncoderExt;
+    pub use super::audio_ring_buffer::AudioRingBufferExt;
     pub use super::stream_volume::StreamVolumeExt;
 }
@@ -37,10 +37,13 @@ mod audio_format;
 pub use crate::audio_format::*;
 mod audio_format_info;
 pub use crate::audio_format_info::*;
+mod audio_ring_buffer;
 mod audio_ring_buffer_spec;
 pub use crate::audio_ring_buffer_spec::*;
 mod audio_info;
 pub use crate::audio_info::*;
+#[cfg(feature = "serde")]
+mod audio_info_serde;
 mod audio_meta;
 pub use crate::audio_meta::*;
 mod audio_channel_position;
@@ -63,7 +66,13 @@ mod audio_encoder;
 mod audio_filter;
 
 mod audio_converter;
-pub use crate::audio_converter::AudioConverterConfig;
+pub use crate::audio_converter::{AudioConverter, AudioConverterConfig};
+
+mod audio_resampler;
+pub use crate::audio_resampler::{AudioResampler, AudioResamplerOptions};
+
+mod audio_channel_mixer;
+pub use crate::audio_channel_mixer::AudioChannelMixer;
 
 // Re-export all the traits in a prelude module, so that applications
 // can always "use gst_audio::prelude::*" without getting conflicts
@@ -76,7 +85,8 @@ pub mod prelude {
         audio_aggregator::AudioAggregatorExtManual,
         audio_aggregator_convert_pad::AudioAggregatorConvertPadExtManual,
         audio_aggregator_pad::AudioAggregatorPadExtManual, audio_filter::AudioFilterExtManual,
-        audio_format::AudioFormatIteratorExt, auto::traits::*,
+        audio_format::AudioFormatIteratorExt, audio_ring_buffer::AudioRingBufferExtManual,
+        auto::traits::*,
     };
 }
 
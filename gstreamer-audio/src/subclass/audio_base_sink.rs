@@ -1,9 +1,61 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use glib::translate::*;
 use gst_base::{prelude::*, subclass::prelude::*};
 
-use crate::AudioBaseSink;
+use crate::{AudioBaseSink, AudioRingBuffer, ffi};
 
-pub trait AudioBaseSinkImpl: BaseSinkImpl + ObjectSubclass<Type: IsA<AudioBaseSink>> {}
+pub trait AudioBaseSinkImpl: BaseSinkImpl + ObjectSubclass<Type: IsA<AudioBaseSink>> {
+    fn create_ringbuffer(&self) -> Option<AudioRingBuffer> {
+        self.parent_create_ringbuffer()
+    }
+}
 
-unsafe impl<T: AudioBaseSinkImpl> IsSubclassable<T> for AudioBaseSink {}
+pub trait AudioBaseSinkImplExt: AudioBaseSinkImpl {
+    fn parent_create_ringbuffer(&self) -> Option<AudioRingBuffer> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstAudioBaseSinkClass;
+            let f = (*parent_class).create_ringbuffer?;
+            from_glib_none(f(self
+                .obj()
+                .unsafe_cast_ref::<AudioBaseSink>()
+                .to_glib_none()
+                .0))
+        }
+    }
+}
+
+impl<T: AudioBaseSinkImpl> AudioBaseSinkImplExt for T {}
+
+unsafe impl<T: AudioBaseSinkImpl> IsSubclassable<T> for AudioBaseSink {
+    fn class_init(klass: &mut glib::Class<Self>) {
+        Self::parent_class_init::<T>(klass);
+        let klass = klass.as_mut();
+        klass.create_ringbuffer = Some(audiobasesink_create_ringbuffer::<T>);
+    }
+}
+
+unsafe extern "C" fn audiobasesink_create_ringbuffer<T: AudioBaseSinkImpl>(
+    ptr: *mut ffi::GstAudioBaseSink,
+) -> *mut ffi::GstAudioRingBuffer {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        gst::element_panic_to_error!(imp, std::ptr::null_mut(), {
+            match imp.create_ringbuffer() {
+                Some(ringbuffer) => {
+                    // The reference we're going to return, the initial reference is going to
+                    // be dropped here now
+                    let ringbuffer = ringbuffer.into_glib_ptr();
+                    // The caller expects a floating reference and takes ownership by
+                    // parenting the ring buffer to the element
+                    glib::gobject_ffi::g_object_force_floating(ringbuffer as *mut _);
+                    ringbuffer
+                }
+                None => std::ptr::null_mut(),
+            }
+        })
+    }
+}
@@ -0,0 +1,568 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ptr, slice};
+
+use glib::{prelude::*, translate::*};
+use gst::{LoggableError, subclass::prelude::*};
+
+use crate::{AudioRingBuffer, AudioRingBufferFormatType, AudioRingBufferSpec, ffi};
+
+// rustdoc-stripper-ignore-next
+/// Implementation of a [`AudioRingBuffer`].
+///
+/// The ring buffer memory of `segtotal * segsize` bytes is allocated after a successful
+/// [`acquire()`](AudioRingBufferImpl::acquire) unless the parent class already provided
+/// it, and is freed again on [`release()`](AudioRingBufferImpl::release). Memory provided
+/// by the parent class is left to the parent class' `release()`.
+///
+/// `acquire()`, `release()`, `start()`, `pause()`, `resume()` and `stop()` are called
+/// with the object lock of the ring buffer taken.
+pub trait AudioRingBufferImpl: GstObjectImpl + ObjectSubclass<Type: IsA<AudioRingBuffer>> {
+    fn open_device(&self) -> Result<(), LoggableError> {
+        self.parent_open_device()
+    }
+
+    fn acquire(&self, spec: &mut AudioRingBufferSpec) -> Result<(), LoggableError> {
+        self.parent_acquire(spec)
+    }
+
+    fn release(&self) -> Result<(), LoggableError> {
+        self.parent_release()
+    }
+
+    fn close_device(&self) -> Result<(), LoggableError> {
+        self.parent_close_device()
+    }
+
+    fn start(&self) -> Result<(), LoggableError> {
+        self.parent_start()
+    }
+
+    fn pause(&self) -> Result<(), LoggableError> {
+        self.parent_pause()
+    }
+
+    fn resume(&self) -> Result<(), LoggableError> {
+        self.parent_resume()
+    }
+
+    fn stop(&self) -> Result<(), LoggableError> {
+        self.parent_stop()
+    }
+
+    fn delay(&self) -> u32 {
+        self.parent_delay()
+    }
+
+    fn activate(&self, active: bool) -> Result<(), LoggableError> {
+        self.parent_activate(active)
+    }
+
+    fn commit(
+        &self,
+        sample: &mut u64,
+        data: &[u8],
+        in_samples: i32,
+        out_samples: i32,
+        accum: &mut i32,
+    ) -> u32 {
+        self.parent_commit(sample, data, in_samples, out_samples, accum)
+    }
+
+    fn clear_all(&self) {
+        self.parent_clear_all()
+    }
+}
+
+macro_rules! parent_bool_vfunc {
+    ($name:ident, $vfunc:ident, $msg:literal) => {
+        fn $name(&self) -> Result<(), LoggableError> {
+            unsafe {
+                let data = Self::type_data();
+                let parent_class =
+                    data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+                let f = match (*parent_class).$vfunc {
+                    Some(f) => f,
+                    None => return Ok(()),
+                };
+                gst::result_from_gboolean!(
+                    f(self
+                        .obj()
+                        .unsafe_cast_ref::<AudioRingBuffer>()
+                        .to_glib_none()
+                        .0),
+                    gst::CAT_RUST,
+                    $msg
+                )
+            }
+        }
+    };
+}
+
+pub trait AudioRingBufferImplExt: AudioRingBufferImpl {
+    parent_bool_vfunc!(
+        parent_open_device,
+        open_device,
+        "Failed to open device using the parent function"
+    );
+
+    fn parent_acquire(&self, spec: &mut AudioRingBufferSpec) -> Result<(), LoggableError> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+            let f = match (*parent_class).acquire {
+                Some(f) => f,
+                None => return Ok(()),
+            };
+            gst::result_from_gboolean!(
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<AudioRingBuffer>()
+                        .to_glib_none()
+                        .0,
+                    &mut spec.0
+                ),
+                gst::CAT_RUST,
+                "Failed to acquire ring buffer using the parent function"
+            )
+        }
+    }
+
+    parent_bool_vfunc!(
+        parent_release,
+        release,
+        "Failed to release ring buffer using the parent function"
+    );
+    parent_bool_vfunc!(
+        parent_close_device,
+        close_device,
+        "Failed to close device using the parent function"
+    );
+    parent_bool_vfunc!(
+        parent_start,
+        start,
+        "Failed to start ring buffer using the parent function"
+    );
+    parent_bool_vfunc!(
+        parent_pause,
+        pause,
+        "Failed to pause ring buffer using the parent function"
+    );
+    parent_bool_vfunc!(
+        parent_resume,
+        resume,
+        "Failed to resume ring buffer using the parent function"
+    );
+    parent_bool_vfunc!(
+        parent_stop,
+        stop,
+        "Failed to stop ring buffer using the parent function"
+    );
+
+    fn parent_delay(&self) -> u32 {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+            let f = match (*parent_class).delay {
+                Some(f) => f,
+                None => return 0,
+            };
+            f(self
+                .obj()
+                .unsafe_cast_ref::<AudioRingBuffer>()
+                .to_glib_none()
+                .0)
+        }
+    }
+
+    fn parent_activate(&self, active: bool) -> Result<(), LoggableError> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+            // Like in `gst_audio_ring_buffer_activate()`, not implementing activation means
+            // that there is nothing to do
+            let f = match (*parent_class).activate {
+                Some(f) => f,
+                None => return Ok(()),
+            };
+            gst::result_from_gboolean!(
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<AudioRingBuffer>()
+                        .to_glib_none()
+                        .0,
+                    active.into_glib()
+                ),
+                gst::CAT_RUST,
+                "Failed to activate ring buffer using the parent function"
+            )
+        }
+    }
+
+    fn parent_commit(
+        &self,
+        sample: &mut u64,
+        data: &[u8],
+        in_samples: i32,
+        out_samples: i32,
+        accum: &mut i32,
+    ) -> u32 {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_class =
+                type_data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+            let f = match (*parent_class).commit {
+                Some(f) => f,
+                None => return 0,
+            };
+            f(
+                self.obj()
+                    .unsafe_cast_ref::<AudioRingBuffer>()
+                    .to_glib_none()
+                    .0,
+                sample,
+                mut_override(data.as_ptr()),
+                in_samples,
+                out_samples,
+                accum,
+            )
+        }
+    }
+
+    fn parent_clear_all(&self) {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstAudioRingBufferClass;
+            if let Some(f) = (*parent_class).clear_all {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<AudioRingBuffer>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+}
+
+impl<T: AudioRingBufferImpl> AudioRingBufferImplExt for T {}
+
+unsafe impl<T: AudioRingBufferImpl> IsSubclassable<T> for AudioRingBuffer {
+    fn class_init(klass: &mut glib::Class<Self>) {
+        Self::parent_class_init::<T>(klass);
+        let klass = klass.as_mut();
+        klass.open_device = Some(ring_buffer_open_device::<T>);
+        klass.acquire = Some(ring_buffer_acquire::<T>);
+        klass.release = Some(ring_buffer_release::<T>);
+        klass.close_device = Some(ring_buffer_close_device::<T>);
+        klass.start = Some(ring_buffer_start::<T>);
+        klass.pause = Some(ring_buffer_pause::<T>);
+        klass.resume = Some(ring_buffer_resume::<T>);
+        klass.stop = Some(ring_buffer_stop::<T>);
+        klass.delay = Some(ring_buffer_delay::<T>);
+        klass.activate = Some(ring_buffer_activate::<T>);
+        klass.commit = Some(ring_buffer_commit::<T>);
+        klass.clear_all = Some(ring_buffer_clear_all::<T>);
+    }
+}
+
+macro_rules! bool_vfunc_trampoline {
+    ($name:ident, $func:ident) => {
+        unsafe extern "C" fn $name<T: AudioRingBufferImpl>(
+            ptr: *mut ffi::GstAudioRingBuffer,
+        ) -> glib::ffi::gboolean {
+            unsafe {
+                let instance = &*(ptr as *mut T::Instance);
+                let imp = instance.imp();
+
+                match imp.$func() {
+                    Ok(()) => true,
+                    Err(err) => {
+                        err.log_with_imp(imp);
+                        false
+                    }
+                }
+                .into_glib()
+            }
+        }
+    };
+}
+
+bool_vfunc_trampoline!(ring_buffer_open_device, open_device);
+bool_vfunc_trampoline!(ring_buffer_close_device, close_device);
+bool_vfunc_trampoline!(ring_buffer_start, start);
+bool_vfunc_trampoline!(ring_buffer_pause, pause);
+bool_vfunc_trampoline!(ring_buffer_resume, resume);
+bool_vfunc_trampoline!(ring_buffer_stop, stop);
+
+// Keeps track of the memory allocated by `ring_buffer_acquire()`, which is freed again by
+// `ring_buffer_release()`
+fn memory_quark() -> glib::Quark {
+    static MEMORY_QUARK: std::sync::OnceLock<glib::Quark> = std::sync::OnceLock::new();
+
+    *MEMORY_QUARK.get_or_init(|| glib::Quark::from_str("gstreamer-rs-audio-ring-buffer-memory"))
+}
+
+unsafe extern "C" fn ring_buffer_acquire<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+    spec: *mut ffi::GstAudioRingBufferSpec,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        let spec = &mut *(spec as *mut AudioRingBufferSpec);
+
+        if let Err(err) = imp.acquire(spec) {
+            err.log_with_imp(imp);
+            return glib::ffi::GFALSE;
+        }
+
+        // Allocate the ring buffer memory unless the parent class already did so
+        if (*ptr).memory.is_null() {
+            let size = spec.segtotal().max(0) as usize * spec.segsize().max(0) as usize;
+            let memory = glib::ffi::g_malloc0(size) as *mut u8;
+            if spec.type_() == AudioRingBufferFormatType::Raw {
+                ffi::gst_audio_format_info_fill_silence(spec.0.info.finfo, memory as _, size);
+            }
+            (*ptr).memory = memory;
+            (*ptr).size = size;
+            glib::gobject_ffi::g_object_set_qdata(
+                ptr as *mut _,
+                memory_quark().into_glib(),
+                memory as glib::ffi::gpointer,
+            );
+        }
+
+        glib::ffi::GTRUE
+    }
+}
+
+unsafe extern "C" fn ring_buffer_release<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        let res = imp.release();
+
+        let memory =
+            glib::gobject_ffi::g_object_steal_qdata(ptr as *mut _, memory_quark().into_glib())
+                as *mut u8;
+        if !memory.is_null() && memory == (*ptr).memory {
+            glib::ffi::g_free(memory as glib::ffi::gpointer);
+            (*ptr).memory = ptr::null_mut();
+            (*ptr).size = 0;
+        }
+
+        match res {
+            Ok(()) => true,
+            Err(err) => {
+                err.log_with_imp(imp);
+                false
+            }
+        }
+        .into_glib()
+    }
+}
+
+unsafe extern "C" fn ring_buffer_delay<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+) -> u32 {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.delay()
+    }
+}
+
+unsafe extern "C" fn ring_buffer_activate<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+    active: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        match imp.activate(from_glib(active)) {
+            Ok(()) => true,
+            Err(err) => {
+                err.log_with_imp(imp);
+                false
+            }
+        }
+        .into_glib()
+    }
+}
+
+unsafe extern "C" fn ring_buffer_commit<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+    sample: *mut u64,
+    data: *mut u8,
+    in_samples: i32,
+    out_samples: i32,
+    accum: *mut i32,
+) -> u32 {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        let bpf = (*ptr).spec.info.bpf.max(0) as usize;
+        let data = if data.is_null() || in_samples <= 0 {
+            &[][..]
+        } else {
+            slice::from_raw_parts(data, in_samples as usize * bpf)
+        };
+
+        imp.commit(&mut *sample, data, in_samples, out_samples, &mut *accum)
+    }
+}
+
+unsafe extern "C" fn ring_buffer_clear_all<T: AudioRingBufferImpl>(
+    ptr: *mut ffi::GstAudioRingBuffer,
+) {
+    unsafe {
+        let instance = &*(ptr as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.clear_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::prelude::*;
+
+    pub mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct FakeDevice {
+            pub calls: Mutex<Vec<&'static str>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for FakeDevice {
+            const NAME: &'static str = "TestFakeAudioRingBuffer";
+            type Type = super::FakeDevice;
+            type ParentType = AudioRingBuffer;
+        }
+
+        impl ObjectImpl for FakeDevice {}
+
+        impl GstObjectImpl for FakeDevice {}
+
+        impl AudioRingBufferImpl for FakeDevice {
+            fn open_device(&self) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("open_device");
+                Ok(())
+            }
+
+            fn acquire(&self, spec: &mut AudioRingBufferSpec) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("acquire");
+                assert_eq!(spec.audio_info().rate(), 1000);
+                Ok(())
+            }
+
+            fn release(&self) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("release");
+                Ok(())
+            }
+
+            fn close_device(&self) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("close_device");
+                Ok(())
+            }
+
+            fn start(&self) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("start");
+                Ok(())
+            }
+
+            fn stop(&self) -> Result<(), LoggableError> {
+                self.calls.lock().unwrap().push("stop");
+                Ok(())
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct FakeDevice(ObjectSubclass<imp::FakeDevice>) @extends AudioRingBuffer, gst::Object;
+    }
+
+    #[test]
+    fn test_fake_device() {
+        gst::init().unwrap();
+
+        let ringbuffer = glib::Object::new::<FakeDevice>();
+
+        let caps = crate::AudioCapsBuilder::new_interleaved()
+            .format(crate::AUDIO_FORMAT_S16)
+            .rate(1000)
+            .channels(1)
+            .build();
+        // 10 samples per segment, 20 segments
+        let mut spec = AudioRingBufferSpec::from_caps(&caps, 200_000, 10_000).unwrap();
+        assert_eq!(spec.segsize(), 20);
+        assert_eq!(spec.segtotal(), 20);
+
+        ringbuffer.open_device().unwrap();
+        assert!(ringbuffer.device_is_open());
+        ringbuffer.acquire(&mut spec).unwrap();
+        assert!(ringbuffer.is_acquired());
+        assert_eq!(ringbuffer.samples_per_segment(), 10);
+        // The spec of an acquired ring buffer can't be changed anymore
+        let mut other_spec = AudioRingBufferSpec::from_caps(&caps, 400_000, 20_000).unwrap();
+        assert!(ringbuffer.acquire(&mut other_spec).is_err());
+        // Activation isn't implemented, which is not an error
+        ringbuffer.activate(true).unwrap();
+        assert!(ringbuffer.is_active());
+        assert_eq!(ringbuffer.samples_per_segment(), 10);
+
+        let data = (0..20u8).collect::<Vec<_>>();
+        let mut sample = 0;
+        let mut accum = 0;
+        assert_eq!(
+            ringbuffer.commit(&mut sample, &data, 10, 10, &mut accum),
+            10
+        );
+        assert_eq!(sample, 10);
+
+        ringbuffer.may_start(true);
+        ringbuffer.start().unwrap();
+        assert_eq!(ringbuffer.state(), crate::AudioRingBufferState::Started);
+
+        // Play back the first segment like a device thread would
+        let played = ringbuffer
+            .prepare_read(|segment, memory| {
+                assert_eq!(segment, 0);
+                memory.to_vec()
+            })
+            .unwrap();
+        assert_eq!(played, data);
+        ringbuffer.clear(0);
+        ringbuffer.advance(1);
+        assert_eq!(ringbuffer.samples_done(), 10);
+
+        ringbuffer.stop().unwrap();
+        ringbuffer.release().unwrap();
+        assert!(!ringbuffer.is_acquired());
+        ringbuffer.close_device().unwrap();
+
+        assert_eq!(
+            *ringbuffer.imp().calls.lock().unwrap(),
+            [
+                "open_device",
+                "acquire",
+                "start",
+                "stop",
+                "release",
+                "close_device"
+            ]
+        );
+    }
+}
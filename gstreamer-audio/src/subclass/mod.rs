@@ -10,6 +10,7 @@ mod audio_base_src;
 mod audio_decoder;
 mod audio_encoder;
 mod audio_filter;
+mod audio_ring_buffer;
 mod audio_sink;
 mod audio_src;
 
@@ -21,11 +22,12 @@ pub mod prelude {
         audio_aggregator::{AudioAggregatorImpl, AudioAggregatorImplExt},
         audio_aggregator_convert_pad::AudioAggregatorConvertPadImpl,
         audio_aggregator_pad::{AudioAggregatorPadImpl, AudioAggregatorPadImplExt},
-        audio_base_sink::AudioBaseSinkImpl,
-        audio_base_src::AudioBaseSrcImpl,
+        audio_base_sink::{AudioBaseSinkImpl, AudioBaseSinkImplExt},
+        audio_base_src::{AudioBaseSrcImpl, AudioBaseSrcImplExt},
         audio_decoder::{AudioDecoderImpl, AudioDecoderImplExt},
         audio_encoder::{AudioEncoderImpl, AudioEncoderImplExt},
         audio_filter::{AudioFilterImpl, AudioFilterImplExt},
+        audio_ring_buffer::{AudioRingBufferImpl, AudioRingBufferImplExt},
         audio_sink::{AudioSinkImpl, AudioSinkImplExt},
         audio_src::{AudioSrcImpl, AudioSrcImplExt},
     };
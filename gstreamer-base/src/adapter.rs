@@ -237,6 +237,15 @@ impl Adapter {
             ffi::gst_adapter_push(self.to_glib_none().0, buf.into_glib_ptr());
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a reader over the currently available data that does not consume it.
+    pub fn peek_reader(&self) -> AdapterPeekReader<'_> {
+        AdapterPeekReader {
+            adapter: self,
+            pos: 0,
+        }
+    }
 }
 
 impl io::Read for Adapter {
@@ -266,15 +275,90 @@ impl io::Read for Adapter {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Reader for looking ahead into the data of an [`Adapter`] without flushing it.
+///
+/// Created by [`Adapter::peek_reader`] and [`UniqueAdapter::peek_reader`].
 #[derive(Debug)]
-pub struct UniqueAdapter(Adapter);
+pub struct AdapterPeekReader<'a> {
+    adapter: &'a Adapter,
+    pos: usize,
+}
+
+impl AdapterPeekReader<'_> {
+    // rustdoc-stripper-ignore-next
+    /// Returns the offset of the next byte to be read, relative to the start of the
+    /// adapter.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+}
+
+impl io::Read for AdapterPeekReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        let len = std::cmp::min(buf.len(), self.adapter.available().saturating_sub(self.pos));
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        self.adapter
+            .copy(self.pos, &mut buf[0..len])
+            .map_err(io::Error::other)?;
+        self.pos += len;
+
+        Ok(len)
+    }
+}
+
+// Same semantics as the `io::Seek` implementations of `gst::buffer::BufferCursor`: seeking
+// beyond the end is clamped to the end and seeking before the start fails.
+impl io::Seek for AdapterPeekReader<'_> {
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64, io::Error> {
+        let size = self.adapter.available() as u64;
+        let before_start =
+            || io::Error::new(io::ErrorKind::InvalidInput, "Seek before start of adapter");
+        let cur_offset = std::cmp::min(self.pos as u64, size);
+
+        let pos = match pos {
+            io::SeekFrom::Start(off) => std::cmp::min(size, off),
+            io::SeekFrom::End(off) if off <= 0 => size,
+            io::SeekFrom::End(off) => size.checked_sub(off as u64).ok_or_else(before_start)?,
+            io::SeekFrom::Current(off) if off <= 0 => cur_offset
+                .checked_sub(off.unsigned_abs())
+                .ok_or_else(before_start)?,
+            io::SeekFrom::Current(off) => {
+                std::cmp::min(size, cur_offset.checked_add(off as u64).unwrap_or(size))
+            }
+        };
+        self.pos = pos as usize;
+
+        Ok(pos)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Adapter that can only be used by a single owner.
+///
+/// Unlike [`Adapter`] this implements [`io::BufRead`], which gives direct access to the
+/// data of the first queued buffer without copying.
+#[derive(Debug)]
+pub struct UniqueAdapter(
+    Adapter,
+    // Keeps the region returned by `BufRead::fill_buf()` mapped
+    Option<gst::MappedBuffer<gst::buffer::Readable>>,
+);
 
 unsafe impl Send for UniqueAdapter {}
 unsafe impl Sync for UniqueAdapter {}
 
 impl UniqueAdapter {
     pub fn new() -> Self {
-        Self(Adapter::new())
+        Self(Adapter::new(), None)
     }
 
     pub fn available(&self) -> usize {
@@ -397,6 +481,10 @@ impl UniqueAdapter {
         self.0.push(buf);
     }
 
+    pub fn peek_reader(&self) -> AdapterPeekReader<'_> {
+        self.0.peek_reader()
+    }
+
     #[doc(alias = "gst_adapter_map")]
     pub fn map(&mut self, nbytes: usize) -> Result<UniqueAdapterMap<'_>, glib::error::BoolError> {
         assert!(nbytes <= self.available());
@@ -459,3 +547,83 @@ impl io::Read for UniqueAdapter {
         self.0.read(buf)
     }
 }
+
+impl io::BufRead for UniqueAdapter {
+    fn fill_buf(&mut self) -> Result<&[u8], io::Error> {
+        self.1 = None;
+
+        let len = match self.available_fast() {
+            0 => self.available(),
+            len => len,
+        };
+        if len == 0 {
+            return Ok(&[]);
+        }
+
+        // Only the first buffer is returned unless the adapter already assembled more
+        // data, so this doesn't copy
+        let buffer = self.buffer_fast(len).map_err(io::Error::other)?;
+        let map = buffer
+            .into_mapped_buffer_readable()
+            .map_err(|_| io::Error::other("Failed to map buffer readable"))?;
+
+        Ok(self.1.insert(map).as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.1 = None;
+        self.flush(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Read, Seek, SeekFrom};
+
+    use super::*;
+
+    #[test]
+    fn test_buf_read() {
+        gst::init().unwrap();
+
+        let mut adapter = UniqueAdapter::new();
+        adapter.push(gst::Buffer::from_slice(b"abc\ndef"));
+        adapter.push(gst::Buffer::from_slice(b"gh\nij"));
+
+        assert_eq!(adapter.fill_buf().unwrap(), b"abc\ndef");
+
+        let mut peek = adapter.peek_reader();
+        let mut data = [0; 8];
+        peek.read_exact(&mut data).unwrap();
+        assert_eq!(&data, b"abc\ndefg");
+        assert_eq!(peek.position(), 8);
+        assert_eq!(peek.read(&mut data).unwrap(), 4);
+        assert_eq!(peek.read(&mut data).unwrap(), 0);
+        assert_eq!(adapter.available(), 12);
+
+        assert_eq!(peek.seek(SeekFrom::Start(2)).unwrap(), 2);
+        assert_eq!(peek.seek(SeekFrom::Current(4)).unwrap(), 6);
+        assert_eq!(peek.read(&mut data[..3]).unwrap(), 3);
+        assert_eq!(&data[..3], b"fgh");
+        assert_eq!(peek.seek(SeekFrom::Current(-9)).unwrap(), 0);
+        assert!(peek.seek(SeekFrom::Current(-1)).is_err());
+        assert_eq!(peek.seek(SeekFrom::End(2)).unwrap(), 10);
+        assert!(peek.seek(SeekFrom::End(13)).is_err());
+        assert_eq!(peek.seek(SeekFrom::End(0)).unwrap(), 12);
+        assert_eq!(peek.seek(SeekFrom::Start(100)).unwrap(), 12);
+        assert_eq!(peek.seek(SeekFrom::Current(i64::MAX)).unwrap(), 12);
+        assert_eq!(peek.position(), 12);
+
+        let mut line = String::new();
+        adapter.read_line(&mut line).unwrap();
+        assert_eq!(line, "abc\n");
+        line.clear();
+        adapter.read_line(&mut line).unwrap();
+        assert_eq!(line, "defgh\n");
+        line.clear();
+        adapter.read_line(&mut line).unwrap();
+        assert_eq!(line, "ij");
+        assert_eq!(adapter.available(), 0);
+        assert!(adapter.fill_buf().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,357 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::ReadError;
+
+// rustdoc-stripper-ignore-next
+/// Reader for parsing bit fields from a byte slice, most significant bit first.
+///
+/// Readers created with [`new_nal`](Self::new_nal) transparently skip the emulation
+/// prevention bytes of H.264/H.265 NAL units, i.e. every `0x03` that follows two `0x00`
+/// bytes. Positions are always reported relative to the underlying data.
+///
+/// All reads fail without changing the position if not enough data is left.
+///
+/// ```
+/// # use gstreamer_base::BitReader;
+/// let mut reader = BitReader::new(&[0b1010_0110, 0b1100_0000]);
+/// assert_eq!(reader.read_bit()?, true);
+/// assert_eq!(reader.read_bits_u8(3)?, 0b010);
+/// assert_eq!(reader.read_ue()?, 2);
+/// assert_eq!(reader.read_se()?, -1);
+/// # Ok::<(), gstreamer_base::ReadError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GstBitReader")]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    // Current byte in `data`
+    byte: usize,
+    // Bits already consumed from the current byte
+    bit: u32,
+    // Number of consecutive zero bytes directly before the current byte
+    zeros: u32,
+    nal: bool,
+}
+
+macro_rules! read_bits {
+    ($read:ident, $peek:ident, $ty:ty) => {
+        // rustdoc-stripper-ignore-next
+        /// Reads `n` bits as an unsigned integer.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `n` is larger than the number of bits of the return type.
+        #[inline]
+        pub fn $read(&mut self, n: u32) -> Result<$ty, ReadError> {
+            assert!(n <= <$ty>::BITS);
+            Ok(self.read_bits(n)? as $ty)
+        }
+
+        // rustdoc-stripper-ignore-next
+        /// Reads `n` bits as an unsigned integer without advancing the position.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `n` is larger than the number of bits of the return type.
+        #[inline]
+        pub fn $peek(&self, n: u32) -> Result<$ty, ReadError> {
+            let mut r = *self;
+            r.$read(n)
+        }
+    };
+}
+
+impl<'a> BitReader<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        Self {
+            data,
+            byte: 0,
+            bit: 0,
+            zeros: 0,
+            nal: false,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a reader for an escaped H.264/H.265 NAL unit that skips emulation
+    /// prevention bytes.
+    #[inline]
+    pub fn new_nal(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        Self {
+            nal: true,
+            ..Self::new(data)
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the current position in bits.
+    #[doc(alias = "gst_bit_reader_get_pos")]
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.byte * 8 + self.bit as usize
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of bits left in the underlying data.
+    ///
+    /// For NAL readers this includes any emulation prevention bytes that are still ahead.
+    #[doc(alias = "gst_bit_reader_get_remaining")]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.pos()
+    }
+
+    #[inline]
+    pub fn has_remaining(&self) -> bool {
+        self.remaining() > 0
+    }
+
+    #[inline]
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bit == 0
+    }
+
+    #[inline]
+    fn skip_emulation_prevention(&mut self) {
+        if self.nal && self.zeros >= 2 && self.data.get(self.byte) == Some(&0x03) {
+            self.byte += 1;
+            self.zeros = 0;
+        }
+    }
+
+    fn read_bits(&mut self, n: u32) -> Result<u64, ReadError> {
+        debug_assert!(n <= 64);
+
+        let mut r = *self;
+        let mut n = n;
+        let mut v = 0u64;
+
+        while n > 0 {
+            if r.bit == 0 {
+                r.skip_emulation_prevention();
+            }
+            let byte = *r.data.get(r.byte).ok_or(ReadError::NotEnoughData)?;
+
+            let avail = 8 - r.bit;
+            let take = avail.min(n);
+            let bits = ((byte as u64) >> (avail - take)) & ((1 << take) - 1);
+            v = (v << take) | bits;
+            r.bit += take;
+            n -= take;
+
+            if r.bit == 8 {
+                r.zeros = if byte == 0 { r.zeros + 1 } else { 0 };
+                r.byte += 1;
+                r.bit = 0;
+            }
+        }
+
+        *self = r;
+        Ok(v)
+    }
+
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool, ReadError> {
+        Ok(self.read_bits(1)? != 0)
+    }
+
+    #[inline]
+    pub fn peek_bit(&self) -> Result<bool, ReadError> {
+        let mut r = *self;
+        r.read_bit()
+    }
+
+    read_bits!(read_bits_u8, peek_bits_u8, u8);
+    read_bits!(read_bits_u16, peek_bits_u16, u16);
+    read_bits!(read_bits_u32, peek_bits_u32, u32);
+    read_bits!(read_bits_u64, peek_bits_u64, u64);
+
+    // rustdoc-stripper-ignore-next
+    /// Skips `n` bits.
+    #[doc(alias = "gst_bit_reader_skip")]
+    pub fn skip(&mut self, n: usize) -> Result<(), ReadError> {
+        let mut r = *self;
+        let mut n = n;
+        while n > 0 {
+            let take = n.min(64);
+            r.read_bits(take as u32)?;
+            n -= take;
+        }
+        *self = r;
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Skips to the start of the next byte unless already byte aligned.
+    #[doc(alias = "gst_bit_reader_skip_to_byte")]
+    pub fn skip_to_byte(&mut self) -> Result<(), ReadError> {
+        if self.bit != 0 {
+            self.read_bits(8 - self.bit)?;
+        }
+        Ok(())
+    }
+
+    // Values of up to 32 leading zero bits are accepted, which is needed for `u32::MAX` and
+    // `i32::MIN`.
+    fn read_exp_golomb(&mut self) -> Result<u64, ReadError> {
+        let mut r = *self;
+
+        let mut leading_zeros = 0;
+        while !r.read_bit()? {
+            leading_zeros += 1;
+            if leading_zeros > 32 {
+                return Err(ReadError::InvalidData);
+            }
+        }
+        let v = (1u64 << leading_zeros) - 1 + r.read_bits(leading_zeros)?;
+
+        *self = r;
+        Ok(v)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reads an unsigned Exp-Golomb coded value (`ue(v)`).
+    pub fn read_ue(&mut self) -> Result<u32, ReadError> {
+        let mut r = *self;
+        let v = u32::try_from(r.read_exp_golomb()?).map_err(|_| ReadError::InvalidData)?;
+
+        *self = r;
+        Ok(v)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reads a signed Exp-Golomb coded value (`se(v)`).
+    pub fn read_se(&mut self) -> Result<i32, ReadError> {
+        let mut r = *self;
+        let k = r.read_exp_golomb()? as i64;
+        let v = if k % 2 == 1 { (k + 1) / 2 } else { -(k / 2) };
+        let v = i32::try_from(v).map_err(|_| ReadError::InvalidData)?;
+
+        *self = r;
+        Ok(v)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if there is more data before the RBSP trailing bits (`more_rbsp_data()`).
+    pub fn more_rbsp_data(&self) -> bool {
+        let mut r = *self;
+        if r.bit == 0 {
+            r.skip_emulation_prevention();
+        }
+
+        let mut end = self.data.len();
+        loop {
+            let Some(last) = self.data[..end].iter().rposition(|&b| b != 0) else {
+                return false;
+            };
+
+            // A trailing emulation prevention byte is not part of the RBSP
+            if self.nal
+                && self.data[last] == 0x03
+                && last >= 2
+                && self.data[last - 2..last] == [0, 0]
+            {
+                end = last;
+                continue;
+            }
+
+            let stop_bit = last * 8 + 7 - self.data[last].trailing_zeros() as usize;
+            return r.pos() < stop_bit;
+        }
+    }
+}
+
+impl<'a> From<&'a [u8]> for BitReader<'a> {
+    #[inline]
+    fn from(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        Self::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() {
+        let data = [0b1011_0011, 0b1100_0101, 0xff];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_bit(), Ok(true));
+        assert_eq!(reader.peek_bits_u8(3), Ok(0b011));
+        assert_eq!(reader.read_bits_u16(10), Ok(0b01_1001_1110));
+        assert_eq!(reader.pos(), 11);
+        assert!(!reader.is_byte_aligned());
+        assert_eq!(reader.read_bits_u32(14), Err(ReadError::NotEnoughData));
+        assert_eq!(reader.pos(), 11);
+        assert_eq!(reader.read_bits_u32(13), Ok(0b0_0101_1111_1111));
+        assert!(!reader.has_remaining());
+
+        let mut reader = BitReader::new(&data);
+        reader.skip(3).unwrap();
+        reader.skip_to_byte().unwrap();
+        assert_eq!(reader.pos(), 8);
+        reader.skip_to_byte().unwrap();
+        assert_eq!(reader.pos(), 8);
+        assert_eq!(reader.skip(17), Err(ReadError::NotEnoughData));
+        assert_eq!(reader.read_bits_u64(16), Ok(0xc5ff));
+    }
+
+    #[test]
+    fn test_exp_golomb() {
+        // ue: 0, 1, 2, 3, 7 / se: 1, -1, 2
+        let data = [
+            0b1010_0110,
+            0b0100_0001,
+            0b0000_1001,
+            0b1001_0000,
+            0b0000_0000,
+        ];
+        let mut reader = BitReader::new(&data);
+
+        assert_eq!(reader.read_ue(), Ok(0));
+        assert_eq!(reader.read_ue(), Ok(1));
+        assert_eq!(reader.read_ue(), Ok(2));
+        assert_eq!(reader.read_ue(), Ok(3));
+        assert_eq!(reader.read_ue(), Ok(7));
+        assert_eq!(reader.read_se(), Ok(1));
+        assert_eq!(reader.read_se(), Ok(-1));
+        assert_eq!(reader.read_se(), Ok(2));
+
+        let pos = reader.pos();
+        assert_eq!(reader.read_ue(), Err(ReadError::NotEnoughData));
+        assert_eq!(reader.pos(), pos);
+
+        let mut reader = BitReader::new(&[0, 0, 0, 0, 0x40]);
+        assert_eq!(reader.read_ue(), Err(ReadError::InvalidData));
+
+        // 32 leading zeros with a non-zero suffix don't fit into an u32
+        let mut reader = BitReader::new(&[0, 0, 0, 0, 0x80, 0, 0, 0, 0x80]);
+        assert_eq!(reader.read_ue(), Err(ReadError::InvalidData));
+        assert_eq!(reader.pos(), 0);
+    }
+
+    #[test]
+    fn test_nal() {
+        let data = [0x00, 0x00, 0x03, 0x01, 0x00, 0x00, 0x03, 0x80];
+
+        let mut reader = BitReader::new_nal(&data);
+        assert_eq!(reader.read_bits_u32(24), Ok(0x000001));
+        assert_eq!(reader.pos(), 32);
+        assert!(reader.more_rbsp_data());
+        assert_eq!(reader.read_bits_u16(16), Ok(0x0000));
+        assert!(!reader.more_rbsp_data());
+        assert_eq!(reader.read_bit(), Ok(true));
+        assert_eq!(reader.pos(), 57);
+
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_bits_u32(24), Ok(0x000003));
+
+        let reader = BitReader::new_nal(&[0x80, 0x00, 0x00, 0x03]);
+        assert!(!reader.more_rbsp_data());
+    }
+}
@@ -0,0 +1,220 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+/// Writer for serializing bit fields into a byte vector, most significant bit first.
+///
+/// The written data is not escaped, use [`nal::escape`](crate::nal::escape) to insert
+/// emulation prevention bytes before putting it into an H.264/H.265 NAL unit.
+///
+/// ```
+/// # use gstreamer_base::BitWriter;
+/// let mut writer = BitWriter::new();
+/// writer.write_bit(true);
+/// writer.write_bits(0b010, 3);
+/// writer.write_ue(2);
+/// writer.write_se(-1);
+/// assert_eq!(writer.into_vec(), &[0b1010_0110, 0b1100_0000]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[doc(alias = "GstBitWriter")]
+pub struct BitWriter {
+    data: Vec<u8>,
+    // Pending bits of the last, incomplete byte in the most significant bits
+    cur: u8,
+    // Number of pending bits in `cur`
+    bits: u32,
+}
+
+impl BitWriter {
+    #[doc(alias = "gst_bit_writer_new")]
+    #[inline]
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    #[doc(alias = "gst_bit_writer_new_with_size")]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        skip_assert_initialized!();
+        Self {
+            data: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of bits written so far.
+    #[doc(alias = "gst_bit_writer_get_size")]
+    #[inline]
+    pub fn bit_size(&self) -> usize {
+        self.data.len() * 8 + self.bits as usize
+    }
+
+    #[inline]
+    pub fn is_byte_aligned(&self) -> bool {
+        self.bits == 0
+    }
+
+    #[inline]
+    pub fn write_bit(&mut self, bit: bool) {
+        self.write_bits(bit as u64, 1);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes the `n` least significant bits of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than 64.
+    #[doc(alias = "gst_bit_writer_put_bits_uint64")]
+    pub fn write_bits(&mut self, value: u64, n: u32) {
+        assert!(n <= 64);
+
+        let mut n = n;
+        while n > 0 {
+            let free = 8 - self.bits;
+            let take = free.min(n);
+            let bits = ((value >> (n - take)) & ((1 << take) - 1)) as u8;
+            self.cur |= bits << (free - take);
+            self.bits += take;
+            n -= take;
+
+            if self.bits == 8 {
+                self.data.push(self.cur);
+                self.cur = 0;
+                self.bits = 0;
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes `bytes`, which doesn't need to be byte aligned.
+    #[doc(alias = "gst_bit_writer_put_bytes")]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        if self.bits == 0 {
+            self.data.extend_from_slice(bytes);
+        } else {
+            for &b in bytes {
+                self.write_bits(b as u64, 8);
+            }
+        }
+    }
+
+    fn write_exp_golomb(&mut self, v: u64) {
+        let v = v + 1;
+        let len = u64::BITS - v.leading_zeros();
+        self.write_bits(0, len - 1);
+        self.write_bits(v, len);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes an unsigned Exp-Golomb coded value (`ue(v)`).
+    #[inline]
+    pub fn write_ue(&mut self, v: u32) {
+        self.write_exp_golomb(v as u64);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes a signed Exp-Golomb coded value (`se(v)`).
+    #[inline]
+    pub fn write_se(&mut self, v: i32) {
+        let v = v as i64;
+        let k = if v > 0 { 2 * v - 1 } else { -2 * v };
+        self.write_exp_golomb(k as u64);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Fills up the current byte with `fill` bits unless already byte aligned.
+    #[doc(alias = "gst_bit_writer_align_bytes")]
+    pub fn align_bytes(&mut self, fill: bool) {
+        if self.bits != 0 {
+            let n = 8 - self.bits;
+            self.write_bits(if fill { (1 << n) - 1 } else { 0 }, n);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes the RBSP stop bit followed by zero bits up to the next byte boundary
+    /// (`rbsp_trailing_bits()`).
+    pub fn write_rbsp_trailing_bits(&mut self) {
+        self.write_bit(true);
+        self.align_bytes(false);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the written data, with the last byte padded with zero bits if necessary.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        let mut data = self.data;
+        if self.bits != 0 {
+            data.push(self.cur);
+        }
+        data
+    }
+
+    #[doc(alias = "gst_bit_writer_reset_and_get_buffer")]
+    #[inline]
+    pub fn into_buffer(self) -> gst::Buffer {
+        skip_assert_initialized!();
+        gst::Buffer::from_mut_slice(self.into_vec())
+    }
+}
+
+impl From<BitWriter> for Vec<u8> {
+    #[inline]
+    fn from(writer: BitWriter) -> Self {
+        skip_assert_initialized!();
+        writer.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BitReader;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0b101, 3);
+        writer.write_bits(u64::MAX, 64);
+        writer.write_bytes(&[0xab, 0xcd]);
+        for v in [0, 1, 2, 1000, u32::MAX - 1, u32::MAX] {
+            writer.write_ue(v);
+        }
+        for v in [0, 1, -1, -1000, i32::MAX, i32::MIN + 1, i32::MIN] {
+            writer.write_se(v);
+        }
+        writer.write_rbsp_trailing_bits();
+        assert!(writer.is_byte_aligned());
+
+        let data = writer.into_vec();
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_bits_u8(3), Ok(0b101));
+        assert_eq!(reader.read_bits_u64(64), Ok(u64::MAX));
+        assert_eq!(reader.read_bits_u16(16), Ok(0xabcd));
+        for v in [0, 1, 2, 1000, u32::MAX - 1, u32::MAX] {
+            assert_eq!(reader.read_ue(), Ok(v));
+        }
+        for v in [0, 1, -1, -1000, i32::MAX, i32::MIN + 1, i32::MIN] {
+            assert_eq!(reader.read_se(), Ok(v));
+        }
+        assert!(!reader.more_rbsp_data());
+        assert_eq!(reader.read_bit(), Ok(true));
+        reader.skip_to_byte().unwrap();
+        assert!(!reader.has_remaining());
+    }
+
+    #[test]
+    fn test_align() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0, 2);
+        assert_eq!(writer.bit_size(), 2);
+        writer.align_bytes(true);
+        writer.align_bytes(false);
+        writer.write_bit(true);
+        assert_eq!(writer.bit_size(), 9);
+        assert_eq!(writer.into_vec(), &[0b0011_1111, 0b1000_0000]);
+    }
+}
@@ -0,0 +1,349 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{error, ffi::CStr, fmt};
+
+// rustdoc-stripper-ignore-next
+/// Error returned by [`ByteReader`] and [`BitReader`](crate::BitReader).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadError {
+    // rustdoc-stripper-ignore-next
+    /// Not enough data is left to read the value.
+    NotEnoughData,
+    // rustdoc-stripper-ignore-next
+    /// The data can't be parsed as the requested value.
+    InvalidData,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotEnoughData => f.write_str("Not enough data"),
+            Self::InvalidData => f.write_str("Invalid data"),
+        }
+    }
+}
+
+impl error::Error for ReadError {}
+
+impl From<ReadError> for glib::BoolError {
+    fn from(err: ReadError) -> Self {
+        skip_assert_initialized!();
+        glib::bool_error!("{}", err)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Reader for parsing values from a byte slice.
+///
+/// Works on any `&[u8]`, e.g. a mapped [`gst::BufferRef`] or the map of an
+/// [`Adapter`](crate::Adapter). All reads advance the position, all peeks leave it
+/// unchanged and all of them fail without changing the position if not enough data is
+/// left.
+///
+/// ```
+/// # use gstreamer_base::ByteReader;
+/// let mut reader = ByteReader::new(&[0x00, 0x01, 0x02, 0x03, 0x04]);
+/// assert_eq!(reader.read_u8()?, 0x00);
+/// assert_eq!(reader.read_u16_be()?, 0x0102);
+/// assert_eq!(reader.peek_u16_le()?, 0x0403);
+/// assert_eq!(reader.remaining(), 2);
+/// # Ok::<(), gstreamer_base::ReadError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GstByteReader")]
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+macro_rules! read_int {
+    ($read:ident, $peek:ident, $ty:ty, $from:ident) => {
+        #[inline]
+        pub fn $peek(&self) -> Result<$ty, ReadError> {
+            let bytes = self.peek_array::<{ std::mem::size_of::<$ty>() }>()?;
+            Ok(<$ty>::$from(bytes))
+        }
+
+        #[inline]
+        pub fn $read(&mut self) -> Result<$ty, ReadError> {
+            let v = self.$peek()?;
+            self.pos += std::mem::size_of::<$ty>();
+            Ok(v)
+        }
+    };
+}
+
+macro_rules! read_int24 {
+    ($read:ident, $peek:ident, $ty:ty, $be:expr) => {
+        #[inline]
+        pub fn $peek(&self) -> Result<$ty, ReadError> {
+            let [a, b, c] = self.peek_array::<3>()?;
+            let v = if $be {
+                u32::from_be_bytes([0, a, b, c])
+            } else {
+                u32::from_le_bytes([a, b, c, 0])
+            };
+            // Sign-extend for the signed variants
+            Ok(((v << 8) as $ty) >> 8)
+        }
+
+        #[inline]
+        pub fn $read(&mut self) -> Result<$ty, ReadError> {
+            let v = self.$peek()?;
+            self.pos += 3;
+            Ok(v)
+        }
+    };
+}
+
+impl<'a> ByteReader<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        Self { data, pos: 0 }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the current position in bytes.
+    #[doc(alias = "gst_byte_reader_get_pos")]
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    #[doc(alias = "gst_byte_reader_set_pos")]
+    #[inline]
+    pub fn set_pos(&mut self, pos: usize) -> Result<(), ReadError> {
+        if pos > self.data.len() {
+            return Err(ReadError::NotEnoughData);
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    #[doc(alias = "gst_byte_reader_get_size")]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[doc(alias = "gst_byte_reader_get_remaining")]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    #[inline]
+    pub fn has_remaining(&self) -> bool {
+        self.pos < self.data.len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the complete underlying data.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the data that was not read yet.
+    #[inline]
+    pub fn remaining_data(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    #[doc(alias = "gst_byte_reader_skip")]
+    #[inline]
+    pub fn skip(&mut self, n: usize) -> Result<(), ReadError> {
+        if n > self.remaining() {
+            return Err(ReadError::NotEnoughData);
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    #[doc(alias = "gst_byte_reader_peek_data")]
+    #[inline]
+    pub fn peek_bytes(&self, n: usize) -> Result<&'a [u8], ReadError> {
+        if n > self.remaining() {
+            return Err(ReadError::NotEnoughData);
+        }
+        Ok(&self.data[self.pos..][..n])
+    }
+
+    #[doc(alias = "gst_byte_reader_get_data")]
+    #[inline]
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], ReadError> {
+        let bytes = self.peek_bytes(n)?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    #[inline]
+    pub fn peek_array<const N: usize>(&self) -> Result<[u8; N], ReadError> {
+        Ok(self.peek_bytes(N)?.try_into().unwrap())
+    }
+
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ReadError> {
+        let v = self.peek_array::<N>()?;
+        self.pos += N;
+        Ok(v)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Splits off a reader for the next `n` bytes and advances this reader past them.
+    ///
+    /// This is useful for parsing length-prefixed sections.
+    #[doc(alias = "gst_byte_reader_get_sub_reader")]
+    #[inline]
+    pub fn sub_reader(&mut self, n: usize) -> Result<ByteReader<'a>, ReadError> {
+        Ok(ByteReader::new(self.read_bytes(n)?))
+    }
+
+    read_int!(read_u8, peek_u8, u8, from_be_bytes);
+    read_int!(read_i8, peek_i8, i8, from_be_bytes);
+    read_int!(read_u16_be, peek_u16_be, u16, from_be_bytes);
+    read_int!(read_u16_le, peek_u16_le, u16, from_le_bytes);
+    read_int!(read_i16_be, peek_i16_be, i16, from_be_bytes);
+    read_int!(read_i16_le, peek_i16_le, i16, from_le_bytes);
+    read_int24!(read_u24_be, peek_u24_be, u32, true);
+    read_int24!(read_u24_le, peek_u24_le, u32, false);
+    read_int24!(read_i24_be, peek_i24_be, i32, true);
+    read_int24!(read_i24_le, peek_i24_le, i32, false);
+    read_int!(read_u32_be, peek_u32_be, u32, from_be_bytes);
+    read_int!(read_u32_le, peek_u32_le, u32, from_le_bytes);
+    read_int!(read_i32_be, peek_i32_be, i32, from_be_bytes);
+    read_int!(read_i32_le, peek_i32_le, i32, from_le_bytes);
+    read_int!(read_u64_be, peek_u64_be, u64, from_be_bytes);
+    read_int!(read_u64_le, peek_u64_le, u64, from_le_bytes);
+    read_int!(read_i64_be, peek_i64_be, i64, from_be_bytes);
+    read_int!(read_i64_le, peek_i64_le, i64, from_le_bytes);
+    read_int!(read_f32_be, peek_f32_be, f32, from_be_bytes);
+    read_int!(read_f32_le, peek_f32_le, f32, from_le_bytes);
+    read_int!(read_f64_be, peek_f64_be, f64, from_be_bytes);
+    read_int!(read_f64_le, peek_f64_le, f64, from_le_bytes);
+
+    // rustdoc-stripper-ignore-next
+    /// Reads a NUL-terminated string, including the terminator.
+    #[doc(alias = "gst_byte_reader_get_string_utf8")]
+    pub fn read_cstr(&mut self) -> Result<&'a CStr, ReadError> {
+        let s = CStr::from_bytes_until_nul(self.remaining_data())
+            .map_err(|_| ReadError::NotEnoughData)?;
+        self.pos += s.to_bytes_with_nul().len();
+        Ok(s)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reads a NUL-terminated UTF-8 string, including the terminator.
+    #[doc(alias = "gst_byte_reader_get_string_utf8")]
+    pub fn read_str(&mut self) -> Result<&'a str, ReadError> {
+        let s = CStr::from_bytes_until_nul(self.remaining_data())
+            .map_err(|_| ReadError::NotEnoughData)?
+            .to_str()
+            .map_err(|_| ReadError::InvalidData)?;
+        self.pos += s.len() + 1;
+        Ok(s)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the offset relative to the current position of the first big-endian
+    /// 32 bit value that matches `pattern` after applying `mask`.
+    ///
+    /// Only the next `size` bytes are scanned.
+    #[doc(alias = "gst_byte_reader_masked_scan_uint32")]
+    pub fn masked_scan_u32(&self, mask: u32, pattern: u32, size: usize) -> Option<usize> {
+        let data = self.remaining_data();
+        let data = &data[..size.min(data.len())];
+        data.windows(4)
+            .position(|w| u32::from_be_bytes([w[0], w[1], w[2], w[3]]) & mask == pattern)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the offset relative to the current position of the next
+    /// `00 00 01` start code.
+    pub fn find_start_code(&self) -> Option<usize> {
+        self.remaining_data()
+            .windows(3)
+            .position(|w| w == [0x00, 0x00, 0x01])
+    }
+}
+
+impl<'a> From<&'a [u8]> for ByteReader<'a> {
+    #[inline]
+    fn from(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        Self::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xff, 0xfe, 0xfd,
+        ];
+        let mut reader = ByteReader::new(&data);
+
+        assert_eq!(reader.peek_u32_be(), Ok(0x01020304));
+        assert_eq!(reader.peek_u32_le(), Ok(0x04030201));
+        assert_eq!(reader.read_u8(), Ok(0x01));
+        assert_eq!(reader.read_u16_le(), Ok(0x0302));
+        assert_eq!(reader.read_u24_be(), Ok(0x040506));
+        assert_eq!(reader.read_u16_be(), Ok(0x0708));
+        assert_eq!(reader.pos(), 8);
+        assert_eq!(reader.peek_i24_be(), Ok(-259));
+        assert_eq!(reader.peek_u24_le(), Ok(0xfdfeff));
+        assert_eq!(reader.read_i16_be(), Ok(-2));
+        assert_eq!(reader.read_u16_be(), Err(ReadError::NotEnoughData));
+        assert_eq!(reader.pos(), 10);
+        assert_eq!(reader.read_i8(), Ok(-3));
+        assert!(!reader.has_remaining());
+
+        let mut reader = ByteReader::new(&[0x3f, 0x80, 0x00, 0x00]);
+        assert_eq!(reader.read_f32_be(), Ok(1.0));
+    }
+
+    #[test]
+    fn test_sub_reader() {
+        let data = [0x00, 0x02, 0xaa, 0xbb, 0xcc];
+        let mut reader = ByteReader::new(&data);
+
+        let len = reader.read_u16_be().unwrap() as usize;
+        let mut sub = reader.sub_reader(len).unwrap();
+        assert_eq!(sub.read_bytes(2), Ok(&[0xaa, 0xbb][..]));
+        assert_eq!(sub.read_u8(), Err(ReadError::NotEnoughData));
+        assert_eq!(reader.read_u8(), Ok(0xcc));
+        assert_eq!(reader.sub_reader(1), Err(ReadError::NotEnoughData));
+    }
+
+    #[test]
+    fn test_strings_and_scan() {
+        let data = b"abc\0\xff\0\x00\x00\x00\x01\x67";
+        let mut reader = ByteReader::new(data);
+
+        assert_eq!(reader.read_str(), Ok("abc"));
+        assert_eq!(reader.read_str(), Err(ReadError::InvalidData));
+        assert_eq!(reader.pos(), 4);
+        assert_eq!(reader.read_cstr().unwrap().to_bytes(), b"\xff");
+
+        assert_eq!(reader.find_start_code(), Some(1));
+        assert_eq!(
+            reader.masked_scan_u32(0xffffffff, 0x00000001, reader.remaining()),
+            Some(0)
+        );
+        assert_eq!(reader.masked_scan_u32(0xffffffff, 0x00000167, 4), None);
+        assert_eq!(
+            reader.masked_scan_u32(0xffffffff, 0x00000167, reader.remaining()),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+/// Writer for serializing values into a growable byte vector.
+///
+/// Writing after [`set_pos`](Self::set_pos) moved the position backwards overwrites the
+/// existing data and extends it if necessary.
+///
+/// ```
+/// # use gstreamer_base::ByteWriter;
+/// let mut writer = ByteWriter::new();
+/// writer.write_u8(0x01);
+/// writer.write_u16_be(0x0203);
+/// writer.write_u32_le(0x07060504);
+/// assert_eq!(writer.as_slice(), &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[doc(alias = "GstByteWriter")]
+pub struct ByteWriter {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+macro_rules! write_int {
+    ($write:ident, $ty:ty, $to:ident) => {
+        #[inline]
+        pub fn $write(&mut self, v: $ty) {
+            self.write_bytes(&v.$to());
+        }
+    };
+}
+
+macro_rules! write_int24 {
+    ($write:ident, $ty:ty, $be:expr) => {
+        #[inline]
+        pub fn $write(&mut self, v: $ty) {
+            let v = v as u32;
+            if $be {
+                self.write_bytes(&v.to_be_bytes()[1..]);
+            } else {
+                self.write_bytes(&v.to_le_bytes()[..3]);
+            }
+        }
+    };
+}
+
+impl ByteWriter {
+    #[doc(alias = "gst_byte_writer_new")]
+    #[inline]
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    #[doc(alias = "gst_byte_writer_new_with_size")]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        skip_assert_initialized!();
+        Self {
+            data: Vec::with_capacity(capacity),
+            pos: 0,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a writer that appends to `data`.
+    #[inline]
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        skip_assert_initialized!();
+        let pos = data.len();
+        Self { data, pos }
+    }
+
+    #[doc(alias = "gst_byte_writer_get_pos")]
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Moves the position to `pos`, which must not be after the end of the written data.
+    #[doc(alias = "gst_byte_writer_set_pos")]
+    #[inline]
+    pub fn set_pos(&mut self, pos: usize) -> Result<(), glib::BoolError> {
+        if pos > self.data.len() {
+            return Err(glib::bool_error!(
+                "Position {pos} after end of data {}",
+                self.data.len()
+            ));
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    #[doc(alias = "gst_byte_writer_get_size")]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+
+    #[doc(alias = "gst_byte_writer_reset_and_get_buffer")]
+    #[inline]
+    pub fn into_buffer(self) -> gst::Buffer {
+        skip_assert_initialized!();
+        gst::Buffer::from_mut_slice(self.data)
+    }
+
+    #[doc(alias = "gst_byte_writer_put_data")]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        let overlap = bytes.len().min(self.data.len() - self.pos);
+        let (overwrite, append) = bytes.split_at(overlap);
+        self.data[self.pos..][..overlap].copy_from_slice(overwrite);
+        self.data.extend_from_slice(append);
+        self.pos += bytes.len();
+    }
+
+    #[doc(alias = "gst_byte_writer_fill")]
+    pub fn fill(&mut self, value: u8, n: usize) {
+        let overlap = n.min(self.data.len() - self.pos);
+        self.data[self.pos..][..overlap].fill(value);
+        self.data.resize(self.data.len() + n - overlap, value);
+        self.pos += n;
+    }
+
+    write_int!(write_u8, u8, to_be_bytes);
+    write_int!(write_i8, i8, to_be_bytes);
+    write_int!(write_u16_be, u16, to_be_bytes);
+    write_int!(write_u16_le, u16, to_le_bytes);
+    write_int!(write_i16_be, i16, to_be_bytes);
+    write_int!(write_i16_le, i16, to_le_bytes);
+    write_int24!(write_u24_be, u32, true);
+    write_int24!(write_u24_le, u32, false);
+    write_int24!(write_i24_be, i32, true);
+    write_int24!(write_i24_le, i32, false);
+    write_int!(write_u32_be, u32, to_be_bytes);
+    write_int!(write_u32_le, u32, to_le_bytes);
+    write_int!(write_i32_be, i32, to_be_bytes);
+    write_int!(write_i32_le, i32, to_le_bytes);
+    write_int!(write_u64_be, u64, to_be_bytes);
+    write_int!(write_u64_le, u64, to_le_bytes);
+    write_int!(write_i64_be, i64, to_be_bytes);
+    write_int!(write_i64_le, i64, to_le_bytes);
+    write_int!(write_f32_be, f32, to_be_bytes);
+    write_int!(write_f32_le, f32, to_le_bytes);
+    write_int!(write_f64_be, f64, to_be_bytes);
+    write_int!(write_f64_le, f64, to_le_bytes);
+
+    // rustdoc-stripper-ignore-next
+    /// Writes `s` followed by a NUL terminator.
+    #[doc(alias = "gst_byte_writer_put_string_utf8")]
+    pub fn write_str(&mut self, s: &str) {
+        self.write_bytes(s.as_bytes());
+        self.write_u8(0);
+    }
+}
+
+impl From<Vec<u8>> for ByteWriter {
+    #[inline]
+    fn from(data: Vec<u8>) -> Self {
+        skip_assert_initialized!();
+        Self::from_vec(data)
+    }
+}
+
+impl From<ByteWriter> for Vec<u8> {
+    #[inline]
+    fn from(writer: ByteWriter) -> Self {
+        skip_assert_initialized!();
+        writer.into_vec()
+    }
+}
+
+impl std::io::Write for ByteWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_bytes(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteReader;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut writer = ByteWriter::new();
+        writer.write_u8(0xab);
+        writer.write_i16_le(-2);
+        writer.write_u24_be(0x123456);
+        writer.write_i24_le(-259);
+        writer.write_u64_be(0x0102030405060708);
+        writer.write_f64_le(0.5);
+        writer.write_str("abc");
+
+        let data = writer.into_vec();
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_u8(), Ok(0xab));
+        assert_eq!(reader.read_i16_le(), Ok(-2));
+        assert_eq!(reader.read_u24_be(), Ok(0x123456));
+        assert_eq!(reader.read_i24_le(), Ok(-259));
+        assert_eq!(reader.read_u64_be(), Ok(0x0102030405060708));
+        assert_eq!(reader.read_f64_le(), Ok(0.5));
+        assert_eq!(reader.read_str(), Ok("abc"));
+        assert!(!reader.has_remaining());
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut writer = ByteWriter::new();
+        writer.write_u16_be(0);
+        writer.write_bytes(&[0xaa, 0xbb, 0xcc]);
+
+        // Patch in the length field afterwards
+        writer.set_pos(0).unwrap();
+        writer.write_u16_be(3);
+        assert_eq!(writer.pos(), 2);
+
+        writer.set_pos(4).unwrap();
+        writer.fill(0xff, 3);
+        assert_eq!(
+            writer.as_slice(),
+            &[0x00, 0x03, 0xaa, 0xbb, 0xff, 0xff, 0xff]
+        );
+
+        assert!(writer.set_pos(8).is_err());
+    }
+}
@@ -32,12 +32,21 @@ mod adapter;
 pub use crate::adapter::*;
 mod flow_combiner;
 pub use crate::flow_combiner::*;
+mod byte_reader;
+pub use crate::byte_reader::*;
+mod byte_writer;
+pub use crate::byte_writer::*;
+mod bit_reader;
+pub use crate::bit_reader::*;
+mod bit_writer;
+pub use crate::bit_writer::*;
 mod aggregator;
 mod aggregator_pad;
 mod base_parse;
 mod base_sink;
 mod base_src;
 mod base_transform;
+pub mod nal;
 
 pub mod base_parse_frame;
 pub use crate::base_parse_frame::BaseParseFrame;
@@ -0,0 +1,76 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Helpers for the emulation prevention bytes of H.264/H.265 NAL units.
+//!
+//! Inside a NAL unit every `00 00 0x` sequence with `x <= 3` is escaped as `00 00 03 0x`
+//! so that it can't be mistaken for a start code.
+//! [`BitReader::new_nal`](crate::BitReader::new_nal) handles this while parsing, the
+//! functions here convert complete payloads.
+
+// rustdoc-stripper-ignore-next
+/// Inserts emulation prevention bytes into a raw byte sequence payload (RBSP).
+pub fn escape(data: &[u8]) -> Vec<u8> {
+    skip_assert_initialized!();
+
+    let mut res = Vec::with_capacity(data.len() + data.len() / 64);
+    let mut zeros = 0;
+    for &b in data {
+        if zeros >= 2 && b <= 0x03 {
+            res.push(0x03);
+            zeros = 0;
+        }
+        res.push(b);
+        zeros = if b == 0x00 { zeros + 1 } else { 0 };
+    }
+
+    // A payload ending with a zero byte gets a final emulation prevention byte
+    if data.last() == Some(&0x00) {
+        res.push(0x03);
+    }
+
+    res
+}
+
+// rustdoc-stripper-ignore-next
+/// Removes emulation prevention bytes from the payload of a NAL unit.
+pub fn unescape(data: &[u8]) -> Vec<u8> {
+    skip_assert_initialized!();
+
+    let mut res = Vec::with_capacity(data.len());
+    let mut zeros = 0;
+    for &b in data {
+        if zeros >= 2 && b == 0x03 {
+            zeros = 0;
+            continue;
+        }
+        res.push(b);
+        zeros = if b == 0x00 { zeros + 1 } else { 0 };
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        let rbsp = [
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00,
+        ];
+        let escaped = escape(&rbsp);
+        assert_eq!(
+            escaped,
+            &[
+                0x00, 0x00, 0x03, 0x01, 0x00, 0x00, 0x03, 0x00, 0x00, 0x03, 0x00, 0x04, 0x00, 0x00,
+                0x03
+            ]
+        );
+        assert_eq!(unescape(&escaped), &rbsp);
+
+        assert_eq!(escape(&[0x00, 0x00, 0x04]), &[0x00, 0x00, 0x04]);
+        assert_eq!(unescape(&[0x00, 0x03, 0x03]), &[0x00, 0x03, 0x03]);
+    }
+}
@@ -30,6 +30,13 @@ mod test_clock;
 pub mod harness;
 pub use crate::harness::Harness;
 
+#[cfg(feature = "rtp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rtp")))]
+pub mod rtp_roundtrip;
+#[cfg(feature = "rtp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rtp")))]
+pub use crate::rtp_roundtrip::{RTPNetworkPattern, RTPRoundtrip};
+
 // Re-export all the traits in a prelude module, so that applications
 // can always "use gst_check::prelude::*" without getting conflicts
 pub mod prelude {
@@ -0,0 +1,700 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Round-trip testing of RTP payloaders and depayloaders.
+//!
+//! [`RTPRoundtrip`] connects a payloader and a depayloader through two
+//! [`Harness`]es and a simulated network that can drop, duplicate and reorder
+//! packets according to an [`RTPNetworkPattern`].
+//!
+//! ```no_run
+//! # use gstreamer_check::rtp_roundtrip::{RTPNetworkPattern, RTPRoundtrip};
+//! # gst::init().unwrap();
+//! let pay = gst::ElementFactory::make("rtpvrawpay").build().unwrap();
+//! let depay = gst::ElementFactory::make("rtpvrawdepay").build().unwrap();
+//!
+//! let mut roundtrip = RTPRoundtrip::builder(&pay, &depay)
+//!     .caps(&gst::Caps::builder("video/x-raw").build())
+//!     .mtu(1400)
+//!     .seqnum_offset(u16::MAX - 2)
+//!     .network(RTPNetworkPattern::new().drop_packet(5).delay_packet(7, 2))
+//!     .build();
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use gst::prelude::*;
+
+use crate::Harness;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RTPPacketFate {
+    Delivered,
+    Dropped,
+    Duplicated,
+    // rustdoc-stripper-ignore-next
+    /// Delivered after the given number of following packets.
+    Delayed(u64),
+}
+
+// rustdoc-stripper-ignore-next
+/// Information about a packet produced by the payloader.
+#[derive(Debug, Clone)]
+pub struct RTPPacketInfo {
+    pub index: u64,
+    pub seqnum: u16,
+    pub timestamp: u32,
+    pub ssrc: u32,
+    pub payload_type: u8,
+    pub marker: bool,
+    pub payload_size: u32,
+    pub pts: Option<gst::ClockTime>,
+    pub flags: gst::BufferFlags,
+    pub fate: RTPPacketFate,
+}
+
+// rustdoc-stripper-ignore-next
+/// Deterministic network behaviour between payloader and depayloader.
+///
+/// Packets are identified by their index in the output of the payloader,
+/// starting at 0.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct RTPNetworkPattern {
+    drop: BTreeSet<u64>,
+    duplicate: BTreeSet<u64>,
+    delay: BTreeMap<u64, u64>,
+}
+
+impl RTPNetworkPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn drop_packet(mut self, index: u64) -> Self {
+        self.drop.insert(index);
+        self
+    }
+
+    pub fn drop_packets(mut self, indices: impl IntoIterator<Item = u64>) -> Self {
+        self.drop.extend(indices);
+        self
+    }
+
+    pub fn duplicate_packet(mut self, index: u64) -> Self {
+        self.duplicate.insert(index);
+        self
+    }
+
+    pub fn duplicate_packets(mut self, indices: impl IntoIterator<Item = u64>) -> Self {
+        self.duplicate.extend(indices);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Delivers packet `index` only after the following `by` packets were
+    /// produced by the payloader.
+    pub fn delay_packet(mut self, index: u64, by: u64) -> Self {
+        self.delay.insert(index, by);
+        self
+    }
+
+    fn fate(&self, index: u64) -> RTPPacketFate {
+        if self.drop.contains(&index) {
+            RTPPacketFate::Dropped
+        } else if let Some(by) = self.delay.get(&index) {
+            RTPPacketFate::Delayed(*by)
+        } else if self.duplicate.contains(&index) {
+            RTPPacketFate::Duplicated
+        } else {
+            RTPPacketFate::Delivered
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Payloader/depayloader pair connected by a simulated network.
+#[derive(Debug)]
+pub struct RTPRoundtrip {
+    pay: Harness,
+    depay: Harness,
+    network: RTPNetworkPattern,
+    depay_caps: Option<gst::Caps>,
+    held: Vec<(u64, gst::Buffer)>,
+    packets: Vec<RTPPacketInfo>,
+}
+
+impl RTPRoundtrip {
+    pub fn builder<'a>(
+        payloader: &'a impl IsA<gst::Element>,
+        depayloader: &'a impl IsA<gst::Element>,
+    ) -> RTPRoundtripBuilder<'a> {
+        assert_initialized_main_thread!();
+        RTPRoundtripBuilder {
+            payloader: payloader.as_ref(),
+            depayloader: depayloader.as_ref(),
+            caps: None,
+            mtu: None,
+            seqnum_offset: None,
+            timestamp_offset: None,
+            ssrc: None,
+            source_info: false,
+            network: RTPNetworkPattern::default(),
+        }
+    }
+
+    pub fn payloader(&self) -> &Harness {
+        &self.pay
+    }
+
+    pub fn payloader_mut(&mut self) -> &mut Harness {
+        &mut self.pay
+    }
+
+    pub fn depayloader(&self) -> &Harness {
+        &self.depay
+    }
+
+    pub fn depayloader_mut(&mut self) -> &mut Harness {
+        &mut self.depay
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// All packets produced by the payloader so far.
+    pub fn packets(&self) -> &[RTPPacketInfo] {
+        &self.packets
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Pushes `buffer` into the payloader, passes the resulting packets through
+    /// the network and returns the buffers produced by the depayloader.
+    pub fn push(&mut self, buffer: gst::Buffer) -> Result<Vec<gst::Buffer>, gst::FlowError> {
+        self.pay.push(buffer)?;
+        self.forward_packets()?;
+
+        Ok(self.pull_output())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sends EOS through both elements, delivers all delayed packets and
+    /// returns the remaining buffers produced by the depayloader.
+    pub fn finish(&mut self) -> Result<Vec<gst::Buffer>, gst::FlowError> {
+        self.pay.push_event(gst::event::Eos::new());
+        self.forward_packets()?;
+
+        for (_, packet) in std::mem::take(&mut self.held) {
+            self.deliver(packet)?;
+        }
+        self.depay.push_event(gst::event::Eos::new());
+
+        Ok(self.pull_output())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Asserts that the sequence numbers of all packets produced by the
+    /// payloader are consecutive and that their RTP timestamps never go
+    /// backwards, taking wraparounds into account.
+    #[track_caller]
+    pub fn assert_packet_continuity(&self) {
+        let mut ext_ts = u64::MAX;
+        let mut prev: Option<(u16, u64)> = None;
+
+        for packet in &self.packets {
+            let ts = gst_rtp::ext_timestamp(&mut ext_ts, packet.timestamp);
+            if let Some((prev_seqnum, prev_ts)) = prev {
+                assert_eq!(
+                    packet.seqnum,
+                    prev_seqnum.wrapping_add(1),
+                    "Packet {} has non-consecutive seqnum",
+                    packet.index
+                );
+                assert!(
+                    ts >= prev_ts,
+                    "Packet {} has RTP timestamp going backwards",
+                    packet.index
+                );
+            }
+            prev = Some((packet.seqnum, ts));
+        }
+    }
+
+    fn forward_packets(&mut self) -> Result<(), gst::FlowError> {
+        while let Some(packet) = self.pay.try_pull() {
+            let index = self.packets.len() as u64;
+            let fate = self.network.fate(index);
+
+            let rtp = gst_rtp::RTPBuffer::from_buffer_readable(&packet)
+                .expect("Payloader produced an invalid RTP packet");
+            self.packets.push(RTPPacketInfo {
+                index,
+                seqnum: rtp.seq(),
+                timestamp: rtp.timestamp(),
+                ssrc: rtp.ssrc(),
+                payload_type: rtp.payload_type(),
+                marker: rtp.is_marker(),
+                payload_size: rtp.payload_size(),
+                pts: packet.pts(),
+                flags: packet.flags(),
+                fate,
+            });
+            drop(rtp);
+
+            match fate {
+                RTPPacketFate::Delivered => self.deliver(packet)?,
+                RTPPacketFate::Dropped => (),
+                RTPPacketFate::Duplicated => {
+                    self.deliver(packet.copy())?;
+                    self.deliver(packet)?;
+                }
+                RTPPacketFate::Delayed(by) => self.held.push((index + by, packet)),
+            }
+
+            let (release, held) = std::mem::take(&mut self.held)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(release_after, _)| *release_after <= index);
+            self.held = held;
+            for (_, packet) in release {
+                self.deliver(packet)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn deliver(&mut self, packet: gst::Buffer) -> Result<(), gst::FlowError> {
+        if self.depay_caps.is_none() {
+            let caps = self
+                .pay
+                .sinkpad()
+                .and_then(|pad| pad.current_caps())
+                .expect("Payloader did not negotiate caps");
+            self.depay.set_src_caps(caps.clone());
+            self.depay_caps = Some(caps);
+        }
+
+        self.depay.push(packet).map(|_| ())
+    }
+
+    fn pull_output(&mut self) -> Vec<gst::Buffer> {
+        std::iter::from_fn(|| self.depay.try_pull()).collect()
+    }
+}
+
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTPRoundtripBuilder<'a> {
+    payloader: &'a gst::Element,
+    depayloader: &'a gst::Element,
+    caps: Option<&'a gst::Caps>,
+    mtu: Option<u32>,
+    seqnum_offset: Option<u16>,
+    timestamp_offset: Option<u32>,
+    ssrc: Option<u32>,
+    source_info: bool,
+    network: RTPNetworkPattern,
+}
+
+impl<'a> RTPRoundtripBuilder<'a> {
+    // rustdoc-stripper-ignore-next
+    /// Caps of the buffers pushed into the payloader.
+    pub fn caps(self, caps: &'a gst::Caps) -> Self {
+        Self {
+            caps: Some(caps),
+            ..self
+        }
+    }
+
+    pub fn mtu(self, mtu: u32) -> Self {
+        Self {
+            mtu: Some(mtu),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sequence number of the first packet, e.g. close to `u16::MAX` to test
+    /// wraparounds.
+    pub fn seqnum_offset(self, seqnum_offset: u16) -> Self {
+        Self {
+            seqnum_offset: Some(seqnum_offset),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// RTP timestamp of the first packet, e.g. close to `u32::MAX` to test
+    /// wraparounds. `u32::MAX` itself selects a random offset.
+    pub fn timestamp_offset(self, timestamp_offset: u32) -> Self {
+        Self {
+            timestamp_offset: Some(timestamp_offset),
+            ..self
+        }
+    }
+
+    pub fn ssrc(self, ssrc: u32) -> Self {
+        Self {
+            ssrc: Some(ssrc),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Enables the `source-info` property of payloader and depayloader so
+    /// that `RTPSourceMeta`s are added to the output buffers.
+    pub fn source_info(self, source_info: bool) -> Self {
+        Self {
+            source_info,
+            ..self
+        }
+    }
+
+    pub fn network(self, network: RTPNetworkPattern) -> Self {
+        Self { network, ..self }
+    }
+
+    #[must_use = "Building the roundtrip without using it has no effect"]
+    pub fn build(self) -> RTPRoundtrip {
+        if let Some(mtu) = self.mtu {
+            self.payloader.set_property("mtu", mtu);
+        }
+        if let Some(seqnum_offset) = self.seqnum_offset {
+            self.payloader
+                .set_property("seqnum-offset", i32::from(seqnum_offset));
+        }
+        if let Some(timestamp_offset) = self.timestamp_offset {
+            self.payloader
+                .set_property("timestamp-offset", timestamp_offset);
+        }
+        if let Some(ssrc) = self.ssrc {
+            self.payloader.set_property("ssrc", ssrc);
+        }
+        if self.source_info {
+            self.payloader.set_property("source-info", true);
+            self.depayloader.set_property("source-info", true);
+        }
+
+        let mut pay = Harness::with_element(self.payloader, Some("sink"), Some("src"));
+        let depay = Harness::with_element(self.depayloader, Some("sink"), Some("src"));
+
+        if let Some(caps) = self.caps {
+            pay.set_src_caps(caps.clone());
+        }
+
+        RTPRoundtrip {
+            pay,
+            depay,
+            network: self.network,
+            depay_caps: None,
+            held: Vec::new(),
+            packets: Vec::new(),
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Asserts that all flags of `set` and none of `unset` are set on `buffer`.
+#[track_caller]
+pub fn assert_buffer_flags(
+    buffer: &gst::BufferRef,
+    set: gst::BufferFlags,
+    unset: gst::BufferFlags,
+) {
+    let flags = buffer.flags();
+    assert!(
+        flags.contains(set),
+        "Buffer flags {flags:?} don't contain {set:?}"
+    );
+    assert!(
+        !flags.intersects(unset),
+        "Buffer flags {flags:?} contain {:?}",
+        flags & unset
+    );
+}
+
+// rustdoc-stripper-ignore-next
+/// Asserts that `buffer` has an `RTPSourceMeta` with the given SSRC and CSRCs.
+#[cfg(feature = "v1_16")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+#[track_caller]
+pub fn assert_source_meta(buffer: &gst::BufferRef, ssrc: Option<u32>, csrc: &[u32]) {
+    let meta = buffer
+        .meta::<gst_rtp::RTPSourceMeta>()
+        .expect("Buffer has no RTPSourceMeta");
+    assert_eq!(meta.ssrc(), ssrc);
+    assert_eq!(meta.csrc(), csrc);
+}
+
+#[cfg(all(test, feature = "v1_20"))]
+mod tests {
+    use std::sync::{LazyLock, Mutex};
+
+    use gst::subclass::prelude::*;
+    use gst_rtp::{prelude::*, subclass::prelude::*};
+
+    use super::*;
+
+    // Splits each input buffer into packets of at most MTU size and sets the
+    // marker on the last one of each buffer.
+    mod imp_pay {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestPay;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestPay {
+            const NAME: &'static str = "GstCheckTestRtpPay";
+            type Type = super::TestPay;
+            type ParentType = gst_rtp::RTPBasePayload;
+        }
+
+        impl ObjectImpl for TestPay {}
+        impl GstObjectImpl for TestPay {}
+
+        impl ElementImpl for TestPay {
+            fn pad_templates() -> &'static [gst::PadTemplate] {
+                static PAD_TEMPLATES: LazyLock<Vec<gst::PadTemplate>> = LazyLock::new(|| {
+                    vec![
+                        gst::PadTemplate::new(
+                            "src",
+                            gst::PadDirection::Src,
+                            gst::PadPresence::Always,
+                            &gst::Caps::new_empty_simple("application/x-rtp"),
+                        )
+                        .unwrap(),
+                        gst::PadTemplate::new(
+                            "sink",
+                            gst::PadDirection::Sink,
+                            gst::PadPresence::Always,
+                            &gst::Caps::new_any(),
+                        )
+                        .unwrap(),
+                    ]
+                });
+
+                PAD_TEMPLATES.as_ref()
+            }
+        }
+
+        impl RTPBasePayloadImpl for TestPay {
+            fn set_caps(&self, _caps: &gst::Caps) -> Result<(), gst::LoggableError> {
+                let obj = self.obj();
+                obj.set_options("application", true, "X-GST-CHECK", 90000);
+                obj.set_outcaps(None)
+                    .map_err(|_| gst::loggable_error!(gst::CAT_RUST, "Failed to set caps"))
+            }
+
+            fn handle_buffer(
+                &self,
+                buffer: gst::Buffer,
+            ) -> Result<gst::FlowSuccess, gst::FlowError> {
+                let obj = self.obj();
+                let max_payload = gst_rtp::calc_payload_len(obj.mtu(), 0, 0) as usize;
+                let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+                let n_chunks = map.len().div_ceil(max_payload);
+
+                for (i, chunk) in map.chunks(max_payload).enumerate() {
+                    let mut out = obj.allocate_output_buffer(chunk.len() as u32, 0, 0);
+                    {
+                        let out = out.get_mut().unwrap();
+                        out.set_pts(buffer.pts());
+                        let mut rtp = gst_rtp::RTPBuffer::from_buffer_writable(out)
+                            .map_err(|_| gst::FlowError::Error)?;
+                        rtp.payload_mut().unwrap().copy_from_slice(chunk);
+                        rtp.set_marker(i + 1 == n_chunks);
+                    }
+                    obj.push(out)?;
+                }
+
+                Ok(gst::FlowSuccess::Ok)
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestPay(ObjectSubclass<imp_pay::TestPay>)
+            @extends gst_rtp::RTPBasePayload, gst::Element, gst::Object;
+    }
+
+    // Concatenates the payloads up to a packet with marker and discards
+    // incomplete data on discontinuities.
+    mod imp_depay {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestDepay {
+            pending: Mutex<Vec<u8>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestDepay {
+            const NAME: &'static str = "GstCheckTestRtpDepay";
+            type Type = super::TestDepay;
+            type ParentType = gst_rtp::RTPBaseDepayload;
+        }
+
+        impl ObjectImpl for TestDepay {}
+        impl GstObjectImpl for TestDepay {}
+
+        impl ElementImpl for TestDepay {
+            fn pad_templates() -> &'static [gst::PadTemplate] {
+                static PAD_TEMPLATES: LazyLock<Vec<gst::PadTemplate>> = LazyLock::new(|| {
+                    vec![
+                        gst::PadTemplate::new(
+                            "src",
+                            gst::PadDirection::Src,
+                            gst::PadPresence::Always,
+                            &gst::Caps::new_any(),
+                        )
+                        .unwrap(),
+                        gst::PadTemplate::new(
+                            "sink",
+                            gst::PadDirection::Sink,
+                            gst::PadPresence::Always,
+                            &gst::Caps::new_empty_simple("application/x-rtp"),
+                        )
+                        .unwrap(),
+                    ]
+                });
+
+                PAD_TEMPLATES.as_ref()
+            }
+        }
+
+        impl RTPBaseDepayloadImpl for TestDepay {
+            fn set_caps(&self, _caps: &gst::Caps) -> Result<(), gst::LoggableError> {
+                self.obj().src_pad().push_event(gst::event::Caps::new(
+                    &gst::Caps::new_empty_simple("application/x-check-test"),
+                ));
+                Ok(())
+            }
+
+            fn process_rtp_packet(
+                &self,
+                rtp_buffer: &gst_rtp::RTPBuffer<gst_rtp::rtp_buffer::Readable>,
+            ) -> Option<gst::Buffer> {
+                let mut pending = self.pending.lock().unwrap();
+                if rtp_buffer
+                    .buffer()
+                    .flags()
+                    .contains(gst::BufferFlags::DISCONT)
+                {
+                    pending.clear();
+                }
+
+                pending.extend_from_slice(rtp_buffer.payload().unwrap());
+                if rtp_buffer.is_marker() {
+                    Some(gst::Buffer::from_mut_slice(std::mem::take(&mut *pending)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestDepay(ObjectSubclass<imp_depay::TestDepay>)
+            @extends gst_rtp::RTPBaseDepayload, gst::Element, gst::Object;
+    }
+
+    fn roundtrip(network: RTPNetworkPattern) -> RTPRoundtrip {
+        gst::init().unwrap();
+
+        let pay = glib::Object::new::<TestPay>();
+        let depay = glib::Object::new::<TestDepay>();
+        RTPRoundtrip::builder(&pay, &depay)
+            .caps(&gst::Caps::new_empty_simple("application/x-check-test"))
+            .mtu(112)
+            .seqnum_offset(u16::MAX - 2)
+            .timestamp_offset(u32::MAX - 10)
+            .ssrc(0x1234)
+            .source_info(true)
+            .network(network)
+            .build()
+    }
+
+    fn frame(n: u8, pts: u64) -> gst::Buffer {
+        let mut buffer = gst::Buffer::from_mut_slice(vec![n; 250]);
+        buffer
+            .get_mut()
+            .unwrap()
+            .set_pts(gst::ClockTime::from_mseconds(pts));
+        buffer
+    }
+
+    #[test]
+    fn lossless() {
+        let mut roundtrip = roundtrip(RTPNetworkPattern::new());
+
+        for i in 0..3 {
+            let out = roundtrip.push(frame(i, 40 * i as u64)).unwrap();
+            assert_eq!(out.len(), 1);
+            assert_eq!(out[0].map_readable().unwrap().as_slice(), &[i; 250][..]);
+            assert_source_meta(&out[0], Some(0x1234), &[]);
+        }
+        assert!(roundtrip.finish().unwrap().is_empty());
+
+        // 250 bytes in packets with 100 bytes payload each
+        let packets = roundtrip.packets();
+        assert_eq!(packets.len(), 9);
+        assert_eq!(packets[0].seqnum, u16::MAX - 2);
+        assert_eq!(packets[3].seqnum, 0);
+        assert!(packets[0].timestamp > packets[3].timestamp);
+        assert!(packets[2].marker);
+        roundtrip.assert_packet_continuity();
+    }
+
+    #[test]
+    fn loss() {
+        let mut roundtrip = roundtrip(RTPNetworkPattern::new().drop_packet(4));
+
+        let out = roundtrip.push(frame(0, 0)).unwrap();
+        assert_eq!(out.len(), 1);
+
+        let out = roundtrip.push(frame(1, 40)).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_buffer_flags(
+            &out[0],
+            gst::BufferFlags::DISCONT,
+            gst::BufferFlags::empty(),
+        );
+        assert_eq!(out[0].size(), 50);
+
+        let out = roundtrip.push(frame(2, 80)).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_buffer_flags(
+            &out[0],
+            gst::BufferFlags::empty(),
+            gst::BufferFlags::DISCONT,
+        );
+        assert_eq!(roundtrip.packets()[4].fate, RTPPacketFate::Dropped);
+    }
+
+    #[test]
+    fn duplication_and_reordering() {
+        let mut roundtrip = roundtrip(
+            RTPNetworkPattern::new()
+                .duplicate_packet(1)
+                .delay_packet(3, 1),
+        );
+
+        let mut out = Vec::new();
+        for i in 0..3 {
+            out.extend(roundtrip.push(frame(i, 40 * i as u64)).unwrap());
+        }
+        out.extend(roundtrip.finish().unwrap());
+
+        // The duplicate is discarded, the delayed packet arrives too late and
+        // causes a discontinuity.
+        assert_eq!(out[0].size(), 250);
+        assert_buffer_flags(
+            &out[1],
+            gst::BufferFlags::DISCONT,
+            gst::BufferFlags::empty(),
+        );
+        assert_eq!(out.last().unwrap().size(), 250);
+        assert_eq!(roundtrip.packets()[1].fate, RTPPacketFate::Duplicated);
+        assert_eq!(roundtrip.packets()[3].fate, RTPPacketFate::Delayed(1));
+    }
+}
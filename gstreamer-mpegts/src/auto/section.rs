@@ -72,12 +72,6 @@ impl Section {
         unsafe { from_glib_full(ffi::gst_mpegts_section_get_data(self.to_glib_none_mut().0)) }
     }
 
-    //#[doc(alias = "gst_mpegts_section_get_eit")]
-    //#[doc(alias = "get_eit")]
-    //pub fn eit(&mut self) -> /*Ignored*/Option<EIT> {
-    //    unsafe { TODO: call ffi:gst_mpegts_section_get_eit() }
-    //}
-
     //#[doc(alias = "gst_mpegts_section_get_nit")]
     //#[doc(alias = "get_nit")]
     //pub fn nit(&mut self) -> /*Ignored*/Option<NIT> {
@@ -90,18 +84,6 @@ impl Section {
     //    unsafe { TODO: call ffi:gst_mpegts_section_get_pat() }
     //}
 
-    //#[doc(alias = "gst_mpegts_section_get_pmt")]
-    //#[doc(alias = "get_pmt")]
-    //pub fn pmt(&mut self) -> /*Ignored*/Option<PMT> {
-    //    unsafe { TODO: call ffi:gst_mpegts_section_get_pmt() }
-    //}
-
-    //#[doc(alias = "gst_mpegts_section_get_sdt")]
-    //#[doc(alias = "get_sdt")]
-    //pub fn sdt(&mut self) -> /*Ignored*/Option<SDT> {
-    //    unsafe { TODO: call ffi:gst_mpegts_section_get_sdt() }
-    //}
-
     //#[doc(alias = "gst_mpegts_section_get_sit")]
     //#[doc(alias = "get_sit")]
     //pub fn sit(&mut self) -> /*Ignored*/Option<SIT> {
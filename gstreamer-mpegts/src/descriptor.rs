@@ -0,0 +1,813 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Typed MPEG-TS descriptors as found in the descriptor loops of PSI/SI sections.
+
+use glib::translate::*;
+
+use crate::ffi;
+
+const TAG_REGISTRATION: u8 = 0x05;
+const TAG_CA: u8 = 0x09;
+const TAG_ISO_639_LANGUAGE: u8 = 0x0a;
+const TAG_DVB_NETWORK_NAME: u8 = 0x40;
+const TAG_DVB_SERVICE: u8 = 0x48;
+const TAG_DVB_SHORT_EVENT: u8 = 0x4d;
+const TAG_DVB_COMPONENT: u8 = 0x50;
+const TAG_DVB_STREAM_IDENTIFIER: u8 = 0x52;
+const TAG_DVB_TELETEXT: u8 = 0x56;
+const TAG_DVB_SUBTITLING: u8 = 0x59;
+const TAG_DVB_PRIVATE_DATA_SPECIFIER: u8 = 0x5f;
+const TAG_DVB_AC3: u8 = 0x6a;
+const TAG_DVB_ENHANCED_AC3: u8 = 0x7a;
+const TAG_LOGICAL_CHANNEL: u8 = 0x83;
+
+// rustdoc-stripper-ignore-next
+/// Language entry of an ISO 639 language descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iso639LanguageEntry {
+    // rustdoc-stripper-ignore-next
+    /// ISO 639-2 language code, e.g. `eng`.
+    pub language: String,
+    pub audio_type: u8,
+}
+
+// rustdoc-stripper-ignore-next
+/// Payload of a DVB component descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsComponentDescriptor")]
+pub struct DvbComponent {
+    pub stream_content_ext: u8,
+    pub stream_content: u8,
+    pub component_type: u8,
+    pub component_tag: u8,
+    pub language: String,
+    pub text: String,
+}
+
+// rustdoc-stripper-ignore-next
+/// Page entry of a DVB teletext descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DvbTeletextEntry {
+    pub language: String,
+    pub teletext_type: u8,
+    pub magazine_number: u8,
+    pub page_number: u8,
+}
+
+// rustdoc-stripper-ignore-next
+/// Subtitle entry of a DVB subtitling descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DvbSubtitlingEntry {
+    pub language: String,
+    pub subtitling_type: u8,
+    pub composition_page_id: u16,
+    pub ancillary_page_id: u16,
+}
+
+// rustdoc-stripper-ignore-next
+/// Payload of a DVB AC-3 descriptor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DvbAc3 {
+    pub component_type: Option<u8>,
+    pub bsid: Option<u8>,
+    pub mainid: Option<u8>,
+    pub asvc: Option<u8>,
+    pub additional_info: Vec<u8>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Payload of a DVB enhanced AC-3 descriptor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DvbEnhancedAc3 {
+    pub component_type: Option<u8>,
+    pub bsid: Option<u8>,
+    pub mainid: Option<u8>,
+    pub asvc: Option<u8>,
+    pub mixinfoexists: bool,
+    pub substream1: Option<u8>,
+    pub substream2: Option<u8>,
+    pub substream3: Option<u8>,
+    pub additional_info: Vec<u8>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Service entry of a logical channel descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsLogicalChannel")]
+pub struct LogicalChannelEntry {
+    pub service_id: u16,
+    pub visible_service: bool,
+    pub logical_channel_number: u16,
+}
+
+// rustdoc-stripper-ignore-next
+/// A single MPEG-TS descriptor.
+///
+/// Descriptors with an unsupported tag or with a payload that can't be parsed are
+/// represented as [`Descriptor::Unknown`].
+///
+/// Parsing DVB texts that are neither UTF-8 nor plain ASCII requires GStreamer to be
+/// initialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsDescriptor")]
+#[non_exhaustive]
+pub enum Descriptor {
+    Registration {
+        format_identifier: [u8; 4],
+        additional_info: Vec<u8>,
+    },
+    Ca {
+        ca_system_id: u16,
+        ca_pid: u16,
+        private_data: Vec<u8>,
+    },
+    Iso639Language(Vec<Iso639LanguageEntry>),
+    DvbNetworkName(String),
+    DvbService {
+        service_type: u8,
+        provider_name: String,
+        service_name: String,
+    },
+    DvbShortEvent {
+        language: String,
+        event_name: String,
+        text: String,
+    },
+    DvbComponent(DvbComponent),
+    DvbStreamIdentifier(u8),
+    DvbTeletext(Vec<DvbTeletextEntry>),
+    DvbSubtitling(Vec<DvbSubtitlingEntry>),
+    DvbPrivateDataSpecifier {
+        private_data_specifier: u32,
+        private_data: Vec<u8>,
+    },
+    DvbAc3(DvbAc3),
+    DvbEnhancedAc3(DvbEnhancedAc3),
+    LogicalChannel(Vec<LogicalChannelEntry>),
+    Unknown {
+        tag: u8,
+        data: Vec<u8>,
+    },
+}
+
+impl Descriptor {
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::Registration { .. } => TAG_REGISTRATION,
+            Self::Ca { .. } => TAG_CA,
+            Self::Iso639Language(_) => TAG_ISO_639_LANGUAGE,
+            Self::DvbNetworkName(_) => TAG_DVB_NETWORK_NAME,
+            Self::DvbService { .. } => TAG_DVB_SERVICE,
+            Self::DvbShortEvent { .. } => TAG_DVB_SHORT_EVENT,
+            Self::DvbComponent(_) => TAG_DVB_COMPONENT,
+            Self::DvbStreamIdentifier(_) => TAG_DVB_STREAM_IDENTIFIER,
+            Self::DvbTeletext(_) => TAG_DVB_TELETEXT,
+            Self::DvbSubtitling(_) => TAG_DVB_SUBTITLING,
+            Self::DvbPrivateDataSpecifier { .. } => TAG_DVB_PRIVATE_DATA_SPECIFIER,
+            Self::DvbAc3(_) => TAG_DVB_AC3,
+            Self::DvbEnhancedAc3(_) => TAG_DVB_ENHANCED_AC3,
+            Self::LogicalChannel(_) => TAG_LOGICAL_CHANNEL,
+            Self::Unknown { tag, .. } => *tag,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a single descriptor from the start of `data` and returns it together with
+    /// the number of bytes it occupied.
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), glib::BoolError> {
+        let [tag, length, ..] = *data else {
+            return Err(glib::bool_error!("Descriptor header truncated"));
+        };
+        let payload = data
+            .get(2..2 + length as usize)
+            .ok_or_else(|| glib::bool_error!("Descriptor payload truncated"))?;
+
+        Ok((Self::from_payload(tag, payload), 2 + payload.len()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a complete descriptor loop.
+    pub fn parse_loop(mut data: &[u8]) -> Result<Vec<Self>, glib::BoolError> {
+        let mut descriptors = Vec::new();
+        while !data.is_empty() {
+            let (descriptor, len) = Self::parse(data)?;
+            descriptors.push(descriptor);
+            data = &data[len..];
+        }
+
+        Ok(descriptors)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a descriptor from its tag and payload.
+    pub fn from_payload(tag: u8, payload: &[u8]) -> Self {
+        parse_payload(tag, payload).unwrap_or_else(|| Self::Unknown {
+            tag,
+            data: payload.to_vec(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes the payload of the descriptor, without tag and length.
+    pub fn payload(&self) -> Result<Vec<u8>, glib::BoolError> {
+        let mut p = Vec::new();
+
+        match self {
+            Self::Registration {
+                format_identifier,
+                additional_info,
+            } => {
+                p.extend_from_slice(format_identifier);
+                p.extend_from_slice(additional_info);
+            }
+            Self::Ca {
+                ca_system_id,
+                ca_pid,
+                private_data,
+            } => {
+                if *ca_pid > 0x1fff {
+                    return Err(glib::bool_error!("Invalid CA PID {}", ca_pid));
+                }
+                p.extend_from_slice(&ca_system_id.to_be_bytes());
+                p.extend_from_slice(&(0xe000 | ca_pid).to_be_bytes());
+                p.extend_from_slice(private_data);
+            }
+            Self::Iso639Language(entries) => {
+                for entry in entries {
+                    p.extend_from_slice(&language_code(&entry.language)?);
+                    p.push(entry.audio_type);
+                }
+            }
+            Self::DvbNetworkName(name) => p.extend_from_slice(&encode_text(name)?),
+            Self::DvbService {
+                service_type,
+                provider_name,
+                service_name,
+            } => {
+                p.push(*service_type);
+                write_text_with_length(&mut p, provider_name)?;
+                write_text_with_length(&mut p, service_name)?;
+            }
+            Self::DvbShortEvent {
+                language,
+                event_name,
+                text,
+            } => {
+                p.extend_from_slice(&language_code(language)?);
+                write_text_with_length(&mut p, event_name)?;
+                write_text_with_length(&mut p, text)?;
+            }
+            Self::DvbComponent(component) => {
+                if component.stream_content_ext > 0x0f || component.stream_content > 0x0f {
+                    return Err(glib::bool_error!(
+                        "Invalid stream content {}/{}",
+                        component.stream_content_ext,
+                        component.stream_content
+                    ));
+                }
+                p.push((component.stream_content_ext << 4) | component.stream_content);
+                p.push(component.component_type);
+                p.push(component.component_tag);
+                p.extend_from_slice(&language_code(&component.language)?);
+                p.extend_from_slice(&encode_text(&component.text)?);
+            }
+            Self::DvbStreamIdentifier(component_tag) => p.push(*component_tag),
+            Self::DvbTeletext(entries) => {
+                for entry in entries {
+                    if entry.teletext_type > 0x1f {
+                        return Err(glib::bool_error!(
+                            "Invalid teletext type {}",
+                            entry.teletext_type
+                        ));
+                    }
+                    if entry.magazine_number > 0x07 {
+                        return Err(glib::bool_error!(
+                            "Invalid magazine number {}",
+                            entry.magazine_number
+                        ));
+                    }
+                    p.extend_from_slice(&language_code(&entry.language)?);
+                    p.push((entry.teletext_type << 3) | entry.magazine_number);
+                    p.push(entry.page_number);
+                }
+            }
+            Self::DvbSubtitling(entries) => {
+                for entry in entries {
+                    p.extend_from_slice(&language_code(&entry.language)?);
+                    p.push(entry.subtitling_type);
+                    p.extend_from_slice(&entry.composition_page_id.to_be_bytes());
+                    p.extend_from_slice(&entry.ancillary_page_id.to_be_bytes());
+                }
+            }
+            Self::DvbPrivateDataSpecifier {
+                private_data_specifier,
+                private_data,
+            } => {
+                p.extend_from_slice(&private_data_specifier.to_be_bytes());
+                p.extend_from_slice(private_data);
+            }
+            Self::DvbAc3(ac3) => {
+                let fields = [ac3.component_type, ac3.bsid, ac3.mainid, ac3.asvc];
+                p.push(flags(&fields));
+                p.extend(fields.into_iter().flatten());
+                p.extend_from_slice(&ac3.additional_info);
+            }
+            Self::DvbEnhancedAc3(eac3) => {
+                let fields = [eac3.component_type, eac3.bsid, eac3.mainid, eac3.asvc];
+                let substreams = [eac3.substream1, eac3.substream2, eac3.substream3];
+                p.push(
+                    flags(&fields)
+                        | (u8::from(eac3.mixinfoexists) << 3)
+                        | (flags(&substreams) >> 5),
+                );
+                p.extend(fields.into_iter().flatten());
+                p.extend(substreams.into_iter().flatten());
+                p.extend_from_slice(&eac3.additional_info);
+            }
+            Self::LogicalChannel(entries) => {
+                for entry in entries {
+                    if entry.logical_channel_number > 0x3ff {
+                        return Err(glib::bool_error!(
+                            "Invalid logical channel number {}",
+                            entry.logical_channel_number
+                        ));
+                    }
+                    p.extend_from_slice(&entry.service_id.to_be_bytes());
+                    let v = (u16::from(entry.visible_service) << 15)
+                        | 0x7c00
+                        | entry.logical_channel_number;
+                    p.extend_from_slice(&v.to_be_bytes());
+                }
+            }
+            Self::Unknown { data, .. } => p.extend_from_slice(data),
+        }
+
+        Ok(p)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes the complete descriptor including tag and length.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, glib::BoolError> {
+        let payload = self.payload()?;
+        let length = u8::try_from(payload.len())
+            .map_err(|_| glib::bool_error!("Descriptor payload too large"))?;
+
+        let mut data = Vec::with_capacity(2 + payload.len());
+        data.push(self.tag());
+        data.push(length);
+        data.extend_from_slice(&payload);
+
+        Ok(data)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes a complete descriptor loop.
+    pub fn write_loop(descriptors: &[Self]) -> Result<Vec<u8>, glib::BoolError> {
+        let mut data = Vec::new();
+        for descriptor in descriptors {
+            data.extend_from_slice(&descriptor.to_bytes()?);
+        }
+
+        Ok(data)
+    }
+
+    // Reads a descriptor owned by one of the C section structs
+    pub(crate) unsafe fn from_ffi(ptr: *const crate::ffi::GstMpegtsDescriptor) -> Self {
+        unsafe {
+            let desc = &*ptr;
+            // `data` includes the tag and length
+            let data = std::slice::from_raw_parts(desc.data, desc.length as usize + 2);
+            Self::from_payload(desc.tag, &data[2..])
+        }
+    }
+}
+
+fn parse_payload(tag: u8, p: &[u8]) -> Option<Descriptor> {
+    let descriptor = match tag {
+        TAG_REGISTRATION => Descriptor::Registration {
+            format_identifier: p.get(..4)?.try_into().unwrap(),
+            additional_info: p[4..].to_vec(),
+        },
+        TAG_CA => {
+            let ca_system_id = u16::from_be_bytes(p.get(..2)?.try_into().unwrap());
+            let ca_pid = u16::from_be_bytes(p.get(2..4)?.try_into().unwrap()) & 0x1fff;
+            Descriptor::Ca {
+                ca_system_id,
+                ca_pid,
+                private_data: p[4..].to_vec(),
+            }
+        }
+        TAG_ISO_639_LANGUAGE => Descriptor::Iso639Language(
+            entries::<4>(p)?
+                .map(|e| Iso639LanguageEntry {
+                    language: language_string(&e[..3]),
+                    audio_type: e[3],
+                })
+                .collect(),
+        ),
+        TAG_DVB_NETWORK_NAME => Descriptor::DvbNetworkName(decode_text(p)),
+        TAG_DVB_SERVICE => {
+            let service_type = *p.first()?;
+            let (provider_name, rest) = read_text_with_length(&p[1..])?;
+            let (service_name, _) = read_text_with_length(rest)?;
+            Descriptor::DvbService {
+                service_type,
+                provider_name,
+                service_name,
+            }
+        }
+        TAG_DVB_SHORT_EVENT => {
+            let language = language_string(p.get(..3)?);
+            let (event_name, rest) = read_text_with_length(&p[3..])?;
+            let (text, _) = read_text_with_length(rest)?;
+            Descriptor::DvbShortEvent {
+                language,
+                event_name,
+                text,
+            }
+        }
+        TAG_DVB_COMPONENT => {
+            let header = p.get(..6)?;
+            Descriptor::DvbComponent(DvbComponent {
+                stream_content_ext: header[0] >> 4,
+                stream_content: header[0] & 0x0f,
+                component_type: header[1],
+                component_tag: header[2],
+                language: language_string(&header[3..6]),
+                text: decode_text(&p[6..]),
+            })
+        }
+        TAG_DVB_STREAM_IDENTIFIER => Descriptor::DvbStreamIdentifier(*p.first()?),
+        TAG_DVB_TELETEXT => Descriptor::DvbTeletext(
+            entries::<5>(p)?
+                .map(|e| DvbTeletextEntry {
+                    language: language_string(&e[..3]),
+                    teletext_type: e[3] >> 3,
+                    magazine_number: e[3] & 0x07,
+                    page_number: e[4],
+                })
+                .collect(),
+        ),
+        TAG_DVB_SUBTITLING => Descriptor::DvbSubtitling(
+            entries::<8>(p)?
+                .map(|e| DvbSubtitlingEntry {
+                    language: language_string(&e[..3]),
+                    subtitling_type: e[3],
+                    composition_page_id: u16::from_be_bytes([e[4], e[5]]),
+                    ancillary_page_id: u16::from_be_bytes([e[6], e[7]]),
+                })
+                .collect(),
+        ),
+        TAG_DVB_PRIVATE_DATA_SPECIFIER => Descriptor::DvbPrivateDataSpecifier {
+            private_data_specifier: u32::from_be_bytes(p.get(..4)?.try_into().unwrap()),
+            private_data: p[4..].to_vec(),
+        },
+        TAG_DVB_AC3 => {
+            let (&flags, mut rest) = p.split_first()?;
+            let mut field = |bit: u8| read_flagged(flags, bit, &mut rest);
+            let ac3 = DvbAc3 {
+                component_type: field(7)?,
+                bsid: field(6)?,
+                mainid: field(5)?,
+                asvc: field(4)?,
+                additional_info: Vec::new(),
+            };
+            Descriptor::DvbAc3(DvbAc3 {
+                additional_info: rest.to_vec(),
+                ..ac3
+            })
+        }
+        TAG_DVB_ENHANCED_AC3 => {
+            let (&flags, mut rest) = p.split_first()?;
+            let mut field = |bit: u8| read_flagged(flags, bit, &mut rest);
+            let eac3 = DvbEnhancedAc3 {
+                component_type: field(7)?,
+                bsid: field(6)?,
+                mainid: field(5)?,
+                asvc: field(4)?,
+                mixinfoexists: flags & 0x08 != 0,
+                substream1: field(2)?,
+                substream2: field(1)?,
+                substream3: field(0)?,
+                additional_info: Vec::new(),
+            };
+            Descriptor::DvbEnhancedAc3(DvbEnhancedAc3 {
+                additional_info: rest.to_vec(),
+                ..eac3
+            })
+        }
+        TAG_LOGICAL_CHANNEL => Descriptor::LogicalChannel(
+            entries::<4>(p)?
+                .map(|e| LogicalChannelEntry {
+                    service_id: u16::from_be_bytes([e[0], e[1]]),
+                    visible_service: e[2] & 0x80 != 0,
+                    logical_channel_number: u16::from_be_bytes([e[2], e[3]]) & 0x3ff,
+                })
+                .collect(),
+        ),
+        _ => return None,
+    };
+
+    Some(descriptor)
+}
+
+// Splits a payload into fixed size entries
+fn entries<const N: usize>(p: &[u8]) -> Option<impl Iterator<Item = &[u8]>> {
+    (p.len() % N == 0).then(|| p.chunks_exact(N))
+}
+
+// Reads the next byte if `bit` is set in `flags`
+fn read_flagged(flags: u8, bit: u8, rest: &mut &[u8]) -> Option<Option<u8>> {
+    if flags & (1 << bit) == 0 {
+        return Some(None);
+    }
+
+    let (&v, tail) = rest.split_first()?;
+    *rest = tail;
+    Some(Some(v))
+}
+
+// Returns the flags byte with the top bits set for the present fields
+fn flags(fields: &[Option<u8>]) -> u8 {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.is_some())
+        .fold(0, |flags, (i, _)| flags | (0x80 >> i))
+}
+
+fn language_string(code: &[u8]) -> String {
+    String::from_utf8_lossy(code).into_owned()
+}
+
+fn language_code(language: &str) -> Result<[u8; 3], glib::BoolError> {
+    language
+        .as_bytes()
+        .try_into()
+        .ok()
+        .filter(|code: &[u8; 3]| code.is_ascii())
+        .ok_or_else(|| glib::bool_error!("Invalid ISO 639 language code '{}'", language))
+}
+
+fn read_text_with_length(p: &[u8]) -> Option<(String, &[u8])> {
+    let (&len, rest) = p.split_first()?;
+    let text = rest.get(..len as usize)?;
+    Some((decode_text(text), &rest[len as usize..]))
+}
+
+fn write_text_with_length(p: &mut Vec<u8>, text: &str) -> Result<(), glib::BoolError> {
+    let text = encode_text(text)?;
+    let len = u8::try_from(text.len()).map_err(|_| glib::bool_error!("Text too long"))?;
+    p.push(len);
+    p.extend_from_slice(&text);
+
+    Ok(())
+}
+
+// rustdoc-stripper-ignore-next
+/// Decodes a DVB text string (ETSI EN 300 468 Annex A).
+///
+/// UTF-8 and plain ASCII are decoded directly, all other character tables are converted by
+/// the C library.
+fn decode_text(data: &[u8]) -> String {
+    match data {
+        [] => return String::new(),
+        [0x15, rest @ ..] => return String::from_utf8_lossy(rest).into_owned(),
+        // The default table is identical to ASCII for these
+        _ if data.iter().all(|b| (0x20..0x7f).contains(b)) => {
+            return String::from_utf8(data.to_vec()).unwrap();
+        }
+        _ => (),
+    }
+
+    assert_initialized_main_thread!();
+
+    // The text is decoded as the payload of a network name descriptor, which consists of
+    // only the text. Descriptor payloads are never longer than 255 bytes.
+    unsafe {
+        let desc =
+            ffi::gst_mpegts_descriptor_from_custom(TAG_DVB_NETWORK_NAME, data.as_ptr(), data.len());
+        if desc.is_null() {
+            return String::from_utf8_lossy(data).into_owned();
+        }
+
+        let mut name = std::ptr::null_mut();
+        let res = ffi::gst_mpegts_descriptor_parse_dvb_network_name(desc, &mut name);
+        ffi::gst_mpegts_descriptor_free(desc);
+
+        if res == glib::ffi::GFALSE || name.is_null() {
+            String::from_utf8_lossy(data).into_owned()
+        } else {
+            from_glib_full(name)
+        }
+    }
+}
+
+// ASCII is written as is, everything else as UTF-8. Control characters are rejected as a
+// leading one would select a different character table.
+fn encode_text(text: &str) -> Result<Vec<u8>, glib::BoolError> {
+    if text.chars().any(|c| c < ' ') {
+        return Err(glib::bool_error!("Text contains control characters"));
+    }
+
+    if text.is_ascii() {
+        return Ok(text.as_bytes().to_vec());
+    }
+
+    let mut data = Vec::with_capacity(text.len() + 1);
+    data.push(0x15);
+    data.extend_from_slice(text.as_bytes());
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let descriptors = vec![
+            Descriptor::Registration {
+                format_identifier: *b"HEVC",
+                additional_info: vec![],
+            },
+            Descriptor::Ca {
+                ca_system_id: 0x0b00,
+                ca_pid: 0x1234,
+                private_data: vec![1, 2],
+            },
+            Descriptor::Iso639Language(vec![
+                Iso639LanguageEntry {
+                    language: String::from("eng"),
+                    audio_type: 0,
+                },
+                Iso639LanguageEntry {
+                    language: String::from("deu"),
+                    audio_type: 3,
+                },
+            ]),
+            Descriptor::DvbService {
+                service_type: 0x01,
+                provider_name: String::from("Provider"),
+                service_name: String::from("Ünïcode TV"),
+            },
+            Descriptor::DvbShortEvent {
+                language: String::from("fra"),
+                event_name: String::from("Journal"),
+                text: String::new(),
+            },
+            Descriptor::DvbComponent(DvbComponent {
+                stream_content_ext: 0xf,
+                stream_content: 0x5,
+                component_type: 0x0b,
+                component_tag: 1,
+                language: String::from("eng"),
+                text: String::from("HD"),
+            }),
+            Descriptor::DvbStreamIdentifier(3),
+            Descriptor::DvbTeletext(vec![DvbTeletextEntry {
+                language: String::from("nld"),
+                teletext_type: 2,
+                magazine_number: 1,
+                page_number: 0x88,
+            }]),
+            Descriptor::DvbSubtitling(vec![DvbSubtitlingEntry {
+                language: String::from("swe"),
+                subtitling_type: 0x10,
+                composition_page_id: 1,
+                ancillary_page_id: 2,
+            }]),
+            Descriptor::DvbPrivateDataSpecifier {
+                private_data_specifier: 0x28,
+                private_data: vec![],
+            },
+            Descriptor::DvbAc3(DvbAc3 {
+                component_type: Some(0x42),
+                asvc: Some(1),
+                ..Default::default()
+            }),
+            Descriptor::DvbEnhancedAc3(DvbEnhancedAc3 {
+                bsid: Some(16),
+                mixinfoexists: true,
+                substream2: Some(7),
+                additional_info: vec![0xff],
+                ..Default::default()
+            }),
+            Descriptor::LogicalChannel(vec![LogicalChannelEntry {
+                service_id: 0x1001,
+                visible_service: true,
+                logical_channel_number: 101,
+            }]),
+            Descriptor::Unknown {
+                tag: 0x7f,
+                data: vec![0x15, 0x00],
+            },
+        ];
+
+        let data = Descriptor::write_loop(&descriptors).unwrap();
+        assert_eq!(Descriptor::parse_loop(&data).unwrap(), descriptors);
+    }
+
+    #[test]
+    fn test_payload_ranges() {
+        let component = DvbComponent {
+            stream_content_ext: 0x10,
+            stream_content: 0x5,
+            component_type: 0x0b,
+            component_tag: 1,
+            language: String::from("eng"),
+            text: String::new(),
+        };
+        assert!(Descriptor::DvbComponent(component).payload().is_err());
+
+        let entry = DvbTeletextEntry {
+            language: String::from("nld"),
+            teletext_type: 2,
+            magazine_number: 8,
+            page_number: 0x88,
+        };
+        assert!(
+            Descriptor::DvbTeletext(vec![entry.clone()])
+                .payload()
+                .is_err()
+        );
+        assert!(
+            Descriptor::DvbTeletext(vec![DvbTeletextEntry {
+                teletext_type: 0x20,
+                magazine_number: 1,
+                ..entry
+            }])
+            .payload()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let (descriptor, len) =
+            Descriptor::parse(&[0x0a, 0x04, b'e', b'n', b'g', 0x01, 0xff]).unwrap();
+        assert_eq!(len, 6);
+        assert_eq!(
+            descriptor,
+            Descriptor::Iso639Language(vec![Iso639LanguageEntry {
+                language: String::from("eng"),
+                audio_type: 1,
+            }])
+        );
+
+        // Registration descriptor with a too short payload
+        assert_eq!(
+            Descriptor::parse(&[0x05, 0x02, b'A', b'C']).unwrap().0,
+            Descriptor::Unknown {
+                tag: 0x05,
+                data: vec![b'A', b'C'],
+            }
+        );
+
+        assert!(Descriptor::parse(&[0x05]).is_err());
+        assert!(Descriptor::parse(&[0x05, 0x04, b'A']).is_err());
+
+        assert_eq!(
+            Descriptor::DvbNetworkName(String::from("Net"))
+                .to_bytes()
+                .unwrap(),
+            &[0x40, 0x03, b'N', b'e', b't']
+        );
+        assert!(
+            Descriptor::DvbStreamIdentifier(0)
+                .to_bytes()
+                .is_ok_and(|data| data == [0x52, 0x01, 0x00])
+        );
+        assert!(
+            Descriptor::Iso639Language(vec![Iso639LanguageEntry {
+                language: String::from("en"),
+                audio_type: 0,
+            }])
+            .to_bytes()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        gst::init().unwrap();
+
+        assert_eq!(decode_text(b"News"), "News");
+        // ISO 6937 with a non-spacing acute accent
+        assert_eq!(decode_text(b"Caf\xc2e\x8aBar"), "Café\nBar");
+        // ISO 8859-5
+        assert_eq!(decode_text(b"\x01\xbf\xe0\xd8\xd2\xd5\xe2"), "Привет");
+        // ISO 8859-7
+        assert_eq!(decode_text(b"\x03\xc3\xe5\xe9\xe1"), "Γεια");
+        assert_eq!(decode_text(b"\x10\x00\x01\xe9"), "é");
+        assert_eq!(decode_text(b"\x15\xc3\xa9"), "é");
+        assert_eq!(encode_text("é").unwrap(), b"\x15\xc3\xa9");
+        assert!(encode_text("\x05Net").is_err());
+        assert!(
+            Descriptor::DvbNetworkName(String::from("Net\x01"))
+                .payload()
+                .is_err()
+        );
+    }
+}
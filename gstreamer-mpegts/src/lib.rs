@@ -30,6 +30,16 @@ mod auto;
 #[allow(unused_imports)]
 pub use crate::auto::*;
 
+mod descriptor;
+pub use crate::descriptor::*;
+
+#[cfg(feature = "v1_20")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+mod section;
+#[cfg(feature = "v1_20")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+pub use crate::section::*;
+
 #[cfg(feature = "v1_20")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
 #[doc(alias = "gst_event_new_mpegts_section")]
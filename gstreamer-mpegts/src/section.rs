@@ -0,0 +1,351 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::translate::*;
+
+use crate::{Descriptor, Section, ffi};
+
+// rustdoc-stripper-ignore-next
+/// Program map table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsPMT")]
+pub struct Pmt {
+    pub pcr_pid: u16,
+    pub program_number: u16,
+    pub descriptors: Vec<Descriptor>,
+    pub streams: Vec<PmtStream>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Elementary stream entry of a [`Pmt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsPMTStream")]
+pub struct PmtStream {
+    pub stream_type: u8,
+    pub pid: u16,
+    pub descriptors: Vec<Descriptor>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Service description table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsSDT")]
+pub struct Sdt {
+    pub original_network_id: u16,
+    pub actual_ts: bool,
+    pub transport_stream_id: u16,
+    pub services: Vec<SdtService>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Service entry of an [`Sdt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstMpegtsSDTService")]
+pub struct SdtService {
+    pub service_id: u16,
+    pub eit_schedule_flag: bool,
+    pub eit_present_following_flag: bool,
+    pub running_status: u8,
+    pub free_ca_mode: bool,
+    pub descriptors: Vec<Descriptor>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Event information table.
+#[derive(Debug, Clone, PartialEq)]
+#[doc(alias = "GstMpegtsEIT")]
+pub struct Eit {
+    pub transport_stream_id: u16,
+    pub original_network_id: u16,
+    pub segment_last_section_number: u8,
+    pub last_table_id: u8,
+    pub actual_stream: bool,
+    pub present_following: bool,
+    pub events: Vec<EitEvent>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Event entry of an [`Eit`].
+#[derive(Debug, Clone, PartialEq)]
+#[doc(alias = "GstMpegtsEITEvent")]
+pub struct EitEvent {
+    pub event_id: u16,
+    pub start_time: Option<gst::DateTime>,
+    pub duration: gst::ClockTime,
+    pub running_status: u8,
+    pub free_ca_mode: bool,
+    pub descriptors: Vec<Descriptor>,
+}
+
+unsafe fn array_items<'a, T>(array: *const glib::ffi::GPtrArray) -> &'a [*const T] {
+    unsafe {
+        if array.is_null() || (*array).len == 0 {
+            return &[];
+        }
+
+        std::slice::from_raw_parts((*array).pdata as *const *const T, (*array).len as usize)
+    }
+}
+
+unsafe fn descriptors(array: *const glib::ffi::GPtrArray) -> Vec<Descriptor> {
+    unsafe {
+        array_items::<ffi::GstMpegtsDescriptor>(array)
+            .iter()
+            .map(|&desc| Descriptor::from_ffi(desc))
+            .collect()
+    }
+}
+
+impl Section {
+    #[doc(alias = "gst_mpegts_section_get_pmt")]
+    #[doc(alias = "get_pmt")]
+    pub fn pmt(&mut self) -> Option<Pmt> {
+        unsafe {
+            let pmt = ffi::gst_mpegts_section_get_pmt(self.to_glib_none_mut().0);
+            if pmt.is_null() {
+                return None;
+            }
+            let pmt = &*pmt;
+
+            let streams = array_items::<ffi::GstMpegtsPMTStream>(pmt.streams)
+                .iter()
+                .map(|&stream| {
+                    let stream = &*stream;
+                    PmtStream {
+                        stream_type: stream.stream_type,
+                        pid: stream.pid,
+                        descriptors: descriptors(stream.descriptors),
+                    }
+                })
+                .collect();
+
+            Some(Pmt {
+                pcr_pid: pmt.pcr_pid,
+                program_number: pmt.program_number,
+                descriptors: descriptors(pmt.descriptors),
+                streams,
+            })
+        }
+    }
+
+    #[doc(alias = "gst_mpegts_section_get_sdt")]
+    #[doc(alias = "get_sdt")]
+    pub fn sdt(&mut self) -> Option<Sdt> {
+        unsafe {
+            let sdt = ffi::gst_mpegts_section_get_sdt(self.to_glib_none_mut().0);
+            if sdt.is_null() {
+                return None;
+            }
+            let sdt = &*sdt;
+
+            let services = array_items::<ffi::GstMpegtsSDTService>(sdt.services)
+                .iter()
+                .map(|&service| {
+                    let service = &*service;
+                    SdtService {
+                        service_id: service.service_id,
+                        eit_schedule_flag: from_glib(service.EIT_schedule_flag),
+                        eit_present_following_flag: from_glib(service.EIT_present_following_flag),
+                        running_status: service.running_status as u8,
+                        free_ca_mode: from_glib(service.free_CA_mode),
+                        descriptors: descriptors(service.descriptors),
+                    }
+                })
+                .collect();
+
+            Some(Sdt {
+                original_network_id: sdt.original_network_id,
+                actual_ts: from_glib(sdt.actual_ts),
+                transport_stream_id: sdt.transport_stream_id,
+                services,
+            })
+        }
+    }
+
+    #[doc(alias = "gst_mpegts_section_get_eit")]
+    #[doc(alias = "get_eit")]
+    pub fn eit(&mut self) -> Option<Eit> {
+        unsafe {
+            let eit = ffi::gst_mpegts_section_get_eit(self.to_glib_none_mut().0);
+            if eit.is_null() {
+                return None;
+            }
+            let eit = &*eit;
+
+            let events = array_items::<ffi::GstMpegtsEITEvent>(eit.events)
+                .iter()
+                .map(|&event| {
+                    let event = &*event;
+                    EitEvent {
+                        event_id: event.event_id,
+                        start_time: from_glib_none(event.start_time),
+                        duration: gst::ClockTime::from_seconds(event.duration as u64),
+                        running_status: event.running_status as u8,
+                        free_ca_mode: from_glib(event.free_CA_mode),
+                        descriptors: descriptors(event.descriptors),
+                    }
+                })
+                .collect();
+
+            Some(Eit {
+                transport_stream_id: eit.transport_stream_id,
+                original_network_id: eit.original_network_id,
+                segment_last_section_number: eit.segment_last_section_number,
+                last_table_id: eit.last_table_id,
+                actual_stream: from_glib(eit.actual_stream),
+                present_following: from_glib(eit.present_following),
+                events,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Iso639LanguageEntry;
+
+    const PMT: &[u8] = &[
+        0x02, 0xb0, 0x23, 0x00, 0x01, 0xc1, 0x00, 0x00, 0xe1, 0x00, 0xf0, 0x06, 0x05, 0x04, 0x48,
+        0x44, 0x4d, 0x56, 0x1b, 0xe1, 0x00, 0xf0, 0x00, 0x0f, 0xe1, 0x01, 0xf0, 0x06, 0x0a, 0x04,
+        0x65, 0x6e, 0x67, 0x00, 0xee, 0x60, 0x27, 0x0d,
+    ];
+
+    const SDT: &[u8] = &[
+        0x42, 0xf0, 0x1d, 0x00, 0x01, 0xc1, 0x00, 0x00, 0x23, 0x3a, 0xff, 0x00, 0x01, 0xfd, 0x80,
+        0x0c, 0x48, 0x0a, 0x01, 0x03, 0x50, 0x72, 0x76, 0x04, 0x43, 0x68, 0x61, 0x6e, 0xfd, 0x9b,
+        0xc9, 0x39,
+    ];
+
+    const EIT: &[u8] = &[
+        0x4e, 0xf0, 0x26, 0x00, 0x01, 0xc1, 0x00, 0x00, 0x00, 0x01, 0x23, 0x3a, 0x00, 0x4e, 0x00,
+        0x42, 0xeb, 0x96, 0x12, 0x30, 0x00, 0x01, 0x30, 0x00, 0x80, 0x0b, 0x4d, 0x09, 0x65, 0x6e,
+        0x67, 0x04, 0x4e, 0x65, 0x77, 0x73, 0x00, 0x79, 0xf7, 0x46, 0x73,
+    ];
+
+    #[test]
+    fn test_pmt() {
+        gst::init().unwrap();
+
+        let mut section = Section::new(0x1000, PMT).unwrap();
+        assert!(section.sdt().is_none());
+        let pmt = section.pmt().unwrap();
+
+        assert_eq!(
+            pmt,
+            Pmt {
+                pcr_pid: 0x100,
+                program_number: 1,
+                descriptors: vec![Descriptor::Registration {
+                    format_identifier: *b"HDMV",
+                    additional_info: vec![],
+                }],
+                streams: vec![
+                    PmtStream {
+                        stream_type: 0x1b,
+                        pid: 0x100,
+                        descriptors: vec![],
+                    },
+                    PmtStream {
+                        stream_type: 0x0f,
+                        pid: 0x101,
+                        descriptors: vec![Descriptor::Iso639Language(vec![Iso639LanguageEntry {
+                            language: String::from("eng"),
+                            audio_type: 0,
+                        }])],
+                    },
+                ],
+            }
+        );
+
+        assert_eq!(
+            Descriptor::write_loop(&pmt.descriptors).unwrap(),
+            &PMT[12..18]
+        );
+        assert_eq!(
+            Descriptor::write_loop(&pmt.streams[1].descriptors).unwrap(),
+            &PMT[28..34]
+        );
+    }
+
+    #[test]
+    fn test_sdt() {
+        gst::init().unwrap();
+
+        let mut section = Section::new(0x11, SDT).unwrap();
+        assert!(section.eit().is_none());
+        let sdt = section.sdt().unwrap();
+
+        assert_eq!(
+            sdt,
+            Sdt {
+                original_network_id: 0x233a,
+                actual_ts: true,
+                transport_stream_id: 1,
+                services: vec![SdtService {
+                    service_id: 1,
+                    eit_schedule_flag: false,
+                    eit_present_following_flag: true,
+                    running_status: 4,
+                    free_ca_mode: false,
+                    descriptors: vec![Descriptor::DvbService {
+                        service_type: 0x01,
+                        provider_name: String::from("Prv"),
+                        service_name: String::from("Chan"),
+                    }],
+                }],
+            }
+        );
+
+        assert_eq!(
+            Descriptor::write_loop(&sdt.services[0].descriptors).unwrap(),
+            &SDT[16..28]
+        );
+    }
+
+    #[test]
+    fn test_eit() {
+        gst::init().unwrap();
+
+        let mut section = Section::new(0x12, EIT).unwrap();
+        assert!(section.pmt().is_none());
+        let eit = section.eit().unwrap();
+
+        assert_eq!(eit.transport_stream_id, 1);
+        assert_eq!(eit.original_network_id, 0x233a);
+        assert_eq!(eit.segment_last_section_number, 0);
+        assert_eq!(eit.last_table_id, 0x4e);
+        assert!(eit.actual_stream);
+        assert!(eit.present_following);
+        assert_eq!(eit.events.len(), 1);
+
+        let event = &eit.events[0];
+        assert_eq!(event.event_id, 0x42);
+        let start_time = event.start_time.as_ref().unwrap();
+        assert_eq!(
+            (
+                start_time.year(),
+                start_time.month(),
+                start_time.day(),
+                start_time.hour(),
+                start_time.minute(),
+            ),
+            (2024, Some(1), Some(1), Some(12), Some(30))
+        );
+        assert_eq!(event.duration, gst::ClockTime::from_seconds(5400));
+        assert_eq!(event.running_status, 4);
+        assert!(!event.free_ca_mode);
+        assert_eq!(
+            event.descriptors,
+            [Descriptor::DvbShortEvent {
+                language: String::from("eng"),
+                event_name: String::from("News"),
+                text: String::new(),
+            }]
+        );
+
+        assert_eq!(
+            Descriptor::write_loop(&event.descriptors).unwrap(),
+            &EIT[26..37]
+        );
+    }
+}
@@ -23,8 +23,9 @@ macro_rules! skip_assert_initialized {
 
 #[allow(unused_imports)]
 mod auto;
-pub use crate::{auto::*, net_address_meta::*};
+pub use crate::{auto::*, net_address_meta::*, net_control_message_meta::*};
 mod net_address_meta;
+mod net_control_message_meta;
 
 mod ptp_clock;
 pub use ptp_clock::PtpStatisticsCallback;
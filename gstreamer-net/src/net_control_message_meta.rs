@@ -0,0 +1,385 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use crate::ffi;
+use glib::translate::*;
+use gst::prelude::*;
+
+#[repr(transparent)]
+#[doc(alias = "GstNetControlMessageMeta")]
+pub struct NetControlMessageMeta(ffi::GstNetControlMessageMeta);
+
+unsafe impl Send for NetControlMessageMeta {}
+unsafe impl Sync for NetControlMessageMeta {}
+
+impl NetControlMessageMeta {
+    #[doc(alias = "gst_buffer_add_net_control_message_meta")]
+    pub fn add<'a, M: IsA<gio::SocketControlMessage>>(
+        buffer: &'a mut gst::BufferRef,
+        message: &M,
+    ) -> gst::MetaRefMut<'a, Self, gst::meta::Standalone> {
+        skip_assert_initialized!();
+        unsafe {
+            let meta = ffi::gst_buffer_add_net_control_message_meta(
+                buffer.as_mut_ptr(),
+                message.as_ref().to_glib_none().0,
+            );
+            Self::from_mut_ptr(buffer, meta)
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the control messages of all metas on `buffer`.
+    pub fn messages(
+        buffer: &gst::BufferRef,
+    ) -> impl Iterator<Item = gio::SocketControlMessage> + '_ {
+        skip_assert_initialized!();
+        buffer.iter_meta::<Self>().map(|meta| meta.message())
+    }
+
+    #[doc(alias = "get_message")]
+    #[inline]
+    pub fn message(&self) -> gio::SocketControlMessage {
+        unsafe { from_glib_none(self.0.message) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Decodes the control message of this meta.
+    ///
+    /// See [`NetControlMessage::from_socket_control_message()`].
+    pub fn decode(&self) -> Option<NetControlMessage> {
+        NetControlMessage::from_socket_control_message(&self.message())
+    }
+}
+
+unsafe impl MetaAPI for NetControlMessageMeta {
+    type GstType = ffi::GstNetControlMessageMeta;
+
+    #[doc(alias = "gst_net_control_message_meta_api_get_type")]
+    #[inline]
+    fn meta_api() -> glib::Type {
+        unsafe { from_glib(ffi::gst_net_control_message_meta_api_get_type()) }
+    }
+}
+
+impl fmt::Debug for NetControlMessageMeta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NetControlMessageMeta")
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod consts {
+    pub const IPPROTO_IP: i32 = 0;
+    pub const IPPROTO_IPV6: i32 = 41;
+    pub const IP_TOS: i32 = 1;
+    pub const IP_TTL: i32 = 2;
+    pub const IP_PKTINFO: i32 = 8;
+    pub const IPV6_PKTINFO: i32 = 50;
+    pub const IPV6_HOPLIMIT: i32 = 52;
+    pub const IPV6_TCLASS: i32 = 67;
+}
+
+// The received TOS and TTL use the IP_RECV* values as message type here
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod consts {
+    pub const IPPROTO_IP: i32 = 0;
+    pub const IPPROTO_IPV6: i32 = 41;
+    pub const IP_TOS: i32 = 27;
+    pub const IP_TTL: i32 = 24;
+    pub const IP_PKTINFO: i32 = 26;
+    pub const IPV6_PKTINFO: i32 = 46;
+    pub const IPV6_HOPLIMIT: i32 = 47;
+    pub const IPV6_TCLASS: i32 = 36;
+}
+
+#[cfg(windows)]
+mod consts {
+    pub const IPPROTO_IP: i32 = 0;
+    pub const IPPROTO_IPV6: i32 = 41;
+    pub const IP_TOS: i32 = 3;
+    pub const IP_TTL: i32 = 4;
+    pub const IP_PKTINFO: i32 = 19;
+    pub const IPV6_PKTINFO: i32 = 19;
+    pub const IPV6_HOPLIMIT: i32 = 21;
+    pub const IPV6_TCLASS: i32 = 39;
+}
+
+// rustdoc-stripper-ignore-next
+/// Decoded payload of a socket control message.
+///
+/// Only the IP level messages for packet information, TOS / traffic class and TTL / hop
+/// limit are decoded, everything else is returned as [`NetControlMessage::Other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NetControlMessage {
+    // rustdoc-stripper-ignore-next
+    /// `IP_PKTINFO`
+    Ipv4PacketInfo {
+        // rustdoc-stripper-ignore-next
+        /// Index of the interface the packet was received on.
+        interface_index: u32,
+        // rustdoc-stripper-ignore-next
+        /// Local address the packet was received with. Not available on all platforms.
+        local_address: Option<Ipv4Addr>,
+        // rustdoc-stripper-ignore-next
+        /// Destination address from the IP header, e.g. the multicast group.
+        destination_address: Ipv4Addr,
+    },
+    // rustdoc-stripper-ignore-next
+    /// `IPV6_PKTINFO`
+    Ipv6PacketInfo {
+        interface_index: u32,
+        destination_address: Ipv6Addr,
+    },
+    // rustdoc-stripper-ignore-next
+    /// `IP_TOS`
+    Tos(u8),
+    // rustdoc-stripper-ignore-next
+    /// `IPV6_TCLASS`
+    TrafficClass(u8),
+    // rustdoc-stripper-ignore-next
+    /// `IP_TTL`
+    Ttl(u8),
+    // rustdoc-stripper-ignore-next
+    /// `IPV6_HOPLIMIT`
+    HopLimit(u8),
+    Other {
+        level: i32,
+        msg_type: i32,
+        data: Vec<u8>,
+    },
+}
+
+impl NetControlMessage {
+    // rustdoc-stripper-ignore-next
+    /// Decodes the payload of a control message with the given level and type.
+    pub fn parse(level: i32, msg_type: i32, data: &[u8]) -> Self {
+        skip_assert_initialized!();
+
+        parse_ip_message(level, msg_type, data).unwrap_or_else(|| Self::Other {
+            level,
+            msg_type,
+            data: data.to_vec(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Decodes `message` by serializing it and decoding the result with [`Self::parse()`].
+    ///
+    /// Returns `None` if the message doesn't support serialization.
+    pub fn from_socket_control_message(
+        message: &impl IsA<gio::SocketControlMessage>,
+    ) -> Option<Self> {
+        skip_assert_initialized!();
+
+        unsafe {
+            let message = message.as_ref().to_glib_none().0;
+
+            let class = (*(message as *mut glib::gobject_ffi::GTypeInstance)).g_class
+                as *const gio::ffi::GSocketControlMessageClass;
+            if (*class).serialize.is_none() {
+                return None;
+            }
+
+            let level = gio::ffi::g_socket_control_message_get_level(message);
+            let msg_type = gio::ffi::g_socket_control_message_get_msg_type(message);
+            let size = gio::ffi::g_socket_control_message_get_size(message);
+
+            let mut data = vec![0u8; size];
+            gio::ffi::g_socket_control_message_serialize(message, data.as_mut_ptr() as *mut _);
+
+            Some(Self::parse(level, msg_type, &data))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the DSCP value of a `Tos` or `TrafficClass` message.
+    pub fn dscp(&self) -> Option<u8> {
+        match self {
+            Self::Tos(v) | Self::TrafficClass(v) => Some(v >> 2),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    windows
+))]
+fn parse_ip_message(level: i32, msg_type: i32, data: &[u8]) -> Option<NetControlMessage> {
+    use consts::*;
+
+    match (level, msg_type) {
+        (IPPROTO_IP, IP_PKTINFO) => parse_ipv4_packet_info(data),
+        (IPPROTO_IPV6, IPV6_PKTINFO) => {
+            // struct in6_pktinfo { struct in6_addr ipi6_addr; int ipi6_ifindex; }
+            let data = data.get(..20)?;
+            Some(NetControlMessage::Ipv6PacketInfo {
+                interface_index: u32::from_ne_bytes(data[16..20].try_into().unwrap()),
+                destination_address: Ipv6Addr::from(<[u8; 16]>::try_from(&data[..16]).unwrap()),
+            })
+        }
+        (IPPROTO_IP, IP_TOS) => parse_int(data).map(NetControlMessage::Tos),
+        (IPPROTO_IPV6, IPV6_TCLASS) => parse_int(data).map(NetControlMessage::TrafficClass),
+        (IPPROTO_IP, IP_TTL) => parse_int(data).map(NetControlMessage::Ttl),
+        (IPPROTO_IPV6, IPV6_HOPLIMIT) => parse_int(data).map(NetControlMessage::HopLimit),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    windows
+)))]
+fn parse_ip_message(_level: i32, _msg_type: i32, _data: &[u8]) -> Option<NetControlMessage> {
+    None
+}
+
+#[cfg(windows)]
+fn parse_ipv4_packet_info(data: &[u8]) -> Option<NetControlMessage> {
+    // struct in_pktinfo { IN_ADDR ipi_addr; ULONG ipi_ifindex; }
+    let data = data.get(..8)?;
+
+    Some(NetControlMessage::Ipv4PacketInfo {
+        interface_index: u32::from_ne_bytes(data[4..8].try_into().unwrap()),
+        local_address: None,
+        destination_address: Ipv4Addr::from(<[u8; 4]>::try_from(&data[..4]).unwrap()),
+    })
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+fn parse_ipv4_packet_info(data: &[u8]) -> Option<NetControlMessage> {
+    // struct in_pktinfo { int ipi_ifindex; struct in_addr ipi_spec_dst; struct in_addr ipi_addr; }
+    let data = data.get(..12)?;
+
+    Some(NetControlMessage::Ipv4PacketInfo {
+        interface_index: u32::from_ne_bytes(data[..4].try_into().unwrap()),
+        local_address: Some(Ipv4Addr::from(<[u8; 4]>::try_from(&data[4..8]).unwrap())),
+        destination_address: Ipv4Addr::from(<[u8; 4]>::try_from(&data[8..12]).unwrap()),
+    })
+}
+
+// Depending on the platform and message these are either a single byte or an int
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    windows
+))]
+fn parse_int(data: &[u8]) -> Option<u8> {
+    match data.len() {
+        1 => Some(data[0]),
+        4 => u8::try_from(i32::from_ne_bytes(data.try_into().unwrap())).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse() {
+        let mut pktinfo = Vec::new();
+        pktinfo.extend_from_slice(&3i32.to_ne_bytes());
+        pktinfo.extend_from_slice(&[192, 168, 1, 10]);
+        pktinfo.extend_from_slice(&[239, 1, 2, 3]);
+        assert_eq!(
+            NetControlMessage::parse(0, 8, &pktinfo),
+            NetControlMessage::Ipv4PacketInfo {
+                interface_index: 3,
+                local_address: Some(Ipv4Addr::new(192, 168, 1, 10)),
+                destination_address: Ipv4Addr::new(239, 1, 2, 3),
+            }
+        );
+
+        let mut pktinfo = Ipv6Addr::LOCALHOST.octets().to_vec();
+        pktinfo.extend_from_slice(&1i32.to_ne_bytes());
+        assert_eq!(
+            NetControlMessage::parse(41, 50, &pktinfo),
+            NetControlMessage::Ipv6PacketInfo {
+                interface_index: 1,
+                destination_address: Ipv6Addr::LOCALHOST,
+            }
+        );
+
+        // DSCP AF41
+        let tos = NetControlMessage::parse(0, 1, &[0x88]);
+        assert_eq!(tos, NetControlMessage::Tos(0x88));
+        assert_eq!(tos.dscp(), Some(34));
+
+        assert_eq!(
+            NetControlMessage::parse(0, 2, &64i32.to_ne_bytes()),
+            NetControlMessage::Ttl(64)
+        );
+        assert_eq!(
+            NetControlMessage::parse(41, 52, &[1, 2]),
+            NetControlMessage::Other {
+                level: 41,
+                msg_type: 52,
+                data: vec![1, 2],
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    fn deserialize(level: i32, msg_type: i32, data: &[u8]) -> Option<gio::SocketControlMessage> {
+        unsafe {
+            from_glib_full(gio::ffi::g_socket_control_message_deserialize(
+                level,
+                msg_type,
+                data.len(),
+                data.as_ptr() as glib::ffi::gpointer,
+            ))
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn decode_meta(message: &gio::SocketControlMessage) -> Option<NetControlMessage> {
+        let mut buffer = gst::Buffer::new();
+        NetControlMessageMeta::add(buffer.get_mut().unwrap(), message);
+        let meta = buffer.meta::<NetControlMessageMeta>().unwrap();
+        meta.decode()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_decode_meta() {
+        gst::init().unwrap();
+
+        // SCM_CREDENTIALS, struct ucred { pid_t pid; uid_t uid; gid_t gid; }
+        let mut ucred = Vec::new();
+        ucred.extend_from_slice(&(std::process::id() as i32).to_ne_bytes());
+        ucred.extend_from_slice(&1000u32.to_ne_bytes());
+        ucred.extend_from_slice(&1000u32.to_ne_bytes());
+        let message = deserialize(1, 2, &ucred).unwrap();
+        assert_eq!(
+            decode_meta(&message),
+            Some(NetControlMessage::Other {
+                level: 1,
+                msg_type: 2,
+                data: ucred,
+            })
+        );
+    }
+}
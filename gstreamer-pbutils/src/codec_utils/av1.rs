@@ -0,0 +1,681 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! AV1 sequence header OBU and `av1C` parsing.
+
+use gst_video::gst_base::{BitReader, BitWriter, ReadError};
+
+use super::{ChromaFormat, ColorDescription, VideoCodecParams};
+
+pub const OBU_SEQUENCE_HEADER: u8 = 1;
+pub const OBU_TEMPORAL_DELIMITER: u8 = 2;
+pub const OBU_METADATA: u8 = 5;
+
+// rustdoc-stripper-ignore-next
+/// Header of an open bitstream unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObuHeader {
+    pub obu_type: u8,
+    pub temporal_id: u8,
+    pub spatial_id: u8,
+    // rustdoc-stripper-ignore-next
+    /// Size of the header including the size field, if any.
+    pub header_size: usize,
+    // rustdoc-stripper-ignore-next
+    /// Size of the payload if the OBU has a size field.
+    pub payload_size: Option<usize>,
+}
+
+impl ObuHeader {
+    pub fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut r = BitReader::new(data);
+
+        if r.read_bit()? {
+            return Err(glib::bool_error!("Forbidden bit set"));
+        }
+        let obu_type = r.read_bits_u8(4)?;
+        let extension = r.read_bit()?;
+        let has_size = r.read_bit()?;
+        let _reserved_1bit = r.read_bit()?;
+
+        let (temporal_id, spatial_id) = if extension {
+            let temporal_id = r.read_bits_u8(3)?;
+            let spatial_id = r.read_bits_u8(2)?;
+            let _extension_header_reserved_3bits = r.read_bits_u8(3)?;
+            (temporal_id, spatial_id)
+        } else {
+            (0, 0)
+        };
+
+        let payload_size = if has_size {
+            Some(read_leb128(&mut r)? as usize)
+        } else {
+            None
+        };
+
+        Ok(ObuHeader {
+            obu_type,
+            temporal_id,
+            spatial_id,
+            header_size: r.pos() / 8,
+            payload_size,
+        })
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns an iterator over the OBUs in `data` together with their payloads.
+///
+/// All but the last OBU must have a size field.
+pub fn obus(data: &[u8]) -> impl Iterator<Item = Result<(ObuHeader, &[u8]), glib::BoolError>> {
+    skip_assert_initialized!();
+
+    let mut data = data;
+    std::iter::from_fn(move || {
+        if data.is_empty() {
+            return None;
+        }
+
+        let cur = data;
+        let res = ObuHeader::parse(cur).and_then(|header| {
+            let rest = &cur[header.header_size..];
+            let size = header.payload_size.unwrap_or(rest.len());
+            let payload = rest
+                .get(..size)
+                .ok_or_else(|| glib::bool_error!("OBU payload truncated"))?;
+            Ok((header, payload, &rest[size..]))
+        });
+
+        let res = match res {
+            Ok((header, payload, rest)) => {
+                data = rest;
+                Ok((header, payload))
+            }
+            Err(err) => {
+                data = &[];
+                Err(err)
+            }
+        };
+
+        Some(res)
+    })
+}
+
+fn read_leb128(r: &mut BitReader) -> Result<u64, ReadError> {
+    let mut value = 0;
+    for i in 0..8 {
+        let b = r.read_bits_u8(8)?;
+        value |= ((b & 0x7f) as u64) << (i * 7);
+        if b & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(ReadError::InvalidData)
+}
+
+fn read_uvlc(r: &mut BitReader) -> Result<u32, ReadError> {
+    let mut leading_zeros = 0;
+    while !r.read_bit()? {
+        leading_zeros += 1;
+        if leading_zeros >= 32 {
+            return Ok(u32::MAX);
+        }
+    }
+
+    Ok(((1u64 << leading_zeros) - 1 + r.read_bits_u64(leading_zeros)?) as u32)
+}
+
+// rustdoc-stripper-ignore-next
+/// Timing information of the sequence header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingInfo {
+    pub num_units_in_display_tick: u32,
+    pub time_scale: u32,
+    // rustdoc-stripper-ignore-next
+    /// Number of ticks per picture if all pictures have the same duration.
+    pub num_ticks_per_picture: Option<u64>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Operating point of the sequence header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatingPoint {
+    pub idc: u16,
+    pub seq_level_idx: u8,
+    pub seq_tier: u8,
+    pub initial_display_delay: Option<u8>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Color configuration of the sequence header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorConfig {
+    pub bit_depth: u8,
+    pub mono_chrome: bool,
+    pub color_description: Option<ColorDescription>,
+    pub color_range: bool,
+    pub subsampling_x: bool,
+    pub subsampling_y: bool,
+    pub chroma_sample_position: u8,
+    pub separate_uv_delta_q: bool,
+}
+
+impl ColorConfig {
+    pub fn chroma_format(&self) -> ChromaFormat {
+        match (self.mono_chrome, self.subsampling_x, self.subsampling_y) {
+            (true, _, _) => ChromaFormat::Monochrome,
+            (false, true, true) => ChromaFormat::Yuv420,
+            (false, true, false) => ChromaFormat::Yuv422,
+            (false, false, _) => ChromaFormat::Yuv444,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Parsed AV1 sequence header OBU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstAV1SequenceHeaderOBU")]
+pub struct SequenceHeader {
+    pub seq_profile: u8,
+    pub still_picture: bool,
+    pub reduced_still_picture_header: bool,
+    pub timing_info: Option<TimingInfo>,
+    pub operating_points: Vec<OperatingPoint>,
+    pub max_frame_width: u32,
+    pub max_frame_height: u32,
+    pub color_config: ColorConfig,
+    pub film_grain_params_present: bool,
+}
+
+impl SequenceHeader {
+    // rustdoc-stripper-ignore-next
+    /// Parses the first sequence header OBU from `data`, which can contain other OBUs
+    /// before it.
+    pub fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        for obu in obus(data) {
+            let (header, payload) = obu?;
+            if header.obu_type == OBU_SEQUENCE_HEADER {
+                return Self::parse_payload(payload);
+            }
+        }
+
+        Err(glib::bool_error!("No sequence header OBU"))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the first sequence header OBU of an `av1C` `codec_data`.
+    pub fn from_codec_data(codec_data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let record = Av1CodecConfigurationRecord::parse(codec_data)?;
+        Self::parse(&record.config_obus)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the payload of a sequence header OBU.
+    pub fn parse_payload(payload: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut r = BitReader::new(payload);
+
+        let seq_profile = r.read_bits_u8(3)?;
+        if seq_profile > 2 {
+            return Err(glib::bool_error!("Unsupported profile {}", seq_profile));
+        }
+        let still_picture = r.read_bit()?;
+        let reduced_still_picture_header = r.read_bit()?;
+
+        let mut timing_info = None;
+        let mut operating_points = Vec::new();
+        if reduced_still_picture_header {
+            operating_points.push(OperatingPoint {
+                idc: 0,
+                seq_level_idx: r.read_bits_u8(5)?,
+                seq_tier: 0,
+                initial_display_delay: None,
+            });
+        } else {
+            let mut buffer_delay_length = None;
+            if r.read_bit()? {
+                let num_units_in_display_tick = r.read_bits_u32(32)?;
+                let time_scale = r.read_bits_u32(32)?;
+                let equal_picture_interval = r.read_bit()?;
+                let num_ticks_per_picture = if equal_picture_interval {
+                    Some(read_uvlc(&mut r)? as u64 + 1)
+                } else {
+                    None
+                };
+                timing_info = Some(TimingInfo {
+                    num_units_in_display_tick,
+                    time_scale,
+                    num_ticks_per_picture,
+                });
+
+                let decoder_model_info_present = r.read_bit()?;
+                if decoder_model_info_present {
+                    buffer_delay_length = Some(r.read_bits_u32(5)? + 1);
+                    let _num_units_in_decoding_tick = r.read_bits_u32(32)?;
+                    let _buffer_removal_time_length_minus_1 = r.read_bits_u8(5)?;
+                    let _frame_presentation_time_length_minus_1 = r.read_bits_u8(5)?;
+                }
+            }
+
+            let initial_display_delay_present = r.read_bit()?;
+            let operating_points_cnt = r.read_bits_u8(5)? + 1;
+            for _ in 0..operating_points_cnt {
+                let idc = r.read_bits_u16(12)?;
+                let seq_level_idx = r.read_bits_u8(5)?;
+                let seq_tier = if seq_level_idx > 7 {
+                    r.read_bits_u8(1)?
+                } else {
+                    0
+                };
+
+                if let Some(n) = buffer_delay_length {
+                    let decoder_model_present_for_this_op = r.read_bit()?;
+                    if decoder_model_present_for_this_op {
+                        let _decoder_buffer_delay = r.read_bits_u32(n)?;
+                        let _encoder_buffer_delay = r.read_bits_u32(n)?;
+                        let _low_delay_mode_flag = r.read_bit()?;
+                    }
+                }
+
+                let initial_display_delay = if initial_display_delay_present && r.read_bit()? {
+                    Some(r.read_bits_u8(4)? + 1)
+                } else {
+                    None
+                };
+
+                operating_points.push(OperatingPoint {
+                    idc,
+                    seq_level_idx,
+                    seq_tier,
+                    initial_display_delay,
+                });
+            }
+        }
+
+        let frame_width_bits = r.read_bits_u32(4)? + 1;
+        let frame_height_bits = r.read_bits_u32(4)? + 1;
+        let max_frame_width = r.read_bits_u32(frame_width_bits)? + 1;
+        let max_frame_height = r.read_bits_u32(frame_height_bits)? + 1;
+
+        let frame_id_numbers_present = !reduced_still_picture_header && r.read_bit()?;
+        if frame_id_numbers_present {
+            let _delta_frame_id_length_minus_2 = r.read_bits_u8(4)?;
+            let _additional_frame_id_length_minus_1 = r.read_bits_u8(3)?;
+        }
+
+        let _use_128x128_superblock = r.read_bit()?;
+        let _enable_filter_intra = r.read_bit()?;
+        let _enable_intra_edge_filter = r.read_bit()?;
+
+        if !reduced_still_picture_header {
+            let _enable_interintra_compound = r.read_bit()?;
+            let _enable_masked_compound = r.read_bit()?;
+            let _enable_warped_motion = r.read_bit()?;
+            let _enable_dual_filter = r.read_bit()?;
+            let enable_order_hint = r.read_bit()?;
+            if enable_order_hint {
+                let _enable_jnt_comp = r.read_bit()?;
+                let _enable_ref_frame_mvs = r.read_bit()?;
+            }
+
+            let seq_choose_screen_content_tools = r.read_bit()?;
+            let seq_force_screen_content_tools = if seq_choose_screen_content_tools {
+                2
+            } else {
+                r.read_bits_u8(1)?
+            };
+            if seq_force_screen_content_tools > 0 {
+                let seq_choose_integer_mv = r.read_bit()?;
+                if !seq_choose_integer_mv {
+                    let _seq_force_integer_mv = r.read_bit()?;
+                }
+            }
+
+            if enable_order_hint {
+                let _order_hint_bits_minus_1 = r.read_bits_u8(3)?;
+            }
+        }
+
+        let _enable_superres = r.read_bit()?;
+        let _enable_cdef = r.read_bit()?;
+        let _enable_restoration = r.read_bit()?;
+
+        let color_config = parse_color_config(&mut r, seq_profile)?;
+        let film_grain_params_present = r.read_bit()?;
+
+        Ok(SequenceHeader {
+            seq_profile,
+            still_picture,
+            reduced_still_picture_header,
+            timing_info,
+            operating_points,
+            max_frame_width,
+            max_frame_height,
+            color_config,
+            film_grain_params_present,
+        })
+    }
+
+    pub fn params(&self) -> VideoCodecParams {
+        let framerate = self.timing_info.and_then(|timing_info| {
+            super::fraction(
+                timing_info.time_scale as u64,
+                timing_info.num_units_in_display_tick as u64
+                    * timing_info.num_ticks_per_picture.unwrap_or(1),
+            )
+        });
+
+        VideoCodecParams {
+            width: self.max_frame_width,
+            height: self.max_frame_height,
+            framerate,
+            pixel_aspect_ratio: None,
+            interlaced: false,
+            chroma_format: self.color_config.chroma_format(),
+            bit_depth_luma: self.color_config.bit_depth,
+            bit_depth_chroma: self.color_config.bit_depth,
+            color_description: self.color_config.color_description,
+            full_range: self.color_config.color_range,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value used for the `profile` caps field.
+    pub fn profile_str(&self) -> &'static str {
+        match self.seq_profile {
+            0 => "main",
+            1 => "high",
+            _ => "professional",
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the stream properties and profile of this sequence header on the `video/x-av1`
+    /// `caps`.
+    pub fn set_caps(&self, caps: &mut gst::CapsRef) {
+        assert_initialized_main_thread!();
+
+        self.params().set_caps(caps);
+        caps.set("profile", self.profile_str());
+    }
+}
+
+fn parse_color_config(r: &mut BitReader, seq_profile: u8) -> Result<ColorConfig, glib::BoolError> {
+    const CP_BT_709: u8 = 1;
+    const TC_SRGB: u8 = 13;
+    const MC_IDENTITY: u8 = 0;
+    const CSP_UNKNOWN: u8 = 0;
+
+    let high_bitdepth = r.read_bit()?;
+    let bit_depth = if seq_profile == 2 && high_bitdepth {
+        if r.read_bit()? { 12 } else { 10 }
+    } else if high_bitdepth {
+        10
+    } else {
+        8
+    };
+
+    let mono_chrome = seq_profile != 1 && r.read_bit()?;
+
+    let color_description = if r.read_bit()? {
+        Some(ColorDescription {
+            colour_primaries: r.read_bits_u8(8)?,
+            transfer_characteristics: r.read_bits_u8(8)?,
+            matrix_coefficients: r.read_bits_u8(8)?,
+        })
+    } else {
+        None
+    };
+
+    if mono_chrome {
+        return Ok(ColorConfig {
+            bit_depth,
+            mono_chrome,
+            color_description,
+            color_range: r.read_bit()?,
+            subsampling_x: true,
+            subsampling_y: true,
+            chroma_sample_position: CSP_UNKNOWN,
+            separate_uv_delta_q: false,
+        });
+    }
+
+    let (color_range, subsampling_x, subsampling_y, chroma_sample_position) = if color_description
+        .is_some_and(|desc| {
+            desc.colour_primaries == CP_BT_709
+                && desc.transfer_characteristics == TC_SRGB
+                && desc.matrix_coefficients == MC_IDENTITY
+        }) {
+        (true, false, false, CSP_UNKNOWN)
+    } else {
+        let color_range = r.read_bit()?;
+        let (subsampling_x, subsampling_y) = match seq_profile {
+            0 => (true, true),
+            1 => (false, false),
+            _ if bit_depth == 12 => {
+                let subsampling_x = r.read_bit()?;
+                (subsampling_x, subsampling_x && r.read_bit()?)
+            }
+            _ => (true, false),
+        };
+        let chroma_sample_position = if subsampling_x && subsampling_y {
+            r.read_bits_u8(2)?
+        } else {
+            CSP_UNKNOWN
+        };
+        (
+            color_range,
+            subsampling_x,
+            subsampling_y,
+            chroma_sample_position,
+        )
+    };
+
+    Ok(ColorConfig {
+        bit_depth,
+        mono_chrome,
+        color_description,
+        color_range,
+        subsampling_x,
+        subsampling_y,
+        chroma_sample_position,
+        separate_uv_delta_q: r.read_bit()?,
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// `AV1CodecConfigurationRecord` as used for the `codec_data` of AV1 streams and in the
+/// `av1C` box of the AV1 ISOBMFF binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Av1CodecConfigurationRecord {
+    pub seq_profile: u8,
+    pub seq_level_idx_0: u8,
+    pub seq_tier_0: u8,
+    pub high_bitdepth: bool,
+    pub twelve_bit: bool,
+    pub monochrome: bool,
+    pub chroma_subsampling_x: bool,
+    pub chroma_subsampling_y: bool,
+    pub chroma_sample_position: u8,
+    pub initial_presentation_delay: Option<u8>,
+    // rustdoc-stripper-ignore-next
+    /// Sequence header and metadata OBUs, each with a size field.
+    pub config_obus: Vec<u8>,
+}
+
+impl Av1CodecConfigurationRecord {
+    // rustdoc-stripper-ignore-next
+    /// Creates a configuration record from a complete sequence header OBU with size field.
+    pub fn from_sequence_header_obu(obu: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let seq = SequenceHeader::parse(obu)?;
+        let op = seq.operating_points[0];
+        let color_config = seq.color_config;
+
+        Ok(Av1CodecConfigurationRecord {
+            seq_profile: seq.seq_profile,
+            seq_level_idx_0: op.seq_level_idx,
+            seq_tier_0: op.seq_tier,
+            high_bitdepth: color_config.bit_depth > 8,
+            twelve_bit: color_config.bit_depth == 12,
+            monochrome: color_config.mono_chrome,
+            chroma_subsampling_x: color_config.subsampling_x,
+            chroma_subsampling_y: color_config.subsampling_y,
+            chroma_sample_position: color_config.chroma_sample_position,
+            initial_presentation_delay: None,
+            config_obus: obu.to_vec(),
+        })
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut r = BitReader::new(data);
+
+        let marker = r.read_bit()?;
+        let version = r.read_bits_u8(7)?;
+        if !marker || version != 1 {
+            return Err(glib::bool_error!("Unsupported av1C version"));
+        }
+
+        let seq_profile = r.read_bits_u8(3)?;
+        let seq_level_idx_0 = r.read_bits_u8(5)?;
+        let seq_tier_0 = r.read_bits_u8(1)?;
+        let high_bitdepth = r.read_bit()?;
+        let twelve_bit = r.read_bit()?;
+        let monochrome = r.read_bit()?;
+        let chroma_subsampling_x = r.read_bit()?;
+        let chroma_subsampling_y = r.read_bit()?;
+        let chroma_sample_position = r.read_bits_u8(2)?;
+        let _reserved = r.read_bits_u8(3)?;
+        let initial_presentation_delay_present = r.read_bit()?;
+        let initial_presentation_delay_minus_one = r.read_bits_u8(4)?;
+
+        Ok(Av1CodecConfigurationRecord {
+            seq_profile,
+            seq_level_idx_0,
+            seq_tier_0,
+            high_bitdepth,
+            twelve_bit,
+            monochrome,
+            chroma_subsampling_x,
+            chroma_subsampling_y,
+            chroma_sample_position,
+            initial_presentation_delay: initial_presentation_delay_present
+                .then_some(initial_presentation_delay_minus_one + 1),
+            config_obus: data[4..].to_vec(),
+        })
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut w = BitWriter::with_capacity(4 + self.config_obus.len());
+
+        w.write_bit(true);
+        w.write_bits(1, 7);
+        w.write_bits(self.seq_profile as u64, 3);
+        w.write_bits(self.seq_level_idx_0 as u64, 5);
+        w.write_bits(self.seq_tier_0 as u64, 1);
+        w.write_bit(self.high_bitdepth);
+        w.write_bit(self.twelve_bit);
+        w.write_bit(self.monochrome);
+        w.write_bit(self.chroma_subsampling_x);
+        w.write_bit(self.chroma_subsampling_y);
+        w.write_bits(self.chroma_sample_position as u64, 2);
+        w.write_bits(0, 3);
+        match self.initial_presentation_delay {
+            Some(delay) => {
+                w.write_bit(true);
+                w.write_bits(delay.saturating_sub(1) as u64, 4);
+            }
+            None => w.write_bits(0, 5),
+        }
+        w.write_bytes(&self.config_obus);
+
+        w.into_vec()
+    }
+
+    pub fn to_buffer(&self) -> gst::Buffer {
+        gst::Buffer::from_mut_slice(self.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1920x1080 main profile, level 4.0, 10 bit, 30 fps, BT.2020 PQ
+    const SEQUENCE_HEADER: &[u8] = &[
+        0x0a, 0x17, 0x04, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x7b, 0x00, 0x00, 0x08, 0x55,
+        0x77, 0xf8, 0x6e, 0x7f, 0xfc, 0xe8, 0x48, 0x80, 0x48, 0x20,
+    ];
+
+    #[test]
+    fn test_sequence_header() {
+        let seq = SequenceHeader::parse(SEQUENCE_HEADER).unwrap();
+        assert_eq!(seq.seq_profile, 0);
+        assert_eq!(seq.operating_points.len(), 1);
+        assert_eq!(seq.operating_points[0].seq_level_idx, 8);
+        assert_eq!(seq.max_frame_width, 1920);
+        assert_eq!(seq.max_frame_height, 1080);
+
+        let params = seq.params();
+        assert_eq!(params.framerate, Some(gst::Fraction::new(30, 1)));
+        assert_eq!(params.chroma_format, ChromaFormat::Yuv420);
+        assert_eq!(params.bit_depth_luma, 10);
+        assert_eq!(
+            params.color_description,
+            Some(ColorDescription {
+                colour_primaries: 9,
+                transfer_characteristics: 16,
+                matrix_coefficients: 9,
+            })
+        );
+
+        // Temporal delimiter in front of the sequence header
+        let mut data = vec![0x12, 0x00];
+        data.extend_from_slice(SEQUENCE_HEADER);
+        assert_eq!(SequenceHeader::parse(&data).unwrap(), seq);
+
+        assert!(SequenceHeader::parse(&[0x12, 0x00]).is_err());
+        assert!(SequenceHeader::parse(&SEQUENCE_HEADER[..8]).is_err());
+    }
+
+    #[test]
+    fn test_av1c() {
+        let record =
+            Av1CodecConfigurationRecord::from_sequence_header_obu(SEQUENCE_HEADER).unwrap();
+        let data = record.to_vec();
+        assert_eq!(&data[..4], &[0x81, 0x08, 0x4c, 0x00]);
+
+        let parsed = Av1CodecConfigurationRecord::parse(&data).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(
+            SequenceHeader::from_codec_data(&data).unwrap(),
+            SequenceHeader::parse(SEQUENCE_HEADER).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_caps() {
+        gst::init().unwrap();
+
+        let mut caps = gst::Caps::new_empty_simple("video/x-av1");
+        SequenceHeader::parse(SEQUENCE_HEADER)
+            .unwrap()
+            .set_caps(caps.get_mut().unwrap());
+
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<i32>("width"), Ok(1920));
+        assert_eq!(s.get::<&str>("profile"), Ok("main"));
+        assert_eq!(s.get::<&str>("chroma-format"), Ok("4:2:0"));
+    }
+}
@@ -0,0 +1,621 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! H.264 sequence parameter set and `avcC` parsing.
+
+use gst_video::gst_base::{BitReader, BitWriter, ByteReader, ByteWriter};
+
+use super::{ChromaFormat, ColorDescription, VideoCodecParams};
+
+pub const NAL_UNIT_TYPE_SPS: u8 = 7;
+pub const NAL_UNIT_TYPE_PPS: u8 = 8;
+pub const NAL_UNIT_TYPE_SPS_EXT: u8 = 13;
+
+// Profiles that signal chroma format and bit depth in the SPS and the `avcC`
+const HIGH_PROFILES: [u8; 13] = [100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134, 135];
+
+// rustdoc-stripper-ignore-next
+/// Returns the `nal_unit_type` of an H.264 NAL unit.
+pub fn nal_unit_type(nal: &[u8]) -> Option<u8> {
+    skip_assert_initialized!();
+    nal.first().map(|b| b & 0x1f)
+}
+
+// rustdoc-stripper-ignore-next
+/// Timing information of the VUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingInfo {
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub fixed_frame_rate: bool,
+}
+
+// rustdoc-stripper-ignore-next
+/// The parts of the video usability information that describe the stream format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Vui {
+    pub aspect_ratio: Option<gst::Fraction>,
+    pub full_range: bool,
+    pub color_description: Option<ColorDescription>,
+    pub timing_info: Option<TimingInfo>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Parsed H.264 sequence parameter set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstH264SPS")]
+pub struct Sps {
+    pub profile_idc: u8,
+    pub constraint_flags: u8,
+    pub level_idc: u8,
+    pub seq_parameter_set_id: u32,
+    pub chroma_format_idc: u32,
+    pub separate_colour_plane: bool,
+    pub bit_depth_luma: u8,
+    pub bit_depth_chroma: u8,
+    pub pic_width_in_mbs: u32,
+    pub pic_height_in_map_units: u32,
+    pub frame_mbs_only: bool,
+    // rustdoc-stripper-ignore-next
+    /// Left, right, top and bottom frame cropping offsets.
+    pub frame_crop: [u32; 4],
+    pub vui: Option<Vui>,
+}
+
+impl Sps {
+    // rustdoc-stripper-ignore-next
+    /// Parses an escaped SPS NAL unit including its NAL unit header.
+    pub fn parse(nal: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        if nal_unit_type(nal) != Some(NAL_UNIT_TYPE_SPS) {
+            return Err(glib::bool_error!("Not an SPS NAL unit"));
+        }
+
+        let mut r = BitReader::new_nal(&nal[1..]);
+
+        let profile_idc = r.read_bits_u8(8)?;
+        let constraint_flags = r.read_bits_u8(8)?;
+        let level_idc = r.read_bits_u8(8)?;
+        let seq_parameter_set_id = r.read_ue()?;
+        if seq_parameter_set_id > 31 {
+            return Err(glib::bool_error!("Invalid SPS id {}", seq_parameter_set_id));
+        }
+
+        let mut chroma_format_idc = 1;
+        let mut separate_colour_plane = false;
+        let mut bit_depth_luma = 8;
+        let mut bit_depth_chroma = 8;
+        if HIGH_PROFILES.contains(&profile_idc) {
+            chroma_format_idc = r.read_ue()?;
+            if chroma_format_idc > 3 {
+                return Err(glib::bool_error!(
+                    "Invalid chroma format {}",
+                    chroma_format_idc
+                ));
+            }
+            if chroma_format_idc == 3 {
+                separate_colour_plane = r.read_bit()?;
+            }
+            bit_depth_luma = bit_depth(r.read_ue()?)?;
+            bit_depth_chroma = bit_depth(r.read_ue()?)?;
+            let _qpprime_y_zero_transform_bypass = r.read_bit()?;
+            let seq_scaling_matrix_present = r.read_bit()?;
+            if seq_scaling_matrix_present {
+                let n = if chroma_format_idc != 3 { 8 } else { 12 };
+                for i in 0..n {
+                    let seq_scaling_list_present = r.read_bit()?;
+                    if seq_scaling_list_present {
+                        skip_scaling_list(&mut r, if i < 6 { 16 } else { 64 })?;
+                    }
+                }
+            }
+        }
+
+        let _log2_max_frame_num_minus4 = r.read_ue()?;
+        let pic_order_cnt_type = r.read_ue()?;
+        match pic_order_cnt_type {
+            0 => {
+                let _log2_max_pic_order_cnt_lsb_minus4 = r.read_ue()?;
+            }
+            1 => {
+                let _delta_pic_order_always_zero = r.read_bit()?;
+                let _offset_for_non_ref_pic = r.read_se()?;
+                let _offset_for_top_to_bottom_field = r.read_se()?;
+                let num_ref_frames_in_pic_order_cnt_cycle = r.read_ue()?;
+                if num_ref_frames_in_pic_order_cnt_cycle > 255 {
+                    return Err(glib::bool_error!("Invalid number of reference frames"));
+                }
+                for _ in 0..num_ref_frames_in_pic_order_cnt_cycle {
+                    let _offset_for_ref_frame = r.read_se()?;
+                }
+            }
+            2 => (),
+            _ => {
+                return Err(glib::bool_error!(
+                    "Invalid picture order count type {}",
+                    pic_order_cnt_type
+                ));
+            }
+        }
+
+        let _max_num_ref_frames = r.read_ue()?;
+        let _gaps_in_frame_num_value_allowed = r.read_bit()?;
+        let pic_width_in_mbs = r
+            .read_ue()?
+            .checked_add(1)
+            .ok_or_else(|| glib::bool_error!("Invalid picture width"))?;
+        let pic_height_in_map_units = r
+            .read_ue()?
+            .checked_add(1)
+            .ok_or_else(|| glib::bool_error!("Invalid picture height"))?;
+        let frame_mbs_only = r.read_bit()?;
+        if !frame_mbs_only {
+            let _mb_adaptive_frame_field = r.read_bit()?;
+        }
+        let _direct_8x8_inference = r.read_bit()?;
+
+        let mut frame_crop = [0; 4];
+        if r.read_bit()? {
+            for offset in &mut frame_crop {
+                *offset = r.read_ue()?;
+            }
+        }
+
+        let vui = if r.read_bit()? {
+            Some(parse_vui(&mut r)?)
+        } else {
+            None
+        };
+
+        Ok(Sps {
+            profile_idc,
+            constraint_flags,
+            level_idc,
+            seq_parameter_set_id,
+            chroma_format_idc,
+            separate_colour_plane,
+            bit_depth_luma,
+            bit_depth_chroma,
+            pic_width_in_mbs,
+            pic_height_in_map_units,
+            frame_mbs_only,
+            frame_crop,
+            vui,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the first SPS of an `avcC` `codec_data`.
+    pub fn from_codec_data(codec_data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let record = AvcDecoderConfigurationRecord::parse(codec_data)?;
+        let sps = record
+            .sps
+            .first()
+            .ok_or_else(|| glib::bool_error!("No SPS in codec data"))?;
+
+        Self::parse(sps)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the size of the frames after cropping.
+    pub fn dimensions(&self) -> Result<(u32, u32), glib::BoolError> {
+        let chroma_array_type = if self.separate_colour_plane {
+            0
+        } else {
+            self.chroma_format_idc
+        };
+        let (sub_width_c, sub_height_c) = match chroma_array_type {
+            1 => (2, 2),
+            2 => (2, 1),
+            _ => (1, 1),
+        };
+        let frame_height_factor = if self.frame_mbs_only { 1 } else { 2 };
+
+        let [left, right, top, bottom] = self.frame_crop;
+        let crop_x = sub_width_c * (left as u64 + right as u64);
+        let crop_y = sub_height_c * frame_height_factor * (top as u64 + bottom as u64);
+
+        let width = (self.pic_width_in_mbs as u64 * 16)
+            .checked_sub(crop_x)
+            .and_then(|width| u32::try_from(width).ok());
+        let height = (frame_height_factor * self.pic_height_in_map_units as u64 * 16)
+            .checked_sub(crop_y)
+            .and_then(|height| u32::try_from(height).ok());
+
+        match (width, height) {
+            (Some(width), Some(height)) if width > 0 && height > 0 => Ok((width, height)),
+            _ => Err(glib::bool_error!("Invalid frame size")),
+        }
+    }
+
+    pub fn params(&self) -> Result<VideoCodecParams, glib::BoolError> {
+        let (width, height) = self.dimensions()?;
+        let chroma_format = ChromaFormat::from_idc(self.chroma_format_idc).ok_or_else(|| {
+            glib::bool_error!("Invalid chroma_format_idc {}", self.chroma_format_idc)
+        })?;
+        let vui = self.vui.unwrap_or_default();
+
+        Ok(VideoCodecParams {
+            width,
+            height,
+            // Each tick is a field
+            framerate: vui.timing_info.and_then(|timing_info| {
+                super::fraction(
+                    timing_info.time_scale as u64,
+                    2 * timing_info.num_units_in_tick as u64,
+                )
+            }),
+            pixel_aspect_ratio: vui.aspect_ratio,
+            interlaced: !self.frame_mbs_only,
+            chroma_format,
+            bit_depth_luma: self.bit_depth_luma,
+            bit_depth_chroma: self.bit_depth_chroma,
+            color_description: vui.color_description,
+            full_range: vui.full_range,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the stream properties, level and profile of this SPS on the `video/x-h264`
+    /// `caps`.
+    pub fn set_caps(&self, caps: &mut gst::CapsRef) -> Result<(), glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        self.params()?.set_caps(caps);
+        crate::codec_utils_h264_caps_set_level_and_profile(
+            caps,
+            &[self.profile_idc, self.constraint_flags, self.level_idc],
+        )
+    }
+}
+
+fn bit_depth(bit_depth_minus8: u32) -> Result<u8, glib::BoolError> {
+    if bit_depth_minus8 > 6 {
+        return Err(glib::bool_error!(
+            "Invalid bit depth {}",
+            bit_depth_minus8 as u64 + 8
+        ));
+    }
+
+    Ok(bit_depth_minus8 as u8 + 8)
+}
+
+fn skip_scaling_list(r: &mut BitReader, size: usize) -> Result<(), glib::BoolError> {
+    let mut last_scale = 8;
+    let mut next_scale = 8;
+    for _ in 0..size {
+        if next_scale != 0 {
+            let delta_scale = r.read_se()?;
+            if !(-128..=127).contains(&delta_scale) {
+                return Err(glib::bool_error!(
+                    "Invalid scaling list delta {}",
+                    delta_scale
+                ));
+            }
+            next_scale = (last_scale + delta_scale).rem_euclid(256);
+        }
+        if next_scale != 0 {
+            last_scale = next_scale;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_vui(r: &mut BitReader) -> Result<Vui, glib::BoolError> {
+    let aspect_ratio = super::parse_aspect_ratio(r)?;
+
+    let overscan_info_present = r.read_bit()?;
+    if overscan_info_present {
+        let _overscan_appropriate = r.read_bit()?;
+    }
+
+    let (full_range, color_description) = super::parse_video_signal_type(r)?;
+
+    let chroma_loc_info_present = r.read_bit()?;
+    if chroma_loc_info_present {
+        let _chroma_sample_loc_type_top_field = r.read_ue()?;
+        let _chroma_sample_loc_type_bottom_field = r.read_ue()?;
+    }
+
+    let timing_info = if r.read_bit()? {
+        Some(TimingInfo {
+            num_units_in_tick: r.read_bits_u32(32)?,
+            time_scale: r.read_bits_u32(32)?,
+            fixed_frame_rate: r.read_bit()?,
+        })
+    } else {
+        None
+    };
+
+    // The remaining HRD and bitstream restriction parameters are not needed
+
+    Ok(Vui {
+        aspect_ratio,
+        full_range,
+        color_description,
+        timing_info,
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// `AVCDecoderConfigurationRecord` as used for the `codec_data` of `avc` and `avc3`
+/// streams and in the `avcC` box of ISO/IEC 14496-15.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvcDecoderConfigurationRecord {
+    pub profile_idc: u8,
+    pub profile_compatibility: u8,
+    pub level_idc: u8,
+    // rustdoc-stripper-ignore-next
+    /// Size of the length prefix of each NAL unit in the stream, 1, 2 or 4 bytes.
+    pub nal_length_size: u8,
+    pub sps: Vec<Vec<u8>>,
+    pub pps: Vec<Vec<u8>>,
+    pub sps_ext: Vec<Vec<u8>>,
+}
+
+impl AvcDecoderConfigurationRecord {
+    // rustdoc-stripper-ignore-next
+    /// Creates a configuration record with 4 byte NAL unit lengths from escaped SPS and PPS
+    /// NAL units.
+    pub fn from_parameter_sets(sps: &[&[u8]], pps: &[&[u8]]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let first = Sps::parse(
+            sps.first()
+                .ok_or_else(|| glib::bool_error!("No SPS provided"))?,
+        )?;
+
+        Ok(AvcDecoderConfigurationRecord {
+            profile_idc: first.profile_idc,
+            profile_compatibility: first.constraint_flags,
+            level_idc: first.level_idc,
+            nal_length_size: 4,
+            sps: sps.iter().map(|sps| sps.to_vec()).collect(),
+            pps: pps.iter().map(|pps| pps.to_vec()).collect(),
+            sps_ext: Vec::new(),
+        })
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut r = ByteReader::new(data);
+
+        let version = r.read_u8()?;
+        if version != 1 {
+            return Err(glib::bool_error!("Unsupported avcC version {}", version));
+        }
+        let profile_idc = r.read_u8()?;
+        let profile_compatibility = r.read_u8()?;
+        let level_idc = r.read_u8()?;
+        let nal_length_size = (r.read_u8()? & 0x03) + 1;
+        if nal_length_size == 3 {
+            return Err(glib::bool_error!("Invalid NAL unit length size"));
+        }
+
+        let num_sps = r.read_u8()? & 0x1f;
+        let sps = read_nal_units(&mut r, num_sps as usize)?;
+        let num_pps = r.read_u8()?;
+        let pps = read_nal_units(&mut r, num_pps as usize)?;
+
+        // The extension is missing in many files even for high profiles
+        let mut sps_ext = Vec::new();
+        if HIGH_PROFILES.contains(&profile_idc) && r.remaining() >= 4 {
+            r.skip(3)?;
+            let num_sps_ext = r.read_u8()?;
+            sps_ext = read_nal_units(&mut r, num_sps_ext as usize)?;
+        }
+
+        Ok(AvcDecoderConfigurationRecord {
+            profile_idc,
+            profile_compatibility,
+            level_idc,
+            nal_length_size,
+            sps,
+            pps,
+            sps_ext,
+        })
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>, glib::BoolError> {
+        if !matches!(self.nal_length_size, 1 | 2 | 4) {
+            return Err(glib::bool_error!(
+                "Invalid NAL unit length size {}",
+                self.nal_length_size
+            ));
+        }
+        if self.sps.len() > 31 || self.pps.len() > 255 || self.sps_ext.len() > 255 {
+            return Err(glib::bool_error!("Too many parameter sets"));
+        }
+
+        let mut w = ByteWriter::new();
+        w.write_u8(1);
+        w.write_u8(self.profile_idc);
+        w.write_u8(self.profile_compatibility);
+        w.write_u8(self.level_idc);
+        w.write_u8(0xfc | (self.nal_length_size - 1));
+        w.write_u8(0xe0 | self.sps.len() as u8);
+        write_nal_units(&mut w, &self.sps)?;
+        w.write_u8(self.pps.len() as u8);
+        write_nal_units(&mut w, &self.pps)?;
+
+        if HIGH_PROFILES.contains(&self.profile_idc) {
+            let sps = Sps::parse(
+                self.sps
+                    .first()
+                    .ok_or_else(|| glib::bool_error!("No SPS"))?,
+            )?;
+
+            let mut bw = BitWriter::new();
+            bw.write_bits(0x3f, 6);
+            bw.write_bits(sps.chroma_format_idc as u64, 2);
+            bw.write_bits(0x1f, 5);
+            bw.write_bits(sps.bit_depth_luma as u64 - 8, 3);
+            bw.write_bits(0x1f, 5);
+            bw.write_bits(sps.bit_depth_chroma as u64 - 8, 3);
+            w.write_bytes(&bw.into_vec());
+            w.write_u8(self.sps_ext.len() as u8);
+            write_nal_units(&mut w, &self.sps_ext)?;
+        }
+
+        Ok(w.into_vec())
+    }
+
+    pub fn to_buffer(&self) -> Result<gst::Buffer, glib::BoolError> {
+        Ok(gst::Buffer::from_mut_slice(self.to_vec()?))
+    }
+}
+
+fn read_nal_units(r: &mut ByteReader, n: usize) -> Result<Vec<Vec<u8>>, glib::BoolError> {
+    (0..n)
+        .map(|_| {
+            let len = r.read_u16_be()? as usize;
+            Ok(r.read_bytes(len)?.to_vec())
+        })
+        .collect()
+}
+
+fn write_nal_units(w: &mut ByteWriter, nal_units: &[Vec<u8>]) -> Result<(), glib::BoolError> {
+    for nal in nal_units {
+        let len = u16::try_from(nal.len()).map_err(|_| glib::bool_error!("NAL unit too large"))?;
+        w.write_u16_be(len);
+        w.write_bytes(nal);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1920x1080 high profile, level 4.0, 25 fps, BT.709
+    const SPS: &[u8] = &[
+        0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40, 0x78, 0x02, 0x27, 0xe5, 0xc0, 0x5a, 0x80, 0x80,
+        0x80, 0xa0, 0x00, 0x00, 0x03, 0x00, 0x20, 0x00, 0x00, 0x06, 0x50, 0x80,
+    ];
+    const PPS: &[u8] = &[0x68, 0xeb, 0xe3, 0xcb, 0x22, 0xc0];
+
+    #[test]
+    fn test_sps() {
+        let sps = Sps::parse(SPS).unwrap();
+        assert_eq!(sps.profile_idc, 100);
+        assert_eq!(sps.level_idc, 40);
+        assert_eq!(sps.chroma_format_idc, 1);
+        assert_eq!(sps.bit_depth_luma, 8);
+        assert_eq!(sps.pic_width_in_mbs, 120);
+        assert_eq!(sps.pic_height_in_map_units, 68);
+        assert_eq!(sps.frame_crop, [0, 0, 0, 4]);
+        assert_eq!(sps.dimensions().unwrap(), (1920, 1080));
+
+        let params = sps.params().unwrap();
+        assert_eq!(params.framerate, Some(gst::Fraction::new(25, 1)));
+        assert_eq!(params.pixel_aspect_ratio, Some(gst::Fraction::new(1, 1)));
+        assert_eq!(params.chroma_format, ChromaFormat::Yuv420);
+        assert!(!params.interlaced);
+        assert!(!params.full_range);
+        assert_eq!(
+            params.color_description,
+            Some(ColorDescription {
+                colour_primaries: 1,
+                transfer_characteristics: 1,
+                matrix_coefficients: 1,
+            })
+        );
+
+        let mut invalid = sps.clone();
+        invalid.chroma_format_idc = 4;
+        assert!(invalid.params().is_err());
+
+        assert!(Sps::parse(PPS).is_err());
+        assert!(Sps::parse(&SPS[..10]).is_err());
+    }
+
+    #[test]
+    fn test_sps_invalid() {
+        // Baseline profile SPS with the given picture width
+        let sps = |pic_width_in_mbs_minus1| {
+            let mut w = BitWriter::new();
+            w.write_bits(0x67, 8);
+            w.write_bits(66, 8);
+            w.write_bits(0, 8);
+            w.write_bits(30, 8);
+            w.write_ue(0);
+            w.write_ue(0);
+            w.write_ue(2);
+            w.write_ue(1);
+            w.write_bit(false);
+            w.write_ue(pic_width_in_mbs_minus1);
+            w.write_ue(0);
+            w.write_bit(true);
+            w.write_bit(true);
+            w.write_bit(false);
+            w.write_bit(false);
+            w.write_rbsp_trailing_bits();
+            w.into_vec()
+        };
+        assert_eq!(Sps::parse(&sps(0)).unwrap().pic_width_in_mbs, 1);
+        assert!(Sps::parse(&sps(u32::MAX)).is_err());
+
+        // High profile SPS with a single scaling list using the given delta
+        let sps = |delta_scale| {
+            let mut w = BitWriter::new();
+            w.write_bits(0x67, 8);
+            w.write_bits(100, 8);
+            w.write_bits(0, 8);
+            w.write_bits(40, 8);
+            w.write_ue(0);
+            w.write_ue(1);
+            w.write_ue(0);
+            w.write_ue(0);
+            w.write_bit(false);
+            w.write_bit(true);
+            w.write_bit(true);
+            w.write_se(delta_scale);
+            w.write_rbsp_trailing_bits();
+            w.into_vec()
+        };
+        assert!(Sps::parse(&sps(128)).is_err());
+        assert!(Sps::parse(&sps(-129)).is_err());
+        assert!(Sps::parse(&sps(i32::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_avcc() {
+        let record = AvcDecoderConfigurationRecord::from_parameter_sets(&[SPS], &[PPS]).unwrap();
+        let data = record.to_vec().unwrap();
+        assert_eq!(&data[..6], &[0x01, 0x64, 0x00, 0x28, 0xff, 0xe1]);
+        assert_eq!(&data[data.len() - 4..], &[0xfd, 0xf8, 0xf8, 0x00]);
+
+        let parsed = AvcDecoderConfigurationRecord::parse(&data).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(
+            Sps::from_codec_data(&data).unwrap(),
+            Sps::parse(SPS).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_caps() {
+        gst::init().unwrap();
+
+        let mut caps = gst::Caps::builder("video/x-h264")
+            .field("stream-format", "avc")
+            .build();
+        Sps::parse(SPS)
+            .unwrap()
+            .set_caps(caps.get_mut().unwrap())
+            .unwrap();
+
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<i32>("width"), Ok(1920));
+        assert_eq!(s.get::<i32>("height"), Ok(1080));
+        assert_eq!(s.get::<&str>("chroma-format"), Ok("4:2:0"));
+        assert_eq!(s.get::<&str>("profile"), Ok("high"));
+        assert_eq!(s.get::<&str>("level"), Ok("4"));
+    }
+}
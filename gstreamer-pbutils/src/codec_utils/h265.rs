@@ -0,0 +1,741 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! H.265 sequence parameter set and `hvcC` parsing.
+
+use gst_video::gst_base::{BitReader, ByteReader, ByteWriter};
+
+use super::{ChromaFormat, ColorDescription, VideoCodecParams};
+
+pub const NAL_UNIT_TYPE_VPS: u8 = 32;
+pub const NAL_UNIT_TYPE_SPS: u8 = 33;
+pub const NAL_UNIT_TYPE_PPS: u8 = 34;
+pub const NAL_UNIT_TYPE_PREFIX_SEI: u8 = 39;
+pub const NAL_UNIT_TYPE_SUFFIX_SEI: u8 = 40;
+
+// rustdoc-stripper-ignore-next
+/// Returns the `nal_unit_type` of an H.265 NAL unit.
+pub fn nal_unit_type(nal: &[u8]) -> Option<u8> {
+    skip_assert_initialized!();
+    nal.first().map(|b| (b >> 1) & 0x3f)
+}
+
+// rustdoc-stripper-ignore-next
+/// Timing information of the VUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingInfo {
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+}
+
+// rustdoc-stripper-ignore-next
+/// The parts of the video usability information that describe the stream format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Vui {
+    pub aspect_ratio: Option<gst::Fraction>,
+    pub full_range: bool,
+    pub color_description: Option<ColorDescription>,
+    pub field_seq: bool,
+    pub timing_info: Option<TimingInfo>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Parsed H.265 sequence parameter set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[doc(alias = "GstH265SPS")]
+pub struct Sps {
+    pub max_sub_layers: u8,
+    pub temporal_id_nesting: bool,
+    // rustdoc-stripper-ignore-next
+    /// The 12 bytes of the general part of `profile_tier_level()`.
+    pub profile_tier_level: [u8; 12],
+    pub seq_parameter_set_id: u32,
+    pub chroma_format_idc: u32,
+    pub separate_colour_plane: bool,
+    pub pic_width_in_luma_samples: u32,
+    pub pic_height_in_luma_samples: u32,
+    // rustdoc-stripper-ignore-next
+    /// Left, right, top and bottom conformance window offsets.
+    pub conformance_window: [u32; 4],
+    pub bit_depth_luma: u8,
+    pub bit_depth_chroma: u8,
+    pub vui: Option<Vui>,
+}
+
+impl Sps {
+    // rustdoc-stripper-ignore-next
+    /// Parses an escaped SPS NAL unit including its NAL unit header.
+    pub fn parse(nal: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        if nal.len() < 2 || nal_unit_type(nal) != Some(NAL_UNIT_TYPE_SPS) {
+            return Err(glib::bool_error!("Not an SPS NAL unit"));
+        }
+
+        let mut r = BitReader::new_nal(&nal[2..]);
+
+        let _sps_video_parameter_set_id = r.read_bits_u8(4)?;
+        let max_sub_layers = r.read_bits_u8(3)? + 1;
+        if max_sub_layers > 7 {
+            return Err(glib::bool_error!("Invalid number of sub-layers"));
+        }
+        let temporal_id_nesting = r.read_bit()?;
+        let profile_tier_level = parse_profile_tier_level(&mut r, max_sub_layers)?;
+
+        let seq_parameter_set_id = r.read_ue()?;
+        if seq_parameter_set_id > 15 {
+            return Err(glib::bool_error!("Invalid SPS id {}", seq_parameter_set_id));
+        }
+        let chroma_format_idc = r.read_ue()?;
+        if chroma_format_idc > 3 {
+            return Err(glib::bool_error!(
+                "Invalid chroma format {}",
+                chroma_format_idc
+            ));
+        }
+        let separate_colour_plane = chroma_format_idc == 3 && r.read_bit()?;
+        let pic_width_in_luma_samples = r.read_ue()?;
+        let pic_height_in_luma_samples = r.read_ue()?;
+
+        let mut conformance_window = [0; 4];
+        if r.read_bit()? {
+            for offset in &mut conformance_window {
+                *offset = r.read_ue()?;
+            }
+        }
+
+        let bit_depth_luma = bit_depth(r.read_ue()?)?;
+        let bit_depth_chroma = bit_depth(r.read_ue()?)?;
+        let log2_max_pic_order_cnt_lsb_minus4 = r.read_ue()?;
+        if log2_max_pic_order_cnt_lsb_minus4 > 12 {
+            return Err(glib::bool_error!("Invalid picture order count size"));
+        }
+        let log2_max_pic_order_cnt_lsb = log2_max_pic_order_cnt_lsb_minus4 + 4;
+
+        let sub_layer_ordering_info_present = r.read_bit()?;
+        let first_sub_layer = if sub_layer_ordering_info_present {
+            0
+        } else {
+            max_sub_layers - 1
+        };
+        for _ in first_sub_layer..max_sub_layers {
+            let _max_dec_pic_buffering_minus1 = r.read_ue()?;
+            let _max_num_reorder_pics = r.read_ue()?;
+            let _max_latency_increase_plus1 = r.read_ue()?;
+        }
+
+        let _log2_min_luma_coding_block_size_minus3 = r.read_ue()?;
+        let _log2_diff_max_min_luma_coding_block_size = r.read_ue()?;
+        let _log2_min_luma_transform_block_size_minus2 = r.read_ue()?;
+        let _log2_diff_max_min_luma_transform_block_size = r.read_ue()?;
+        let _max_transform_hierarchy_depth_inter = r.read_ue()?;
+        let _max_transform_hierarchy_depth_intra = r.read_ue()?;
+
+        let scaling_list_enabled = r.read_bit()?;
+        if scaling_list_enabled {
+            let sps_scaling_list_data_present = r.read_bit()?;
+            if sps_scaling_list_data_present {
+                skip_scaling_list_data(&mut r)?;
+            }
+        }
+
+        let _amp_enabled = r.read_bit()?;
+        let _sample_adaptive_offset_enabled = r.read_bit()?;
+
+        let pcm_enabled = r.read_bit()?;
+        if pcm_enabled {
+            let _pcm_sample_bit_depth_luma_minus1 = r.read_bits_u8(4)?;
+            let _pcm_sample_bit_depth_chroma_minus1 = r.read_bits_u8(4)?;
+            let _log2_min_pcm_luma_coding_block_size_minus3 = r.read_ue()?;
+            let _log2_diff_max_min_pcm_luma_coding_block_size = r.read_ue()?;
+            let _pcm_loop_filter_disabled = r.read_bit()?;
+        }
+
+        let num_short_term_ref_pic_sets = r.read_ue()?;
+        if num_short_term_ref_pic_sets > 64 {
+            return Err(glib::bool_error!(
+                "Invalid number of short-term reference picture sets"
+            ));
+        }
+        let mut num_delta_pocs = Vec::with_capacity(num_short_term_ref_pic_sets as usize);
+        for idx in 0..num_short_term_ref_pic_sets as usize {
+            let n = parse_st_ref_pic_set(&mut r, idx, &num_delta_pocs)?;
+            num_delta_pocs.push(n);
+        }
+
+        let long_term_ref_pics_present = r.read_bit()?;
+        if long_term_ref_pics_present {
+            let num_long_term_ref_pics_sps = r.read_ue()?;
+            if num_long_term_ref_pics_sps > 32 {
+                return Err(glib::bool_error!(
+                    "Invalid number of long-term reference pictures"
+                ));
+            }
+            for _ in 0..num_long_term_ref_pics_sps {
+                let _lt_ref_pic_poc_lsb_sps = r.read_bits_u32(log2_max_pic_order_cnt_lsb)?;
+                let _used_by_curr_pic_lt_sps = r.read_bit()?;
+            }
+        }
+
+        let _sps_temporal_mvp_enabled = r.read_bit()?;
+        let _strong_intra_smoothing_enabled = r.read_bit()?;
+
+        let vui = if r.read_bit()? {
+            Some(parse_vui(&mut r)?)
+        } else {
+            None
+        };
+
+        Ok(Sps {
+            max_sub_layers,
+            temporal_id_nesting,
+            profile_tier_level,
+            seq_parameter_set_id,
+            chroma_format_idc,
+            separate_colour_plane,
+            pic_width_in_luma_samples,
+            pic_height_in_luma_samples,
+            conformance_window,
+            bit_depth_luma,
+            bit_depth_chroma,
+            vui,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the first SPS of an `hvcC` `codec_data`.
+    pub fn from_codec_data(codec_data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let record = HevcDecoderConfigurationRecord::parse(codec_data)?;
+        let sps = record
+            .nal_units(NAL_UNIT_TYPE_SPS)
+            .next()
+            .ok_or_else(|| glib::bool_error!("No SPS in codec data"))?;
+
+        Self::parse(sps)
+    }
+
+    pub fn general_profile_idc(&self) -> u8 {
+        self.profile_tier_level[0] & 0x1f
+    }
+
+    pub fn general_tier(&self) -> bool {
+        self.profile_tier_level[0] & 0x20 != 0
+    }
+
+    pub fn general_level_idc(&self) -> u8 {
+        self.profile_tier_level[11]
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the size of the frames after applying the conformance window.
+    pub fn dimensions(&self) -> Result<(u32, u32), glib::BoolError> {
+        let chroma_array_type = if self.separate_colour_plane {
+            0
+        } else {
+            self.chroma_format_idc
+        };
+        let (sub_width_c, sub_height_c) = match chroma_array_type {
+            1 => (2, 2),
+            2 => (2, 1),
+            _ => (1, 1),
+        };
+
+        let [left, right, top, bottom] = self.conformance_window;
+        let width = (self.pic_width_in_luma_samples as u64)
+            .checked_sub(sub_width_c * (left as u64 + right as u64))
+            .and_then(|width| u32::try_from(width).ok());
+        let height = (self.pic_height_in_luma_samples as u64)
+            .checked_sub(sub_height_c * (top as u64 + bottom as u64))
+            .and_then(|height| u32::try_from(height).ok());
+
+        match (width, height) {
+            (Some(width), Some(height)) if width > 0 && height > 0 => Ok((width, height)),
+            _ => Err(glib::bool_error!("Invalid frame size")),
+        }
+    }
+
+    pub fn params(&self) -> Result<VideoCodecParams, glib::BoolError> {
+        let (width, height) = self.dimensions()?;
+        let chroma_format = ChromaFormat::from_idc(self.chroma_format_idc).ok_or_else(|| {
+            glib::bool_error!("Invalid chroma_format_idc {}", self.chroma_format_idc)
+        })?;
+        let vui = self.vui.unwrap_or_default();
+
+        Ok(VideoCodecParams {
+            width,
+            height,
+            framerate: vui.timing_info.and_then(|timing_info| {
+                super::fraction(
+                    timing_info.time_scale as u64,
+                    timing_info.num_units_in_tick as u64,
+                )
+            }),
+            pixel_aspect_ratio: vui.aspect_ratio,
+            interlaced: vui.field_seq,
+            chroma_format,
+            bit_depth_luma: self.bit_depth_luma,
+            bit_depth_chroma: self.bit_depth_chroma,
+            color_description: vui.color_description,
+            full_range: vui.full_range,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the stream properties, level, tier and profile of this SPS on the
+    /// `video/x-h265` `caps`.
+    pub fn set_caps(&self, caps: &mut gst::CapsRef) -> Result<(), glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        self.params()?.set_caps(caps);
+        crate::codec_utils_h265_caps_set_level_tier_and_profile(caps, &self.profile_tier_level)
+    }
+}
+
+fn bit_depth(bit_depth_minus8: u32) -> Result<u8, glib::BoolError> {
+    if bit_depth_minus8 > 8 {
+        return Err(glib::bool_error!(
+            "Invalid bit depth {}",
+            bit_depth_minus8 as u64 + 8
+        ));
+    }
+
+    Ok(bit_depth_minus8 as u8 + 8)
+}
+
+fn parse_profile_tier_level(
+    r: &mut BitReader,
+    max_sub_layers: u8,
+) -> Result<[u8; 12], glib::BoolError> {
+    let mut general = [0; 12];
+    for b in &mut general {
+        *b = r.read_bits_u8(8)?;
+    }
+
+    let mut sub_layer_profile_present = [false; 7];
+    let mut sub_layer_level_present = [false; 7];
+    for i in 0..(max_sub_layers - 1) as usize {
+        sub_layer_profile_present[i] = r.read_bit()?;
+        sub_layer_level_present[i] = r.read_bit()?;
+    }
+    if max_sub_layers > 1 {
+        for _ in (max_sub_layers - 1)..8 {
+            let _reserved_zero_2bits = r.read_bits_u8(2)?;
+        }
+    }
+    for i in 0..(max_sub_layers - 1) as usize {
+        if sub_layer_profile_present[i] {
+            r.skip(88)?;
+        }
+        if sub_layer_level_present[i] {
+            r.skip(8)?;
+        }
+    }
+
+    Ok(general)
+}
+
+fn skip_scaling_list_data(r: &mut BitReader) -> Result<(), glib::BoolError> {
+    for size_id in 0..4 {
+        let step = if size_id == 3 { 3 } else { 1 };
+        for _matrix_id in (0..6).step_by(step) {
+            let scaling_list_pred_mode = r.read_bit()?;
+            if !scaling_list_pred_mode {
+                let _scaling_list_pred_matrix_id_delta = r.read_ue()?;
+            } else {
+                let coef_num = std::cmp::min(64, 1 << (4 + (size_id << 1)));
+                if size_id > 1 {
+                    let scaling_list_dc_coef_minus8 = r.read_se()?;
+                    if !(-7..=247).contains(&scaling_list_dc_coef_minus8) {
+                        return Err(glib::bool_error!("Invalid scaling list DC coefficient"));
+                    }
+                }
+                for _ in 0..coef_num {
+                    let scaling_list_delta_coef = r.read_se()?;
+                    if !(-128..=127).contains(&scaling_list_delta_coef) {
+                        return Err(glib::bool_error!(
+                            "Invalid scaling list delta {}",
+                            scaling_list_delta_coef
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the number of delta POCs of the parsed reference picture set
+fn parse_st_ref_pic_set(
+    r: &mut BitReader,
+    idx: usize,
+    num_delta_pocs: &[u32],
+) -> Result<u32, glib::BoolError> {
+    let inter_ref_pic_set_prediction = idx != 0 && r.read_bit()?;
+
+    if inter_ref_pic_set_prediction {
+        // `delta_idx_minus1` is only present in slice headers, so the reference is
+        // always the previous set
+        let _delta_rps_sign = r.read_bit()?;
+        let _abs_delta_rps_minus1 = r.read_ue()?;
+
+        let mut n = 0;
+        for _ in 0..=num_delta_pocs[idx - 1] {
+            let used_by_curr_pic = r.read_bit()?;
+            let use_delta = used_by_curr_pic || r.read_bit()?;
+            if use_delta {
+                n += 1;
+            }
+        }
+
+        Ok(n)
+    } else {
+        let num_negative_pics = r.read_ue()?;
+        let num_positive_pics = r.read_ue()?;
+        if num_negative_pics > 16 || num_positive_pics > 16 {
+            return Err(glib::bool_error!("Invalid reference picture set"));
+        }
+        for _ in 0..(num_negative_pics + num_positive_pics) {
+            let _delta_poc_minus1 = r.read_ue()?;
+            let _used_by_curr_pic = r.read_bit()?;
+        }
+
+        Ok(num_negative_pics + num_positive_pics)
+    }
+}
+
+fn parse_vui(r: &mut BitReader) -> Result<Vui, glib::BoolError> {
+    let aspect_ratio = super::parse_aspect_ratio(r)?;
+
+    let overscan_info_present = r.read_bit()?;
+    if overscan_info_present {
+        let _overscan_appropriate = r.read_bit()?;
+    }
+
+    let (full_range, color_description) = super::parse_video_signal_type(r)?;
+
+    let chroma_loc_info_present = r.read_bit()?;
+    if chroma_loc_info_present {
+        let _chroma_sample_loc_type_top_field = r.read_ue()?;
+        let _chroma_sample_loc_type_bottom_field = r.read_ue()?;
+    }
+
+    let _neutral_chroma_indication = r.read_bit()?;
+    let field_seq = r.read_bit()?;
+    let _frame_field_info_present = r.read_bit()?;
+
+    let default_display_window = r.read_bit()?;
+    if default_display_window {
+        for _ in 0..4 {
+            let _def_disp_win_offset = r.read_ue()?;
+        }
+    }
+
+    let timing_info = if r.read_bit()? {
+        Some(TimingInfo {
+            num_units_in_tick: r.read_bits_u32(32)?,
+            time_scale: r.read_bits_u32(32)?,
+        })
+    } else {
+        None
+    };
+
+    // The remaining HRD and bitstream restriction parameters are not needed
+
+    Ok(Vui {
+        aspect_ratio,
+        full_range,
+        color_description,
+        field_seq,
+        timing_info,
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// Array of NAL units of the same type in an [`HevcDecoderConfigurationRecord`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HevcNalArray {
+    // rustdoc-stripper-ignore-next
+    /// Whether all NAL units of this type are in the array and none are in the stream.
+    pub array_completeness: bool,
+    pub nal_unit_type: u8,
+    pub nal_units: Vec<Vec<u8>>,
+}
+
+// rustdoc-stripper-ignore-next
+/// `HEVCDecoderConfigurationRecord` as used for the `codec_data` of `hvc1` and `hev1`
+/// streams and in the `hvcC` box of ISO/IEC 14496-15.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HevcDecoderConfigurationRecord {
+    // rustdoc-stripper-ignore-next
+    /// The 12 bytes of the general part of `profile_tier_level()`.
+    pub profile_tier_level: [u8; 12],
+    pub min_spatial_segmentation_idc: u16,
+    pub parallelism_type: u8,
+    pub chroma_format_idc: u8,
+    pub bit_depth_luma: u8,
+    pub bit_depth_chroma: u8,
+    pub avg_frame_rate: u16,
+    pub constant_frame_rate: u8,
+    pub num_temporal_layers: u8,
+    pub temporal_id_nested: bool,
+    // rustdoc-stripper-ignore-next
+    /// Size of the length prefix of each NAL unit in the stream, 1, 2 or 4 bytes.
+    pub nal_length_size: u8,
+    pub arrays: Vec<HevcNalArray>,
+}
+
+impl HevcDecoderConfigurationRecord {
+    // rustdoc-stripper-ignore-next
+    /// Creates a configuration record with 4 byte NAL unit lengths from escaped VPS, SPS
+    /// and PPS NAL units.
+    pub fn from_parameter_sets(
+        vps: &[&[u8]],
+        sps: &[&[u8]],
+        pps: &[&[u8]],
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let first = Sps::parse(
+            sps.first()
+                .ok_or_else(|| glib::bool_error!("No SPS provided"))?,
+        )?;
+
+        let arrays = [
+            (NAL_UNIT_TYPE_VPS, vps),
+            (NAL_UNIT_TYPE_SPS, sps),
+            (NAL_UNIT_TYPE_PPS, pps),
+        ]
+        .into_iter()
+        .filter(|(_, nal_units)| !nal_units.is_empty())
+        .map(|(nal_unit_type, nal_units)| HevcNalArray {
+            array_completeness: true,
+            nal_unit_type,
+            nal_units: nal_units.iter().map(|nal| nal.to_vec()).collect(),
+        })
+        .collect();
+
+        Ok(HevcDecoderConfigurationRecord {
+            profile_tier_level: first.profile_tier_level,
+            min_spatial_segmentation_idc: 0,
+            parallelism_type: 0,
+            chroma_format_idc: first.chroma_format_idc as u8,
+            bit_depth_luma: first.bit_depth_luma,
+            bit_depth_chroma: first.bit_depth_chroma,
+            avg_frame_rate: 0,
+            constant_frame_rate: 0,
+            num_temporal_layers: first.max_sub_layers,
+            temporal_id_nested: first.temporal_id_nesting,
+            nal_length_size: 4,
+            arrays,
+        })
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut r = ByteReader::new(data);
+
+        let version = r.read_u8()?;
+        if version != 1 {
+            return Err(glib::bool_error!("Unsupported hvcC version {}", version));
+        }
+        let profile_tier_level = r.read_array::<12>()?;
+        let min_spatial_segmentation_idc = r.read_u16_be()? & 0x0fff;
+        let parallelism_type = r.read_u8()? & 0x03;
+        let chroma_format_idc = r.read_u8()? & 0x03;
+        let bit_depth_luma = (r.read_u8()? & 0x07) + 8;
+        let bit_depth_chroma = (r.read_u8()? & 0x07) + 8;
+        let avg_frame_rate = r.read_u16_be()?;
+        let flags = r.read_u8()?;
+        let nal_length_size = (flags & 0x03) + 1;
+        if nal_length_size == 3 {
+            return Err(glib::bool_error!("Invalid NAL unit length size"));
+        }
+
+        let num_arrays = r.read_u8()?;
+        let mut arrays = Vec::with_capacity(num_arrays as usize);
+        for _ in 0..num_arrays {
+            let b = r.read_u8()?;
+            let num_nalus = r.read_u16_be()?;
+            let nal_units = (0..num_nalus)
+                .map(|_| {
+                    let len = r.read_u16_be()? as usize;
+                    Ok(r.read_bytes(len)?.to_vec())
+                })
+                .collect::<Result<Vec<_>, glib::BoolError>>()?;
+
+            arrays.push(HevcNalArray {
+                array_completeness: b & 0x80 != 0,
+                nal_unit_type: b & 0x3f,
+                nal_units,
+            });
+        }
+
+        Ok(HevcDecoderConfigurationRecord {
+            profile_tier_level,
+            min_spatial_segmentation_idc,
+            parallelism_type,
+            chroma_format_idc,
+            bit_depth_luma,
+            bit_depth_chroma,
+            avg_frame_rate,
+            constant_frame_rate: flags >> 6,
+            num_temporal_layers: (flags >> 3) & 0x07,
+            temporal_id_nested: flags & 0x04 != 0,
+            nal_length_size,
+            arrays,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all NAL units of the given type.
+    pub fn nal_units(&self, nal_unit_type: u8) -> impl Iterator<Item = &[u8]> {
+        self.arrays
+            .iter()
+            .filter(move |array| array.nal_unit_type == nal_unit_type)
+            .flat_map(|array| array.nal_units.iter().map(Vec::as_slice))
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>, glib::BoolError> {
+        if !matches!(self.nal_length_size, 1 | 2 | 4) {
+            return Err(glib::bool_error!(
+                "Invalid NAL unit length size {}",
+                self.nal_length_size
+            ));
+        }
+        let num_arrays =
+            u8::try_from(self.arrays.len()).map_err(|_| glib::bool_error!("Too many arrays"))?;
+
+        let mut w = ByteWriter::new();
+        w.write_u8(1);
+        w.write_bytes(&self.profile_tier_level);
+        w.write_u16_be(0xf000 | (self.min_spatial_segmentation_idc & 0x0fff));
+        w.write_u8(0xfc | (self.parallelism_type & 0x03));
+        w.write_u8(0xfc | (self.chroma_format_idc & 0x03));
+        w.write_u8(0xf8 | (self.bit_depth_luma.wrapping_sub(8) & 0x07));
+        w.write_u8(0xf8 | (self.bit_depth_chroma.wrapping_sub(8) & 0x07));
+        w.write_u16_be(self.avg_frame_rate);
+        w.write_u8(
+            (self.constant_frame_rate & 0x03) << 6
+                | (self.num_temporal_layers & 0x07) << 3
+                | (self.temporal_id_nested as u8) << 2
+                | (self.nal_length_size - 1),
+        );
+
+        w.write_u8(num_arrays);
+        for array in &self.arrays {
+            w.write_u8((array.array_completeness as u8) << 7 | (array.nal_unit_type & 0x3f));
+            let num_nalus = u16::try_from(array.nal_units.len())
+                .map_err(|_| glib::bool_error!("Too many NAL units"))?;
+            w.write_u16_be(num_nalus);
+            for nal in &array.nal_units {
+                let len = u16::try_from(nal.len())
+                    .map_err(|_| glib::bool_error!("NAL unit too large"))?;
+                w.write_u16_be(len);
+                w.write_bytes(nal);
+            }
+        }
+
+        Ok(w.into_vec())
+    }
+
+    pub fn to_buffer(&self) -> Result<gst::Buffer, glib::BoolError> {
+        Ok(gst::Buffer::from_mut_slice(self.to_vec()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3840x2160 Main 10, level 5.1, 50 fps, BT.2020 PQ
+    const SPS: &[u8] = &[
+        0x42, 0x01, 0x01, 0x02, 0x20, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00, 0x00,
+        0x03, 0x00, 0x99, 0xa0, 0x01, 0xe0, 0x20, 0x02, 0x1c, 0x4d, 0x96, 0x57, 0x92, 0x4f, 0xaf,
+        0xff, 0xea, 0xaa, 0xaa, 0xeb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfa, 0xaa, 0xb9,
+        0xaf, 0x6d, 0x02, 0xfc, 0x05, 0xa8, 0x48, 0x80, 0x48, 0x20, 0x00, 0x00, 0x03, 0x00, 0x20,
+        0x00, 0x00, 0x06, 0x41,
+    ];
+    const VPS: &[u8] = &[
+        0x40, 0x01, 0x0c, 0x01, 0xff, 0xff, 0x02, 0x20, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00,
+        0x03, 0x00, 0x00, 0x03, 0x00, 0x99, 0x95, 0x98, 0x09,
+    ];
+    const PPS: &[u8] = &[0x44, 0x01, 0xc1, 0x72, 0xb4, 0x62, 0x40];
+
+    #[test]
+    fn test_sps() {
+        let sps = Sps::parse(SPS).unwrap();
+        assert_eq!(sps.general_profile_idc(), 2);
+        assert!(!sps.general_tier());
+        assert_eq!(sps.general_level_idc(), 153);
+        assert_eq!(sps.max_sub_layers, 1);
+        assert_eq!(sps.bit_depth_luma, 10);
+        assert_eq!(sps.dimensions().unwrap(), (3840, 2160));
+
+        let params = sps.params().unwrap();
+        assert_eq!(params.framerate, Some(gst::Fraction::new(50, 1)));
+        assert_eq!(params.chroma_format, ChromaFormat::Yuv420);
+        assert_eq!(
+            params.color_description,
+            Some(ColorDescription {
+                colour_primaries: 9,
+                transfer_characteristics: 16,
+                matrix_coefficients: 9,
+            })
+        );
+
+        let mut invalid = sps.clone();
+        invalid.chroma_format_idc = 4;
+        assert!(invalid.params().is_err());
+
+        assert!(Sps::parse(VPS).is_err());
+        assert!(Sps::parse(&SPS[..12]).is_err());
+    }
+
+    #[test]
+    fn test_hvcc() {
+        let record =
+            HevcDecoderConfigurationRecord::from_parameter_sets(&[VPS], &[SPS], &[PPS]).unwrap();
+        assert_eq!(record.num_temporal_layers, 1);
+        assert_eq!(record.bit_depth_luma, 10);
+
+        let data = record.to_vec().unwrap();
+        assert_eq!(data[0], 1);
+        assert_eq!(&data[1..13], &Sps::parse(SPS).unwrap().profile_tier_level);
+        assert_eq!(data[22], 3);
+
+        let parsed = HevcDecoderConfigurationRecord::parse(&data).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(
+            parsed.nal_units(NAL_UNIT_TYPE_PPS).collect::<Vec<_>>(),
+            [PPS]
+        );
+        assert_eq!(
+            Sps::from_codec_data(&data).unwrap(),
+            Sps::parse(SPS).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_caps() {
+        gst::init().unwrap();
+
+        let mut caps = gst::Caps::builder("video/x-h265")
+            .field("stream-format", "hvc1")
+            .build();
+        Sps::parse(SPS)
+            .unwrap()
+            .set_caps(caps.get_mut().unwrap())
+            .unwrap();
+
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<i32>("width"), Ok(3840));
+        assert_eq!(s.get::<u32>("bit-depth-luma"), Ok(10));
+        assert_eq!(s.get::<&str>("profile"), Ok("main-10"));
+        assert_eq!(s.get::<&str>("tier"), Ok("main"));
+        assert_eq!(s.get::<&str>("level"), Ok("5.1"));
+    }
+}
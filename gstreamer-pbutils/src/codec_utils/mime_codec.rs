@@ -0,0 +1,687 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt::Write;
+
+use gst_video::gst_base::BitReader;
+
+use super::{av1, h264, h265};
+
+// (profile, profile_idc, constraint flags)
+const H264_PROFILES: &[(&str, u8, u8)] = &[
+    ("baseline", 66, 0x00),
+    ("constrained-baseline", 66, 0x40),
+    ("main", 77, 0x00),
+    ("extended", 88, 0x00),
+    ("high", 100, 0x00),
+    ("progressive-high", 100, 0x08),
+    ("constrained-high", 100, 0x0c),
+    ("high-10", 110, 0x00),
+    ("high-10-intra", 110, 0x10),
+    ("high-4:2:2", 122, 0x00),
+    ("high-4:2:2-intra", 122, 0x10),
+    ("high-4:4:4", 244, 0x00),
+    ("high-4:4:4-intra", 244, 0x10),
+    ("cavlc-4:4:4-intra", 44, 0x00),
+    ("multiview-high", 118, 0x00),
+    ("stereo-high", 128, 0x00),
+];
+
+// (audio object type, profile)
+const AAC_PROFILES: &[(u8, &str)] = &[
+    (1, "main"),
+    (2, "lc"),
+    (3, "ssr"),
+    (4, "ltp"),
+    (5, "he-aac"),
+    (29, "he-aac-v2"),
+];
+
+// Codecs without any parameters in their codec string
+const SIMPLE_CODECS: &[(&str, &str)] = &[
+    ("Opus", "audio/x-opus"),
+    ("fLaC", "audio/x-flac"),
+    ("ac-3", "audio/x-ac3"),
+    ("ec-3", "audio/x-eac3"),
+    ("vp8", "video/x-vp8"),
+];
+
+// rustdoc-stripper-ignore-next
+/// Converts `caps` to an RFC 6381 codec string as used in the `codecs` parameter of MIME
+/// types, e.g. `avc1.64001f` or `mp4a.40.2`.
+///
+/// The profile and level are taken from the `codec_data` if there is any and from the
+/// corresponding caps fields otherwise. If neither is available only the codec's four
+/// character code is returned.
+///
+/// Unlike `codec_utils_caps_get_mime_codec()` this doesn't depend on the GStreamer version
+/// and also handles VP9 profiles.
+pub fn caps_get_mime_codec(caps: &gst::CapsRef) -> Result<String, glib::BoolError> {
+    skip_assert_initialized!();
+
+    let s = caps
+        .structure(0)
+        .ok_or_else(|| glib::bool_error!("Empty caps"))?;
+
+    let codec_data = s.get::<gst::Buffer>("codec_data").ok();
+    let codec_data = codec_data
+        .as_ref()
+        .map(|codec_data| codec_data.map_readable())
+        .transpose()?;
+    let codec_data = codec_data.as_deref();
+
+    match s.name().as_str() {
+        "video/x-h264" => h264_mime_codec(s, codec_data),
+        "video/x-h265" => h265_mime_codec(s, codec_data),
+        "video/x-av1" => av1_mime_codec(s, codec_data),
+        "video/x-vp9" => vp9_mime_codec(s),
+        "audio/mpeg" => mpeg_audio_mime_codec(s, codec_data),
+        name => SIMPLE_CODECS
+            .iter()
+            .find(|(_, media_type)| *media_type == name)
+            .map(|(codec, _)| String::from(*codec))
+            .ok_or_else(|| glib::bool_error!("Unsupported caps {}", name)),
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Creates caps from an RFC 6381 codec string as returned by [`caps_get_mime_codec()`].
+pub fn caps_from_mime_codec(codec: &str) -> Result<gst::Caps, glib::BoolError> {
+    assert_initialized_main_thread!();
+
+    let parts = codec.trim().split('.').collect::<Vec<_>>();
+    let (fourcc, params) = parts.split_first().unwrap();
+
+    let caps = match *fourcc {
+        "avc1" | "avc3" => h264_caps(fourcc, params),
+        "hvc1" | "hev1" => h265_caps(fourcc, params),
+        "av01" => av1_caps(params),
+        "vp09" => vp9_caps(params),
+        "vp9" if params.is_empty() => Some(gst::Caps::new_empty_simple("video/x-vp9")),
+        "vp08" => Some(gst::Caps::new_empty_simple("video/x-vp8")),
+        "mp3" if params.is_empty() => Some(
+            gst::Caps::builder("audio/mpeg")
+                .field("mpegversion", 1i32)
+                .field("layer", 3i32)
+                .build(),
+        ),
+        fourcc if fourcc.eq_ignore_ascii_case("mp4a") => mpeg_audio_caps(params),
+        fourcc if params.is_empty() => SIMPLE_CODECS
+            .iter()
+            .find(|(codec, _)| codec.eq_ignore_ascii_case(fourcc))
+            .map(|(_, media_type)| gst::Caps::new_empty_simple(media_type)),
+        _ => None,
+    };
+
+    caps.ok_or_else(|| glib::bool_error!("Unsupported codec string '{}'", codec))
+}
+
+fn field_str<'a>(s: &'a gst::StructureRef, field: &str) -> Option<&'a str> {
+    s.get(field).ok()
+}
+
+fn parse_hex(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 8 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u32::from_str_radix(s, 16).ok()
+}
+
+fn parse_dec<T: std::str::FromStr>(s: &str) -> Option<T> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+// Parses a `major[.minor]` level string
+fn parse_level(level: &str) -> Option<(u8, u8)> {
+    let (major, minor) = level.split_once('.').unwrap_or((level, "0"));
+    Some((parse_dec(major)?, parse_dec(minor)?))
+}
+
+fn level_to_string(major: u8, minor: u8) -> String {
+    if minor == 0 {
+        major.to_string()
+    } else {
+        format!("{major}.{minor}")
+    }
+}
+
+fn h264_mime_codec(
+    s: &gst::StructureRef,
+    codec_data: Option<&[u8]>,
+) -> Result<String, glib::BoolError> {
+    let fourcc = if field_str(s, "stream-format") == Some("avc3") {
+        "avc3"
+    } else {
+        "avc1"
+    };
+
+    let (profile_idc, constraint_flags, level_idc) = if let Some(codec_data) = codec_data {
+        let record = h264::AvcDecoderConfigurationRecord::parse(codec_data)?;
+        (
+            record.profile_idc,
+            record.profile_compatibility,
+            record.level_idc,
+        )
+    } else {
+        let (Some(profile), Some(level)) = (field_str(s, "profile"), field_str(s, "level")) else {
+            return Ok(String::from(fourcc));
+        };
+
+        let &(_, profile_idc, mut constraint_flags) = H264_PROFILES
+            .iter()
+            .find(|(name, _, _)| *name == profile)
+            .ok_or_else(|| glib::bool_error!("Unsupported H.264 profile {}", profile))?;
+
+        let level_idc = if level == "1b" {
+            // Level 1b is signalled with constraint_set3_flag in the non-high profiles
+            if matches!(profile_idc, 66 | 77 | 88) {
+                constraint_flags |= 0x10;
+                11
+            } else {
+                9
+            }
+        } else {
+            parse_level(level)
+                .and_then(|(major, minor)| major.checked_mul(10)?.checked_add(minor))
+                .ok_or_else(|| glib::bool_error!("Invalid H.264 level {}", level))?
+        };
+
+        (profile_idc, constraint_flags, level_idc)
+    };
+
+    Ok(format!(
+        "{fourcc}.{profile_idc:02x}{constraint_flags:02x}{level_idc:02x}"
+    ))
+}
+
+fn h264_caps(fourcc: &str, params: &[&str]) -> Option<gst::Caps> {
+    let mut caps = gst::Caps::builder("video/x-h264")
+        .field(
+            "stream-format",
+            if fourcc == "avc3" { "avc3" } else { "avc" },
+        )
+        .field("alignment", "au")
+        .build();
+
+    match params {
+        [] => (),
+        [profile_level] if profile_level.len() == 6 => {
+            let profile_level = parse_hex(profile_level)?.to_be_bytes();
+            crate::codec_utils_h264_caps_set_level_and_profile(
+                caps.get_mut().unwrap(),
+                &profile_level[1..],
+            )
+            .ok()?;
+        }
+        _ => return None,
+    }
+
+    Some(caps)
+}
+
+fn h265_profile_tier_level_from_caps(
+    profile: &str,
+    tier: Option<&str>,
+    level: &str,
+) -> Result<[u8; 12], glib::BoolError> {
+    let profile_idc = match profile {
+        "main" => 1,
+        "main-10" => 2,
+        "main-still-picture" => 3,
+        p if p.starts_with("high-throughput") => 5,
+        p if p.starts_with("multiview") => 6,
+        p if p.starts_with("scalable") => 7,
+        p if p.starts_with("3d") => 8,
+        p if p.starts_with("screen-extended") => 9,
+        p if p.starts_with("main") || p.starts_with("monochrome") => 4,
+        _ => {
+            return Err(glib::bool_error!("Unsupported H.265 profile {}", profile));
+        }
+    };
+
+    let level_idc = parse_level(level)
+        .and_then(|(major, minor)| u8::try_from((major as u32 * 10 + minor as u32) * 3).ok())
+        .ok_or_else(|| glib::bool_error!("Invalid H.265 level {}", level))?;
+
+    let mut profile_tier_level = [0; 12];
+    profile_tier_level[0] = (u8::from(tier == Some("high")) << 5) | profile_idc;
+
+    // Main profile streams also conform to the Main 10 profile
+    let mut compatibility_flags = 1u32 << (31 - profile_idc);
+    if profile_idc == 1 {
+        compatibility_flags |= 1 << (31 - 2);
+    }
+    profile_tier_level[1..5].copy_from_slice(&compatibility_flags.to_be_bytes());
+
+    // progressive_source_flag, non_packed_constraint_flag and frame_only_constraint_flag
+    profile_tier_level[5] = 0xb0;
+    profile_tier_level[11] = level_idc;
+
+    Ok(profile_tier_level)
+}
+
+fn h265_profile_tier_level_from_params(params: &[&str]) -> Option<[u8; 12]> {
+    let [
+        profile,
+        compatibility_flags,
+        tier_level,
+        constraint_flags @ ..,
+    ] = params
+    else {
+        return None;
+    };
+    if constraint_flags.len() > 6 {
+        return None;
+    }
+
+    let (profile_space, profile) = match profile.as_bytes().first() {
+        Some(b'A') => (1, &profile[1..]),
+        Some(b'B') => (2, &profile[1..]),
+        Some(b'C') => (3, &profile[1..]),
+        _ => (0, *profile),
+    };
+    let profile_idc = parse_dec::<u8>(profile).filter(|&idc| idc < 32)?;
+    let compatibility_flags = parse_hex(compatibility_flags)?.reverse_bits();
+    let tier = match tier_level.as_bytes().first() {
+        Some(b'L') => 0,
+        Some(b'H') => 1,
+        _ => return None,
+    };
+    let level_idc = parse_dec::<u8>(&tier_level[1..])?;
+
+    let mut profile_tier_level = [0; 12];
+    profile_tier_level[0] = (profile_space << 6) | (tier << 5) | profile_idc;
+    profile_tier_level[1..5].copy_from_slice(&compatibility_flags.to_be_bytes());
+    for (i, flags) in constraint_flags.iter().enumerate() {
+        profile_tier_level[5 + i] = parse_hex(flags).and_then(|f| u8::try_from(f).ok())?;
+    }
+    profile_tier_level[11] = level_idc;
+
+    Some(profile_tier_level)
+}
+
+fn h265_mime_codec(
+    s: &gst::StructureRef,
+    codec_data: Option<&[u8]>,
+) -> Result<String, glib::BoolError> {
+    let fourcc = if field_str(s, "stream-format") == Some("hev1") {
+        "hev1"
+    } else {
+        "hvc1"
+    };
+
+    let profile_tier_level = if let Some(codec_data) = codec_data {
+        h265::HevcDecoderConfigurationRecord::parse(codec_data)?.profile_tier_level
+    } else {
+        let (Some(profile), Some(level)) = (field_str(s, "profile"), field_str(s, "level")) else {
+            return Ok(String::from(fourcc));
+        };
+
+        h265_profile_tier_level_from_caps(profile, field_str(s, "tier"), level)?
+    };
+
+    let compatibility_flags = u32::from_be_bytes(profile_tier_level[1..5].try_into().unwrap());
+    let mut codec = format!(
+        "{fourcc}.{}{}.{:X}.{}{}",
+        ["", "A", "B", "C"][(profile_tier_level[0] >> 6) as usize],
+        profile_tier_level[0] & 0x1f,
+        compatibility_flags.reverse_bits(),
+        if profile_tier_level[0] & 0x20 != 0 {
+            'H'
+        } else {
+            'L'
+        },
+        profile_tier_level[11],
+    );
+
+    // Trailing zero bytes of the constraint flags are omitted
+    let constraint_flags = &profile_tier_level[5..11];
+    let len = constraint_flags
+        .iter()
+        .rposition(|&b| b != 0)
+        .map_or(0, |pos| pos + 1);
+    for flags in &constraint_flags[..len] {
+        write!(codec, ".{flags:X}").unwrap();
+    }
+
+    Ok(codec)
+}
+
+fn h265_caps(fourcc: &str, params: &[&str]) -> Option<gst::Caps> {
+    let mut caps = gst::Caps::builder("video/x-h265")
+        .field("stream-format", fourcc)
+        .field("alignment", "au")
+        .build();
+
+    if !params.is_empty() {
+        let profile_tier_level = h265_profile_tier_level_from_params(params)?;
+        crate::codec_utils_h265_caps_set_level_tier_and_profile(
+            caps.get_mut().unwrap(),
+            &profile_tier_level,
+        )
+        .ok()?;
+    }
+
+    Some(caps)
+}
+
+fn av1_mime_codec(
+    s: &gst::StructureRef,
+    codec_data: Option<&[u8]>,
+) -> Result<String, glib::BoolError> {
+    let (profile, seq_level_idx, tier, bit_depth) = if let Some(codec_data) = codec_data {
+        let record = av1::Av1CodecConfigurationRecord::parse(codec_data)?;
+        let bit_depth = if record.twelve_bit {
+            12
+        } else if record.high_bitdepth {
+            10
+        } else {
+            8
+        };
+
+        (
+            record.seq_profile,
+            record.seq_level_idx_0,
+            record.seq_tier_0,
+            bit_depth,
+        )
+    } else {
+        let (Some(profile), Some(level)) = (field_str(s, "profile"), field_str(s, "level")) else {
+            return Ok(String::from("av01"));
+        };
+
+        let profile = match profile {
+            "main" => 0,
+            "high" => 1,
+            "professional" => 2,
+            _ => return Err(glib::bool_error!("Unsupported AV1 profile {}", profile)),
+        };
+        let seq_level_idx = parse_level(level)
+            .filter(|&(major, minor)| (2..=7).contains(&major) && minor < 4)
+            .map(|(major, minor)| (major - 2) * 4 + minor)
+            .ok_or_else(|| glib::bool_error!("Invalid AV1 level {}", level))?;
+
+        (
+            profile,
+            seq_level_idx,
+            u8::from(field_str(s, "tier") == Some("high")),
+            s.get::<u32>("bit-depth-luma").unwrap_or(8),
+        )
+    };
+
+    Ok(format!(
+        "av01.{profile}.{seq_level_idx:02}{}.{bit_depth:02}",
+        if tier != 0 { 'H' } else { 'M' }
+    ))
+}
+
+fn av1_caps(params: &[&str]) -> Option<gst::Caps> {
+    let builder = gst::Caps::builder("video/x-av1")
+        .field("stream-format", "obu-stream")
+        .field("alignment", "tu");
+
+    if params.is_empty() {
+        return Some(builder.build());
+    }
+
+    // The optional parameters after the bit depth can only be left out all together
+    let [profile, level_tier, bit_depth, optional @ ..] = params else {
+        return None;
+    };
+
+    let profile = match *profile {
+        "0" => "main",
+        "1" => "high",
+        "2" => "professional",
+        _ => return None,
+    };
+    if level_tier.len() != 3 || !level_tier.is_ascii() {
+        return None;
+    }
+    let seq_level_idx = parse_dec::<u8>(&level_tier[..2]).filter(|&idx| idx < 24)?;
+    let tier = match &level_tier[2..] {
+        "M" => "main",
+        "H" => "high",
+        _ => return None,
+    };
+    let bit_depth = parse_dec::<u32>(bit_depth).filter(|d| [8, 10, 12].contains(d))?;
+
+    let chroma_format = match optional {
+        [] => "4:2:0",
+        [monochrome, chroma_subsampling, ..] => match (*monochrome, *chroma_subsampling) {
+            ("1", _) => "4:0:0",
+            ("0", "110" | "111" | "112") => "4:2:0",
+            ("0", "100") => "4:2:2",
+            ("0", "000") => "4:4:4",
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(
+        builder
+            .field("profile", profile)
+            .field(
+                "level",
+                format!("{}.{}", 2 + seq_level_idx / 4, seq_level_idx % 4),
+            )
+            .field("tier", tier)
+            .field("bit-depth-luma", bit_depth)
+            .field("bit-depth-chroma", bit_depth)
+            .field("chroma-format", chroma_format)
+            .build(),
+    )
+}
+
+fn vp9_mime_codec(s: &gst::StructureRef) -> Result<String, glib::BoolError> {
+    let Some(level) = field_str(s, "level") else {
+        return Ok(String::from("vp9"));
+    };
+
+    let profile = match field_str(s, "profile") {
+        Some(profile) => parse_dec::<u8>(profile)
+            .filter(|&profile| profile <= 3)
+            .ok_or_else(|| glib::bool_error!("Unsupported VP9 profile {}", profile))?,
+        None => 0,
+    };
+    let level = parse_level(level)
+        .and_then(|(major, minor)| major.checked_mul(10)?.checked_add(minor))
+        .ok_or_else(|| glib::bool_error!("Invalid VP9 level {}", level))?;
+    let bit_depth = s.get::<u32>("bit-depth-luma").unwrap_or(8);
+
+    Ok(format!("vp09.{profile:02}.{level:02}.{bit_depth:02}"))
+}
+
+fn vp9_caps(params: &[&str]) -> Option<gst::Caps> {
+    let [profile, level, bit_depth, optional @ ..] = params else {
+        return None;
+    };
+
+    let profile = parse_dec::<u8>(profile).filter(|&profile| profile <= 3)?;
+    let level = parse_dec::<u8>(level)?;
+    let bit_depth = parse_dec::<u32>(bit_depth).filter(|d| [8, 10, 12].contains(d))?;
+    let chroma_format = match optional.first().copied() {
+        None => None,
+        Some("00" | "01") => Some("4:2:0"),
+        Some("02") => Some("4:2:2"),
+        Some("03") => Some("4:4:4"),
+        Some(_) => return None,
+    };
+
+    Some(
+        gst::Caps::builder("video/x-vp9")
+            .field("profile", profile.to_string())
+            .field("level", level_to_string(level / 10, level % 10))
+            .field("bit-depth-luma", bit_depth)
+            .field("bit-depth-chroma", bit_depth)
+            .field_if_some("chroma-format", chroma_format)
+            .build(),
+    )
+}
+
+fn mpeg_audio_mime_codec(
+    s: &gst::StructureRef,
+    codec_data: Option<&[u8]>,
+) -> Result<String, glib::BoolError> {
+    match s.get::<i32>("mpegversion") {
+        Ok(1) => {
+            if matches!(s.get::<i32>("mpegaudioversion"), Ok(2 | 3)) {
+                Ok(String::from("mp4a.69"))
+            } else {
+                Ok(String::from("mp4a.6B"))
+            }
+        }
+        Ok(2 | 4) => {
+            let audio_object_type = if let Some(codec_data) = codec_data {
+                let mut r = BitReader::new(codec_data);
+                let audio_object_type = r.read_bits_u8(5)?;
+                if audio_object_type == 31 {
+                    Some(32 + r.read_bits_u8(6)?)
+                } else {
+                    Some(audio_object_type)
+                }
+            } else {
+                field_str(s, "profile").and_then(|profile| {
+                    AAC_PROFILES
+                        .iter()
+                        .find(|(_, name)| *name == profile)
+                        .map(|(audio_object_type, _)| *audio_object_type)
+                })
+            };
+
+            match audio_object_type {
+                Some(audio_object_type) => Ok(format!("mp4a.40.{audio_object_type}")),
+                None => Ok(String::from("mp4a.40")),
+            }
+        }
+        _ => Err(glib::bool_error!("Unsupported MPEG audio caps")),
+    }
+}
+
+fn mpeg_audio_caps(params: &[&str]) -> Option<gst::Caps> {
+    let builder = gst::Caps::builder("audio/mpeg");
+
+    match params {
+        [oti] if oti.eq_ignore_ascii_case("6b") => Some(
+            builder
+                .field("mpegversion", 1i32)
+                .field("mpegaudioversion", 1i32)
+                .build(),
+        ),
+        [oti] if *oti == "69" => Some(
+            builder
+                .field("mpegversion", 1i32)
+                .field("mpegaudioversion", 2i32)
+                .build(),
+        ),
+        ["40"] => Some(builder.field("mpegversion", 4i32).build()),
+        ["40", audio_object_type] => {
+            let audio_object_type = parse_dec::<u8>(audio_object_type)?;
+            let profile = AAC_PROFILES
+                .iter()
+                .find(|(aot, _)| *aot == audio_object_type)
+                .map(|(_, profile)| *profile);
+
+            Some(
+                builder
+                    .field("mpegversion", 4i32)
+                    .field_if_some("profile", profile)
+                    .build(),
+            )
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        gst::init().unwrap();
+
+        for codec in [
+            "avc1.64001f",
+            "avc3.42401e",
+            "hvc1.1.6.L93.B0",
+            "hev1.2.4.L153.B0",
+            "av01.0.04M.08",
+            "av01.2.12H.12",
+            "vp09.00.10.08",
+            "vp09.02.31.10",
+            "mp4a.40.2",
+            "mp4a.40.5",
+            "mp4a.6B",
+            "Opus",
+            "fLaC",
+            "ac-3",
+            "ec-3",
+            "vp8",
+        ] {
+            let caps = caps_from_mime_codec(codec).unwrap();
+            assert_eq!(caps_get_mime_codec(&caps).unwrap(), codec, "{caps}");
+        }
+    }
+
+    #[test]
+    fn test_caps_fields() {
+        gst::init().unwrap();
+
+        let caps = caps_from_mime_codec("avc1.64001f").unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("profile"), Ok("high"));
+        assert_eq!(s.get::<&str>("level"), Ok("3.1"));
+
+        let caps = caps_from_mime_codec("hvc1.2.4.L153.B0").unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("profile"), Ok("main-10"));
+        assert_eq!(s.get::<&str>("tier"), Ok("main"));
+        assert_eq!(s.get::<&str>("level"), Ok("5.1"));
+
+        let caps = caps_from_mime_codec("av01.0.04M.08.0.112.09.16.09.0").unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("level"), Ok("3.0"));
+        assert_eq!(s.get::<&str>("chroma-format"), Ok("4:2:0"));
+
+        assert_eq!(
+            caps_from_mime_codec("opus").unwrap(),
+            gst::Caps::new_empty_simple("audio/x-opus")
+        );
+
+        for codec in ["foo", "avc1.6400", "hvc1.1.6", "av01.3.04M.08", "mp4a.40.x"] {
+            assert!(caps_from_mime_codec(codec).is_err(), "{codec}");
+        }
+    }
+
+    #[test]
+    fn test_codec_data() {
+        gst::init().unwrap();
+
+        let caps = gst::Caps::builder("video/x-av1")
+            .field(
+                "codec_data",
+                gst::Buffer::from_slice([0x81, 0x08, 0x4c, 0x00]),
+            )
+            .build();
+        assert_eq!(caps_get_mime_codec(&caps).unwrap(), "av01.0.08M.10");
+
+        let caps = gst::Caps::builder("audio/mpeg")
+            .field("mpegversion", 4i32)
+            .field("profile", "main")
+            .field("codec_data", gst::Buffer::from_slice([0x12, 0x10]))
+            .build();
+        assert_eq!(caps_get_mime_codec(&caps).unwrap(), "mp4a.40.2");
+
+        let caps = gst::Caps::builder("video/x-h264")
+            .field("stream-format", "avc3")
+            .build();
+        assert_eq!(caps_get_mime_codec(&caps).unwrap(), "avc3");
+
+        assert!(caps_get_mime_codec(&gst::Caps::new_empty_simple("video/x-raw")).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Parsers for the headers of common video bitstreams.
+//!
+//! These work on in-band parameter sets as well as on the `codec_data` of caps, extract
+//! the basic stream properties as [`VideoCodecParams`] and can create new `codec_data`.
+//! [`h264::Sps::set_caps`], [`h265::Sps::set_caps`] and
+//! [`av1::SequenceHeader::set_caps`] combine this with the corresponding
+//! `codec_utils_*_caps_set_level_*` functions.
+//!
+//! [`caps_get_mime_codec()`] and [`caps_from_mime_codec()`] convert between caps and
+//! RFC 6381 codec strings.
+
+use gst_video::gst_base::{BitReader, ByteReader};
+
+pub mod av1;
+pub mod h264;
+pub mod h265;
+
+mod mime_codec;
+pub use self::mime_codec::{caps_from_mime_codec, caps_get_mime_codec};
+
+// rustdoc-stripper-ignore-next
+/// Chroma format of a video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChromaFormat {
+    Monochrome,
+    Yuv420,
+    Yuv422,
+    Yuv444,
+}
+
+impl ChromaFormat {
+    // rustdoc-stripper-ignore-next
+    /// Creates the chroma format from a `chroma_format_idc` as used by H.264 and H.265.
+    pub fn from_idc(chroma_format_idc: u32) -> Option<Self> {
+        skip_assert_initialized!();
+        match chroma_format_idc {
+            0 => Some(Self::Monochrome),
+            1 => Some(Self::Yuv420),
+            2 => Some(Self::Yuv422),
+            3 => Some(Self::Yuv444),
+            _ => None,
+        }
+    }
+
+    pub fn to_idc(self) -> u32 {
+        match self {
+            Self::Monochrome => 0,
+            Self::Yuv420 => 1,
+            Self::Yuv422 => 2,
+            Self::Yuv444 => 3,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value used for the `chroma-format` caps field.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Self::Monochrome => "4:0:0",
+            Self::Yuv420 => "4:2:0",
+            Self::Yuv422 => "4:2:2",
+            Self::Yuv444 => "4:4:4",
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Colour description of a video stream as code points of ISO/IEC 23091-4 / ITU-T H.273.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorDescription {
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+}
+
+// rustdoc-stripper-ignore-next
+/// Basic properties of a video stream as signalled in its headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoCodecParams {
+    pub width: u32,
+    pub height: u32,
+    pub framerate: Option<gst::Fraction>,
+    pub pixel_aspect_ratio: Option<gst::Fraction>,
+    pub interlaced: bool,
+    pub chroma_format: ChromaFormat,
+    pub bit_depth_luma: u8,
+    pub bit_depth_chroma: u8,
+    pub color_description: Option<ColorDescription>,
+    pub full_range: bool,
+}
+
+impl VideoCodecParams {
+    #[cfg(feature = "v1_18")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_18")))]
+    pub fn colorimetry(&self) -> Option<gst_video::VideoColorimetry> {
+        assert_initialized_main_thread!();
+
+        let desc = self.color_description?;
+
+        Some(gst_video::VideoColorimetry::new(
+            if self.full_range {
+                gst_video::VideoColorRange::Range0_255
+            } else {
+                gst_video::VideoColorRange::Range16_235
+            },
+            gst_video::VideoColorMatrix::from_iso(desc.matrix_coefficients as u32),
+            gst_video::VideoTransferFunction::from_iso(desc.transfer_characteristics as u32),
+            gst_video::VideoColorPrimaries::from_iso(desc.colour_primaries as u32),
+        ))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the `width`, `height`, `framerate`, `pixel-aspect-ratio`, `interlace-mode`,
+    /// `chroma-format`, `bit-depth-luma`, `bit-depth-chroma` and `colorimetry` fields of
+    /// all structures of `caps`.
+    pub fn set_caps(&self, caps: &mut gst::CapsRef) {
+        assert_initialized_main_thread!();
+
+        caps.set("width", self.width as i32);
+        caps.set("height", self.height as i32);
+        caps.set_if_some("framerate", self.framerate);
+        caps.set_if_some("pixel-aspect-ratio", self.pixel_aspect_ratio);
+        caps.set(
+            "interlace-mode",
+            if self.interlaced {
+                "mixed"
+            } else {
+                "progressive"
+            },
+        );
+        caps.set("chroma-format", self.chroma_format.to_str());
+        caps.set("bit-depth-luma", self.bit_depth_luma as u32);
+        caps.set("bit-depth-chroma", self.bit_depth_chroma as u32);
+
+        #[cfg(feature = "v1_18")]
+        {
+            caps.set_if_some(
+                "colorimetry",
+                self.colorimetry()
+                    .map(|colorimetry| colorimetry.to_string()),
+            );
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns an iterator over the NAL units of an H.264/H.265 Annex B byte stream.
+///
+/// The returned NAL units don't include the start codes and are still escaped.
+pub fn annex_b_nal_units(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    skip_assert_initialized!();
+
+    let mut reader = ByteReader::new(data);
+    let mut started = false;
+
+    std::iter::from_fn(move || {
+        loop {
+            let start = reader.find_start_code()?;
+            if !started {
+                reader.skip(start + 3).unwrap();
+                started = true;
+                continue;
+            }
+
+            let rest = reader.remaining_data();
+            let (nal, next) = match reader.find_start_code() {
+                Some(end) => (&rest[..end], end + 3),
+                None => (rest, rest.len()),
+            };
+            reader.skip(next).unwrap();
+
+            // Trailing zero bytes belong to the next start code
+            let len = nal.iter().rposition(|&b| b != 0).map_or(0, |pos| pos + 1);
+            if len > 0 {
+                return Some(&nal[..len]);
+            }
+        }
+    })
+    .fuse()
+}
+
+fn fraction(num: u64, den: u64) -> Option<gst::Fraction> {
+    if num == 0 || den == 0 {
+        return None;
+    }
+
+    Some(gst::Fraction::new(
+        i32::try_from(num).ok()?,
+        i32::try_from(den).ok()?,
+    ))
+}
+
+// Parses `aspect_ratio_info`, which is the same in the H.264 and H.265 VUI
+fn parse_aspect_ratio(
+    r: &mut BitReader,
+) -> Result<Option<gst::Fraction>, gst_video::gst_base::ReadError> {
+    const SAR: [(u64, u64); 17] = [
+        (0, 0),
+        (1, 1),
+        (12, 11),
+        (10, 11),
+        (16, 11),
+        (40, 33),
+        (24, 11),
+        (20, 11),
+        (32, 11),
+        (80, 33),
+        (18, 11),
+        (15, 11),
+        (64, 33),
+        (160, 99),
+        (4, 3),
+        (3, 2),
+        (2, 1),
+    ];
+
+    if !r.read_bit()? {
+        return Ok(None);
+    }
+
+    let aspect_ratio_idc = r.read_bits_u8(8)?;
+    let (num, den) = if aspect_ratio_idc == 255 {
+        (r.read_bits_u16(16)? as u64, r.read_bits_u16(16)? as u64)
+    } else {
+        SAR.get(aspect_ratio_idc as usize)
+            .copied()
+            .unwrap_or((0, 0))
+    };
+
+    Ok(fraction(num, den))
+}
+
+// Parses `video_signal_type`, which is the same in the H.264 and H.265 VUI
+fn parse_video_signal_type(
+    r: &mut BitReader,
+) -> Result<(bool, Option<ColorDescription>), gst_video::gst_base::ReadError> {
+    if !r.read_bit()? {
+        return Ok((false, None));
+    }
+
+    let _video_format = r.read_bits_u8(3)?;
+    let full_range = r.read_bit()?;
+    let color_description = if r.read_bit()? {
+        Some(ColorDescription {
+            colour_primaries: r.read_bits_u8(8)?,
+            transfer_characteristics: r.read_bits_u8(8)?,
+            matrix_coefficients: r.read_bits_u8(8)?,
+        })
+    } else {
+        None
+    };
+
+    Ok((full_range, color_description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annex_b() {
+        let data = [
+            0x00, 0x00, 0x00, 0x01, 0x67, 0x42, 0x00, 0x00, 0x01, 0x68, 0xce, 0x00, 0x00, 0x00,
+            0x01, 0x65, 0x88, 0x00,
+        ];
+
+        let nals = annex_b_nal_units(&data).collect::<Vec<_>>();
+        assert_eq!(
+            nals,
+            [&[0x67, 0x42][..], &[0x68, 0xce][..], &[0x65, 0x88][..]]
+        );
+
+        assert_eq!(annex_b_nal_units(&[0x67, 0x42]).count(), 0);
+    }
+}
@@ -65,6 +65,8 @@ pub mod encoding_profile;
 pub mod functions;
 pub use crate::functions::*;
 
+pub mod codec_utils;
+
 pub mod subclass;
 
 pub mod audio_visualizer;
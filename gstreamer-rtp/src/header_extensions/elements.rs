@@ -0,0 +1,446 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::sync::Mutex;
+
+use gst::prelude::*;
+
+use super::*;
+use crate::{RTPHeaderExtension, RTPHeaderExtensionFlags};
+
+// rustdoc-stripper-ignore-next
+/// Value written by the header extension elements if none was set explicitly.
+pub trait RTPHeaderExtensionElementValue: RTPHeaderExtensionValue {
+    // rustdoc-stripper-ignore-next
+    /// Returns the value to write for the next packet. `value` is the value
+    /// that was set on the element, if any.
+    fn next_value(value: &mut Option<Self>, extension: &RTPHeaderExtension) -> Option<Self> {
+        let _ = extension;
+        value.clone()
+    }
+}
+
+// Header extensions are not added to the pipeline and usually don't get a clock set. Use the
+// clock of the closest ancestor that has one, or otherwise the system clock, which is also the
+// default clock of pipelines.
+fn clock(extension: &RTPHeaderExtension) -> gst::Clock {
+    let mut object = Some(extension.clone().upcast::<gst::Object>());
+    while let Some(current) = object {
+        if let Some(clock) = current
+            .downcast_ref::<gst::Element>()
+            .and_then(|element| element.clock())
+        {
+            return clock;
+        }
+        object = current.parent();
+    }
+
+    gst::SystemClock::obtain()
+}
+
+impl RTPHeaderExtensionElementValue for AbsSendTime {
+    // Uses the current time of the pipeline clock if no value was set.
+    fn next_value(value: &mut Option<Self>, extension: &RTPHeaderExtension) -> Option<Self> {
+        Some(value.unwrap_or_else(|| Self::from_time(clock(extension).time())))
+    }
+}
+
+impl RTPHeaderExtensionElementValue for TransportWideCC {
+    // Increments the sequence number with every written packet.
+    fn next_value(value: &mut Option<Self>, _extension: &RTPHeaderExtension) -> Option<Self> {
+        let current = value.unwrap_or(Self { seqnum: 0 });
+        *value = Some(Self {
+            seqnum: current.seqnum.wrapping_add(1),
+        });
+        Some(current)
+    }
+}
+
+impl RTPHeaderExtensionElementValue for AudioLevel {}
+impl RTPHeaderExtensionElementValue for VideoOrientation {}
+impl RTPHeaderExtensionElementValue for Mid {}
+impl RTPHeaderExtensionElementValue for RtpStreamId {}
+impl RTPHeaderExtensionElementValue for RepairedRtpStreamId {}
+impl RTPHeaderExtensionElementValue for PlayoutDelay {}
+impl RTPHeaderExtensionElementValue for AbsoluteCaptureTime {}
+
+#[derive(Debug)]
+struct State<T> {
+    value: Option<T>,
+    last_read: Option<T>,
+}
+
+impl<T> Default for State<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            last_read: None,
+        }
+    }
+}
+
+fn max_size<T: RTPHeaderExtensionElementValue>() -> usize {
+    T::MAX_SIZE
+}
+
+fn write<T: RTPHeaderExtensionElementValue>(
+    state: &Mutex<State<T>>,
+    extension: &RTPHeaderExtension,
+    write_flags: RTPHeaderExtensionFlags,
+    output_data: &mut [u8],
+) -> Result<usize, gst::LoggableError> {
+    let value = T::next_value(&mut state.lock().unwrap().value, extension);
+    let Some(value) = value else {
+        return Ok(0);
+    };
+
+    let len = value
+        .write_into(output_data)
+        .map_err(|err| gst::loggable_error!(gst::CAT_RUST, "{}", err))?;
+    if len > 16 && !write_flags.contains(RTPHeaderExtensionFlags::TWO_BYTE) {
+        return Err(gst::loggable_error!(
+            gst::CAT_RUST,
+            "{} bytes of extension data require a two-byte header",
+            len
+        ));
+    }
+
+    Ok(len)
+}
+
+fn read<T: RTPHeaderExtensionElementValue>(
+    state: &Mutex<State<T>>,
+    input_data: &[u8],
+) -> Result<(), gst::LoggableError> {
+    let value =
+        T::parse(input_data).map_err(|err| gst::loggable_error!(gst::CAT_RUST, "{}", err))?;
+    state.lock().unwrap().last_read = Some(value);
+
+    Ok(())
+}
+
+macro_rules! header_extension_element {
+    ($value:ty, $name:ident, $imp:ident, $type_name:literal, $long_name:literal) => {
+        mod $imp {
+            use std::sync::Mutex;
+
+            use crate::subclass::prelude::*;
+
+            use glib::prelude::*;
+
+            use super::{State, read, write};
+
+            #[derive(Default)]
+            pub struct Extension {
+                pub(super) state: Mutex<State<$value>>,
+            }
+
+            #[glib::object_subclass]
+            impl ObjectSubclass for Extension {
+                const NAME: &'static str = $type_name;
+                type Type = super::$name;
+                type ParentType = crate::RTPHeaderExtension;
+            }
+
+            impl ObjectImpl for Extension {}
+            impl GstObjectImpl for Extension {}
+
+            impl ElementImpl for Extension {
+                fn metadata() -> Option<&'static gst::subclass::ElementMetadata> {
+                    static ELEMENT_METADATA: std::sync::LazyLock<gst::subclass::ElementMetadata> =
+                        std::sync::LazyLock::new(|| {
+                            gst::subclass::ElementMetadata::new(
+                                $long_name,
+                                &crate::RTP_HDREXT_ELEMENT_CLASS,
+                                concat!("RTP header extension ", $long_name),
+                                "The GStreamer developers",
+                            )
+                        });
+
+                    Some(&*ELEMENT_METADATA)
+                }
+            }
+
+            impl RTPHeaderExtensionImpl for Extension {
+                const URI: &'static str = <$value as super::RTPHeaderExtensionValue>::URI;
+
+                fn supported_flags(&self) -> crate::RTPHeaderExtensionFlags {
+                    crate::RTPHeaderExtensionFlags::ONE_BYTE
+                        | crate::RTPHeaderExtensionFlags::TWO_BYTE
+                }
+
+                fn max_size(&self, _input: &gst::BufferRef) -> usize {
+                    super::max_size::<$value>()
+                }
+
+                fn write(
+                    &self,
+                    _input: &gst::BufferRef,
+                    write_flags: crate::RTPHeaderExtensionFlags,
+                    _output: &gst::BufferRef,
+                    output_data: &mut [u8],
+                ) -> Result<usize, gst::LoggableError> {
+                    write(
+                        &self.state,
+                        self.obj().upcast_ref(),
+                        write_flags,
+                        output_data,
+                    )
+                }
+
+                fn read(
+                    &self,
+                    _read_flags: crate::RTPHeaderExtensionFlags,
+                    input_data: &[u8],
+                    _output: &mut gst::BufferRef,
+                ) -> Result<(), gst::LoggableError> {
+                    read(&self.state, input_data)
+                }
+            }
+        }
+
+        glib::wrapper! {
+            #[doc = concat!("RTP header extension element for [`", stringify!($value), "`].")]
+            pub struct $name(ObjectSubclass<$imp::Extension>)
+                @extends crate::RTPHeaderExtension, gst::Element, gst::Object;
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                assert_initialized_main_thread!();
+                glib::Object::new()
+            }
+
+            // rustdoc-stripper-ignore-next
+            /// Sets the value written to the following packets.
+            pub fn set_value(&self, value: Option<$value>) {
+                use glib::subclass::prelude::*;
+                self.imp().state.lock().unwrap().value = value;
+            }
+
+            // rustdoc-stripper-ignore-next
+            /// Returns the value that was read from the last packet.
+            pub fn last_read_value(&self) -> Option<$value> {
+                use glib::subclass::prelude::*;
+                self.imp().state.lock().unwrap().last_read.clone()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+header_extension_element!(
+    AbsSendTime,
+    RTPHeaderExtensionAbsSendTime,
+    imp_abs_send_time,
+    "GstRsRTPHeaderExtensionAbsSendTime",
+    "Absolute Send Time"
+);
+header_extension_element!(
+    TransportWideCC,
+    RTPHeaderExtensionTransportWideCC,
+    imp_twcc,
+    "GstRsRTPHeaderExtensionTransportWideCC",
+    "Transport Wide Congestion Control"
+);
+header_extension_element!(
+    AudioLevel,
+    RTPHeaderExtensionAudioLevel,
+    imp_audio_level,
+    "GstRsRTPHeaderExtensionAudioLevel",
+    "Client-to-Mixer Audio Level"
+);
+header_extension_element!(
+    VideoOrientation,
+    RTPHeaderExtensionVideoOrientation,
+    imp_video_orientation,
+    "GstRsRTPHeaderExtensionVideoOrientation",
+    "Coordination of Video Orientation"
+);
+header_extension_element!(
+    Mid,
+    RTPHeaderExtensionMid,
+    imp_mid,
+    "GstRsRTPHeaderExtensionMid",
+    "Media Identification"
+);
+header_extension_element!(
+    RtpStreamId,
+    RTPHeaderExtensionRtpStreamId,
+    imp_rid,
+    "GstRsRTPHeaderExtensionRtpStreamId",
+    "RTP Stream Identifier"
+);
+header_extension_element!(
+    RepairedRtpStreamId,
+    RTPHeaderExtensionRepairedRtpStreamId,
+    imp_repaired_rid,
+    "GstRsRTPHeaderExtensionRepairedRtpStreamId",
+    "Repaired RTP Stream Identifier"
+);
+header_extension_element!(
+    PlayoutDelay,
+    RTPHeaderExtensionPlayoutDelay,
+    imp_playout_delay,
+    "GstRsRTPHeaderExtensionPlayoutDelay",
+    "Playout Delay"
+);
+header_extension_element!(
+    AbsoluteCaptureTime,
+    RTPHeaderExtensionAbsoluteCaptureTime,
+    imp_abs_capture_time,
+    "GstRsRTPHeaderExtensionAbsoluteCaptureTime",
+    "Absolute Capture Time"
+);
+
+// rustdoc-stripper-ignore-next
+/// Registers all header extension elements of this module.
+///
+/// The elements are registered with `MARGINAL` rank so that they are only
+/// selected by [`RTPHeaderExtension::create_from_uri`] if no other
+/// implementation for the URI is available.
+pub fn register(plugin: Option<&gst::Plugin>) -> Result<(), glib::BoolError> {
+    assert_initialized_main_thread!();
+
+    let elements = [
+        (
+            "rsrtphdrextabssendtime",
+            RTPHeaderExtensionAbsSendTime::static_type(),
+        ),
+        (
+            "rsrtphdrexttwcc",
+            RTPHeaderExtensionTransportWideCC::static_type(),
+        ),
+        (
+            "rsrtphdrextaudiolevel",
+            RTPHeaderExtensionAudioLevel::static_type(),
+        ),
+        (
+            "rsrtphdrextvideoorientation",
+            RTPHeaderExtensionVideoOrientation::static_type(),
+        ),
+        ("rsrtphdrextmid", RTPHeaderExtensionMid::static_type()),
+        (
+            "rsrtphdrextrid",
+            RTPHeaderExtensionRtpStreamId::static_type(),
+        ),
+        (
+            "rsrtphdrextrepairedrid",
+            RTPHeaderExtensionRepairedRtpStreamId::static_type(),
+        ),
+        (
+            "rsrtphdrextplayoutdelay",
+            RTPHeaderExtensionPlayoutDelay::static_type(),
+        ),
+        (
+            "rsrtphdrextabscapturetime",
+            RTPHeaderExtensionAbsoluteCaptureTime::static_type(),
+        ),
+    ];
+
+    for (name, type_) in elements {
+        gst::Element::register(plugin, name, gst::Rank::MARGINAL, type_)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_write_read() {
+        gst::init().unwrap();
+        register(None).unwrap();
+
+        let ext = crate::RTPHeaderExtension::create_from_uri(TransportWideCC::URI)
+            .unwrap()
+            .downcast::<RTPHeaderExtensionTransportWideCC>()
+            .unwrap();
+        ext.set_id(1);
+        ext.set_value(Some(TransportWideCC { seqnum: u16::MAX }));
+
+        let input = gst::Buffer::new();
+        let output = gst::Buffer::new();
+        for expected in [u16::MAX, 0] {
+            let mut data = [0u8; 2];
+            let len = ext
+                .write(
+                    &input,
+                    RTPHeaderExtensionFlags::ONE_BYTE,
+                    &output,
+                    &mut data,
+                )
+                .unwrap();
+            assert_eq!(len, 2);
+
+            let mut output = gst::Buffer::new();
+            assert!(ext.read(
+                RTPHeaderExtensionFlags::ONE_BYTE,
+                &data,
+                output.get_mut().unwrap()
+            ));
+            assert_eq!(
+                ext.last_read_value(),
+                Some(TransportWideCC { seqnum: expected })
+            );
+        }
+
+        let abs_send_time = RTPHeaderExtensionAbsSendTime::new();
+        let mut data = [0u8; 3];
+        let before = AbsSendTime::from_time(gst::SystemClock::obtain().time());
+        assert_eq!(
+            abs_send_time
+                .write(
+                    &input,
+                    RTPHeaderExtensionFlags::ONE_BYTE,
+                    &output,
+                    &mut data
+                )
+                .unwrap(),
+            3
+        );
+        let written = AbsSendTime::parse(&data).unwrap();
+        // Allow for wrapping around between the two clock reads
+        assert!(written.raw().wrapping_sub(before.raw()) & 0xff_ffff < 1 << 18);
+
+        let mid = RTPHeaderExtensionMid::new();
+        let mut data = [0u8; 255];
+        assert_eq!(
+            mid.write(
+                &input,
+                RTPHeaderExtensionFlags::ONE_BYTE,
+                &output,
+                &mut data
+            )
+            .unwrap(),
+            0
+        );
+        mid.set_value(Some(Mid::new("a".repeat(20)).unwrap()));
+        assert!(
+            mid.write(
+                &input,
+                RTPHeaderExtensionFlags::ONE_BYTE,
+                &output,
+                &mut data
+            )
+            .is_err()
+        );
+        assert_eq!(
+            mid.write(
+                &input,
+                RTPHeaderExtensionFlags::TWO_BYTE,
+                &output,
+                &mut data
+            )
+            .unwrap(),
+            20
+        );
+    }
+}
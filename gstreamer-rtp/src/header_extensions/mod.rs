@@ -0,0 +1,511 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Typed representations of commonly used RTP header extensions.
+//!
+//! Each type implements [`RTPHeaderExtensionValue`] for parsing from and
+//! writing to the one-byte or two-byte header extensions of an [`RTPBuffer`].
+//! With the `v1_20` feature, corresponding [`RTPHeaderExtension`](crate::RTPHeaderExtension)
+//! subclasses can be registered with [`register`].
+
+use std::fmt;
+
+use crate::rtp_buffer::{RTPBuffer, Writable};
+
+#[cfg(feature = "v1_20")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+mod elements;
+#[cfg(feature = "v1_20")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+pub use self::elements::*;
+
+// rustdoc-stripper-ignore-next
+/// Value of a specific RTP header extension.
+pub trait RTPHeaderExtensionValue: Sized + Clone + fmt::Debug + Send + 'static {
+    // rustdoc-stripper-ignore-next
+    /// URI of the extension as used in the SDP `extmap` attribute.
+    const URI: &'static str;
+    // rustdoc-stripper-ignore-next
+    /// Maximum size of the serialized extension data in bytes.
+    const MAX_SIZE: usize;
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the extension data, without the one-byte or two-byte header.
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError>;
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes the extension data into `data` and returns the number of
+    /// bytes written.
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError>;
+
+    // rustdoc-stripper-ignore-next
+    /// Reads the first extension with the given `id` from `buffer`.
+    ///
+    /// Returns `None` if the buffer contains no such extension.
+    fn read<T>(buffer: &RTPBuffer<T>, id: u8) -> Option<Result<Self, glib::BoolError>> {
+        buffer
+            .extension_onebyte_header(id, 0)
+            .or_else(|| {
+                buffer
+                    .extension_twobytes_header(id, 0)
+                    .map(|(_appbits, data)| data)
+            })
+            .map(Self::parse)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds this extension with the given `id` to `buffer`.
+    ///
+    /// The two-byte header form is used if `two_bytes` is `true`.
+    fn write(
+        &self,
+        buffer: &mut RTPBuffer<Writable>,
+        id: u8,
+        two_bytes: bool,
+    ) -> Result<(), glib::BoolError> {
+        let mut data = [0u8; 255];
+        let len = self.write_into(&mut data)?;
+        let data = &data[..len];
+
+        if two_bytes {
+            buffer.add_extension_twobytes_header(0, id, data)
+        } else {
+            if !(1..=14).contains(&id) {
+                return Err(glib::bool_error!(
+                    "Invalid id {} for one-byte header extension",
+                    id
+                ));
+            }
+            if data.is_empty() || data.len() > 16 {
+                return Err(glib::bool_error!(
+                    "Extension data of {} bytes does not fit into a one-byte header",
+                    data.len()
+                ));
+            }
+            buffer.add_extension_onebyte_header(id, data)
+        }
+    }
+}
+
+fn check_len(data: &[u8], expected: usize, name: &str) -> Result<(), glib::BoolError> {
+    if data.len() < expected {
+        Err(glib::bool_error!(
+            "Too short {} extension: {} < {}",
+            name,
+            data.len(),
+            expected
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_space(data: &[u8], needed: usize) -> Result<(), glib::BoolError> {
+    if data.len() < needed {
+        Err(glib::bool_error!(
+            "Not enough space for extension: {} < {}",
+            data.len(),
+            needed
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Absolute send time in 6.18 fixed point seconds, wrapping every 64 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AbsSendTime(u32);
+
+impl AbsSendTime {
+    // rustdoc-stripper-ignore-next
+    /// Creates a send time from its raw 24 bit representation.
+    ///
+    /// Bits above the lower 24 bits are ignored.
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw & 0xff_ffff)
+    }
+
+    pub fn from_time(time: gst::ClockTime) -> Self {
+        let raw = ((time.nseconds() as u128) << 18) / (gst::ClockTime::SECOND.nseconds() as u128);
+        Self((raw & 0xff_ffff) as u32)
+    }
+
+    pub fn raw(&self) -> u32 {
+        self.0
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Send time modulo 64 seconds.
+    pub fn time(&self) -> gst::ClockTime {
+        gst::ClockTime::from_nseconds(
+            ((self.0 as u128 * gst::ClockTime::SECOND.nseconds() as u128) >> 18) as u64,
+        )
+    }
+}
+
+impl RTPHeaderExtensionValue for AbsSendTime {
+    const URI: &'static str = "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time";
+    const MAX_SIZE: usize = 3;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 3, "abs-send-time")?;
+        Ok(Self(u32::from_be_bytes([0, data[0], data[1], data[2]])))
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        check_space(data, 3)?;
+        data[..3].copy_from_slice(&self.0.to_be_bytes()[1..]);
+        Ok(3)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Transport-wide sequence number as used for transport-wide congestion
+/// control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransportWideCC {
+    pub seqnum: u16,
+}
+
+impl RTPHeaderExtensionValue for TransportWideCC {
+    const URI: &'static str =
+        "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
+    const MAX_SIZE: usize = 2;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 2, "transport-wide-cc")?;
+        Ok(Self {
+            seqnum: u16::from_be_bytes([data[0], data[1]]),
+        })
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        check_space(data, 2)?;
+        data[..2].copy_from_slice(&self.seqnum.to_be_bytes());
+        Ok(2)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Client-to-mixer audio level as defined in RFC 6464.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioLevel {
+    pub voice_activity: bool,
+    // rustdoc-stripper-ignore-next
+    /// Audio level in -dBov, between 0 and 127.
+    pub level: u8,
+}
+
+impl RTPHeaderExtensionValue for AudioLevel {
+    const URI: &'static str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+    const MAX_SIZE: usize = 1;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 1, "audio-level")?;
+        Ok(Self {
+            voice_activity: data[0] & 0x80 != 0,
+            level: data[0] & 0x7f,
+        })
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        if self.level > 127 {
+            return Err(glib::bool_error!("Invalid audio level {}", self.level));
+        }
+        check_space(data, 1)?;
+        data[0] = ((self.voice_activity as u8) << 7) | self.level;
+        Ok(1)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Coordination of video orientation (CVO) as defined in 3GPP TS 26.114.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoOrientation {
+    // rustdoc-stripper-ignore-next
+    /// `true` if the video was captured by a back-facing camera.
+    pub back_facing: bool,
+    pub horizontal_flip: bool,
+    // rustdoc-stripper-ignore-next
+    /// Counter-clockwise rotation in degrees: 0, 90, 180 or 270.
+    pub rotation: u16,
+}
+
+impl RTPHeaderExtensionValue for VideoOrientation {
+    const URI: &'static str = "urn:3gpp:video-orientation";
+    const MAX_SIZE: usize = 1;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 1, "video-orientation")?;
+        Ok(Self {
+            back_facing: data[0] & 0x08 != 0,
+            horizontal_flip: data[0] & 0x04 != 0,
+            rotation: (data[0] & 0x03) as u16 * 90,
+        })
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        if self.rotation % 90 != 0 || self.rotation >= 360 {
+            return Err(glib::bool_error!("Invalid rotation {}", self.rotation));
+        }
+        check_space(data, 1)?;
+        data[0] = ((self.back_facing as u8) << 3)
+            | ((self.horizontal_flip as u8) << 2)
+            | (self.rotation / 90) as u8;
+        Ok(1)
+    }
+}
+
+macro_rules! sdes_extension {
+    ($(#[$attr:meta])* $name:ident, $uri:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Result<Self, glib::BoolError> {
+                let id = id.into();
+                if id.is_empty() || id.len() > 255 {
+                    return Err(glib::bool_error!("Invalid identifier length {}", id.len()));
+                }
+                Ok(Self(id))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl RTPHeaderExtensionValue for $name {
+            const URI: &'static str = $uri;
+            const MAX_SIZE: usize = 255;
+
+            fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+                // Some implementations pad the identifier with NUL bytes
+                let len = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                let id = std::str::from_utf8(&data[..len])
+                    .map_err(|_| glib::bool_error!("Identifier is not valid UTF-8"))?;
+                Self::new(id)
+            }
+
+            fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+                check_space(data, self.0.len())?;
+                data[..self.0.len()].copy_from_slice(self.0.as_bytes());
+                Ok(self.0.len())
+            }
+        }
+    };
+}
+
+sdes_extension!(
+    // rustdoc-stripper-ignore-next
+    /// Media identification as defined in RFC 9143.
+    Mid,
+    "urn:ietf:params:rtp-hdrext:sdes:mid"
+);
+sdes_extension!(
+    // rustdoc-stripper-ignore-next
+    /// RTP stream identifier as defined in RFC 8852.
+    RtpStreamId,
+    "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id"
+);
+sdes_extension!(
+    // rustdoc-stripper-ignore-next
+    /// Identifier of the RTP stream repaired by this stream as defined in
+    /// RFC 8852.
+    RepairedRtpStreamId,
+    "urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id"
+);
+
+// rustdoc-stripper-ignore-next
+/// Playout delay limits, in 10ms granularity up to 40.95s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayoutDelay {
+    pub min: gst::ClockTime,
+    pub max: gst::ClockTime,
+}
+
+impl PlayoutDelay {
+    const GRANULARITY: gst::ClockTime = gst::ClockTime::from_mseconds(10);
+    const MAX_VALUE: u64 = 0xfff;
+}
+
+impl RTPHeaderExtensionValue for PlayoutDelay {
+    const URI: &'static str = "http://www.webrtc.org/experiments/rtp-hdrext/playout-delay";
+    const MAX_SIZE: usize = 3;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 3, "playout-delay")?;
+        let min = ((data[0] as u64) << 4) | ((data[1] as u64) >> 4);
+        let max = (((data[1] & 0x0f) as u64) << 8) | data[2] as u64;
+        Ok(Self {
+            min: Self::GRANULARITY * min,
+            max: Self::GRANULARITY * max,
+        })
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        let min = self.min.nseconds() / Self::GRANULARITY.nseconds();
+        let max = self.max.nseconds() / Self::GRANULARITY.nseconds();
+        if min > Self::MAX_VALUE || max > Self::MAX_VALUE || min > max {
+            return Err(glib::bool_error!(
+                "Invalid playout delay {} - {}",
+                self.min,
+                self.max
+            ));
+        }
+        check_space(data, 3)?;
+        data[0] = (min >> 4) as u8;
+        data[1] = (((min & 0x0f) << 4) | (max >> 8)) as u8;
+        data[2] = (max & 0xff) as u8;
+        Ok(3)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Absolute capture time of the first sample of the packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AbsoluteCaptureTime {
+    // rustdoc-stripper-ignore-next
+    /// NTP timestamp in 32.32 fixed point seconds.
+    pub timestamp: u64,
+    // rustdoc-stripper-ignore-next
+    /// Estimated offset between the capturer's and the sender's clock in
+    /// signed 32.32 fixed point seconds.
+    pub estimated_capture_clock_offset: Option<i64>,
+}
+
+impl RTPHeaderExtensionValue for AbsoluteCaptureTime {
+    const URI: &'static str = "http://www.webrtc.org/experiments/rtp-hdrext/abs-capture-time";
+    const MAX_SIZE: usize = 16;
+
+    fn parse(data: &[u8]) -> Result<Self, glib::BoolError> {
+        check_len(data, 8, "abs-capture-time")?;
+        let timestamp = u64::from_be_bytes(data[..8].try_into().unwrap());
+        let estimated_capture_clock_offset = if data.len() >= 16 {
+            Some(i64::from_be_bytes(data[8..16].try_into().unwrap()))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            timestamp,
+            estimated_capture_clock_offset,
+        })
+    }
+
+    fn write_into(&self, data: &mut [u8]) -> Result<usize, glib::BoolError> {
+        let len = if self.estimated_capture_clock_offset.is_some() {
+            16
+        } else {
+            8
+        };
+        check_space(data, len)?;
+        data[..8].copy_from_slice(&self.timestamp.to_be_bytes());
+        if let Some(offset) = self.estimated_capture_clock_offset {
+            data[8..16].copy_from_slice(&offset.to_be_bytes());
+        }
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtp_buffer::RTPBufferExt;
+
+    fn roundtrip<T: RTPHeaderExtensionValue + PartialEq>(value: T, two_bytes: bool) {
+        let mut buffer = gst::Buffer::new_rtp_with_sizes(0, 0, 0).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtp = RTPBuffer::from_buffer_writable(buffer).unwrap();
+            value.write(&mut rtp, 3, two_bytes).unwrap();
+        }
+
+        let rtp = RTPBuffer::from_buffer_readable(&buffer).unwrap();
+        assert_eq!(T::read(&rtp, 3).unwrap().unwrap(), value);
+        assert!(T::read(&rtp, 4).is_none());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        gst::init().unwrap();
+
+        assert_eq!(AbsSendTime::from_raw(0x1234_5678).raw(), 0x34_5678);
+
+        for two_bytes in [false, true] {
+            roundtrip(AbsSendTime::from_raw(0x123456), two_bytes);
+            roundtrip(TransportWideCC { seqnum: 0xfffe }, two_bytes);
+            roundtrip(
+                AudioLevel {
+                    voice_activity: true,
+                    level: 42,
+                },
+                two_bytes,
+            );
+            roundtrip(
+                VideoOrientation {
+                    back_facing: true,
+                    horizontal_flip: false,
+                    rotation: 270,
+                },
+                two_bytes,
+            );
+            roundtrip(Mid::new("audio0").unwrap(), two_bytes);
+            roundtrip(RtpStreamId::new("hi").unwrap(), two_bytes);
+            roundtrip(RepairedRtpStreamId::new("lo").unwrap(), two_bytes);
+            roundtrip(
+                PlayoutDelay {
+                    min: gst::ClockTime::from_mseconds(100),
+                    max: gst::ClockTime::from_mseconds(40950),
+                },
+                two_bytes,
+            );
+            roundtrip(
+                AbsoluteCaptureTime {
+                    timestamp: 0x0123_4567_89ab_cdef,
+                    estimated_capture_clock_offset: Some(-(1 << 32)),
+                },
+                two_bytes,
+            );
+        }
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let mut data = [0u8; 3];
+        PlayoutDelay {
+            min: gst::ClockTime::from_mseconds(10),
+            max: gst::ClockTime::from_mseconds(20),
+        }
+        .write_into(&mut data)
+        .unwrap();
+        assert_eq!(data, [0x00, 0x10, 0x02]);
+
+        let orientation = VideoOrientation::parse(&[0x05]).unwrap();
+        assert!(!orientation.back_facing);
+        assert!(orientation.horizontal_flip);
+        assert_eq!(orientation.rotation, 90);
+
+        let send_time = AbsSendTime::from_time(gst::ClockTime::from_mseconds(65_500));
+        assert_eq!(send_time.time(), gst::ClockTime::from_mseconds(1500));
+
+        assert_eq!(Mid::parse(b"0\0\0").unwrap().as_str(), "0");
+        assert!(Mid::parse(b"").is_err());
+        assert!(
+            AudioLevel {
+                voice_activity: false,
+                level: 128
+            }
+            .write_into(&mut data)
+            .is_err()
+        );
+    }
+}
@@ -38,6 +38,8 @@ pub use crate::rtp_buffer::{
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
 pub mod rtp_header_extension;
 
+pub mod header_extensions;
+
 #[cfg(feature = "v1_20")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
 pub mod rtp_base_payload;
@@ -64,7 +66,8 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
     pub use crate::rtp_header_extension::RTPHeaderExtensionExtManual;
     pub use crate::{
-        auto::traits::*, rtp_base_depayload::RTPBaseDepayloadExtManual, rtp_buffer::RTPBufferExt,
+        auto::traits::*, header_extensions::RTPHeaderExtensionValue,
+        rtp_base_depayload::RTPBaseDepayloadExtManual, rtp_buffer::RTPBufferExt,
     };
 }
 
@@ -0,0 +1,627 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// from gst-gir-files (https://gitlab.freedesktop.org/gstreamer/gir-files-rs.git)
+// DO NOT EDIT
+
+use crate::ffi;
+use glib::translate::*;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYCacheType")]
+pub enum MIKEYCacheType {
+    #[doc(alias = "GST_MIKEY_CACHE_NONE")]
+    None,
+    #[doc(alias = "GST_MIKEY_CACHE_ALWAYS")]
+    Always,
+    #[doc(alias = "GST_MIKEY_CACHE_FOR_CSB")]
+    ForCsb,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYCacheType {
+    type GlibType = ffi::GstMIKEYCacheType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYCacheType {
+        match self {
+            Self::None => ffi::GST_MIKEY_CACHE_NONE,
+            Self::Always => ffi::GST_MIKEY_CACHE_ALWAYS,
+            Self::ForCsb => ffi::GST_MIKEY_CACHE_FOR_CSB,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYCacheType> for MIKEYCacheType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYCacheType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_CACHE_NONE => Self::None,
+            ffi::GST_MIKEY_CACHE_ALWAYS => Self::Always,
+            ffi::GST_MIKEY_CACHE_FOR_CSB => Self::ForCsb,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYEncAlg")]
+pub enum MIKEYEncAlg {
+    #[doc(alias = "GST_MIKEY_ENC_NULL")]
+    Null,
+    #[doc(alias = "GST_MIKEY_ENC_AES_CM_128")]
+    AesCm128,
+    #[doc(alias = "GST_MIKEY_ENC_AES_KW_128")]
+    AesKw128,
+    #[cfg(feature = "v1_20")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+    #[doc(alias = "GST_MIKEY_ENC_AES_GCM_128")]
+    AesGcm128,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYEncAlg {
+    type GlibType = ffi::GstMIKEYEncAlg;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYEncAlg {
+        match self {
+            Self::Null => ffi::GST_MIKEY_ENC_NULL,
+            Self::AesCm128 => ffi::GST_MIKEY_ENC_AES_CM_128,
+            Self::AesKw128 => ffi::GST_MIKEY_ENC_AES_KW_128,
+            #[cfg(feature = "v1_20")]
+            Self::AesGcm128 => ffi::GST_MIKEY_ENC_AES_GCM_128,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYEncAlg> for MIKEYEncAlg {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYEncAlg) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_ENC_NULL => Self::Null,
+            ffi::GST_MIKEY_ENC_AES_CM_128 => Self::AesCm128,
+            ffi::GST_MIKEY_ENC_AES_KW_128 => Self::AesKw128,
+            #[cfg(feature = "v1_20")]
+            ffi::GST_MIKEY_ENC_AES_GCM_128 => Self::AesGcm128,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYKVType")]
+pub enum MIKEYKVType {
+    #[doc(alias = "GST_MIKEY_KV_NULL")]
+    Null,
+    #[doc(alias = "GST_MIKEY_KV_SPI")]
+    Spi,
+    #[doc(alias = "GST_MIKEY_KV_INTERVAL")]
+    Interval,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYKVType {
+    type GlibType = ffi::GstMIKEYKVType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYKVType {
+        match self {
+            Self::Null => ffi::GST_MIKEY_KV_NULL,
+            Self::Spi => ffi::GST_MIKEY_KV_SPI,
+            Self::Interval => ffi::GST_MIKEY_KV_INTERVAL,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYKVType> for MIKEYKVType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYKVType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_KV_NULL => Self::Null,
+            ffi::GST_MIKEY_KV_SPI => Self::Spi,
+            ffi::GST_MIKEY_KV_INTERVAL => Self::Interval,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYKeyDataType")]
+pub enum MIKEYKeyDataType {
+    #[doc(alias = "GST_MIKEY_KD_TGK")]
+    Tgk,
+    #[doc(alias = "GST_MIKEY_KD_TEK")]
+    Tek,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYKeyDataType {
+    type GlibType = ffi::GstMIKEYKeyDataType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYKeyDataType {
+        match self {
+            Self::Tgk => ffi::GST_MIKEY_KD_TGK,
+            Self::Tek => ffi::GST_MIKEY_KD_TEK,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYKeyDataType> for MIKEYKeyDataType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYKeyDataType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_KD_TGK => Self::Tgk,
+            ffi::GST_MIKEY_KD_TEK => Self::Tek,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYMacAlg")]
+pub enum MIKEYMacAlg {
+    #[doc(alias = "GST_MIKEY_MAC_NULL")]
+    Null,
+    #[doc(alias = "GST_MIKEY_MAC_HMAC_SHA_1_160")]
+    HmacSha1160,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYMacAlg {
+    type GlibType = ffi::GstMIKEYMacAlg;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYMacAlg {
+        match self {
+            Self::Null => ffi::GST_MIKEY_MAC_NULL,
+            Self::HmacSha1160 => ffi::GST_MIKEY_MAC_HMAC_SHA_1_160,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYMacAlg> for MIKEYMacAlg {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYMacAlg) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_MAC_NULL => Self::Null,
+            ffi::GST_MIKEY_MAC_HMAC_SHA_1_160 => Self::HmacSha1160,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYMapType")]
+pub enum MIKEYMapType {
+    #[doc(alias = "GST_MIKEY_MAP_TYPE_SRTP")]
+    Srtp,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYMapType {
+    type GlibType = ffi::GstMIKEYMapType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYMapType {
+        match self {
+            Self::Srtp => ffi::GST_MIKEY_MAP_TYPE_SRTP,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYMapType> for MIKEYMapType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYMapType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_MAP_TYPE_SRTP => Self::Srtp,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYPRFFunc")]
+pub enum MIKEYPRFFunc {
+    #[doc(alias = "GST_MIKEY_PRF_MIKEY_1")]
+    Mikey1,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYPRFFunc {
+    type GlibType = ffi::GstMIKEYPRFFunc;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYPRFFunc {
+        match self {
+            Self::Mikey1 => ffi::GST_MIKEY_PRF_MIKEY_1,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYPRFFunc> for MIKEYPRFFunc {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYPRFFunc) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_PRF_MIKEY_1 => Self::Mikey1,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYPayloadType")]
+pub enum MIKEYPayloadType {
+    #[doc(alias = "GST_MIKEY_PT_LAST")]
+    Last,
+    #[doc(alias = "GST_MIKEY_PT_KEMAC")]
+    Kemac,
+    #[doc(alias = "GST_MIKEY_PT_PKE")]
+    Pke,
+    #[doc(alias = "GST_MIKEY_PT_DH")]
+    Dh,
+    #[doc(alias = "GST_MIKEY_PT_SIGN")]
+    Sign,
+    #[doc(alias = "GST_MIKEY_PT_T")]
+    T,
+    #[doc(alias = "GST_MIKEY_PT_ID")]
+    Id,
+    #[doc(alias = "GST_MIKEY_PT_CERT")]
+    Cert,
+    #[doc(alias = "GST_MIKEY_PT_CHASH")]
+    Chash,
+    #[doc(alias = "GST_MIKEY_PT_V")]
+    V,
+    #[doc(alias = "GST_MIKEY_PT_SP")]
+    Sp,
+    #[doc(alias = "GST_MIKEY_PT_RAND")]
+    Rand,
+    #[doc(alias = "GST_MIKEY_PT_ERR")]
+    Err,
+    #[doc(alias = "GST_MIKEY_PT_KEY_DATA")]
+    KeyData,
+    #[doc(alias = "GST_MIKEY_PT_GEN_EXT")]
+    GenExt,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYPayloadType {
+    type GlibType = ffi::GstMIKEYPayloadType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYPayloadType {
+        match self {
+            Self::Last => ffi::GST_MIKEY_PT_LAST,
+            Self::Kemac => ffi::GST_MIKEY_PT_KEMAC,
+            Self::Pke => ffi::GST_MIKEY_PT_PKE,
+            Self::Dh => ffi::GST_MIKEY_PT_DH,
+            Self::Sign => ffi::GST_MIKEY_PT_SIGN,
+            Self::T => ffi::GST_MIKEY_PT_T,
+            Self::Id => ffi::GST_MIKEY_PT_ID,
+            Self::Cert => ffi::GST_MIKEY_PT_CERT,
+            Self::Chash => ffi::GST_MIKEY_PT_CHASH,
+            Self::V => ffi::GST_MIKEY_PT_V,
+            Self::Sp => ffi::GST_MIKEY_PT_SP,
+            Self::Rand => ffi::GST_MIKEY_PT_RAND,
+            Self::Err => ffi::GST_MIKEY_PT_ERR,
+            Self::KeyData => ffi::GST_MIKEY_PT_KEY_DATA,
+            Self::GenExt => ffi::GST_MIKEY_PT_GEN_EXT,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYPayloadType> for MIKEYPayloadType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYPayloadType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_PT_LAST => Self::Last,
+            ffi::GST_MIKEY_PT_KEMAC => Self::Kemac,
+            ffi::GST_MIKEY_PT_PKE => Self::Pke,
+            ffi::GST_MIKEY_PT_DH => Self::Dh,
+            ffi::GST_MIKEY_PT_SIGN => Self::Sign,
+            ffi::GST_MIKEY_PT_T => Self::T,
+            ffi::GST_MIKEY_PT_ID => Self::Id,
+            ffi::GST_MIKEY_PT_CERT => Self::Cert,
+            ffi::GST_MIKEY_PT_CHASH => Self::Chash,
+            ffi::GST_MIKEY_PT_V => Self::V,
+            ffi::GST_MIKEY_PT_SP => Self::Sp,
+            ffi::GST_MIKEY_PT_RAND => Self::Rand,
+            ffi::GST_MIKEY_PT_ERR => Self::Err,
+            ffi::GST_MIKEY_PT_KEY_DATA => Self::KeyData,
+            ffi::GST_MIKEY_PT_GEN_EXT => Self::GenExt,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYSecProto")]
+pub enum MIKEYSecProto {
+    #[doc(alias = "GST_MIKEY_SEC_PROTO_SRTP")]
+    Srtp,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYSecProto {
+    type GlibType = ffi::GstMIKEYSecProto;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYSecProto {
+        match self {
+            Self::Srtp => ffi::GST_MIKEY_SEC_PROTO_SRTP,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYSecProto> for MIKEYSecProto {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYSecProto) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_SEC_PROTO_SRTP => Self::Srtp,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYSecSRTP")]
+pub enum MIKEYSecSRTP {
+    #[doc(alias = "GST_MIKEY_SP_SRTP_ENC_ALG")]
+    EncAlg,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_ENC_KEY_LEN")]
+    EncKeyLen,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_AUTH_ALG")]
+    AuthAlg,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_AUTH_KEY_LEN")]
+    AuthKeyLen,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_SALT_KEY_LEN")]
+    SaltKeyLen,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_PRF")]
+    Prf,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_KEY_DERIV_RATE")]
+    KeyDerivRate,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_SRTP_ENC")]
+    SrtpEnc,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_SRTCP_ENC")]
+    SrtcpEnc,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_FEC_ORDER")]
+    FecOrder,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_SRTP_AUTH")]
+    SrtpAuth,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_AUTH_TAG_LEN")]
+    AuthTagLen,
+    #[doc(alias = "GST_MIKEY_SP_SRTP_SRTP_PREFIX_LEN")]
+    SrtpPrefixLen,
+    #[cfg(feature = "v1_20")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
+    #[doc(alias = "GST_MIKEY_SP_SRTP_AEAD_AUTH_TAG_LEN")]
+    AeadAuthTagLen,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYSecSRTP {
+    type GlibType = ffi::GstMIKEYSecSRTP;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYSecSRTP {
+        match self {
+            Self::EncAlg => ffi::GST_MIKEY_SP_SRTP_ENC_ALG,
+            Self::EncKeyLen => ffi::GST_MIKEY_SP_SRTP_ENC_KEY_LEN,
+            Self::AuthAlg => ffi::GST_MIKEY_SP_SRTP_AUTH_ALG,
+            Self::AuthKeyLen => ffi::GST_MIKEY_SP_SRTP_AUTH_KEY_LEN,
+            Self::SaltKeyLen => ffi::GST_MIKEY_SP_SRTP_SALT_KEY_LEN,
+            Self::Prf => ffi::GST_MIKEY_SP_SRTP_PRF,
+            Self::KeyDerivRate => ffi::GST_MIKEY_SP_SRTP_KEY_DERIV_RATE,
+            Self::SrtpEnc => ffi::GST_MIKEY_SP_SRTP_SRTP_ENC,
+            Self::SrtcpEnc => ffi::GST_MIKEY_SP_SRTP_SRTCP_ENC,
+            Self::FecOrder => ffi::GST_MIKEY_SP_SRTP_FEC_ORDER,
+            Self::SrtpAuth => ffi::GST_MIKEY_SP_SRTP_SRTP_AUTH,
+            Self::AuthTagLen => ffi::GST_MIKEY_SP_SRTP_AUTH_TAG_LEN,
+            Self::SrtpPrefixLen => ffi::GST_MIKEY_SP_SRTP_SRTP_PREFIX_LEN,
+            #[cfg(feature = "v1_20")]
+            Self::AeadAuthTagLen => ffi::GST_MIKEY_SP_SRTP_AEAD_AUTH_TAG_LEN,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYSecSRTP> for MIKEYSecSRTP {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYSecSRTP) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_SP_SRTP_ENC_ALG => Self::EncAlg,
+            ffi::GST_MIKEY_SP_SRTP_ENC_KEY_LEN => Self::EncKeyLen,
+            ffi::GST_MIKEY_SP_SRTP_AUTH_ALG => Self::AuthAlg,
+            ffi::GST_MIKEY_SP_SRTP_AUTH_KEY_LEN => Self::AuthKeyLen,
+            ffi::GST_MIKEY_SP_SRTP_SALT_KEY_LEN => Self::SaltKeyLen,
+            ffi::GST_MIKEY_SP_SRTP_PRF => Self::Prf,
+            ffi::GST_MIKEY_SP_SRTP_KEY_DERIV_RATE => Self::KeyDerivRate,
+            ffi::GST_MIKEY_SP_SRTP_SRTP_ENC => Self::SrtpEnc,
+            ffi::GST_MIKEY_SP_SRTP_SRTCP_ENC => Self::SrtcpEnc,
+            ffi::GST_MIKEY_SP_SRTP_FEC_ORDER => Self::FecOrder,
+            ffi::GST_MIKEY_SP_SRTP_SRTP_AUTH => Self::SrtpAuth,
+            ffi::GST_MIKEY_SP_SRTP_AUTH_TAG_LEN => Self::AuthTagLen,
+            ffi::GST_MIKEY_SP_SRTP_SRTP_PREFIX_LEN => Self::SrtpPrefixLen,
+            #[cfg(feature = "v1_20")]
+            ffi::GST_MIKEY_SP_SRTP_AEAD_AUTH_TAG_LEN => Self::AeadAuthTagLen,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYTSType")]
+pub enum MIKEYTSType {
+    #[doc(alias = "GST_MIKEY_TS_TYPE_NTP_UTC")]
+    NtpUtc,
+    #[doc(alias = "GST_MIKEY_TS_TYPE_NTP")]
+    Ntp,
+    #[doc(alias = "GST_MIKEY_TS_TYPE_COUNTER")]
+    Counter,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYTSType {
+    type GlibType = ffi::GstMIKEYTSType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYTSType {
+        match self {
+            Self::NtpUtc => ffi::GST_MIKEY_TS_TYPE_NTP_UTC,
+            Self::Ntp => ffi::GST_MIKEY_TS_TYPE_NTP,
+            Self::Counter => ffi::GST_MIKEY_TS_TYPE_COUNTER,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYTSType> for MIKEYTSType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYTSType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_TS_TYPE_NTP_UTC => Self::NtpUtc,
+            ffi::GST_MIKEY_TS_TYPE_NTP => Self::Ntp,
+            ffi::GST_MIKEY_TS_TYPE_COUNTER => Self::Counter,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstMIKEYType")]
+pub enum MIKEYType {
+    #[doc(alias = "GST_MIKEY_TYPE_INVALID")]
+    Invalid,
+    #[doc(alias = "GST_MIKEY_TYPE_PSK_INIT")]
+    PskInit,
+    #[doc(alias = "GST_MIKEY_TYPE_PSK_VERIFY")]
+    PskVerify,
+    #[doc(alias = "GST_MIKEY_TYPE_PK_INIT")]
+    PkInit,
+    #[doc(alias = "GST_MIKEY_TYPE_PK_VERIFY")]
+    PkVerify,
+    #[doc(alias = "GST_MIKEY_TYPE_DH_INIT")]
+    DhInit,
+    #[doc(alias = "GST_MIKEY_TYPE_DH_RESP")]
+    DhResp,
+    #[doc(alias = "GST_MIKEY_TYPE_ERROR")]
+    Error,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for MIKEYType {
+    type GlibType = ffi::GstMIKEYType;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstMIKEYType {
+        match self {
+            Self::Invalid => ffi::GST_MIKEY_TYPE_INVALID,
+            Self::PskInit => ffi::GST_MIKEY_TYPE_PSK_INIT,
+            Self::PskVerify => ffi::GST_MIKEY_TYPE_PSK_VERIFY,
+            Self::PkInit => ffi::GST_MIKEY_TYPE_PK_INIT,
+            Self::PkVerify => ffi::GST_MIKEY_TYPE_PK_VERIFY,
+            Self::DhInit => ffi::GST_MIKEY_TYPE_DH_INIT,
+            Self::DhResp => ffi::GST_MIKEY_TYPE_DH_RESP,
+            Self::Error => ffi::GST_MIKEY_TYPE_ERROR,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMIKEYType> for MIKEYType {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstMIKEYType) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_MIKEY_TYPE_INVALID => Self::Invalid,
+            ffi::GST_MIKEY_TYPE_PSK_INIT => Self::PskInit,
+            ffi::GST_MIKEY_TYPE_PSK_VERIFY => Self::PskVerify,
+            ffi::GST_MIKEY_TYPE_PK_INIT => Self::PkInit,
+            ffi::GST_MIKEY_TYPE_PK_VERIFY => Self::PkVerify,
+            ffi::GST_MIKEY_TYPE_DH_INIT => Self::DhInit,
+            ffi::GST_MIKEY_TYPE_DH_RESP => Self::DhResp,
+            ffi::GST_MIKEY_TYPE_ERROR => Self::Error,
+            value => Self::__Unknown(value),
+        }
+    }
+}
@@ -2,3 +2,17 @@
 // from gir-files (https://github.com/gtk-rs/gir-files)
 // from gst-gir-files (https://gitlab.freedesktop.org/gstreamer/gir-files-rs.git)
 // DO NOT EDIT
+
+mod enums;
+pub use self::enums::MIKEYCacheType;
+pub use self::enums::MIKEYEncAlg;
+pub use self::enums::MIKEYKVType;
+pub use self::enums::MIKEYKeyDataType;
+pub use self::enums::MIKEYMacAlg;
+pub use self::enums::MIKEYMapType;
+pub use self::enums::MIKEYPRFFunc;
+pub use self::enums::MIKEYPayloadType;
+pub use self::enums::MIKEYSecProto;
+pub use self::enums::MIKEYSecSRTP;
+pub use self::enums::MIKEYTSType;
+pub use self::enums::MIKEYType;
@@ -23,6 +23,12 @@ macro_rules! skip_assert_initialized {
 
 #[allow(unused_imports)]
 mod auto;
+pub use crate::auto::*;
+
+mod mikey_message;
+pub use crate::mikey_message::*;
+mod mikey_payload;
+pub use crate::mikey_payload::*;
 
 mod sdp_attribute;
 pub use crate::sdp_attribute::*;
@@ -36,6 +42,8 @@ pub mod sdp_media;
 pub use crate::sdp_media::{SDPMedia, SDPMediaRef};
 pub mod sdp_message;
 pub use crate::sdp_message::{SDPMessage, SDPMessageRef};
+mod sdp_negotiation;
+pub use crate::sdp_negotiation::*;
 mod sdp_origin;
 pub use crate::sdp_origin::*;
 mod sdp_time;
@@ -0,0 +1,790 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, ptr};
+
+use glib::translate::*;
+
+use crate::{
+    MIKEYEncAlg, MIKEYKeyDataType, MIKEYMacAlg, MIKEYMapType, MIKEYPRFFunc, MIKEYPayload,
+    MIKEYPayloadRef, MIKEYPayloadType, MIKEYSecProto, MIKEYSecSRTP, MIKEYTSType, MIKEYType, ffi,
+};
+
+gst::mini_object_wrapper!(MIKEYMessage, MIKEYMessageRef, ffi::GstMIKEYMessage, || {
+    ffi::gst_mikey_message_get_type()
+});
+
+impl MIKEYMessage {
+    #[doc(alias = "gst_mikey_message_new")]
+    pub fn new() -> MIKEYMessage {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_mikey_message_new()) }
+    }
+
+    #[doc(alias = "gst_mikey_message_new_from_data")]
+    pub fn from_data(data: &[u8]) -> Result<MIKEYMessage, glib::Error> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let msg = ffi::gst_mikey_message_new_from_data(
+                data.as_ptr() as glib::ffi::gconstpointer,
+                data.len(),
+                ptr::null_mut(),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(msg))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_new_from_bytes")]
+    pub fn from_bytes(bytes: &glib::Bytes) -> Result<MIKEYMessage, glib::Error> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let msg = ffi::gst_mikey_message_new_from_bytes(
+                bytes.to_glib_none().0,
+                ptr::null_mut(),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(msg))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a base64 encoded MIKEY message, e.g. from the value of an
+    /// `a=key-mgmt:mikey` SDP attribute without the `mikey` prefix.
+    pub fn from_base64(data: &str) -> Result<MIKEYMessage, glib::Error> {
+        assert_initialized_main_thread!();
+        Self::from_data(&glib::base64_decode(data))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a MIKEY message from SRTP caps as used by `srtpenc` and
+    /// `srtpdec`, i.e. from the `srtp-key`, `srtp-cipher`, `srtp-auth`,
+    /// `srtcp-cipher` and `srtcp-auth` fields.
+    #[doc(alias = "gst_mikey_message_new_from_caps")]
+    pub fn from_caps(caps: &gst::CapsRef) -> Option<MIKEYMessage> {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_mikey_message_new_from_caps(caps.as_mut_ptr())) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a `PSK_INIT` message that transports the SRTP
+    /// master key and salt `key` in a KEMAC payload without encryption.
+    pub fn psk_builder(key: &[u8]) -> MIKEYPSKBuilder<'_> {
+        assert_initialized_main_thread!();
+        MIKEYPSKBuilder {
+            key,
+            cipher: "aes-128-icm",
+            auth: "hmac-sha1-80",
+            csb_id: None,
+            crypto_sessions: Vec::new(),
+        }
+    }
+}
+
+impl Default for MIKEYMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MIKEYMessageRef {
+    pub fn version(&self) -> u8 {
+        unsafe { (*self.as_ptr()).version }
+    }
+
+    #[doc(alias = "get_type")]
+    pub fn type_(&self) -> MIKEYType {
+        unsafe { from_glib((*self.as_ptr()).type_) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether a verification message is requested.
+    #[doc(alias = "V")]
+    pub fn verify(&self) -> bool {
+        unsafe { from_glib((*self.as_ptr()).V) }
+    }
+
+    pub fn prf_func(&self) -> MIKEYPRFFunc {
+        unsafe { from_glib((*self.as_ptr()).prf_func) }
+    }
+
+    #[doc(alias = "CSB_id")]
+    pub fn csb_id(&self) -> u32 {
+        unsafe { (*self.as_ptr()).CSB_id }
+    }
+
+    pub fn map_type(&self) -> MIKEYMapType {
+        unsafe { from_glib((*self.as_ptr()).map_type) }
+    }
+
+    #[doc(alias = "gst_mikey_message_set_info")]
+    pub fn set_info(
+        &mut self,
+        version: u8,
+        type_: MIKEYType,
+        verify: bool,
+        prf_func: MIKEYPRFFunc,
+        csb_id: u32,
+        map_type: MIKEYMapType,
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_set_info(
+                    self.as_mut_ptr(),
+                    version,
+                    type_.into_glib(),
+                    verify.into_glib(),
+                    prf_func.into_glib(),
+                    csb_id,
+                    map_type.into_glib(),
+                ),
+                "Failed to set info"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_n_cs")]
+    pub fn n_cs(&self) -> u32 {
+        unsafe { ffi::gst_mikey_message_get_n_cs(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_cs_srtp")]
+    pub fn cs_srtp(&self, idx: u32) -> Option<MIKEYMapSRTP> {
+        if idx >= self.n_cs() {
+            return None;
+        }
+
+        unsafe {
+            let ptr = ffi::gst_mikey_message_get_cs_srtp(self.as_ptr(), idx);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(MIKEYMapSRTP(*ptr))
+            }
+        }
+    }
+
+    pub fn cs_srtps(&self) -> impl ExactSizeIterator<Item = MIKEYMapSRTP> + '_ {
+        (0..self.n_cs()).map(|idx| self.cs_srtp(idx).unwrap())
+    }
+
+    #[doc(alias = "gst_mikey_message_add_cs_srtp")]
+    pub fn add_cs_srtp(&mut self, policy: u8, ssrc: u32, roc: u32) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_cs_srtp(self.as_mut_ptr(), policy, ssrc, roc),
+                "Failed to add crypto session"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_insert_cs_srtp")]
+    pub fn insert_cs_srtp(
+        &mut self,
+        idx: Option<u32>,
+        map: &MIKEYMapSRTP,
+    ) -> Result<(), glib::BoolError> {
+        if let Some(idx) = idx
+            && idx >= self.n_cs()
+        {
+            return Err(glib::bool_error!("Failed to insert crypto session"));
+        }
+
+        let idx = idx.map(|idx| idx as i32).unwrap_or(-1);
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_insert_cs_srtp(self.as_mut_ptr(), idx, &map.0),
+                "Failed to insert crypto session"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_replace_cs_srtp")]
+    pub fn replace_cs_srtp(&mut self, idx: u32, map: &MIKEYMapSRTP) -> Result<(), glib::BoolError> {
+        if idx >= self.n_cs() {
+            return Err(glib::bool_error!("Failed to replace crypto session"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_replace_cs_srtp(self.as_mut_ptr(), idx as i32, &map.0),
+                "Failed to replace crypto session"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_remove_cs_srtp")]
+    pub fn remove_cs_srtp(&mut self, idx: u32) -> Result<(), glib::BoolError> {
+        if idx >= self.n_cs() {
+            return Err(glib::bool_error!("Failed to remove crypto session"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_remove_cs_srtp(self.as_mut_ptr(), idx as i32),
+                "Failed to remove crypto session"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_n_payloads")]
+    pub fn n_payloads(&self) -> u32 {
+        unsafe { ffi::gst_mikey_message_get_n_payloads(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_payload")]
+    pub fn payload(&self, idx: u32) -> Option<&MIKEYPayloadRef> {
+        if idx >= self.n_payloads() {
+            return None;
+        }
+
+        unsafe {
+            let ptr = ffi::gst_mikey_message_get_payload(self.as_ptr(), idx);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(MIKEYPayloadRef::from_ptr(ptr))
+            }
+        }
+    }
+
+    pub fn payloads(&self) -> PayloadsIter<'_> {
+        PayloadsIter::new(self)
+    }
+
+    #[doc(alias = "gst_mikey_message_find_payload")]
+    pub fn find_payload(&self, type_: MIKEYPayloadType, nth: u32) -> Option<&MIKEYPayloadRef> {
+        unsafe {
+            let ptr = ffi::gst_mikey_message_find_payload(self.as_ptr(), type_.into_glib(), nth);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(MIKEYPayloadRef::from_ptr(ptr))
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_add_payload")]
+    pub fn add_payload(&mut self, payload: MIKEYPayload) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_payload(self.as_mut_ptr(), payload.into_glib_ptr()),
+                "Failed to add payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_insert_payload")]
+    pub fn insert_payload(
+        &mut self,
+        idx: u32,
+        payload: MIKEYPayload,
+    ) -> Result<(), glib::BoolError> {
+        if idx > self.n_payloads() {
+            return Err(glib::bool_error!("Failed to insert payload"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_insert_payload(
+                    self.as_mut_ptr(),
+                    idx,
+                    payload.into_glib_ptr()
+                ),
+                "Failed to insert payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_replace_payload")]
+    pub fn replace_payload(
+        &mut self,
+        idx: u32,
+        payload: MIKEYPayload,
+    ) -> Result<(), glib::BoolError> {
+        if idx >= self.n_payloads() {
+            return Err(glib::bool_error!("Failed to replace payload"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_replace_payload(
+                    self.as_mut_ptr(),
+                    idx,
+                    payload.into_glib_ptr()
+                ),
+                "Failed to replace payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_remove_payload")]
+    pub fn remove_payload(&mut self, idx: u32) -> Result<(), glib::BoolError> {
+        if idx >= self.n_payloads() {
+            return Err(glib::bool_error!("Failed to remove payload"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_remove_payload(self.as_mut_ptr(), idx),
+                "Failed to remove payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_add_t")]
+    pub fn add_t(&mut self, type_: MIKEYTSType, ts_value: &[u8]) -> Result<(), glib::BoolError> {
+        self.add_payload(MIKEYPayload::new_t(type_, ts_value)?)
+    }
+
+    #[doc(alias = "gst_mikey_message_add_t_now_ntp_utc")]
+    pub fn add_t_now_ntp_utc(&mut self) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_t_now_ntp_utc(self.as_mut_ptr()),
+                "Failed to add timestamp"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_add_rand")]
+    pub fn add_rand(&mut self, rand: &[u8]) -> Result<(), glib::BoolError> {
+        self.add_payload(MIKEYPayload::new_rand(rand)?)
+    }
+
+    #[doc(alias = "gst_mikey_message_add_rand_len")]
+    pub fn add_rand_len(&mut self, len: u8) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_rand_len(self.as_mut_ptr(), len),
+                "Failed to add random"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_to_bytes")]
+    pub fn to_bytes(&self) -> Result<glib::Bytes, glib::Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let bytes =
+                ffi::gst_mikey_message_to_bytes(self.as_mut_ptr(), ptr::null_mut(), &mut error);
+            if error.is_null() {
+                Ok(from_glib_full(bytes))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_base64_encode")]
+    pub fn base64_encode(&self) -> Result<glib::GString, glib::BoolError> {
+        unsafe {
+            let s: Option<glib::GString> =
+                from_glib_full(ffi::gst_mikey_message_base64_encode(self.as_mut_ptr()));
+            s.ok_or_else(|| glib::bool_error!("Failed to serialize MIKEY message"))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value for an `a=key-mgmt` SDP attribute, i.e. `mikey`
+    /// followed by the base64 encoded message.
+    pub fn key_mgmt_attribute(&self) -> Result<String, glib::BoolError> {
+        Ok(format!("mikey {}", self.base64_encode()?))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Stores the SRTP parameters of the message in `caps`, i.e. the
+    /// `srtp-key`, `srtp-cipher`, `srtp-auth`, `srtcp-cipher` and `srtcp-auth`
+    /// fields.
+    #[doc(alias = "gst_mikey_message_to_caps")]
+    pub fn to_caps(&self, caps: &mut gst::CapsRef) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_to_caps(self.as_ptr(), caps.as_mut_ptr()),
+                "Failed to store SRTP parameters in caps"
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates `application/x-srtp` caps as expected from the `request-key`
+    /// signal of `srtpdec`.
+    ///
+    /// If `ssrc` is given and the message has a crypto session for it, its
+    /// rollover counter is stored in the `roc` field.
+    pub fn srtp_caps(&self, ssrc: Option<u32>) -> Result<gst::Caps, glib::BoolError> {
+        let mut caps = gst::Caps::new_empty_simple("application/x-srtp");
+        self.to_caps(caps.get_mut().unwrap())?;
+
+        if let Some(cs) = ssrc.and_then(|ssrc| self.cs_srtps().find(|cs| cs.ssrc() == ssrc)) {
+            caps.get_mut()
+                .unwrap()
+                .structure_mut(0)
+                .unwrap()
+                .set("roc", cs.roc());
+        }
+
+        Ok(caps)
+    }
+}
+
+impl fmt::Debug for MIKEYMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        MIKEYMessageRef::fmt(self, f)
+    }
+}
+
+impl fmt::Debug for MIKEYMessageRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYMessage")
+            .field("version", &self.version())
+            .field("type", &self.type_())
+            .field("verify", &self.verify())
+            .field("prf-func", &self.prf_func())
+            .field("csb-id", &self.csb_id())
+            .field("map-type", &self.map_type())
+            .field("crypto-sessions", &self.cs_srtps().collect::<Vec<_>>())
+            .field("payloads", &self.payloads().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+#[doc(alias = "GstMIKEYMapSRTP")]
+pub struct MIKEYMapSRTP(ffi::GstMIKEYMapSRTP);
+
+impl MIKEYMapSRTP {
+    pub fn new(policy: u8, ssrc: u32, roc: u32) -> MIKEYMapSRTP {
+        skip_assert_initialized!();
+        MIKEYMapSRTP(ffi::GstMIKEYMapSRTP { policy, ssrc, roc })
+    }
+
+    pub fn policy(&self) -> u8 {
+        self.0.policy
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        self.0.ssrc
+    }
+
+    pub fn roc(&self) -> u32 {
+        self.0.roc
+    }
+}
+
+impl fmt::Debug for MIKEYMapSRTP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYMapSRTP")
+            .field("policy", &self.policy())
+            .field("ssrc", &self.ssrc())
+            .field("roc", &self.roc())
+            .finish()
+    }
+}
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct PayloadsIter<'a> {
+    msg: &'a MIKEYMessageRef,
+    idx: u32,
+    len: u32,
+}
+
+impl<'a> PayloadsIter<'a> {
+    fn new(msg: &'a MIKEYMessageRef) -> PayloadsIter<'a> {
+        skip_assert_initialized!();
+        PayloadsIter {
+            msg,
+            idx: 0,
+            len: msg.n_payloads(),
+        }
+    }
+}
+
+impl<'a> Iterator for PayloadsIter<'a> {
+    type Item = &'a MIKEYPayloadRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.len {
+            return None;
+        }
+
+        let item = self.msg.payload(self.idx).unwrap();
+        self.idx += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.idx) as usize;
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for PayloadsIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.len {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(self.msg.payload(self.len).unwrap())
+    }
+}
+
+impl ExactSizeIterator for PayloadsIter<'_> {}
+
+impl std::iter::FusedIterator for PayloadsIter<'_> {}
+
+// SRTP parameters for the `srtp-cipher` caps field values: RFC 3830
+// encryption algorithm, session key length and session salt length.
+fn srtp_cipher_params(cipher: &str) -> Option<(MIKEYEncAlg, u8, u8)> {
+    skip_assert_initialized!();
+    match cipher {
+        "null" => Some((MIKEYEncAlg::Null, 16, 14)),
+        "aes-128-icm" => Some((MIKEYEncAlg::AesCm128, 16, 14)),
+        "aes-256-icm" => Some((MIKEYEncAlg::AesCm128, 32, 14)),
+        #[cfg(feature = "v1_20")]
+        "aes-128-gcm" => Some((MIKEYEncAlg::AesGcm128, 16, 12)),
+        #[cfg(feature = "v1_20")]
+        "aes-256-gcm" => Some((MIKEYEncAlg::AesGcm128, 32, 12)),
+        _ => None,
+    }
+}
+
+// Authentication tag length in bytes for the `srtp-auth` caps field values.
+fn srtp_auth_tag_len(auth: &str) -> Option<u8> {
+    skip_assert_initialized!();
+    match auth {
+        "null" => Some(0),
+        "hmac-sha1-32" => Some(4),
+        "hmac-sha1-80" => Some(10),
+        _ => None,
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for pre-shared key MIKEY messages, see [`MIKEYMessage::psk_builder()`].
+///
+/// The cipher and authentication names are the ones used in the `srtp-cipher`
+/// and `srtp-auth` caps fields and properties of `srtpenc`, e.g.
+/// `aes-128-icm` and `hmac-sha1-80`. These are also the defaults.
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct MIKEYPSKBuilder<'a> {
+    key: &'a [u8],
+    cipher: &'a str,
+    auth: &'a str,
+    csb_id: Option<u32>,
+    crypto_sessions: Vec<MIKEYMapSRTP>,
+}
+
+impl<'a> MIKEYPSKBuilder<'a> {
+    pub fn cipher(self, cipher: &'a str) -> Self {
+        Self { cipher, ..self }
+    }
+
+    pub fn auth(self, auth: &'a str) -> Self {
+        Self { auth, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Crypto session bundle ID. A random one is used by default.
+    pub fn csb_id(self, csb_id: u32) -> Self {
+        Self {
+            csb_id: Some(csb_id),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an SRTP crypto session for `ssrc` with rollover counter `roc`.
+    pub fn crypto_session(mut self, ssrc: u32, roc: u32) -> Self {
+        self.crypto_sessions.push(MIKEYMapSRTP::new(0, ssrc, roc));
+        self
+    }
+
+    pub fn build(self) -> Result<MIKEYMessage, glib::BoolError> {
+        let (enc_alg, key_len, salt_len) = srtp_cipher_params(self.cipher)
+            .ok_or_else(|| glib::bool_error!("Unsupported SRTP cipher {}", self.cipher))?;
+        let auth_tag_len = srtp_auth_tag_len(self.auth)
+            .ok_or_else(|| glib::bool_error!("Unsupported SRTP auth {}", self.auth))?;
+        let aead = enc_alg != MIKEYEncAlg::Null && enc_alg != MIKEYEncAlg::AesCm128;
+
+        if self.key.len() != key_len as usize + salt_len as usize {
+            return Err(glib::bool_error!(
+                "Master key and salt must be {} bytes for {}",
+                key_len + salt_len,
+                self.cipher
+            ));
+        }
+
+        let mut msg = MIKEYMessage::new();
+        {
+            let msg = msg.get_mut().unwrap();
+            msg.set_info(
+                ffi::GST_MIKEY_VERSION as u8,
+                MIKEYType::PskInit,
+                false,
+                MIKEYPRFFunc::Mikey1,
+                self.csb_id.unwrap_or_else(glib::random_int),
+                MIKEYMapType::Srtp,
+            )?;
+
+            for cs in &self.crypto_sessions {
+                msg.insert_cs_srtp(None, cs)?;
+            }
+
+            msg.add_t_now_ntp_utc()?;
+            msg.add_rand_len(16)?;
+
+            let mut sp = MIKEYPayload::new_sp(0, MIKEYSecProto::Srtp);
+            {
+                let sp = sp.get_mut().unwrap();
+                let enabled = u8::from(enc_alg != MIKEYEncAlg::Null);
+                let auth = u8::from(auth_tag_len != 0);
+
+                let mut params = vec![
+                    (MIKEYSecSRTP::EncAlg, enc_alg.into_glib() as u8),
+                    (MIKEYSecSRTP::EncKeyLen, key_len),
+                    (MIKEYSecSRTP::SaltKeyLen, salt_len),
+                    (MIKEYSecSRTP::SrtpEnc, enabled),
+                    (MIKEYSecSRTP::SrtcpEnc, enabled),
+                ];
+                if aead {
+                    #[cfg(feature = "v1_20")]
+                    params.push((MIKEYSecSRTP::AeadAuthTagLen, 16));
+                } else {
+                    params.extend([
+                        (MIKEYSecSRTP::AuthAlg, auth),
+                        (MIKEYSecSRTP::AuthKeyLen, if auth != 0 { 20 } else { 0 }),
+                        (MIKEYSecSRTP::AuthTagLen, auth_tag_len),
+                        (MIKEYSecSRTP::SrtpAuth, auth),
+                    ]);
+                }
+
+                for (type_, value) in params {
+                    sp.sp_add_param(type_.into_glib() as u8, &[value])?;
+                }
+            }
+            msg.add_payload(sp)?;
+
+            let (key, salt) = self.key.split_at(key_len as usize);
+            let mut kemac = MIKEYPayload::new_kemac(MIKEYEncAlg::Null, MIKEYMacAlg::Null);
+            kemac
+                .get_mut()
+                .unwrap()
+                .kemac_add_sub(MIKEYPayload::new_key_data(
+                    MIKEYKeyDataType::Tek,
+                    key,
+                    Some(salt),
+                )?)?;
+            msg.add_payload(kemac)?;
+        }
+
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MIKEYPayloadView;
+
+    fn init() {
+        gst::init().unwrap();
+    }
+
+    #[test]
+    fn psk_roundtrip() {
+        init();
+
+        let key = (0..30).collect::<Vec<u8>>();
+        let msg = MIKEYMessage::psk_builder(&key)
+            .auth("hmac-sha1-32")
+            .csb_id(0x1234_5678)
+            .crypto_session(0xdead_beef, 3)
+            .build()
+            .unwrap();
+
+        assert_eq!(msg.type_(), MIKEYType::PskInit);
+        assert_eq!(msg.csb_id(), 0x1234_5678);
+        assert_eq!(msg.n_cs(), 1);
+
+        let attr = msg.key_mgmt_attribute().unwrap();
+        let data = attr.strip_prefix("mikey ").unwrap();
+        let parsed = MIKEYMessage::from_base64(data).unwrap();
+
+        assert_eq!(parsed.type_(), MIKEYType::PskInit);
+        assert_eq!(parsed.csb_id(), 0x1234_5678);
+        let cs = parsed.cs_srtp(0).unwrap();
+        assert_eq!(cs.ssrc(), 0xdead_beef);
+        assert_eq!(cs.roc(), 3);
+
+        let types = parsed.payloads().map(|p| p.type_()).collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            [
+                MIKEYPayloadType::T,
+                MIKEYPayloadType::Rand,
+                MIKEYPayloadType::Sp,
+                MIKEYPayloadType::Kemac
+            ]
+        );
+
+        let MIKEYPayloadView::Rand(rand) = parsed.payload(1).unwrap().view() else {
+            unreachable!();
+        };
+        assert_eq!(rand.rand().len(), 16);
+
+        let MIKEYPayloadView::Sp(sp) = parsed.payload(2).unwrap().view() else {
+            unreachable!();
+        };
+        assert_eq!(
+            sp.param_value(MIKEYSecSRTP::AuthTagLen.into_glib() as u8),
+            Some(&[4][..])
+        );
+
+        let kemac = parsed.find_payload(MIKEYPayloadType::Kemac, 0).unwrap();
+        let MIKEYPayloadView::Kemac(kemac) = kemac.view() else {
+            unreachable!();
+        };
+        assert_eq!(kemac.n_subs(), 1);
+        let MIKEYPayloadView::KeyData(key_data) = kemac.sub(0).unwrap().view() else {
+            unreachable!();
+        };
+        assert_eq!(key_data.key(), &key[..16]);
+        assert_eq!(key_data.salt(), Some(&key[16..]));
+
+        let caps = parsed.srtp_caps(Some(0xdead_beef)).unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("srtp-cipher").unwrap(), "aes-128-icm");
+        assert_eq!(s.get::<&str>("srtp-auth").unwrap(), "hmac-sha1-32");
+        assert_eq!(s.get::<u32>("roc").unwrap(), 3);
+        let srtp_key = s.get::<gst::Buffer>("srtp-key").unwrap();
+        assert_eq!(srtp_key.map_readable().unwrap().as_slice(), &key[..]);
+    }
+
+    #[test]
+    fn invalid_key_len() {
+        init();
+
+        assert!(MIKEYMessage::psk_builder(&[0; 16]).build().is_err());
+        assert!(
+            MIKEYMessage::psk_builder(&[0; 30])
+                .cipher("foo")
+                .build()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,629 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, slice};
+
+use glib::translate::*;
+
+use crate::{
+    MIKEYCacheType, MIKEYEncAlg, MIKEYKVType, MIKEYKeyDataType, MIKEYMacAlg, MIKEYPayloadType,
+    MIKEYSecProto, MIKEYTSType, ffi,
+};
+
+gst::mini_object_wrapper!(MIKEYPayload, MIKEYPayloadRef, ffi::GstMIKEYPayload, || {
+    ffi::gst_mikey_payload_get_type()
+});
+
+unsafe fn byte_slice<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    unsafe {
+        if data.is_null() || len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(data, len)
+        }
+    }
+}
+
+impl MIKEYPayload {
+    #[doc(alias = "gst_mikey_payload_new")]
+    pub fn new(type_: MIKEYPayloadType) -> Option<MIKEYPayload> {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_mikey_payload_new(type_.into_glib())) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new KEMAC payload. Key data sub-payloads can be added with
+    /// [`MIKEYPayloadRef::kemac_add_sub()`].
+    #[doc(alias = "gst_mikey_payload_kemac_set")]
+    pub fn new_kemac(enc_alg: MIKEYEncAlg, mac_alg: MIKEYMacAlg) -> MIKEYPayload {
+        assert_initialized_main_thread!();
+        unsafe {
+            let payload = ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_KEMAC);
+            let res =
+                ffi::gst_mikey_payload_kemac_set(payload, enc_alg.into_glib(), mac_alg.into_glib());
+            debug_assert_ne!(res, glib::ffi::GFALSE);
+            from_glib_full(payload)
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_key_data_set_key")]
+    #[doc(alias = "gst_mikey_payload_key_data_set_salt")]
+    pub fn new_key_data(
+        key_type: MIKEYKeyDataType,
+        key: &[u8],
+        salt: Option<&[u8]>,
+    ) -> Result<MIKEYPayload, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let key_len = u16::try_from(key.len())
+            .map_err(|_| glib::bool_error!("Key of {} bytes is too long", key.len()))?;
+        let salt_len = salt
+            .map(|salt| u16::try_from(salt.len()))
+            .transpose()
+            .map_err(|_| glib::bool_error!("Salt is too long"))?;
+
+        unsafe {
+            let payload: MIKEYPayload =
+                from_glib_full(ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_KEY_DATA));
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_key_data_set_key(
+                    payload.as_mut_ptr(),
+                    key_type.into_glib(),
+                    key_len,
+                    key.as_ptr(),
+                ),
+                "Failed to set key"
+            )?;
+            if let (Some(salt), Some(salt_len)) = (salt, salt_len) {
+                glib::result_from_gboolean!(
+                    ffi::gst_mikey_payload_key_data_set_salt(
+                        payload.as_mut_ptr(),
+                        salt_len,
+                        salt.as_ptr(),
+                    ),
+                    "Failed to set salt"
+                )?;
+            }
+
+            Ok(payload)
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new security policy payload. Parameters can be added with
+    /// [`MIKEYPayloadRef::sp_add_param()`].
+    #[doc(alias = "gst_mikey_payload_sp_set")]
+    pub fn new_sp(policy: u32, proto: MIKEYSecProto) -> MIKEYPayload {
+        assert_initialized_main_thread!();
+        unsafe {
+            let payload = ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_SP);
+            let res = ffi::gst_mikey_payload_sp_set(payload, policy, proto.into_glib());
+            debug_assert_ne!(res, glib::ffi::GFALSE);
+            from_glib_full(payload)
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_rand_set")]
+    pub fn new_rand(rand: &[u8]) -> Result<MIKEYPayload, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let len = u8::try_from(rand.len())
+            .map_err(|_| glib::bool_error!("Random of {} bytes is too long", rand.len()))?;
+
+        unsafe {
+            let payload: MIKEYPayload =
+                from_glib_full(ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_RAND));
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_rand_set(payload.as_mut_ptr(), len, rand.as_ptr()),
+                "Failed to set random"
+            )?;
+
+            Ok(payload)
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_t_set")]
+    pub fn new_t(type_: MIKEYTSType, ts_value: &[u8]) -> Result<MIKEYPayload, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let expected_len = ts_value_len(type_)
+            .ok_or_else(|| glib::bool_error!("Unsupported timestamp type {type_:?}"))?;
+        if ts_value.len() != expected_len {
+            return Err(glib::bool_error!(
+                "Timestamp of type {type_:?} must be {expected_len} bytes"
+            ));
+        }
+
+        unsafe {
+            let payload: MIKEYPayload =
+                from_glib_full(ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_T));
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_t_set(
+                    payload.as_mut_ptr(),
+                    type_.into_glib(),
+                    ts_value.as_ptr()
+                ),
+                "Failed to set timestamp"
+            )?;
+
+            Ok(payload)
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_pke_set")]
+    pub fn new_pke(c: MIKEYCacheType, data: &[u8]) -> Result<MIKEYPayload, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let len = u16::try_from(data.len())
+            .map_err(|_| glib::bool_error!("Data of {} bytes is too long", data.len()))?;
+
+        unsafe {
+            let payload: MIKEYPayload =
+                from_glib_full(ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_PKE));
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_pke_set(
+                    payload.as_mut_ptr(),
+                    c.into_glib(),
+                    len,
+                    data.as_ptr()
+                ),
+                "Failed to set PKE data"
+            )?;
+
+            Ok(payload)
+        }
+    }
+}
+
+fn ts_value_len(type_: MIKEYTSType) -> Option<usize> {
+    skip_assert_initialized!();
+    match type_ {
+        MIKEYTSType::NtpUtc | MIKEYTSType::Ntp => Some(8),
+        MIKEYTSType::Counter => Some(4),
+        _ => None,
+    }
+}
+
+impl MIKEYPayloadRef {
+    #[doc(alias = "get_type")]
+    pub fn type_(&self) -> MIKEYPayloadType {
+        unsafe { from_glib((*self.as_ptr()).type_) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Length of the serialized payload in bytes.
+    pub fn len(&self) -> u32 {
+        unsafe { (*self.as_ptr()).len }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn view(&self) -> MIKEYPayloadView<'_> {
+        unsafe {
+            match self.type_() {
+                MIKEYPayloadType::Kemac => {
+                    MIKEYPayloadView::Kemac(&*(self.as_ptr() as *const MIKEYPayloadKEMAC))
+                }
+                MIKEYPayloadType::Pke => {
+                    MIKEYPayloadView::Pke(&*(self.as_ptr() as *const MIKEYPayloadPKE))
+                }
+                MIKEYPayloadType::T => {
+                    MIKEYPayloadView::T(&*(self.as_ptr() as *const MIKEYPayloadT))
+                }
+                MIKEYPayloadType::Sp => {
+                    MIKEYPayloadView::Sp(&*(self.as_ptr() as *const MIKEYPayloadSP))
+                }
+                MIKEYPayloadType::Rand => {
+                    MIKEYPayloadView::Rand(&*(self.as_ptr() as *const MIKEYPayloadRAND))
+                }
+                MIKEYPayloadType::KeyData => {
+                    MIKEYPayloadView::KeyData(&*(self.as_ptr() as *const MIKEYPayloadKeyData))
+                }
+                _ => MIKEYPayloadView::Other(self),
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_kemac_add_sub")]
+    pub fn kemac_add_sub(&mut self, sub: MIKEYPayload) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::Kemac {
+            return Err(glib::bool_error!("Not a KEMAC payload"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_kemac_add_sub(self.as_mut_ptr(), sub.into_glib_ptr()),
+                "Failed to add sub-payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_kemac_remove_sub")]
+    pub fn kemac_remove_sub(&mut self, idx: u32) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::Kemac
+            || idx >= unsafe { ffi::gst_mikey_payload_kemac_get_n_sub(self.as_ptr()) }
+        {
+            return Err(glib::bool_error!("Failed to remove sub-payload"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_kemac_remove_sub(self.as_mut_ptr(), idx),
+                "Failed to remove sub-payload"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_sp_add_param")]
+    pub fn sp_add_param(&mut self, type_: u8, value: &[u8]) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::Sp {
+            return Err(glib::bool_error!("Not a security policy payload"));
+        }
+        let len = u8::try_from(value.len())
+            .map_err(|_| glib::bool_error!("Parameter of {} bytes is too long", value.len()))?;
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_sp_add_param(self.as_mut_ptr(), type_, len, value.as_ptr()),
+                "Failed to add parameter"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_sp_remove_param")]
+    pub fn sp_remove_param(&mut self, idx: u32) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::Sp
+            || idx >= unsafe { ffi::gst_mikey_payload_sp_get_n_params(self.as_ptr()) }
+        {
+            return Err(glib::bool_error!("Failed to remove parameter"));
+        }
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_sp_remove_param(self.as_mut_ptr(), idx),
+                "Failed to remove parameter"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_key_data_set_spi")]
+    pub fn key_data_set_spi(&mut self, spi: &[u8]) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::KeyData {
+            return Err(glib::bool_error!("Not a key data payload"));
+        }
+        let len = u8::try_from(spi.len())
+            .map_err(|_| glib::bool_error!("SPI of {} bytes is too long", spi.len()))?;
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_key_data_set_spi(self.as_mut_ptr(), len, spi.as_ptr()),
+                "Failed to set SPI"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_payload_key_data_set_interval")]
+    pub fn key_data_set_interval(
+        &mut self,
+        valid_from: &[u8],
+        valid_to: &[u8],
+    ) -> Result<(), glib::BoolError> {
+        if self.type_() != MIKEYPayloadType::KeyData {
+            return Err(glib::bool_error!("Not a key data payload"));
+        }
+        let (Ok(vf_len), Ok(vt_len)) =
+            (u8::try_from(valid_from.len()), u8::try_from(valid_to.len()))
+        else {
+            return Err(glib::bool_error!("Interval is too long"));
+        };
+
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_payload_key_data_set_interval(
+                    self.as_mut_ptr(),
+                    vf_len,
+                    valid_from.as_ptr(),
+                    vt_len,
+                    valid_to.as_ptr(),
+                ),
+                "Failed to set interval"
+            )
+        }
+    }
+}
+
+impl fmt::Debug for MIKEYPayload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        MIKEYPayloadRef::fmt(self, f)
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.view() {
+            MIKEYPayloadView::Kemac(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::Pke(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::T(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::Sp(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::Rand(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::KeyData(p) => fmt::Debug::fmt(p, f),
+            MIKEYPayloadView::Other(p) => f
+                .debug_struct("MIKEYPayload")
+                .field("type", &p.type_())
+                .field("len", &p.len())
+                .finish(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MIKEYPayloadView<'a> {
+    Kemac(&'a MIKEYPayloadKEMAC),
+    Pke(&'a MIKEYPayloadPKE),
+    T(&'a MIKEYPayloadT),
+    Sp(&'a MIKEYPayloadSP),
+    Rand(&'a MIKEYPayloadRAND),
+    KeyData(&'a MIKEYPayloadKeyData),
+    Other(&'a MIKEYPayloadRef),
+}
+
+macro_rules! define_payload(
+    ($name:ident, $ffi_name:path) => {
+        #[repr(transparent)]
+        #[doc(alias = stringify!($ffi_name))]
+        pub struct $name($ffi_name);
+
+        unsafe impl Send for $name {}
+        unsafe impl Sync for $name {}
+
+        impl std::ops::Deref for $name {
+            type Target = MIKEYPayloadRef;
+
+            fn deref(&self) -> &Self::Target {
+                unsafe { MIKEYPayloadRef::from_ptr(&self.0.pt) }
+            }
+        }
+    }
+);
+
+define_payload!(MIKEYPayloadKEMAC, ffi::GstMIKEYPayloadKEMAC);
+define_payload!(MIKEYPayloadPKE, ffi::GstMIKEYPayloadPKE);
+define_payload!(MIKEYPayloadT, ffi::GstMIKEYPayloadT);
+define_payload!(MIKEYPayloadSP, ffi::GstMIKEYPayloadSP);
+define_payload!(MIKEYPayloadRAND, ffi::GstMIKEYPayloadRAND);
+define_payload!(MIKEYPayloadKeyData, ffi::GstMIKEYPayloadKeyData);
+
+impl MIKEYPayloadKEMAC {
+    pub fn enc_alg(&self) -> MIKEYEncAlg {
+        unsafe { from_glib(self.0.enc_alg) }
+    }
+
+    pub fn mac_alg(&self) -> MIKEYMacAlg {
+        unsafe { from_glib(self.0.mac_alg) }
+    }
+
+    #[doc(alias = "gst_mikey_payload_kemac_get_n_sub")]
+    pub fn n_subs(&self) -> u32 {
+        unsafe { ffi::gst_mikey_payload_kemac_get_n_sub(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_payload_kemac_get_sub")]
+    pub fn sub(&self, idx: u32) -> Option<&MIKEYPayloadRef> {
+        if idx >= self.n_subs() {
+            return None;
+        }
+
+        unsafe {
+            let ptr = ffi::gst_mikey_payload_kemac_get_sub(self.as_ptr(), idx);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(MIKEYPayloadRef::from_ptr(ptr))
+            }
+        }
+    }
+
+    pub fn subs(&self) -> impl ExactSizeIterator<Item = &MIKEYPayloadRef> + '_ {
+        (0..self.n_subs()).map(|idx| self.sub(idx).unwrap())
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadKEMAC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadKEMAC")
+            .field("enc_alg", &self.enc_alg())
+            .field("mac_alg", &self.mac_alg())
+            .field("subs", &self.subs().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl MIKEYPayloadPKE {
+    #[doc(alias = "C")]
+    pub fn cache_type(&self) -> MIKEYCacheType {
+        unsafe { from_glib(self.0.C) }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe { byte_slice(self.0.data, self.0.data_len as usize) }
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadPKE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadPKE")
+            .field("cache_type", &self.cache_type())
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl MIKEYPayloadT {
+    pub fn ts_type(&self) -> MIKEYTSType {
+        unsafe { from_glib(self.0.type_) }
+    }
+
+    pub fn ts_value(&self) -> &[u8] {
+        let len = ts_value_len(self.ts_type()).unwrap_or(0);
+        unsafe { byte_slice(self.0.ts_value, len) }
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadT")
+            .field("ts_type", &self.ts_type())
+            .field("ts_value", &self.ts_value())
+            .finish()
+    }
+}
+
+impl MIKEYPayloadSP {
+    pub fn policy(&self) -> u32 {
+        self.0.policy
+    }
+
+    pub fn proto(&self) -> MIKEYSecProto {
+        unsafe { from_glib(self.0.proto) }
+    }
+
+    #[doc(alias = "gst_mikey_payload_sp_get_n_params")]
+    pub fn n_params(&self) -> u32 {
+        unsafe { ffi::gst_mikey_payload_sp_get_n_params(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_payload_sp_get_param")]
+    pub fn param(&self, idx: u32) -> Option<&MIKEYPayloadSPParam> {
+        if idx >= self.n_params() {
+            return None;
+        }
+
+        unsafe {
+            let ptr = ffi::gst_mikey_payload_sp_get_param(self.as_ptr(), idx);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(&*(ptr as *const MIKEYPayloadSPParam))
+            }
+        }
+    }
+
+    pub fn params(&self) -> impl ExactSizeIterator<Item = &MIKEYPayloadSPParam> + '_ {
+        (0..self.n_params()).map(|idx| self.param(idx).unwrap())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the first parameter of type `type_`.
+    pub fn param_value(&self, type_: u8) -> Option<&[u8]> {
+        self.params()
+            .find(|param| param.type_() == type_)
+            .map(|param| param.value())
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadSP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadSP")
+            .field("policy", &self.policy())
+            .field("proto", &self.proto())
+            .field("params", &self.params().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[repr(transparent)]
+#[doc(alias = "GstMIKEYPayloadSPParam")]
+pub struct MIKEYPayloadSPParam(ffi::GstMIKEYPayloadSPParam);
+
+unsafe impl Send for MIKEYPayloadSPParam {}
+unsafe impl Sync for MIKEYPayloadSPParam {}
+
+impl MIKEYPayloadSPParam {
+    // rustdoc-stripper-ignore-next
+    /// Parameter type, e.g. a [`MIKEYSecSRTP`](crate::MIKEYSecSRTP) value for
+    /// SRTP policies.
+    pub fn type_(&self) -> u8 {
+        self.0.type_
+    }
+
+    pub fn value(&self) -> &[u8] {
+        unsafe { byte_slice(self.0.val, self.0.len as usize) }
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadSPParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadSPParam")
+            .field("type", &self.type_())
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+impl MIKEYPayloadRAND {
+    pub fn rand(&self) -> &[u8] {
+        unsafe { byte_slice(self.0.rand, self.0.len as usize) }
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadRAND {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadRAND")
+            .field("rand", &self.rand())
+            .finish()
+    }
+}
+
+impl MIKEYPayloadKeyData {
+    pub fn key_type(&self) -> MIKEYKeyDataType {
+        unsafe { from_glib(self.0.key_type) }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        unsafe { byte_slice(self.0.key_data, self.0.key_len as usize) }
+    }
+
+    pub fn salt(&self) -> Option<&[u8]> {
+        if self.0.salt_data.is_null() {
+            None
+        } else {
+            Some(unsafe { byte_slice(self.0.salt_data, self.0.salt_len as usize) })
+        }
+    }
+
+    pub fn kv_type(&self) -> MIKEYKVType {
+        unsafe { from_glib(self.0.kv_type) }
+    }
+
+    pub fn spi(&self) -> Option<&[u8]> {
+        if self.kv_type() != MIKEYKVType::Spi {
+            return None;
+        }
+
+        Some(unsafe { byte_slice(self.0.kv_data[0], self.0.kv_len[0] as usize) })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the `(valid_from, valid_to)` key validity interval.
+    pub fn interval(&self) -> Option<(&[u8], &[u8])> {
+        if self.kv_type() != MIKEYKVType::Interval {
+            return None;
+        }
+
+        unsafe {
+            Some((
+                byte_slice(self.0.kv_data[0], self.0.kv_len[0] as usize),
+                byte_slice(self.0.kv_data[1], self.0.kv_len[1] as usize),
+            ))
+        }
+    }
+}
+
+impl fmt::Debug for MIKEYPayloadKeyData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYPayloadKeyData")
+            .field("key_type", &self.key_type())
+            .field("key_len", &self.key().len())
+            .field("salt_len", &self.salt().map(|salt| salt.len()))
+            .field("kv_type", &self.kv_type())
+            .finish()
+    }
+}
@@ -465,6 +465,24 @@ impl SDPMediaRef {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Parses the MIKEY message of the `key-mgmt` attribute, if any.
+    #[doc(alias = "gst_sdp_media_parse_keymgmt")]
+    pub fn parse_keymgmt(&self) -> Result<Option<crate::MIKEYMessage>, glib::BoolError> {
+        if self.attribute_val("key-mgmt").is_none() {
+            return Ok(None);
+        }
+
+        unsafe {
+            let mut mikey = ptr::null_mut();
+            let result = ffi::gst_sdp_media_parse_keymgmt(&self.0, &mut mikey);
+            match result {
+                ffi::GST_SDP_OK => Ok(from_glib_full(mikey)),
+                _ => Err(glib::bool_error!("Failed to parse key-mgmt attribute")),
+            }
+        }
+    }
+
     #[doc(alias = "gst_sdp_media_remove_attribute")]
     pub fn remove_attribute(&mut self, idx: u32) -> Result<(), glib::BoolError> {
         if idx >= self.attributes_len() {
@@ -627,6 +627,24 @@ impl SDPMessageRef {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Parses the MIKEY message of the `key-mgmt` attribute, if any.
+    #[doc(alias = "gst_sdp_message_parse_keymgmt")]
+    pub fn parse_keymgmt(&self) -> Result<Option<crate::MIKEYMessage>, glib::BoolError> {
+        if self.attribute_val("key-mgmt").is_none() {
+            return Ok(None);
+        }
+
+        unsafe {
+            let mut mikey = ptr::null_mut();
+            let result = ffi::gst_sdp_message_parse_keymgmt(&self.0, &mut mikey);
+            match result {
+                ffi::GST_SDP_OK => Ok(from_glib_full(mikey)),
+                _ => Err(glib::bool_error!("Failed to parse key-mgmt attribute")),
+            }
+        }
+    }
+
     #[doc(alias = "gst_sdp_message_medias_len")]
     pub fn medias_len(&self) -> u32 {
         unsafe { ffi::gst_sdp_message_medias_len(&self.0) }
@@ -1290,6 +1308,19 @@ mod tests {
         assert_eq!(media.formats_len(), 1);
     }
 
+    #[test]
+    fn parse_keymgmt() {
+        init();
+
+        let sdp = "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nm=audio 9 RTP/SAVP 0\r\n";
+        let mut sdp = SDPMessage::parse_buffer(sdp.as_bytes()).unwrap();
+        assert!(sdp.parse_keymgmt().unwrap().is_none());
+        assert!(sdp.media(0).unwrap().parse_keymgmt().unwrap().is_none());
+
+        sdp.add_attribute("key-mgmt", Some("mikey invalid"));
+        assert!(sdp.parse_keymgmt().is_err());
+    }
+
     #[test]
     fn debug_impl() {
         init();
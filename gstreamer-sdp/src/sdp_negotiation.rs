@@ -0,0 +1,726 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt;
+
+use crate::{SDPMedia, SDPMediaRef, SDPMessage, SDPMessageRef};
+
+// Fields that are specific to a single SDP payload type or that are
+// transport related and must not take part in codec matching.
+const NON_CODEC_FIELDS: &[&str] = &["payload", "ssrc", "clock-base", "seqnum-base"];
+
+// rustdoc-stripper-ignore-next
+/// Direction of a media section as signalled by the `sendrecv`, `sendonly`,
+/// `recvonly` and `inactive` attributes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SDPDirection {
+    #[default]
+    SendRecv,
+    SendOnly,
+    RecvOnly,
+    Inactive,
+}
+
+impl SDPDirection {
+    fn from_send_recv(send: bool, recv: bool) -> Self {
+        skip_assert_initialized!();
+        match (send, recv) {
+            (true, true) => SDPDirection::SendRecv,
+            (true, false) => SDPDirection::SendOnly,
+            (false, true) => SDPDirection::RecvOnly,
+            (false, false) => SDPDirection::Inactive,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the direction for the attribute key `attr`, or `None` if it is
+    /// not a direction attribute.
+    pub fn from_attribute(attr: &str) -> Option<Self> {
+        skip_assert_initialized!();
+        match attr {
+            "sendrecv" => Some(SDPDirection::SendRecv),
+            "sendonly" => Some(SDPDirection::SendOnly),
+            "recvonly" => Some(SDPDirection::RecvOnly),
+            "inactive" => Some(SDPDirection::Inactive),
+            _ => None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the direction of `media`.
+    ///
+    /// If the media has no direction attribute, the direction of the session
+    /// `message` is used if given, and `sendrecv` otherwise as mandated by
+    /// RFC 4566.
+    pub fn from_media(media: &SDPMediaRef, message: Option<&SDPMessageRef>) -> Self {
+        skip_assert_initialized!();
+        media
+            .attributes()
+            .find_map(|attr| Self::from_attribute(attr.key()))
+            .or_else(|| {
+                message.and_then(|message| {
+                    message
+                        .attributes()
+                        .find_map(|attr| Self::from_attribute(attr.key()))
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the attribute key of the direction, e.g. `sendrecv`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SDPDirection::SendRecv => "sendrecv",
+            SDPDirection::SendOnly => "sendonly",
+            SDPDirection::RecvOnly => "recvonly",
+            SDPDirection::Inactive => "inactive",
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the direction as seen from the other side, i.e. with sending
+    /// and receiving swapped.
+    pub fn reverse(&self) -> Self {
+        Self::from_send_recv(self.can_recv(), self.can_send())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether media is sent in this direction, i.e. `sendrecv` or `sendonly`.
+    pub fn can_send(&self) -> bool {
+        matches!(self, SDPDirection::SendRecv | SDPDirection::SendOnly)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether media is received in this direction, i.e. `sendrecv` or
+    /// `recvonly`.
+    pub fn can_recv(&self) -> bool {
+        matches!(self, SDPDirection::SendRecv | SDPDirection::RecvOnly)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the direction to put into the answer for an `offered`
+    /// direction if the local side supports `self`, as described in RFC 3264
+    /// section 6.1.
+    pub fn answer(&self, offered: SDPDirection) -> Self {
+        Self::from_send_recv(
+            self.can_send() && offered.can_recv(),
+            self.can_recv() && offered.can_send(),
+        )
+    }
+}
+
+impl fmt::Display for SDPDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Local capabilities for one media section of an answer.
+///
+/// `caps` are `application/x-rtp` caps as returned by
+/// [`SDPMediaRef::caps_from_media()`], without the `payload` field. Payload
+/// types of the offer are accepted if their caps intersect with any of the
+/// structures of `caps`. Note that encoding names are upper case in such
+/// caps, e.g. `encoding-name=OPUS`.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct SDPLocalMedia {
+    kind: String,
+    caps: gst::Caps,
+    direction: SDPDirection,
+    port: u32,
+    header_extensions: Vec<String>,
+}
+
+impl SDPLocalMedia {
+    // rustdoc-stripper-ignore-next
+    /// Creates local capabilities for media sections of type `kind`, e.g.
+    /// `audio` or `video`, that can handle `caps`.
+    pub fn new(kind: &str, caps: gst::Caps) -> Self {
+        skip_assert_initialized!();
+        SDPLocalMedia {
+            kind: kind.to_owned(),
+            caps,
+            direction: SDPDirection::SendRecv,
+            port: 9,
+            header_extensions: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Direction supported locally for this media. Defaults to
+    /// [`SDPDirection::SendRecv`].
+    pub fn direction(self, direction: SDPDirection) -> Self {
+        Self { direction, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Port to put into the answer for this media. Defaults to the discard
+    /// port 9.
+    pub fn port(self, port: u32) -> Self {
+        Self { port, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an RTP header extension URI (RFC 8285) that is supported locally.
+    pub fn header_extension(mut self, uri: &str) -> Self {
+        self.header_extensions.push(uri.to_owned());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the media type, e.g. `audio` or `video`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the caps that can be handled locally.
+    pub fn caps(&self) -> &gst::Caps {
+        &self.caps
+    }
+
+    fn intersect_format(&self, media: &SDPMediaRef, pt: i32) -> Option<gst::Caps> {
+        let mut offered = media.caps_from_media(pt)?;
+        {
+            let offered = offered.make_mut();
+            for s in offered.iter_mut() {
+                s.remove_fields(NON_CODEC_FIELDS.iter().copied());
+            }
+        }
+
+        let caps = self.caps.intersect(&offered);
+        if caps.is_empty() { None } else { Some(caps) }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Extmap<'a> {
+    id: u32,
+    direction: Option<SDPDirection>,
+    uri: &'a str,
+}
+
+impl<'a> Extmap<'a> {
+    fn parse(value: &'a str) -> Option<Self> {
+        let mut parts = value.split_ascii_whitespace();
+        let id = parts.next()?;
+        let uri = parts.next()?;
+        // Split off the optional direction, e.g. `1/sendonly`
+        let (id, direction) = match id.split_once('/') {
+            Some((id, direction)) => (id, Some(SDPDirection::from_attribute(direction)?)),
+            None => (id, None),
+        };
+
+        Some(Extmap {
+            id: id.parse().ok()?,
+            direction,
+            uri,
+        })
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Creates SDP answers for remote offers according to the RFC 3264 offer/answer
+/// model.
+///
+/// Each offered media section is matched with the first unused
+/// [`SDPLocalMedia`] of the same kind. Payload types are accepted if their caps
+/// intersect with the local caps and keep the payload type numbers, `rtpmap`,
+/// `fmtp` and `rtcp-fb` attributes of the offer. The answered direction is
+/// computed from the offered and the local direction, header extensions are
+/// answered with the IDs of the offer and media sections of `BUNDLE` groups
+/// are answered in a `BUNDLE` group again.
+///
+/// Media sections that can't be matched or that are already rejected in the
+/// offer with port 0 are rejected with port 0.
+#[derive(Debug, Clone)]
+pub struct SDPAnswerer {
+    medias: Vec<SDPLocalMedia>,
+    bundle: bool,
+    origin: [String; 3],
+    connection: [String; 3],
+}
+
+impl SDPAnswerer {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an answerer without any local media.
+    pub fn builder() -> SDPAnswererBuilder {
+        skip_assert_initialized!();
+        SDPAnswererBuilder {
+            medias: Vec::new(),
+            bundle: true,
+            origin: ["-".into(), "0".into(), "0".into()],
+            connection: ["IN".into(), "IP4".into(), "0.0.0.0".into()],
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the local media in the order they were added.
+    pub fn medias(&self) -> &[SDPLocalMedia] {
+        &self.medias
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an answer for `offer` with one media section per offered
+    /// media section.
+    ///
+    /// Fails if the offer has no media sections.
+    pub fn create_answer(&self, offer: &SDPMessageRef) -> Result<SDPMessage, glib::BoolError> {
+        if offer.medias_len() == 0 {
+            return Err(glib::bool_error!("Offer has no media sections"));
+        }
+
+        let mut answer = SDPMessage::new();
+        answer.set_version("0");
+        let [username, sess_id, sess_version] = &self.origin;
+        let [nettype, addrtype, address] = &self.connection;
+        answer.set_origin(username, sess_id, sess_version, nettype, addrtype, address);
+        answer.set_session_name(offer.session_name().unwrap_or("-"));
+        answer.add_time("0", "0", &[]);
+
+        let session_extmaps = offer
+            .attributes()
+            .filter(|attr| attr.key() == "extmap")
+            .filter_map(|attr| attr.value().and_then(Extmap::parse))
+            .collect::<Vec<_>>();
+
+        let mut used = vec![false; self.medias.len()];
+        let mut accepted_mids = Vec::new();
+
+        for offered in offer.medias() {
+            let kind = offered.media().unwrap_or_default();
+            // Media sections that are rejected in the offer stay rejected
+            let matched = self
+                .medias
+                .iter()
+                .filter(|_| offered.port() != 0)
+                .zip(used.iter_mut())
+                .filter(|(local, used)| !**used && local.kind == kind)
+                .find_map(|(local, used)| {
+                    let formats = offered
+                        .formats()
+                        .filter_map(|fmt| {
+                            let pt = fmt.parse::<i32>().ok()?;
+                            local.intersect_format(offered, pt).map(|_| fmt)
+                        })
+                        .collect::<Vec<_>>();
+
+                    if formats.is_empty() {
+                        None
+                    } else {
+                        *used = true;
+                        Some((local, formats))
+                    }
+                });
+
+            let media = match matched {
+                Some((local, formats)) => {
+                    if let Some(mid) = offered.attribute_val("mid") {
+                        accepted_mids.push(mid);
+                    }
+
+                    let mut media = answer_media(offer, offered, local, &formats, &session_extmaps);
+                    media.add_connection(nettype, addrtype, address, 0, 0);
+                    media
+                }
+                None => reject_media(offered),
+            };
+
+            answer.add_media(media);
+        }
+
+        if self.bundle {
+            for group in offer
+                .attributes()
+                .filter(|attr| attr.key() == "group")
+                .filter_map(|attr| attr.value())
+            {
+                let Some(mids) = group.strip_prefix("BUNDLE").filter(|mids| {
+                    mids.is_empty() || mids.starts_with(|c: char| c.is_ascii_whitespace())
+                }) else {
+                    continue;
+                };
+
+                let mids = mids
+                    .split_ascii_whitespace()
+                    .filter(|mid| accepted_mids.contains(mid))
+                    .collect::<Vec<_>>();
+
+                if !mids.is_empty() {
+                    answer.add_attribute("group", Some(&format!("BUNDLE {}", mids.join(" "))));
+                }
+            }
+        }
+
+        Ok(answer)
+    }
+}
+
+fn answer_media(
+    offer: &SDPMessageRef,
+    offered: &SDPMediaRef,
+    local: &SDPLocalMedia,
+    formats: &[&str],
+    session_extmaps: &[Extmap],
+) -> SDPMedia {
+    skip_assert_initialized!();
+    let mut media = SDPMedia::new();
+    media.set_media(&local.kind);
+    media.set_port_info(local.port, 0);
+    media.set_proto(offered.proto().unwrap_or("RTP/AVP"));
+
+    for fmt in formats {
+        media.add_format(fmt);
+    }
+
+    if let Some(mid) = offered.attribute_val("mid") {
+        media.add_attribute("mid", Some(mid));
+    }
+
+    let direction = local
+        .direction
+        .answer(SDPDirection::from_media(offered, Some(offer)));
+    media.add_attribute(direction.as_str(), None);
+
+    if offered.attribute_val("rtcp-mux").is_some() {
+        media.add_attribute("rtcp-mux", None);
+    }
+
+    let mut extmap_ids = Vec::new();
+    for extmap in offered
+        .attributes()
+        .filter(|attr| attr.key() == "extmap")
+        .filter_map(|attr| attr.value().and_then(Extmap::parse))
+        .chain(session_extmaps.iter().cloned())
+    {
+        if extmap_ids.contains(&extmap.id)
+            || !local.header_extensions.iter().any(|uri| uri == extmap.uri)
+        {
+            continue;
+        }
+
+        extmap_ids.push(extmap.id);
+        // The direction of the offer is from the point of view of the offerer
+        let value = match extmap.direction {
+            Some(direction) => format!("{}/{} {}", extmap.id, direction.reverse(), extmap.uri),
+            None => format!("{} {}", extmap.id, extmap.uri),
+        };
+        media.add_attribute("extmap", Some(&value));
+    }
+
+    for attr in offered.attributes() {
+        if !matches!(attr.key(), "rtpmap" | "fmtp" | "rtcp-fb") {
+            continue;
+        }
+
+        let Some(value) = attr.value() else {
+            continue;
+        };
+
+        let pt = value.split_ascii_whitespace().next().unwrap_or_default();
+        if formats.contains(&pt) {
+            media.add_attribute(attr.key(), Some(value));
+        }
+    }
+
+    media
+}
+
+fn reject_media(offered: &SDPMediaRef) -> SDPMedia {
+    skip_assert_initialized!();
+    let mut media = SDPMedia::new();
+    media.set_media(offered.media().unwrap_or("application"));
+    media.set_port_info(0, 0);
+    media.set_proto(offered.proto().unwrap_or("RTP/AVP"));
+
+    // At least one format is required, so echo the first offered one
+    media.add_format(offered.format(0).unwrap_or("0"));
+
+    if let Some(mid) = offered.attribute_val("mid") {
+        media.add_attribute("mid", Some(mid));
+    }
+    media.add_attribute(SDPDirection::Inactive.as_str(), None);
+
+    media
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`SDPAnswerer`].
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct SDPAnswererBuilder {
+    medias: Vec<SDPLocalMedia>,
+    bundle: bool,
+    origin: [String; 3],
+    connection: [String; 3],
+}
+
+impl SDPAnswererBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Adds local capabilities for one media section. Offered media sections
+    /// are matched with the local media in the order they were added.
+    pub fn media(mut self, media: SDPLocalMedia) -> Self {
+        self.medias.push(media);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether to accept `BUNDLE` groups of the offer. Defaults to `true`.
+    pub fn bundle(self, bundle: bool) -> Self {
+        Self { bundle, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Username, session ID and session version of the `o=` line of the
+    /// answer. Defaults to `-`, `0` and `0`.
+    ///
+    /// RFC 3264 requires the session version to be incremented with every
+    /// new answer of the same session.
+    pub fn origin(self, username: &str, sess_id: &str, sess_version: &str) -> Self {
+        Self {
+            origin: [username.into(), sess_id.into(), sess_version.into()],
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Network type, address type and address of the local side that are
+    /// used for the `o=` line and the `c=` line of each accepted media.
+    /// Defaults to `IN`, `IP4` and `0.0.0.0`.
+    pub fn connection(self, nettype: &str, addrtype: &str, address: &str) -> Self {
+        Self {
+            connection: [nettype.into(), addrtype.into(), address.into()],
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the [`SDPAnswerer`].
+    #[must_use = "Building the answerer without using it has no effect"]
+    pub fn build(self) -> SDPAnswerer {
+        SDPAnswerer {
+            medias: self.medias,
+            bundle: self.bundle,
+            origin: self.origin,
+            connection: self.connection,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFER: &str = "v=0\r\n\
+        o=- 4858251974351650128 2 IN IP4 127.0.0.1\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        a=group:BUNDLE 0 1 2\r\n\
+        m=audio 9 RTP/AVPF 111 0 8\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=mid:0\r\n\
+        a=sendrecv\r\n\
+        a=rtcp-mux\r\n\
+        a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+        a=extmap:3/sendonly urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+        a=rtpmap:111 opus/48000/2\r\n\
+        a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+        a=rtcp-fb:111 transport-cc\r\n\
+        a=rtpmap:0 PCMU/8000\r\n\
+        a=rtpmap:8 PCMA/8000\r\n\
+        m=video 9 RTP/AVPF 96 97\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=mid:1\r\n\
+        a=sendonly\r\n\
+        a=extmap:4 urn:3gpp:video-orientation\r\n\
+        a=rtpmap:96 VP8/90000\r\n\
+        a=rtpmap:97 H264/90000\r\n\
+        a=fmtp:97 packetization-mode=1;profile-level-id=42e01f\r\n\
+        m=video 9 RTP/AVPF 98\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=mid:2\r\n\
+        a=rtpmap:98 VP9/90000\r\n";
+
+    fn init() {
+        gst::init().unwrap();
+    }
+
+    fn answerer() -> SDPAnswerer {
+        SDPAnswerer::builder()
+            .media(
+                SDPLocalMedia::new(
+                    "audio",
+                    gst::Caps::builder("application/x-rtp")
+                        .field("encoding-name", "OPUS")
+                        .field("clock-rate", 48000i32)
+                        .build(),
+                )
+                .header_extension("urn:ietf:params:rtp-hdrext:sdes:mid"),
+            )
+            .media(
+                SDPLocalMedia::new(
+                    "video",
+                    gst::Caps::builder("application/x-rtp")
+                        .field("encoding-name", "H264")
+                        .field("clock-rate", 90000i32)
+                        .build(),
+                )
+                .direction(SDPDirection::RecvOnly)
+                .header_extension("urn:3gpp:video-orientation"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn direction() {
+        assert_eq!(
+            SDPDirection::SendRecv.answer(SDPDirection::SendOnly),
+            SDPDirection::RecvOnly
+        );
+        assert_eq!(
+            SDPDirection::SendRecv.answer(SDPDirection::RecvOnly),
+            SDPDirection::SendOnly
+        );
+        assert_eq!(
+            SDPDirection::SendOnly.answer(SDPDirection::SendOnly),
+            SDPDirection::Inactive
+        );
+        assert_eq!(
+            SDPDirection::RecvOnly.answer(SDPDirection::SendRecv),
+            SDPDirection::RecvOnly
+        );
+
+        assert_eq!(SDPDirection::SendOnly.reverse(), SDPDirection::RecvOnly);
+        assert_eq!(SDPDirection::RecvOnly.reverse(), SDPDirection::SendOnly);
+        assert_eq!(SDPDirection::SendRecv.reverse(), SDPDirection::SendRecv);
+        assert_eq!(SDPDirection::Inactive.reverse(), SDPDirection::Inactive);
+    }
+
+    #[test]
+    fn create_answer() {
+        init();
+
+        let offer = SDPMessage::parse_buffer(OFFER.as_bytes()).unwrap();
+        let answer = answerer().create_answer(&offer).unwrap();
+
+        assert_eq!(answer.medias_len(), 3);
+        assert_eq!(answer.attribute_val("group"), Some("BUNDLE 0 1"));
+        assert_eq!(answer.origin().unwrap().addr(), Some("0.0.0.0"));
+
+        let audio = answer.media(0).unwrap();
+        assert_eq!(audio.media(), Some("audio"));
+        assert_eq!(audio.port(), 9);
+        assert_eq!(audio.formats().collect::<Vec<_>>(), ["111"]);
+        assert_eq!(audio.attribute_val("mid"), Some("0"));
+        assert_eq!(
+            SDPDirection::from_media(audio, Some(&answer)),
+            SDPDirection::SendRecv
+        );
+        assert!(audio.attribute_val("rtcp-mux").is_some());
+        assert_eq!(
+            audio.attribute_val("extmap"),
+            Some("3/recvonly urn:ietf:params:rtp-hdrext:sdes:mid")
+        );
+        assert_eq!(audio.attribute_val_n("extmap", 1), None);
+        assert_eq!(
+            audio.attribute_val("fmtp"),
+            Some("111 minptime=10;useinbandfec=1")
+        );
+        assert_eq!(audio.attribute_val("rtcp-fb"), Some("111 transport-cc"));
+
+        let video = answer.media(1).unwrap();
+        assert_eq!(video.formats().collect::<Vec<_>>(), ["97"]);
+        assert_eq!(video.attribute_val("rtpmap"), Some("97 H264/90000"));
+        assert_eq!(
+            SDPDirection::from_media(video, Some(&answer)),
+            SDPDirection::RecvOnly
+        );
+        assert_eq!(
+            video.attribute_val("extmap"),
+            Some("4 urn:3gpp:video-orientation")
+        );
+
+        let rejected = answer.media(2).unwrap();
+        assert_eq!(rejected.port(), 0);
+        assert_eq!(rejected.formats().collect::<Vec<_>>(), ["98"]);
+        assert_eq!(rejected.attribute_val("mid"), Some("2"));
+    }
+
+    #[test]
+    fn rejected_in_offer() {
+        init();
+
+        let offer = OFFER
+            .replace("a=group:BUNDLE 0 1 2", "a=group:BUNDLEX 0 1 2")
+            .replace("m=audio 9", "m=audio 0");
+        let offer = SDPMessage::parse_buffer(offer.as_bytes()).unwrap();
+        let answer = answerer().create_answer(&offer).unwrap();
+
+        assert_eq!(answer.attribute_val("group"), None);
+        let audio = answer.media(0).unwrap();
+        assert_eq!(audio.port(), 0);
+        assert_eq!(
+            SDPDirection::from_media(audio, Some(&answer)),
+            SDPDirection::Inactive
+        );
+        assert_eq!(answer.media(1).unwrap().port(), 9);
+    }
+
+    #[test]
+    fn no_bundle() {
+        init();
+
+        let offer = SDPMessage::parse_buffer(OFFER.as_bytes()).unwrap();
+        let answerer = SDPAnswerer::builder()
+            .media(SDPLocalMedia::new(
+                "audio",
+                gst::Caps::builder("application/x-rtp")
+                    .field("encoding-name", "PCMU")
+                    .build(),
+            ))
+            .bundle(false)
+            .build();
+        let answer = answerer.create_answer(&offer).unwrap();
+
+        assert_eq!(answer.attribute_val("group"), None);
+        let audio = answer.media(0).unwrap();
+        assert_eq!(audio.connection(0).unwrap().address(), Some("0.0.0.0"));
+        assert_eq!(audio.formats().collect::<Vec<_>>(), ["0"]);
+        assert_eq!(answer.media(1).unwrap().port(), 0);
+    }
+
+    #[test]
+    fn origin_and_connection() {
+        init();
+
+        let offer = SDPMessage::parse_buffer(OFFER.as_bytes()).unwrap();
+        let answerer = SDPAnswerer::builder()
+            .media(SDPLocalMedia::new(
+                "audio",
+                gst::Caps::builder("application/x-rtp")
+                    .field("encoding-name", "PCMU")
+                    .build(),
+            ))
+            .origin("gst", "1234", "2")
+            .connection("IN", "IP6", "::1")
+            .build();
+        let answer = answerer.create_answer(&offer).unwrap();
+
+        let origin = answer.origin().unwrap();
+        assert_eq!(origin.username(), Some("gst"));
+        assert_eq!(origin.sess_id(), Some("1234"));
+        assert_eq!(origin.sess_version(), Some("2"));
+        assert_eq!(origin.addrtype(), Some("IP6"));
+        assert_eq!(origin.addr(), Some("::1"));
+
+        let connection = answer.media(0).unwrap().connection(0).unwrap();
+        assert_eq!(connection.nettype(), Some("IN"));
+        assert_eq!(connection.addrtype(), Some("IP6"));
+        assert_eq!(connection.address(), Some("::1"));
+
+        // Rejected media have no connection
+        assert!(answer.media(1).unwrap().connection(0).is_none());
+    }
+}
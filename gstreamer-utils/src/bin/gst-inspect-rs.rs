@@ -0,0 +1,221 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// A simplified reimplementation of the gst-inspect-<version> cli tool on top of the
+// gstreamer_utils::inspect module.
+//
+// Without arguments all plugins and their features are listed. Given the name of an element
+// factory or a plugin its details are printed, as JSON if `--json` is passed.
+
+use std::{env, process};
+
+use gst::prelude::*;
+use gstreamer_utils::inspect::{ElementFactoryInfo, PluginInfo, PropertyRange};
+
+fn list_features() {
+    let registry = gst::Registry::get();
+
+    let mut plugins = registry.plugins().into_iter().collect::<Vec<_>>();
+    plugins.sort_by_key(|plugin| plugin.plugin_name());
+
+    for plugin in plugins {
+        let mut features = registry
+            .features_by_plugin(&plugin.plugin_name())
+            .into_iter()
+            .collect::<Vec<_>>();
+        features.sort_by_key(|feature| feature.name());
+
+        for feature in features {
+            match feature.downcast_ref::<gst::ElementFactory>() {
+                Some(factory) => println!(
+                    "{}:  {}: {}",
+                    plugin.plugin_name(),
+                    factory.name(),
+                    factory.longname()
+                ),
+                None => println!("{}:  {}", plugin.plugin_name(), feature.name()),
+            }
+        }
+    }
+}
+
+fn print_plugin(info: &PluginInfo) {
+    println!("Plugin Details:");
+    println!("  Name                     {}", info.name);
+    println!("  Description              {}", info.description);
+    if let Some(ref filename) = info.filename {
+        println!("  Filename                 {filename}");
+    }
+    println!("  Version                  {}", info.version);
+    println!("  License                  {}", info.license);
+    println!("  Source module            {}", info.source);
+    if let Some(ref release_date) = info.release_date {
+        println!("  Source release date      {release_date}");
+    }
+    println!("  Binary package           {}", info.package);
+    println!("  Origin URL               {}", info.origin);
+    println!();
+    println!("  {} features:", info.features.len());
+    for feature in &info.features {
+        println!("  +-- {feature}");
+    }
+}
+
+fn print_element(info: &ElementFactoryInfo) {
+    println!("Factory Details:");
+    println!("  Rank                     {}", info.rank);
+    println!("  Long-name                {}", info.long_name);
+    println!("  Klass                    {}", info.klass);
+    println!("  Description              {}", info.description);
+    println!("  Author                   {}", info.author);
+    if let Some(ref documentation_uri) = info.documentation_uri {
+        println!("  Documentation            {documentation_uri}");
+    }
+    if let Some(ref plugin) = info.plugin {
+        println!("  Plugin                   {plugin}");
+    }
+    println!();
+
+    println!("Type Hierarchy:");
+    println!("  {}", info.type_name);
+    for type_name in &info.type_hierarchy {
+        println!("  +-- {type_name}");
+    }
+    println!();
+
+    if !info.interfaces.is_empty() {
+        println!("Implemented Interfaces:");
+        for interface in &info.interfaces {
+            println!("  {interface}");
+        }
+        println!();
+    }
+
+    println!("Pad Templates:");
+    for templ in &info.pad_templates {
+        println!(
+            "  {} template: '{}' ({})",
+            templ.direction.to_uppercase(),
+            templ.name_template,
+            templ.presence
+        );
+        println!("    Capabilities: {}", templ.caps);
+    }
+    println!();
+
+    if let Some(ref uri_handler) = info.uri_handler {
+        println!("URI handling capabilities:");
+        println!("  Element handles {} URIs", uri_handler.uri_type);
+        println!(
+            "  Supported URI protocols: {}",
+            uri_handler.protocols.join(", ")
+        );
+        println!();
+    }
+
+    println!("Element Properties:");
+    for property in &info.properties {
+        println!(
+            "  {:<24} : {}",
+            property.name,
+            property.blurb.as_deref().unwrap_or_default()
+        );
+        println!("  {:<24}   flags: {}", "", property.flags.join(", "));
+        println!("  {:<24}   type: {}", "", property.type_name);
+        match property.range {
+            Some(PropertyRange::Int { minimum, maximum }) => {
+                println!("  {:<24}   range: {minimum} - {maximum}", "")
+            }
+            Some(PropertyRange::UInt { minimum, maximum }) => {
+                println!("  {:<24}   range: {minimum} - {maximum}", "")
+            }
+            Some(PropertyRange::Float { minimum, maximum }) => {
+                println!("  {:<24}   range: {minimum} - {maximum}", "")
+            }
+            Some(PropertyRange::Fraction {
+                ref minimum,
+                ref maximum,
+            }) => println!("  {:<24}   range: {minimum} - {maximum}", ""),
+            Some(PropertyRange::Enum { ref values } | PropertyRange::Flags { ref values }) => {
+                for value in values {
+                    println!(
+                        "  {:<24}   ({}): {:<16} - {}",
+                        "", value.value, value.nick, value.name
+                    );
+                }
+            }
+            None => (),
+        }
+        if let Some(ref default) = property.default {
+            println!("  {:<24}   default: {default}", "");
+        }
+    }
+
+    for (title, signals) in [
+        ("Element Signals", &info.signals),
+        ("Element Actions", &info.actions),
+    ] {
+        if signals.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!("{title}:");
+        for signal in signals {
+            println!(
+                "  \"{}\" : {} user_function ({})",
+                signal.name,
+                signal.return_type,
+                signal.param_types.join(", ")
+            );
+        }
+    }
+}
+
+fn main() {
+    let mut json = false;
+    let mut name = None;
+    for arg in env::args().skip(1) {
+        if arg == "--json" {
+            json = true;
+        } else if name.is_none() && !arg.starts_with('-') {
+            name = Some(arg);
+        } else {
+            eprintln!("Usage: gst-inspect-rs [--json] [ELEMENT-NAME | PLUGIN-NAME]");
+            process::exit(-1)
+        }
+    }
+
+    gst::init().unwrap();
+
+    let Some(name) = name else {
+        list_features();
+        return;
+    };
+
+    if gst::ElementFactory::find(&name).is_some() {
+        let info = match ElementFactoryInfo::from_name(&name) {
+            Ok(info) => info,
+            Err(err) => {
+                eprintln!("Failed to inspect element '{name}': {err}");
+                process::exit(-1)
+            }
+        };
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&info).unwrap());
+        } else {
+            print_element(&info);
+        }
+    } else if let Some(plugin) = gst::Registry::get().find_plugin(&name) {
+        let info = PluginInfo::new(&plugin);
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&info).unwrap());
+        } else {
+            print_plugin(&info);
+        }
+    } else {
+        eprintln!("No such element or plugin '{name}'");
+        process::exit(-1)
+    }
+}
@@ -0,0 +1,513 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Structured introspection of plugins and element factories.
+//!
+//! This provides the information printed by `gst-inspect-1.0` as plain Rust structs that can be
+//! serialized with the `serde` feature, e.g. for comparing the capabilities of elements between
+//! different GStreamer versions.
+
+use gst::{
+    glib::{self, gobject_ffi, translate::*},
+    prelude::*,
+};
+
+/// Description of a plugin.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PluginInfo {
+    pub name: String,
+    pub description: String,
+    pub filename: Option<String>,
+    pub version: String,
+    pub license: String,
+    pub source: String,
+    pub package: String,
+    pub origin: String,
+    pub release_date: Option<String>,
+    /// Names of all features provided by the plugin, sorted alphabetically.
+    pub features: Vec<String>,
+}
+
+impl PluginInfo {
+    pub fn new(plugin: &gst::Plugin) -> Self {
+        let mut features = gst::Registry::get()
+            .features_by_plugin(&plugin.plugin_name())
+            .into_iter()
+            .map(|feature| feature.name().to_string())
+            .collect::<Vec<_>>();
+        features.sort();
+
+        PluginInfo {
+            name: plugin.plugin_name().into(),
+            description: plugin.description().into(),
+            filename: plugin
+                .filename()
+                .map(|filename| filename.display().to_string()),
+            version: plugin.version().into(),
+            license: plugin.license().into(),
+            source: plugin.source().into(),
+            package: plugin.package().into(),
+            origin: plugin.origin().into(),
+            release_date: plugin.release_date_string().map(Into::into),
+            features,
+        }
+    }
+}
+
+/// Description of an element factory and the element type it creates.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementFactoryInfo {
+    pub name: String,
+    pub long_name: String,
+    pub klass: String,
+    pub description: String,
+    pub author: String,
+    pub documentation_uri: Option<String>,
+    pub icon_name: Option<String>,
+    pub rank: i32,
+    pub plugin: Option<String>,
+    pub type_name: String,
+    /// Names of all parent types, starting with the direct parent.
+    pub type_hierarchy: Vec<String>,
+    pub interfaces: Vec<String>,
+    pub pad_templates: Vec<PadTemplateInfo>,
+    pub uri_handler: Option<UriHandlerInfo>,
+    pub properties: Vec<PropertyInfo>,
+    pub signals: Vec<SignalInfo>,
+    pub actions: Vec<SignalInfo>,
+}
+
+impl ElementFactoryInfo {
+    /// Describes the element factory with the given name.
+    pub fn from_name(name: &str) -> Result<Self, glib::BoolError> {
+        let factory = gst::ElementFactory::find(name)
+            .ok_or_else(|| glib::bool_error!("No such element factory '{}'", name))?;
+        Self::new(&factory)
+    }
+
+    /// Describes the element factory.
+    ///
+    /// This loads the plugin of the factory if necessary.
+    pub fn new(factory: &gst::ElementFactory) -> Result<Self, glib::BoolError> {
+        let factory = factory.load()?;
+        let type_ = factory.element_type();
+
+        let type_hierarchy = std::iter::successors(type_.parent(), |type_| type_.parent())
+            .map(|type_| type_.name().to_owned())
+            .collect();
+
+        let mut interfaces = type_
+            .interfaces()
+            .iter()
+            .map(|type_| type_.name().to_owned())
+            .collect::<Vec<_>>();
+        interfaces.sort();
+
+        let pad_templates = factory
+            .static_pad_templates()
+            .iter()
+            .map(|templ| PadTemplateInfo {
+                name_template: templ.name_template().to_owned(),
+                direction: match templ.direction() {
+                    gst::PadDirection::Src => "src",
+                    gst::PadDirection::Sink => "sink",
+                    _ => "unknown",
+                }
+                .to_owned(),
+                presence: match templ.presence() {
+                    gst::PadPresence::Always => "always",
+                    gst::PadPresence::Sometimes => "sometimes",
+                    _ => "request",
+                }
+                .to_owned(),
+                caps: templ.caps().to_string(),
+            })
+            .collect();
+
+        let uri_handler = type_
+            .is_a(gst::URIHandler::static_type())
+            .then(|| UriHandlerInfo {
+                uri_type: match factory.uri_type() {
+                    gst::URIType::Src => "src",
+                    gst::URIType::Sink => "sink",
+                    _ => "unknown",
+                }
+                .to_owned(),
+                protocols: factory
+                    .uri_protocols()
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+            });
+
+        let properties = glib::object::ObjectClass::from_type(type_)
+            .map(|class| {
+                class
+                    .list_properties()
+                    .iter()
+                    .map(PropertyInfo::new)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (actions, signals) = signals(type_)
+            .into_iter()
+            .partition(|signal| signal.flags.iter().any(|flag| flag == "action"));
+
+        Ok(ElementFactoryInfo {
+            name: factory.name().into(),
+            long_name: factory.longname().to_owned(),
+            klass: factory.klass().to_owned(),
+            description: factory.description().to_owned(),
+            author: factory.author().to_owned(),
+            documentation_uri: factory.documentation_uri().map(ToOwned::to_owned),
+            icon_name: factory.icon_name().map(ToOwned::to_owned),
+            rank: factory.rank().into_glib(),
+            plugin: factory.plugin_name().map(Into::into),
+            type_name: type_.name().to_owned(),
+            type_hierarchy,
+            interfaces,
+            pad_templates,
+            uri_handler,
+            properties,
+            signals,
+            actions,
+        })
+    }
+}
+
+/// Description of a pad template of an element factory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PadTemplateInfo {
+    pub name_template: String,
+    /// `src` or `sink`.
+    pub direction: String,
+    /// `always`, `sometimes` or `request`.
+    pub presence: String,
+    pub caps: String,
+}
+
+/// URI handling capabilities of an element factory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UriHandlerInfo {
+    /// `src` or `sink`.
+    pub uri_type: String,
+    pub protocols: Vec<String>,
+}
+
+/// Description of a property of an element.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyInfo {
+    pub name: String,
+    pub nick: String,
+    pub blurb: Option<String>,
+    pub type_name: String,
+    pub owner_type: String,
+    /// Names of the property flags, e.g. `readable`, `writable` or `controllable`.
+    pub flags: Vec<String>,
+    /// The default value, serialized like in `gst-launch-1.0` pipeline descriptions.
+    ///
+    /// This is `None` if the value can't be serialized, e.g. for object properties.
+    pub default: Option<String>,
+    pub range: Option<PropertyRange>,
+}
+
+impl PropertyInfo {
+    pub fn new(pspec: &glib::ParamSpec) -> Self {
+        PropertyInfo {
+            name: pspec.name().to_owned(),
+            nick: pspec.nick().to_owned(),
+            blurb: pspec.blurb().map(ToOwned::to_owned),
+            type_name: pspec.value_type().name().to_owned(),
+            owner_type: pspec.owner_type().name().to_owned(),
+            flags: param_flags(pspec.flags()),
+            default: pspec.default_value().serialize().ok().map(Into::into),
+            range: PropertyRange::new(pspec),
+        }
+    }
+}
+
+/// Allowed values of a property.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "kebab-case")
+)]
+pub enum PropertyRange {
+    Int { minimum: i64, maximum: i64 },
+    UInt { minimum: u64, maximum: u64 },
+    Float { minimum: f64, maximum: f64 },
+    Fraction { minimum: String, maximum: String },
+    Enum { values: Vec<EnumValueInfo> },
+    Flags { values: Vec<EnumValueInfo> },
+}
+
+impl PropertyRange {
+    /// Returns the allowed values of the property, or `None` if the type has no notion of a
+    /// range.
+    pub fn new(pspec: &glib::ParamSpec) -> Option<Self> {
+        macro_rules! range {
+            ($variant:ident, $($pspec_type:ty),+) => {
+                $(
+                    if let Some(pspec) = pspec.downcast_ref::<$pspec_type>() {
+                        return Some(PropertyRange::$variant {
+                            minimum: pspec.minimum().into(),
+                            maximum: pspec.maximum().into(),
+                        });
+                    }
+                )+
+            };
+        }
+
+        range!(
+            Int,
+            glib::ParamSpecChar,
+            glib::ParamSpecInt,
+            glib::ParamSpecLong,
+            glib::ParamSpecInt64
+        );
+        range!(
+            UInt,
+            glib::ParamSpecUChar,
+            glib::ParamSpecUInt,
+            glib::ParamSpecULong,
+            glib::ParamSpecUInt64
+        );
+        range!(Float, glib::ParamSpecFloat, glib::ParamSpecDouble);
+
+        if let Some(pspec) = pspec.downcast_ref::<gst::ParamSpecFraction>() {
+            return Some(PropertyRange::Fraction {
+                minimum: pspec.minimum().to_string(),
+                maximum: pspec.maximum().to_string(),
+            });
+        }
+
+        if let Some(pspec) = pspec.downcast_ref::<glib::ParamSpecEnum>() {
+            return Some(PropertyRange::Enum {
+                values: pspec
+                    .enum_class()
+                    .values()
+                    .iter()
+                    .map(|value| EnumValueInfo {
+                        value: value.value() as i64,
+                        name: value.name().to_owned(),
+                        nick: value.nick().to_owned(),
+                    })
+                    .collect(),
+            });
+        }
+
+        if let Some(pspec) = pspec.downcast_ref::<glib::ParamSpecFlags>() {
+            return Some(PropertyRange::Flags {
+                values: pspec
+                    .flags_class()
+                    .values()
+                    .iter()
+                    .map(|value| EnumValueInfo {
+                        value: value.value() as i64,
+                        name: value.name().to_owned(),
+                        nick: value.nick().to_owned(),
+                    })
+                    .collect(),
+            });
+        }
+
+        None
+    }
+}
+
+/// A possible value of an enum or flags property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumValueInfo {
+    pub value: i64,
+    pub name: String,
+    pub nick: String,
+}
+
+/// Description of a signal or action signal of an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalInfo {
+    pub name: String,
+    pub owner_type: String,
+    pub return_type: String,
+    pub param_types: Vec<String>,
+    /// Names of the signal flags, e.g. `run-last` or `action`.
+    pub flags: Vec<String>,
+}
+
+fn param_flags(flags: glib::ParamFlags) -> Vec<String> {
+    [
+        (glib::ParamFlags::READABLE, "readable"),
+        (glib::ParamFlags::WRITABLE, "writable"),
+        (glib::ParamFlags::CONSTRUCT, "construct"),
+        (glib::ParamFlags::CONSTRUCT_ONLY, "construct-only"),
+        (glib::ParamFlags::DEPRECATED, "deprecated"),
+        (gst::PARAM_FLAG_CONTROLLABLE, "controllable"),
+        (gst::PARAM_FLAG_MUTABLE_READY, "changeable-in-ready"),
+        (gst::PARAM_FLAG_MUTABLE_PAUSED, "changeable-in-paused"),
+        (gst::PARAM_FLAG_MUTABLE_PLAYING, "changeable-in-playing"),
+        (
+            gst::PARAM_FLAG_CONDITIONALLY_AVAILABLE,
+            "conditionally-available",
+        ),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags.contains(*flag))
+    .map(|(_, name)| name.to_owned())
+    .collect()
+}
+
+// Collects the signals of `type_` and all its parent types below `GObject`.
+fn signals(type_: glib::Type) -> Vec<SignalInfo> {
+    let mut signals = Vec::new();
+
+    for type_ in std::iter::successors(Some(type_), |type_| type_.parent())
+        .take_while(|type_| *type_ != glib::Object::static_type())
+    {
+        unsafe {
+            let mut n_ids = 0;
+            let ids = gobject_ffi::g_signal_list_ids(type_.into_glib(), &mut n_ids);
+            if ids.is_null() {
+                continue;
+            }
+
+            for &id in std::slice::from_raw_parts(ids, n_ids as usize) {
+                let mut query = std::mem::MaybeUninit::zeroed();
+                gobject_ffi::g_signal_query(id, query.as_mut_ptr());
+                let query: gobject_ffi::GSignalQuery = query.assume_init();
+                if query.signal_id == 0 {
+                    continue;
+                }
+
+                let param_types = if query.n_params == 0 {
+                    Vec::new()
+                } else {
+                    std::slice::from_raw_parts(query.param_types, query.n_params as usize)
+                        .iter()
+                        .map(|&param_type| signal_type_name(param_type))
+                        .collect()
+                };
+
+                signals.push(SignalInfo {
+                    name: std::ffi::CStr::from_ptr(query.signal_name)
+                        .to_string_lossy()
+                        .into_owned(),
+                    owner_type: type_.name().to_owned(),
+                    return_type: signal_type_name(query.return_type),
+                    param_types,
+                    flags: signal_flags(query.signal_flags),
+                });
+            }
+
+            glib::ffi::g_free(ids as glib::ffi::gpointer);
+        }
+    }
+
+    signals
+}
+
+fn signal_type_name(type_: glib::ffi::GType) -> String {
+    // Strip the static scope flag that can be set on signal parameter types
+    let type_ = type_ & !gobject_ffi::G_TYPE_FLAG_RESERVED_ID_BIT;
+    unsafe { glib::Type::from_glib(type_).name().to_owned() }
+}
+
+fn signal_flags(flags: gobject_ffi::GSignalFlags) -> Vec<String> {
+    [
+        (gobject_ffi::G_SIGNAL_RUN_FIRST, "run-first"),
+        (gobject_ffi::G_SIGNAL_RUN_LAST, "run-last"),
+        (gobject_ffi::G_SIGNAL_RUN_CLEANUP, "run-cleanup"),
+        (gobject_ffi::G_SIGNAL_NO_RECURSE, "no-recurse"),
+        (gobject_ffi::G_SIGNAL_DETAILED, "detailed"),
+        (gobject_ffi::G_SIGNAL_ACTION, "action"),
+        (gobject_ffi::G_SIGNAL_NO_HOOKS, "no-hooks"),
+        (gobject_ffi::G_SIGNAL_DEPRECATED, "deprecated"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags & flag != 0)
+    .map(|(_, name)| name.to_owned())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_factory_info() {
+        gst::init().unwrap();
+
+        let info = ElementFactoryInfo::from_name("filesrc").unwrap();
+        assert_eq!(info.name, "filesrc");
+        assert_eq!(info.plugin.as_deref(), Some("coreelements"));
+        assert_eq!(info.type_name, "GstFileSrc");
+        assert!(info.type_hierarchy.iter().any(|t| t == "GstElement"));
+        assert!(info.interfaces.iter().any(|i| i == "GstURIHandler"));
+
+        assert_eq!(info.pad_templates.len(), 1);
+        assert_eq!(info.pad_templates[0].name_template, "src");
+        assert_eq!(info.pad_templates[0].direction, "src");
+        assert_eq!(info.pad_templates[0].presence, "always");
+        assert_eq!(info.pad_templates[0].caps, "ANY");
+
+        let uri_handler = info.uri_handler.as_ref().unwrap();
+        assert_eq!(uri_handler.uri_type, "src");
+        assert!(uri_handler.protocols.iter().any(|p| p == "file"));
+
+        let location = info
+            .properties
+            .iter()
+            .find(|p| p.name == "location")
+            .unwrap();
+        assert_eq!(location.type_name, "gchararray");
+        assert!(location.flags.iter().any(|f| f == "writable"));
+        assert!(location.range.is_none());
+
+        let blocksize = info
+            .properties
+            .iter()
+            .find(|p| p.name == "blocksize")
+            .unwrap();
+        assert_eq!(
+            blocksize.range,
+            Some(PropertyRange::UInt {
+                minimum: 1,
+                maximum: u32::MAX as u64
+            })
+        );
+
+        let info = ElementFactoryInfo::from_name("fakesrc").unwrap();
+        assert!(info.signals.iter().any(|s| s.name == "handoff"));
+        assert!(info.actions.is_empty());
+
+        assert!(ElementFactoryInfo::from_name("nonexistent-element").is_err());
+    }
+
+    #[test]
+    fn test_plugin_info() {
+        gst::init().unwrap();
+
+        let plugin = gst::Registry::get().find_plugin("coreelements").unwrap();
+        let info = PluginInfo::new(&plugin);
+        assert_eq!(info.name, "coreelements");
+        assert!(info.features.iter().any(|f| f == "filesrc"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let info = ElementFactoryInfo::from_name("identity").unwrap();
+        let res = serde_json::to_string(&info).unwrap();
+        let info_de: ElementFactoryInfo = serde_json::from_str(&res).unwrap();
+        assert_eq!(info_de, info);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod inspect;
 pub mod streamproducer;
 
 pub use crate::streamproducer::{AddConsumerError, ConsumptionLink, StreamProducer};
@@ -1141,6 +1141,90 @@ impl From<VideoPackFlags> for glib::Value {
     }
 }
 
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[doc(alias = "GstVideoScalerFlags")]
+    pub struct VideoScalerFlags: u32 {
+        #[doc(alias = "GST_VIDEO_SCALER_FLAG_INTERLACED")]
+        const INTERLACED = ffi::GST_VIDEO_SCALER_FLAG_INTERLACED as _;
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for VideoScalerFlags {
+    type GlibType = ffi::GstVideoScalerFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstVideoScalerFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstVideoScalerFlags> for VideoScalerFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstVideoScalerFlags) -> Self {
+        skip_assert_initialized!();
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for VideoScalerFlags {
+    #[inline]
+    #[doc(alias = "gst_video_scaler_flags_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_video_scaler_flags_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for VideoScalerFlags {
+    type ParamSpec = glib::ParamSpecFlags;
+    type SetValue = Self;
+    type BuilderFn = fn(&str) -> glib::ParamSpecFlagsBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder
+    }
+}
+
+impl glib::value::ValueType for VideoScalerFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for VideoScalerFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        unsafe { from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0)) }
+    }
+}
+
+impl ToValue for VideoScalerFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<VideoScalerFlags> for glib::Value {
+    #[inline]
+    fn from(v: VideoScalerFlags) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
+
 bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[doc(alias = "GstVideoTimeCodeFlags")]
@@ -133,6 +133,7 @@ pub use self::flags::VideoFrameFlags;
 pub use self::flags::VideoMultiviewFlags;
 pub use self::flags::VideoOverlayFormatFlags;
 pub use self::flags::VideoPackFlags;
+pub use self::flags::VideoScalerFlags;
 pub use self::flags::VideoTimeCodeFlags;
 
 pub(crate) mod traits {
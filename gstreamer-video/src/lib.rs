@@ -54,6 +54,8 @@ mod video_format_info;
 pub use crate::video_format_info::*;
 mod video_info;
 pub use crate::video_info::*;
+#[cfg(feature = "serde")]
+mod video_info_serde;
 #[cfg(feature = "v1_24")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_24")))]
 mod video_info_dma_drm;
@@ -110,6 +112,8 @@ pub use crate::video_meta::{
 };
 mod video_time_code;
 pub use crate::video_time_code::{ValidVideoTimeCode, VideoTimeCode, VideoTimeCodeMeta};
+#[cfg(all(feature = "serde", feature = "v1_18"))]
+mod video_time_code_serde;
 mod video_time_code_interval;
 pub use crate::video_time_code_interval::VideoTimeCodeInterval;
 mod video_buffer_pool;
@@ -121,6 +125,9 @@ pub use crate::video_buffer_pool::{
 pub mod video_converter;
 pub use crate::video_converter::{VideoConverter, VideoConverterConfig};
 
+mod video_scaler;
+pub use crate::video_scaler::{VideoScaler, VideoScalerConfig};
+
 mod video_codec_frame;
 mod video_decoder;
 mod video_encoder;
@@ -136,6 +143,8 @@ mod video_hdr;
 #[cfg(feature = "v1_18")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_18")))]
 pub use crate::video_hdr::*;
+#[cfg(all(feature = "serde", feature = "v1_18"))]
+mod video_hdr_serde;
 
 mod color_balance_channel;
 
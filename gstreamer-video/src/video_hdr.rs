@@ -322,6 +322,7 @@ impl fmt::Display for VideoMasteringDisplayInfo {
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[doc(alias = "GstVideoMasteringDisplayInfoCoordinates")]
 pub struct VideoMasteringDisplayInfoCoordinate {
     pub x: u16,
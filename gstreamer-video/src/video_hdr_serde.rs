@@ -0,0 +1,143 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{
+    VideoContentLightLevel, VideoMasteringDisplayInfo, VideoMasteringDisplayInfoCoordinate,
+};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VideoContentLightLevelSerde {
+    max_content_light_level: u16,
+    max_frame_average_light_level: u16,
+}
+
+impl Serialize for VideoContentLightLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VideoContentLightLevelSerde {
+            max_content_light_level: self.max_content_light_level(),
+            max_frame_average_light_level: self.max_frame_average_light_level(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoContentLightLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        VideoContentLightLevelSerde::deserialize(deserializer).map(|cll| {
+            VideoContentLightLevel::new(
+                cll.max_content_light_level,
+                cll.max_frame_average_light_level,
+            )
+        })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VideoMasteringDisplayInfoSerde {
+    display_primaries: [VideoMasteringDisplayInfoCoordinate; 3],
+    white_point: VideoMasteringDisplayInfoCoordinate,
+    max_display_mastering_luminance: u32,
+    min_display_mastering_luminance: u32,
+}
+
+impl Serialize for VideoMasteringDisplayInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VideoMasteringDisplayInfoSerde {
+            display_primaries: self.display_primaries(),
+            white_point: self.white_point(),
+            max_display_mastering_luminance: self.max_display_mastering_luminance(),
+            min_display_mastering_luminance: self.min_display_mastering_luminance(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoMasteringDisplayInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        VideoMasteringDisplayInfoSerde::deserialize(deserializer).map(|mdi| {
+            VideoMasteringDisplayInfo::new(
+                mdi.display_primaries,
+                mdi.white_point,
+                mdi.max_display_mastering_luminance,
+                mdi.min_display_mastering_luminance,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        VideoContentLightLevel, VideoMasteringDisplayInfo, VideoMasteringDisplayInfoCoordinate,
+    };
+
+    fn mastering_display_info() -> VideoMasteringDisplayInfo {
+        VideoMasteringDisplayInfo::new(
+            [
+                VideoMasteringDisplayInfoCoordinate { x: 35400, y: 14600 },
+                VideoMasteringDisplayInfoCoordinate { x: 8500, y: 39850 },
+                VideoMasteringDisplayInfoCoordinate { x: 6550, y: 2300 },
+            ],
+            VideoMasteringDisplayInfoCoordinate { x: 15635, y: 16450 },
+            10_000_000,
+            1,
+        )
+    }
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let cll = VideoContentLightLevel::new(1000, 400);
+        let res = serde_json::to_string(&cll).unwrap();
+        assert_eq!(
+            res,
+            "{\"max_content_light_level\":1000,\"max_frame_average_light_level\":400}"
+        );
+
+        let mdi = mastering_display_info();
+        let res = serde_json::to_string(&mdi).unwrap();
+        assert_eq!(
+            res,
+            concat!(
+                "{",
+                "\"display_primaries\":[",
+                "{\"x\":35400,\"y\":14600},",
+                "{\"x\":8500,\"y\":39850},",
+                "{\"x\":6550,\"y\":2300}",
+                "],",
+                "\"white_point\":{\"x\":15635,\"y\":16450},",
+                "\"max_display_mastering_luminance\":10000000,",
+                "\"min_display_mastering_luminance\":1",
+                "}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_caps_roundtrip() {
+        gst::init().unwrap();
+
+        let mut caps = gst::Caps::new_empty_simple("video/x-raw");
+        let cll = VideoContentLightLevel::new(1000, 400);
+        let mdi = mastering_display_info();
+        cll.add_to_caps(caps.get_mut().unwrap());
+        mdi.add_to_caps(caps.get_mut().unwrap());
+
+        let cll_de: VideoContentLightLevel =
+            serde_json::from_str(&serde_json::to_string(&cll).unwrap()).unwrap();
+        let mdi_de: VideoMasteringDisplayInfo =
+            serde_json::from_str(&serde_json::to_string(&mdi).unwrap()).unwrap();
+
+        let mut caps_de = gst::Caps::new_empty_simple("video/x-raw");
+        cll_de.add_to_caps(caps_de.get_mut().unwrap());
+        mdi_de.add_to_caps(caps_de.get_mut().unwrap());
+        assert_eq!(caps, caps_de);
+    }
+}
@@ -0,0 +1,173 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{
+    VideoChromaSite, VideoColorimetry, VideoFieldOrder, VideoFlags, VideoFormat, VideoInfo,
+    VideoInterlaceMode, VideoMultiviewFlags, VideoMultiviewMode,
+};
+
+// Colorimetry, formats and modes use the same strings as in the caps so that a `VideoInfo`
+// serialized from caps converts back to the very same caps.
+impl Serialize for VideoColorimetry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoColorimetry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let colorimetry = String::deserialize(deserializer)?;
+        colorimetry.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VideoInfoSerde {
+    format: String,
+    width: u32,
+    height: u32,
+    interlace_mode: String,
+    flags: VideoFlags,
+    views: u32,
+    chroma_site: VideoChromaSite,
+    colorimetry: VideoColorimetry,
+    par: gst::Fraction,
+    fps: gst::Fraction,
+    multiview_mode: Option<String>,
+    multiview_flags: VideoMultiviewFlags,
+    field_order: Option<String>,
+}
+
+impl Serialize for VideoInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VideoInfoSerde {
+            format: self.format().to_str().to_string(),
+            width: self.width(),
+            height: self.height(),
+            interlace_mode: self.interlace_mode().to_str().to_string(),
+            flags: self.flags(),
+            views: self.views(),
+            chroma_site: self.chroma_site(),
+            colorimetry: self.colorimetry(),
+            par: self.par(),
+            fps: self.fps(),
+            multiview_mode: self.multiview_mode().to_caps_string().map(Into::into),
+            multiview_flags: self.multiview_flags(),
+            field_order: match self.field_order() {
+                VideoFieldOrder::Unknown => None,
+                field_order => Some(field_order.to_str().to_owned()),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let info = VideoInfoSerde::deserialize(deserializer)?;
+
+        let format = info
+            .format
+            .parse::<VideoFormat>()
+            .map_err(de::Error::custom)?;
+        let interlace_mode = info
+            .interlace_mode
+            .parse::<VideoInterlaceMode>()
+            .map_err(de::Error::custom)?;
+        let multiview_mode = match info.multiview_mode {
+            None => VideoMultiviewMode::None,
+            Some(ref multiview_mode) => {
+                match VideoMultiviewMode::from_caps_string(multiview_mode) {
+                    VideoMultiviewMode::None => {
+                        return Err(de::Error::custom(format!(
+                            "invalid multiview mode {multiview_mode}"
+                        )));
+                    }
+                    multiview_mode => multiview_mode,
+                }
+            }
+        };
+        let field_order = info
+            .field_order
+            .as_deref()
+            .map(str::parse::<VideoFieldOrder>)
+            .transpose()
+            .map_err(de::Error::custom)?
+            .unwrap_or(VideoFieldOrder::Unknown);
+
+        VideoInfo::builder(format, info.width, info.height)
+            .interlace_mode(interlace_mode)
+            .flags(info.flags)
+            .views(info.views)
+            .chroma_site(info.chroma_site)
+            .colorimetry(&info.colorimetry)
+            .par(info.par)
+            .fps(info.fps)
+            .multiview_mode(multiview_mode)
+            .multiview_flags(info.multiview_flags)
+            .field_order(field_order)
+            .build()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{VideoColorimetry, VideoFormat, VideoInfo, VideoInterlaceMode};
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let info = VideoInfo::builder(VideoFormat::I420, 320, 240)
+            .fps(gst::Fraction::new(30, 1))
+            .build()
+            .unwrap();
+
+        let res = serde_json::to_value(&info).unwrap();
+        assert_eq!(res["format"], "I420");
+        assert_eq!(res["width"], 320);
+        assert_eq!(res["height"], 240);
+        assert_eq!(res["interlace_mode"], "progressive");
+        assert_eq!(res["views"], 1);
+        assert_eq!(res["colorimetry"], info.colorimetry().to_string().as_str());
+        assert_eq!(res["multiview_mode"], serde_json::Value::Null);
+        assert_eq!(res["field_order"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_caps_roundtrip() {
+        gst::init().unwrap();
+
+        let caps = gst::Caps::builder("video/x-raw")
+            .field("format", "NV12")
+            .field("width", 1920i32)
+            .field("height", 1080i32)
+            .field("framerate", gst::Fraction::new(30000, 1001))
+            .field("pixel-aspect-ratio", gst::Fraction::new(4, 3))
+            .field("interlace-mode", "interleaved")
+            .field("field-order", "top-field-first")
+            .field("colorimetry", "bt709")
+            .field("chroma-site", "mpeg2")
+            .build();
+        let info = VideoInfo::from_caps(&caps).unwrap();
+        assert_eq!(info.interlace_mode(), VideoInterlaceMode::Interleaved);
+
+        let res = serde_json::to_string(&info).unwrap();
+        let info_de: VideoInfo = serde_json::from_str(&res).unwrap();
+        assert_eq!(info_de, info);
+        assert_eq!(info_de.to_caps().unwrap(), info.to_caps().unwrap());
+
+        let colorimetry: VideoColorimetry = serde_json::from_str("\"bt2100-pq\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&colorimetry).unwrap(),
+            "\"bt2100-pq\""
+        );
+    }
+}
@@ -0,0 +1,685 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ops, ptr, slice};
+
+use glib::translate::*;
+
+use crate::{VideoFormat, VideoResamplerMethod, VideoScalerFlags, ffi};
+
+#[derive(Debug)]
+#[doc(alias = "GstVideoScaler")]
+pub struct VideoScaler {
+    ptr: ptr::NonNull<ffi::GstVideoScaler>,
+    flags: VideoScalerFlags,
+    in_size: u32,
+    out_size: u32,
+}
+
+impl Drop for VideoScaler {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_video_scaler_free(self.ptr.as_ptr());
+        }
+    }
+}
+
+// The scaler keeps temporary lines internally, so all scaling requires mutable access
+unsafe impl Send for VideoScaler {}
+unsafe impl Sync for VideoScaler {}
+
+// Size in bytes of one element as processed by the scaler for the given format,
+// or `None` if the scaler can't handle the format.
+fn pixel_stride(format: VideoFormat) -> Option<usize> {
+    skip_assert_initialized!();
+
+    use VideoFormat::*;
+
+    match format {
+        Gray8 => Some(1),
+        Yuy2 | Yvyu | Uyvy => Some(2),
+        Gray16Le | Gray16Be => Some(2),
+        Nv12 | Nv21 | Nv16 | Nv61 | Nv24 => Some(2),
+        Rgb | Bgr | V308 | Iyu2 => Some(3),
+        Ayuv | Rgbx | Bgrx | Xrgb | Xbgr | Rgba | Bgra | Argb | Abgr => Some(4),
+        Argb64 | Ayuv64 => Some(8),
+        _ => None,
+    }
+}
+
+impl VideoScaler {
+    // rustdoc-stripper-ignore-next
+    /// Creates a scaler from `in_size` to `out_size` pixels with `n_taps` taps.
+    ///
+    /// If `n_taps` is 0 a default for `method` is used.
+    #[doc(alias = "gst_video_scaler_new")]
+    pub fn new(
+        method: VideoResamplerMethod,
+        flags: VideoScalerFlags,
+        n_taps: u32,
+        in_size: u32,
+        out_size: u32,
+        options: Option<&VideoScalerConfig>,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+
+        if in_size == 0 || out_size == 0 {
+            return Err(glib::bool_error!("Invalid scaler sizes"));
+        }
+
+        unsafe {
+            let ptr = ffi::gst_video_scaler_new(
+                method.into_glib(),
+                flags.into_glib(),
+                n_taps,
+                in_size,
+                out_size,
+                options
+                    .map(|options| options.0.as_ptr() as *mut _)
+                    .unwrap_or(ptr::null_mut()),
+            );
+            if ptr.is_null() {
+                Err(glib::bool_error!("Failed to create video scaler"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    flags,
+                    in_size,
+                    out_size,
+                })
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Combines a luma and a chroma scaler into a scaler for the packed YUV `out_format`.
+    #[doc(alias = "gst_video_scaler_combine_packed_YUV")]
+    pub fn combine_packed_yuv(
+        y_scale: &VideoScaler,
+        uv_scale: &VideoScaler,
+        in_format: VideoFormat,
+        out_format: VideoFormat,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        unsafe {
+            let ptr = ffi::gst_video_scaler_combine_packed_YUV(
+                y_scale.ptr.as_ptr(),
+                uv_scale.ptr.as_ptr(),
+                in_format.into_glib(),
+                out_format.into_glib(),
+            );
+            if ptr.is_null() {
+                Err(glib::bool_error!("Failed to combine video scalers"))
+            } else {
+                Ok(Self {
+                    ptr: ptr::NonNull::new_unchecked(ptr),
+                    flags: y_scale.flags,
+                    in_size: y_scale.in_size,
+                    out_size: y_scale.out_size,
+                })
+            }
+        }
+    }
+
+    #[inline]
+    pub fn flags(&self) -> VideoScalerFlags {
+        self.flags
+    }
+
+    #[inline]
+    pub fn in_size(&self) -> u32 {
+        self.in_size
+    }
+
+    #[inline]
+    pub fn out_size(&self) -> u32 {
+        self.out_size
+    }
+
+    #[doc(alias = "get_max_taps")]
+    #[doc(alias = "gst_video_scaler_get_max_taps")]
+    pub fn max_taps(&self) -> u32 {
+        unsafe { ffi::gst_video_scaler_get_max_taps(self.ptr.as_ptr()) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the first input pixel and the filter taps used for the output pixel at
+    /// `out_offset`, or `None` if `out_offset` is out of range.
+    #[doc(alias = "get_coeff")]
+    #[doc(alias = "gst_video_scaler_get_coeff")]
+    pub fn coeff(&self, out_offset: u32) -> Option<(u32, &[f64])> {
+        if out_offset >= self.out_size {
+            return None;
+        }
+
+        unsafe {
+            let mut in_offset = 0;
+            let mut n_taps = 0;
+            let taps = ffi::gst_video_scaler_get_coeff(
+                self.ptr.as_ptr(),
+                out_offset,
+                &mut in_offset,
+                &mut n_taps,
+            );
+            if taps.is_null() {
+                None
+            } else {
+                Some((in_offset, slice::from_raw_parts(taps, n_taps as usize)))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Scales the line `src` horizontally into the pixels `dest_offset..dest_offset + width`
+    /// of the line `dest`.
+    #[doc(alias = "gst_video_scaler_horizontal")]
+    pub fn horizontal(
+        &mut self,
+        format: VideoFormat,
+        src: &[u8],
+        dest: &mut [u8],
+        dest_offset: u32,
+        width: u32,
+    ) -> Result<(), glib::BoolError> {
+        let pstride = pixel_stride(format)
+            .ok_or_else(|| glib::bool_error!("Unsupported format {}", format))?;
+
+        if dest_offset as u64 + width as u64 > self.out_size as u64 {
+            return Err(glib::bool_error!("Output region out of range"));
+        }
+        if src.len() < self.in_size as usize * pstride {
+            return Err(glib::bool_error!("Source line too small"));
+        }
+        if dest.len() < (dest_offset + width) as usize * pstride {
+            return Err(glib::bool_error!("Destination line too small"));
+        }
+
+        unsafe {
+            ffi::gst_video_scaler_horizontal(
+                self.ptr.as_ptr(),
+                format.into_glib(),
+                src.as_ptr() as *mut _,
+                dest.as_mut_ptr() as *mut _,
+                dest_offset,
+                width,
+            );
+        }
+
+        Ok(())
+    }
+
+    // Number of input lines `gst_video_scaler_vertical()` accesses. Interlaced scalers only
+    // use every second line to stay within the same field.
+    fn n_src_lines(&self) -> usize {
+        let max_taps = self.max_taps() as usize;
+        if self.flags.contains(VideoScalerFlags::INTERLACED) {
+            max_taps.saturating_sub(1) * 2 + 1
+        } else {
+            max_taps
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Scales vertically into the output line `dest_offset`.
+    ///
+    /// `src_lines` are the [`max_taps()`](Self::max_taps) input lines starting at the
+    /// input offset returned by [`coeff()`](Self::coeff) for `dest_offset`, each of at
+    /// least `width` pixels. For scalers created with [`VideoScalerFlags::INTERLACED`] only
+    /// every second line is used, so `(max_taps() - 1) * 2 + 1` lines are needed.
+    #[doc(alias = "gst_video_scaler_vertical")]
+    pub fn vertical(
+        &mut self,
+        format: VideoFormat,
+        src_lines: &[&[u8]],
+        dest: &mut [u8],
+        dest_offset: u32,
+        width: u32,
+    ) -> Result<(), glib::BoolError> {
+        let pstride = pixel_stride(format)
+            .ok_or_else(|| glib::bool_error!("Unsupported format {}", format))?;
+
+        if dest_offset >= self.out_size {
+            return Err(glib::bool_error!("Output line out of range"));
+        }
+        let n_src_lines = self.n_src_lines();
+        if src_lines.len() < n_src_lines {
+            return Err(glib::bool_error!(
+                "Need {} source lines but got {}",
+                n_src_lines,
+                src_lines.len()
+            ));
+        }
+        let line_size = width as usize * pstride;
+        if src_lines.iter().any(|line| line.len() < line_size) {
+            return Err(glib::bool_error!("Source line too small"));
+        }
+        if dest.len() < line_size {
+            return Err(glib::bool_error!("Destination line too small"));
+        }
+
+        let mut lines = src_lines
+            .iter()
+            .map(|line| line.as_ptr() as glib::ffi::gpointer)
+            .collect::<Vec<_>>();
+
+        unsafe {
+            ffi::gst_video_scaler_vertical(
+                self.ptr.as_ptr(),
+                format.into_glib(),
+                lines.as_mut_ptr(),
+                dest.as_mut_ptr() as *mut _,
+                dest_offset,
+                width,
+            );
+        }
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Scales the plane `src` into the rectangle `x`, `y`, `width`, `height` of the plane `dest`.
+    ///
+    /// If `hscale` or `vscale` is `None` the plane is not scaled in that direction.
+    #[doc(alias = "gst_video_scaler_2d")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn scale(
+        mut hscale: Option<&mut VideoScaler>,
+        mut vscale: Option<&mut VideoScaler>,
+        format: VideoFormat,
+        src: &[u8],
+        src_stride: usize,
+        dest: &mut [u8],
+        dest_stride: usize,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), glib::BoolError> {
+        skip_assert_initialized!();
+
+        let pstride = pixel_stride(format)
+            .ok_or_else(|| glib::bool_error!("Unsupported format {}", format))?;
+
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let (in_width, out_width) = hscale
+            .as_ref()
+            .map(|s| (s.in_size, s.out_size))
+            .unwrap_or((x + width, x + width));
+        let (in_height, out_height) = vscale
+            .as_ref()
+            .map(|s| (s.in_size, s.out_size))
+            .unwrap_or((y + height, y + height));
+
+        if x as u64 + width as u64 > out_width as u64
+            || y as u64 + height as u64 > out_height as u64
+        {
+            return Err(glib::bool_error!("Output rectangle out of range"));
+        }
+        if src_stride > i32::MAX as usize || dest_stride > i32::MAX as usize {
+            return Err(glib::bool_error!("Stride too large"));
+        }
+        if src_stride < in_width as usize * pstride || dest_stride < out_width as usize * pstride {
+            return Err(glib::bool_error!("Stride too small"));
+        }
+        if src.len() < (in_height as usize - 1) * src_stride + in_width as usize * pstride {
+            return Err(glib::bool_error!("Source plane too small"));
+        }
+        if dest.len() < (y + height - 1) as usize * dest_stride + (x + width) as usize * pstride {
+            return Err(glib::bool_error!("Destination plane too small"));
+        }
+
+        unsafe {
+            ffi::gst_video_scaler_2d(
+                hscale
+                    .as_mut()
+                    .map(|s| s.ptr.as_ptr())
+                    .unwrap_or(ptr::null_mut()),
+                vscale
+                    .as_mut()
+                    .map(|s| s.ptr.as_ptr())
+                    .unwrap_or(ptr::null_mut()),
+                format.into_glib(),
+                src.as_ptr() as *mut _,
+                src_stride as i32,
+                dest.as_mut_ptr() as *mut _,
+                dest_stride as i32,
+                x,
+                y,
+                width,
+                height,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoScalerConfig(gst::Structure);
+
+impl ops::Deref for VideoScalerConfig {
+    type Target = gst::StructureRef;
+
+    #[inline]
+    fn deref(&self) -> &gst::StructureRef {
+        self.0.deref()
+    }
+}
+
+impl ops::DerefMut for VideoScalerConfig {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut gst::StructureRef {
+        self.0.deref_mut()
+    }
+}
+
+impl AsRef<gst::StructureRef> for VideoScalerConfig {
+    #[inline]
+    fn as_ref(&self) -> &gst::StructureRef {
+        self.0.as_ref()
+    }
+}
+
+impl AsMut<gst::StructureRef> for VideoScalerConfig {
+    #[inline]
+    fn as_mut(&mut self) -> &mut gst::StructureRef {
+        self.0.as_mut()
+    }
+}
+
+impl Default for VideoScalerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<gst::Structure> for VideoScalerConfig {
+    type Error = glib::BoolError;
+
+    fn try_from(v: gst::Structure) -> Result<Self, Self::Error> {
+        skip_assert_initialized!();
+        if v.name() == "GstVideoScaler" {
+            Ok(Self(v))
+        } else {
+            Err(glib::bool_error!("Structure is no VideoScalerConfig"))
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a gst::StructureRef> for VideoScalerConfig {
+    type Error = glib::BoolError;
+
+    fn try_from(v: &'a gst::StructureRef) -> Result<Self, Self::Error> {
+        skip_assert_initialized!();
+        Self::try_from(v.to_owned())
+    }
+}
+
+impl From<VideoScalerConfig> for gst::Structure {
+    fn from(v: VideoScalerConfig) -> Self {
+        skip_assert_initialized!();
+        v.0
+    }
+}
+
+impl VideoScalerConfig {
+    pub fn new() -> Self {
+        Self(gst::Structure::new_empty("GstVideoScaler"))
+    }
+
+    pub fn set_cubic_b(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstVideoResampler.cubic-b"), v);
+    }
+
+    #[doc(alias = "get_cubic_b")]
+    pub fn cubic_b(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.cubic-b"))
+            .expect("Wrong type")
+            .unwrap_or(1.0 / 3.0)
+    }
+
+    pub fn set_cubic_c(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstVideoResampler.cubic-c"), v);
+    }
+
+    #[doc(alias = "get_cubic_c")]
+    pub fn cubic_c(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.cubic-c"))
+            .expect("Wrong type")
+            .unwrap_or(1.0 / 3.0)
+    }
+
+    pub fn set_envelope(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstVideoResampler.envelope"), v);
+    }
+
+    #[doc(alias = "get_envelope")]
+    pub fn envelope(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.envelope"))
+            .expect("Wrong type")
+            .unwrap_or(2.0)
+    }
+
+    pub fn set_max_taps(&mut self, v: i32) {
+        self.0.set(glib::gstr!("GstVideoResampler.max-taps"), v);
+    }
+
+    #[doc(alias = "get_max_taps")]
+    pub fn max_taps(&self) -> i32 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.max-taps"))
+            .expect("Wrong type")
+            .unwrap_or(128)
+    }
+
+    pub fn set_sharpen(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstVideoResampler.sharpen"), v);
+    }
+
+    #[doc(alias = "get_sharpen")]
+    pub fn sharpen(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.sharpen"))
+            .expect("Wrong type")
+            .unwrap_or(0.0)
+    }
+
+    pub fn set_sharpness(&mut self, v: f64) {
+        self.0.set(glib::gstr!("GstVideoResampler.sharpness"), v);
+    }
+
+    #[doc(alias = "get_sharpness")]
+    pub fn sharpness(&self) -> f64 {
+        self.0
+            .get_optional(glib::gstr!("GstVideoResampler.sharpness"))
+            .expect("Wrong type")
+            .unwrap_or(1.0)
+    }
+
+    pub fn set_dither_method(&mut self, v: crate::VideoDitherMethod) {
+        self.0.set(glib::gstr!("GstVideoScaler.dither-method"), v);
+    }
+
+    #[doc(alias = "get_dither_method")]
+    pub fn dither_method(&self) -> crate::VideoDitherMethod {
+        self.0
+            .get_optional(glib::gstr!("GstVideoScaler.dither-method"))
+            .expect("Wrong type")
+            .unwrap_or(crate::VideoDitherMethod::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coeff() {
+        gst::init().unwrap();
+
+        let scaler = VideoScaler::new(
+            VideoResamplerMethod::Linear,
+            VideoScalerFlags::empty(),
+            0,
+            8,
+            4,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scaler.in_size(), 8);
+        assert_eq!(scaler.out_size(), 4);
+        assert!(scaler.max_taps() > 0);
+
+        for i in 0..4 {
+            let (in_offset, taps) = scaler.coeff(i).unwrap();
+            assert!(in_offset < 8);
+            assert_eq!(taps.len(), scaler.max_taps() as usize);
+            assert!((taps.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        }
+        assert!(scaler.coeff(4).is_none());
+    }
+
+    #[test]
+    fn test_horizontal_vertical() {
+        gst::init().unwrap();
+
+        let mut config = VideoScalerConfig::new();
+        config.set_sharpness(1.0);
+        let mut hscale = VideoScaler::new(
+            VideoResamplerMethod::Cubic,
+            VideoScalerFlags::empty(),
+            0,
+            16,
+            8,
+            Some(&config),
+        )
+        .unwrap();
+
+        let src = [128u8; 16];
+        let mut dest = [0u8; 8];
+        hscale
+            .horizontal(VideoFormat::Gray8, &src, &mut dest, 0, 8)
+            .unwrap();
+        assert_eq!(dest, [128u8; 8]);
+        assert!(
+            hscale
+                .horizontal(VideoFormat::Gray8, &src, &mut dest, 4, 8)
+                .is_err()
+        );
+        assert!(
+            hscale
+                .horizontal(VideoFormat::I420, &src, &mut dest, 0, 8)
+                .is_err()
+        );
+
+        let mut vscale = VideoScaler::new(
+            VideoResamplerMethod::Linear,
+            VideoScalerFlags::empty(),
+            0,
+            4,
+            2,
+            None,
+        )
+        .unwrap();
+        let line = [64u8; 8];
+        let lines = vec![&line[..]; vscale.max_taps() as usize];
+        let mut dest = [0u8; 8];
+        vscale
+            .vertical(VideoFormat::Gray8, &lines, &mut dest, 1, 8)
+            .unwrap();
+        assert_eq!(dest, [64u8; 8]);
+
+        let mut vscale = VideoScaler::new(
+            VideoResamplerMethod::Linear,
+            VideoScalerFlags::INTERLACED,
+            0,
+            8,
+            4,
+            None,
+        )
+        .unwrap();
+        let max_taps = vscale.max_taps() as usize;
+        let lines = vec![&line[..]; max_taps];
+        if max_taps > 1 {
+            assert!(
+                vscale
+                    .vertical(VideoFormat::Gray8, &lines, &mut dest, 1, 8)
+                    .is_err()
+            );
+        }
+        let lines = vec![&line[..]; (max_taps - 1) * 2 + 1];
+        vscale
+            .vertical(VideoFormat::Gray8, &lines, &mut dest, 1, 8)
+            .unwrap();
+        assert_eq!(dest, [64u8; 8]);
+    }
+
+    #[test]
+    fn test_scale() {
+        gst::init().unwrap();
+
+        // Nearest neighbour without size change is an identity copy
+        let mut hscale = VideoScaler::new(
+            VideoResamplerMethod::Nearest,
+            VideoScalerFlags::empty(),
+            0,
+            4,
+            4,
+            None,
+        )
+        .unwrap();
+        let mut vscale = VideoScaler::new(
+            VideoResamplerMethod::Nearest,
+            VideoScalerFlags::empty(),
+            0,
+            4,
+            4,
+            None,
+        )
+        .unwrap();
+
+        let src = (0..32u8).collect::<Vec<_>>();
+        let mut dest = vec![0u8; 4 * 6];
+        VideoScaler::scale(
+            Some(&mut hscale),
+            Some(&mut vscale),
+            VideoFormat::Gray8,
+            &src,
+            8,
+            &mut dest,
+            6,
+            0,
+            0,
+            4,
+            4,
+        )
+        .unwrap();
+        for y in 0..4 {
+            assert_eq!(dest[y * 6..y * 6 + 4], src[y * 8..y * 8 + 4]);
+        }
+
+        assert!(
+            VideoScaler::scale(
+                Some(&mut hscale),
+                None,
+                VideoFormat::Gray8,
+                &src,
+                8,
+                &mut dest,
+                6,
+                2,
+                0,
+                4,
+                4,
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{ValidVideoTimeCode, VideoTimeCode, VideoTimeCodeFlags};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VideoTimeCodeSerde {
+    fps: gst::Fraction,
+    latest_daily_jam: Option<gst::DateTime>,
+    flags: VideoTimeCodeFlags,
+    hours: u32,
+    minutes: u32,
+    seconds: u32,
+    frames: u32,
+    field_count: u32,
+}
+
+macro_rules! impl_serialize {
+    ($name:ident) => {
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                VideoTimeCodeSerde {
+                    fps: self.fps(),
+                    latest_daily_jam: self.latest_daily_jam().map(gst::DateTime::from),
+                    flags: self.flags(),
+                    hours: self.hours(),
+                    minutes: self.minutes(),
+                    seconds: self.seconds(),
+                    frames: self.frames(),
+                    field_count: self.field_count(),
+                }
+                .serialize(serializer)
+            }
+        }
+    };
+}
+
+impl_serialize!(VideoTimeCode);
+impl_serialize!(ValidVideoTimeCode);
+
+impl<'de> Deserialize<'de> for VideoTimeCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let tc = VideoTimeCodeSerde::deserialize(deserializer)?;
+
+        let latest_daily_jam = tc
+            .latest_daily_jam
+            .map(glib::DateTime::try_from)
+            .transpose()
+            .map_err(de::Error::custom)?;
+
+        Ok(VideoTimeCode::new(
+            tc.fps,
+            latest_daily_jam.as_ref(),
+            tc.flags,
+            tc.hours,
+            tc.minutes,
+            tc.seconds,
+            tc.frames,
+            tc.field_count,
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for ValidVideoTimeCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let tc = VideoTimeCode::deserialize(deserializer)?;
+        ValidVideoTimeCode::try_from(tc)
+            .map_err(|tc| de::Error::custom(format!("invalid time code {tc}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ValidVideoTimeCode, VideoTimeCode, VideoTimeCodeFlags};
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let tc = ValidVideoTimeCode::new(
+            gst::Fraction::new(30000, 1001),
+            None,
+            VideoTimeCodeFlags::DROP_FRAME,
+            10,
+            20,
+            30,
+            2,
+            0,
+        )
+        .unwrap();
+
+        let res = serde_json::to_string(&tc).unwrap();
+        assert_eq!(
+            res,
+            concat!(
+                "{",
+                "\"fps\":[30000,1001],",
+                "\"latest_daily_jam\":null,",
+                "\"flags\":\"drop-frame\",",
+                "\"hours\":10,",
+                "\"minutes\":20,",
+                "\"seconds\":30,",
+                "\"frames\":2,",
+                "\"field_count\":0",
+                "}"
+            )
+        );
+
+        let tc_de: ValidVideoTimeCode = serde_json::from_str(&res).unwrap();
+        assert_eq!(tc_de, tc);
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        gst::init().unwrap();
+
+        let json = concat!(
+            "{",
+            "\"fps\":[25,1],",
+            "\"latest_daily_jam\":null,",
+            "\"flags\":\"\",",
+            "\"hours\":10,",
+            "\"minutes\":20,",
+            "\"seconds\":30,",
+            "\"frames\":42,",
+            "\"field_count\":0",
+            "}"
+        );
+
+        let tc: VideoTimeCode = serde_json::from_str(json).unwrap();
+        assert!(!tc.is_valid());
+        assert!(serde_json::from_str::<ValidVideoTimeCode>(json).is_err());
+    }
+}
@@ -29,6 +29,8 @@ mod web_rtc_ice_candidate_pair;
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_22")))]
 mod web_rtc_ice_candidate_stats;
 mod web_rtc_session_description;
+mod web_rtc_stats;
+pub use crate::web_rtc_stats::*;
 #[cfg(feature = "v1_22")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_22")))]
 mod web_rtcice;
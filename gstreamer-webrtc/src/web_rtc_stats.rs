@@ -0,0 +1,704 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Typed representation of the statistics returned by webrtcbin's `get-stats`
+//! action signal.
+//!
+//! ```no_run
+//! # use gstreamer_webrtc::WebRTCStatsReport;
+//! # gst::init().unwrap();
+//! # let webrtcbin = gst::ElementFactory::make("webrtcbin").build().unwrap();
+//! use gst::prelude::*;
+//!
+//! let promise = gst::Promise::with_change_func(|reply| {
+//!     let report = WebRTCStatsReport::from_promise_reply(reply).unwrap();
+//!     for inbound in report.inbound_rtp() {
+//!         println!("{:?} received {:?} packets", inbound.rtp.ssrc, inbound.packets_received);
+//!     }
+//! });
+//! webrtcbin.emit_by_name::<()>("get-stats", &[&None::<gst::Pad>, &promise]);
+//! ```
+
+use std::collections::{BTreeMap, btree_map};
+
+use crate::{WebRTCDTLSTransportState, WebRTCDataChannelState, WebRTCStatsType};
+
+// rustdoc-stripper-ignore-next
+/// Statistics of all objects of a peer connection, keyed by their ID.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct WebRTCStatsReport {
+    stats: BTreeMap<String, WebRTCStats>,
+}
+
+impl WebRTCStatsReport {
+    // rustdoc-stripper-ignore-next
+    /// Parses the structure returned by webrtcbin's `get-stats` signal.
+    ///
+    /// Each field of `s` is expected to hold a structure with the
+    /// statistics of one object. Fields not holding a structure are skipped.
+    pub fn from_structure(s: &gst::StructureRef) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut stats = BTreeMap::new();
+        for (id, value) in s.iter() {
+            let Ok(entry) = value.get::<gst::Structure>() else {
+                continue;
+            };
+            stats.insert(id.to_string(), WebRTCStats::from_structure(&entry)?);
+        }
+
+        Ok(Self { stats })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the reply of a [`gst::Promise`] passed to webrtcbin's
+    /// `get-stats` signal.
+    pub fn from_promise_reply(
+        reply: Result<Option<&gst::StructureRef>, gst::PromiseError>,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        match reply {
+            Ok(Some(s)) => Self::from_structure(s),
+            Ok(None) => Err(glib::bool_error!("Promise replied without statistics")),
+            Err(err) => Err(glib::bool_error!("Promise failed: {:?}", err)),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&WebRTCStats> {
+        self.stats.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, String, WebRTCStats> {
+        self.stats.iter()
+    }
+
+    pub fn codecs(&self) -> impl Iterator<Item = &WebRTCCodecStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::Codec(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn inbound_rtp(&self) -> impl Iterator<Item = &WebRTCInboundRtpStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::InboundRtp(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn outbound_rtp(&self) -> impl Iterator<Item = &WebRTCOutboundRtpStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::OutboundRtp(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn remote_inbound_rtp(&self) -> impl Iterator<Item = &WebRTCRemoteInboundRtpStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::RemoteInboundRtp(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn transports(&self) -> impl Iterator<Item = &WebRTCTransportStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::Transport(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn candidate_pairs(&self) -> impl Iterator<Item = &WebRTCCandidatePairStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::CandidatePair(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn data_channels(&self) -> impl Iterator<Item = &WebRTCDataChannelStats> {
+        self.stats.values().filter_map(|stats| match stats {
+            WebRTCStats::DataChannel(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    pub fn peer_connection(&self) -> Option<&WebRTCPeerConnectionStats> {
+        self.stats.values().find_map(|stats| match stats {
+            WebRTCStats::PeerConnection(stats) => Some(stats),
+            _ => None,
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a WebRTCStatsReport {
+    type Item = (&'a String, &'a WebRTCStats);
+    type IntoIter = btree_map::Iter<'a, String, WebRTCStats>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Statistics of a single object, named after the W3C `RTCStatsType`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "kebab-case"))]
+#[non_exhaustive]
+pub enum WebRTCStats {
+    Codec(WebRTCCodecStats),
+    InboundRtp(WebRTCInboundRtpStats),
+    OutboundRtp(WebRTCOutboundRtpStats),
+    RemoteInboundRtp(WebRTCRemoteInboundRtpStats),
+    Transport(WebRTCTransportStats),
+    CandidatePair(WebRTCCandidatePairStats),
+    DataChannel(WebRTCDataChannelStats),
+    PeerConnection(WebRTCPeerConnectionStats),
+    // rustdoc-stripper-ignore-next
+    /// Statistics of a type without typed representation.
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(WebRTCOtherStats),
+}
+
+impl WebRTCStats {
+    pub fn from_structure(s: &gst::StructureRef) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let common = WebRTCStatsCommon::from_structure(s)?;
+        let stats = match stats_type(s) {
+            Some(WebRTCStatsType::Codec) => Self::Codec(WebRTCCodecStats::parse(common, s)),
+            Some(WebRTCStatsType::InboundRtp) => {
+                Self::InboundRtp(WebRTCInboundRtpStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::OutboundRtp) => {
+                Self::OutboundRtp(WebRTCOutboundRtpStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::RemoteInboundRtp) => {
+                Self::RemoteInboundRtp(WebRTCRemoteInboundRtpStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::Transport) => {
+                Self::Transport(WebRTCTransportStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::CandidatePair) => {
+                Self::CandidatePair(WebRTCCandidatePairStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::DataChannel) => {
+                Self::DataChannel(WebRTCDataChannelStats::parse(common, s))
+            }
+            Some(WebRTCStatsType::PeerConnection) => {
+                Self::PeerConnection(WebRTCPeerConnectionStats::parse(common, s))
+            }
+            _ => Self::Other(WebRTCOtherStats {
+                common,
+                type_: s.name().trim_end_matches("-stats").to_string(),
+            }),
+        };
+
+        Ok(stats)
+    }
+
+    pub fn common(&self) -> &WebRTCStatsCommon {
+        match self {
+            Self::Codec(stats) => &stats.common,
+            Self::InboundRtp(stats) => &stats.rtp.common,
+            Self::OutboundRtp(stats) => &stats.rtp.common,
+            Self::RemoteInboundRtp(stats) => &stats.rtp.common,
+            Self::Transport(stats) => &stats.common,
+            Self::CandidatePair(stats) => &stats.common,
+            Self::DataChannel(stats) => &stats.common,
+            Self::PeerConnection(stats) => &stats.common,
+            Self::Other(stats) => &stats.common,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.common().id
+    }
+}
+
+fn stats_type(s: &gst::StructureRef) -> Option<WebRTCStatsType> {
+    if let Ok(type_) = s.get::<WebRTCStatsType>("type") {
+        return Some(type_);
+    }
+
+    match s.name().trim_end_matches("-stats") {
+        "codec" => Some(WebRTCStatsType::Codec),
+        "inbound-rtp" => Some(WebRTCStatsType::InboundRtp),
+        "outbound-rtp" => Some(WebRTCStatsType::OutboundRtp),
+        "remote-inbound-rtp" => Some(WebRTCStatsType::RemoteInboundRtp),
+        "transport" => Some(WebRTCStatsType::Transport),
+        "candidate-pair" => Some(WebRTCStatsType::CandidatePair),
+        "data-channel" => Some(WebRTCStatsType::DataChannel),
+        "peer-connection" => Some(WebRTCStatsType::PeerConnection),
+        _ => None,
+    }
+}
+
+// The numeric types of the fields differ between GStreamer versions, so
+// accept any integer type that fits.
+fn unsigned<T: TryFrom<u64>>(s: &gst::StructureRef, field: &str) -> Option<T> {
+    let value = s
+        .get::<u64>(field)
+        .ok()
+        .or_else(|| s.get::<u32>(field).ok().map(u64::from))
+        .or_else(|| s.get::<i64>(field).ok().and_then(|v| v.try_into().ok()))
+        .or_else(|| s.get::<i32>(field).ok().and_then(|v| v.try_into().ok()))?;
+
+    value.try_into().ok()
+}
+
+fn signed<T: TryFrom<i64>>(s: &gst::StructureRef, field: &str) -> Option<T> {
+    let value = s
+        .get::<i64>(field)
+        .ok()
+        .or_else(|| s.get::<i32>(field).ok().map(i64::from))
+        .or_else(|| s.get::<u32>(field).ok().map(i64::from))
+        .or_else(|| s.get::<u64>(field).ok().and_then(|v| v.try_into().ok()))?;
+
+    value.try_into().ok()
+}
+
+fn double(s: &gst::StructureRef, field: &str) -> Option<f64> {
+    s.get::<f64>(field)
+        .ok()
+        .or_else(|| s.get::<f32>(field).ok().map(f64::from))
+        .or_else(|| signed::<i64>(s, field).map(|v| v as f64))
+}
+
+fn string(s: &gst::StructureRef, field: &str) -> Option<String> {
+    s.get::<Option<String>>(field).ok().flatten()
+}
+
+// rustdoc-stripper-ignore-next
+/// Fields shared by all statistics.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WebRTCStatsCommon {
+    pub id: String,
+    // rustdoc-stripper-ignore-next
+    /// Time at which the statistics were collected, in milliseconds.
+    pub timestamp: f64,
+}
+
+impl WebRTCStatsCommon {
+    fn from_structure(s: &gst::StructureRef) -> Result<Self, glib::BoolError> {
+        let id = string(s, "id").ok_or_else(|| glib::bool_error!("Statistics without id"))?;
+        let timestamp = double(s, "timestamp").unwrap_or_default();
+
+        Ok(Self { id, timestamp })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCCodecStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub payload_type: Option<u32>,
+    pub mime_type: Option<String>,
+    pub clock_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub sdp_fmtp_line: Option<String>,
+    pub transport_id: Option<String>,
+    pub ssrc: Option<u32>,
+}
+
+impl WebRTCCodecStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            payload_type: unsigned(s, "payload-type"),
+            mime_type: string(s, "mime-type"),
+            clock_rate: unsigned(s, "clock-rate"),
+            channels: unsigned(s, "channels"),
+            sdp_fmtp_line: string(s, "sdp-fmtp-line"),
+            transport_id: string(s, "transport-id"),
+            ssrc: unsigned(s, "ssrc"),
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Fields shared by the statistics of RTP streams.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCRtpStreamStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub ssrc: Option<u32>,
+    pub kind: Option<String>,
+    pub transport_id: Option<String>,
+    pub codec_id: Option<String>,
+}
+
+impl WebRTCRtpStreamStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            ssrc: unsigned(s, "ssrc"),
+            kind: string(s, "kind"),
+            transport_id: string(s, "transport-id"),
+            codec_id: string(s, "codec-id"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCInboundRtpStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub rtp: WebRTCRtpStreamStats,
+    pub packets_received: Option<u64>,
+    pub bytes_received: Option<u64>,
+    pub packets_lost: Option<i64>,
+    pub packets_duplicated: Option<u64>,
+    // rustdoc-stripper-ignore-next
+    /// Interarrival jitter in seconds.
+    pub jitter: Option<f64>,
+    pub fir_count: Option<u32>,
+    pub pli_count: Option<u32>,
+    pub nack_count: Option<u32>,
+    pub remote_id: Option<String>,
+}
+
+impl WebRTCInboundRtpStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            rtp: WebRTCRtpStreamStats::parse(common, s),
+            packets_received: unsigned(s, "packets-received"),
+            bytes_received: unsigned(s, "bytes-received"),
+            packets_lost: signed(s, "packets-lost"),
+            packets_duplicated: unsigned(s, "packets-duplicated"),
+            jitter: double(s, "jitter"),
+            fir_count: unsigned(s, "fir-count"),
+            pli_count: unsigned(s, "pli-count"),
+            nack_count: unsigned(s, "nack-count"),
+            remote_id: string(s, "remote-id"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCOutboundRtpStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub rtp: WebRTCRtpStreamStats,
+    pub packets_sent: Option<u64>,
+    pub bytes_sent: Option<u64>,
+    pub fir_count: Option<u32>,
+    pub pli_count: Option<u32>,
+    pub nack_count: Option<u32>,
+    pub remote_id: Option<String>,
+}
+
+impl WebRTCOutboundRtpStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            rtp: WebRTCRtpStreamStats::parse(common, s),
+            packets_sent: unsigned(s, "packets-sent"),
+            bytes_sent: unsigned(s, "bytes-sent"),
+            fir_count: unsigned(s, "fir-count"),
+            pli_count: unsigned(s, "pli-count"),
+            nack_count: unsigned(s, "nack-count"),
+            remote_id: string(s, "remote-id"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCRemoteInboundRtpStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub rtp: WebRTCRtpStreamStats,
+    pub local_id: Option<String>,
+    pub packets_lost: Option<i64>,
+    pub jitter: Option<f64>,
+    pub fraction_lost: Option<f64>,
+    // rustdoc-stripper-ignore-next
+    /// Round trip time in seconds.
+    pub round_trip_time: Option<f64>,
+}
+
+impl WebRTCRemoteInboundRtpStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            rtp: WebRTCRtpStreamStats::parse(common, s),
+            local_id: string(s, "local-id"),
+            packets_lost: signed(s, "packets-lost"),
+            jitter: double(s, "jitter"),
+            fraction_lost: double(s, "fraction-lost"),
+            round_trip_time: double(s, "round-trip-time"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCTransportStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub packets_sent: Option<u64>,
+    pub packets_received: Option<u64>,
+    pub bytes_sent: Option<u64>,
+    pub bytes_received: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(with = "dtls_state_serde"))]
+    pub dtls_state: Option<WebRTCDTLSTransportState>,
+    pub selected_candidate_pair_id: Option<String>,
+    pub local_certificate_id: Option<String>,
+    pub remote_certificate_id: Option<String>,
+}
+
+impl WebRTCTransportStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            packets_sent: unsigned(s, "packets-sent"),
+            packets_received: unsigned(s, "packets-received"),
+            bytes_sent: unsigned(s, "bytes-sent"),
+            bytes_received: unsigned(s, "bytes-received"),
+            dtls_state: s.get("dtls-state").ok(),
+            selected_candidate_pair_id: string(s, "selected-candidate-pair-id"),
+            local_certificate_id: string(s, "local-certificate-id"),
+            remote_certificate_id: string(s, "remote-certificate-id"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCCandidatePairStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub transport_id: Option<String>,
+    pub local_candidate_id: Option<String>,
+    pub remote_candidate_id: Option<String>,
+    pub nominated: Option<bool>,
+    pub bytes_sent: Option<u64>,
+    pub bytes_received: Option<u64>,
+    pub current_round_trip_time: Option<f64>,
+}
+
+impl WebRTCCandidatePairStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            transport_id: string(s, "transport-id"),
+            local_candidate_id: string(s, "local-candidate-id"),
+            remote_candidate_id: string(s, "remote-candidate-id"),
+            nominated: s.get("nominated").ok(),
+            bytes_sent: unsigned(s, "bytes-sent"),
+            bytes_received: unsigned(s, "bytes-received"),
+            current_round_trip_time: double(s, "current-round-trip-time"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCDataChannelStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub label: Option<String>,
+    pub protocol: Option<String>,
+    pub data_channel_identifier: Option<i32>,
+    #[cfg_attr(feature = "serde", serde(with = "data_channel_state_serde"))]
+    pub state: Option<WebRTCDataChannelState>,
+    pub messages_sent: Option<u32>,
+    pub bytes_sent: Option<u64>,
+    pub messages_received: Option<u32>,
+    pub bytes_received: Option<u64>,
+}
+
+impl WebRTCDataChannelStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            label: string(s, "label"),
+            protocol: string(s, "protocol"),
+            data_channel_identifier: signed(s, "data-channel-identifier"),
+            state: s.get("state").ok(),
+            messages_sent: unsigned(s, "messages-sent"),
+            bytes_sent: unsigned(s, "bytes-sent"),
+            messages_received: unsigned(s, "messages-received"),
+            bytes_received: unsigned(s, "bytes-received"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebRTCPeerConnectionStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    pub data_channels_opened: Option<u32>,
+    pub data_channels_closed: Option<u32>,
+}
+
+impl WebRTCPeerConnectionStats {
+    fn parse(common: WebRTCStatsCommon, s: &gst::StructureRef) -> Self {
+        Self {
+            common,
+            data_channels_opened: unsigned(s, "data-channels-opened"),
+            data_channels_closed: unsigned(s, "data-channels-closed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WebRTCOtherStats {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub common: WebRTCStatsCommon,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+}
+
+// States are serialized with the names of the W3C enums.
+macro_rules! state_serde {
+    ($module:ident, $type:ty, $($variant:ident => $name:literal),+ $(,)?) => {
+        #[cfg(feature = "serde")]
+        mod $module {
+            use serde::{Deserialize, Deserializer, Serializer};
+
+            pub fn serialize<S: Serializer>(
+                state: &Option<$type>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                match state {
+                    $(Some(<$type>::$variant) => serializer.serialize_some($name),)+
+                    _ => serializer.serialize_none(),
+                }
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<$type>, D::Error> {
+                Ok(match Option::<String>::deserialize(deserializer)?.as_deref() {
+                    $(Some($name) => Some(<$type>::$variant),)+
+                    _ => None,
+                })
+            }
+        }
+    };
+}
+
+state_serde!(
+    dtls_state_serde,
+    crate::WebRTCDTLSTransportState,
+    New => "new",
+    Closed => "closed",
+    Failed => "failed",
+    Connecting => "connecting",
+    Connected => "connected",
+);
+
+state_serde!(
+    data_channel_state_serde,
+    crate::WebRTCDataChannelState,
+    Connecting => "connecting",
+    Open => "open",
+    Closing => "closing",
+    Closed => "closed",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> WebRTCStatsReport {
+        gst::init().unwrap();
+
+        let s = gst::Structure::builder("application/x-webrtc-stats")
+            .field(
+                "inbound-rtp_1234",
+                gst::Structure::builder("inbound-rtp-stats")
+                    .field("id", "inbound-rtp_1234")
+                    .field("timestamp", 1000.0f64)
+                    .field("type", WebRTCStatsType::InboundRtp)
+                    .field("ssrc", 1234u32)
+                    .field("codec-id", "codec_0")
+                    .field("packets-received", 100u64)
+                    .field("packets-lost", -1i64)
+                    .field("jitter", 0.01f64)
+                    .build(),
+            )
+            .field(
+                "peer-connection-stats",
+                gst::Structure::builder("peer-connection-stats")
+                    .field("id", "peer-connection-stats")
+                    .field("timestamp", 1000.0f64)
+                    .field("type", WebRTCStatsType::PeerConnection)
+                    .field("data-channels-opened", 1u32)
+                    .build(),
+            )
+            .field(
+                "certificate_0",
+                gst::Structure::builder("certificate-stats")
+                    .field("id", "certificate_0")
+                    .field("timestamp", 1000.0f64)
+                    .field("type", WebRTCStatsType::Certificate)
+                    .build(),
+            )
+            .build();
+
+        WebRTCStatsReport::from_structure(&s).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let report = report();
+        assert_eq!(report.len(), 3);
+
+        let inbound = report.inbound_rtp().next().unwrap();
+        assert_eq!(inbound.rtp.common.id, "inbound-rtp_1234");
+        assert_eq!(inbound.rtp.ssrc, Some(1234));
+        assert_eq!(inbound.rtp.codec_id.as_deref(), Some("codec_0"));
+        assert_eq!(inbound.packets_received, Some(100));
+        assert_eq!(inbound.packets_lost, Some(-1));
+        assert_eq!(inbound.bytes_received, None);
+
+        let pc = report.peer_connection().unwrap();
+        assert_eq!(pc.data_channels_opened, Some(1));
+        assert_eq!(pc.data_channels_closed, None);
+
+        match report.get("certificate_0").unwrap() {
+            WebRTCStats::Other(other) => assert_eq!(other.type_, "certificate"),
+            other => panic!("unexpected stats {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let report = report();
+
+        let json = serde_json::to_value(&report).unwrap();
+        let inbound = &json["inbound-rtp_1234"];
+        assert_eq!(inbound["type"], "inbound-rtp");
+        assert_eq!(inbound["packetsReceived"], 100);
+        assert_eq!(inbound["codecId"], "codec_0");
+        assert_eq!(json["certificate_0"]["type"], "certificate");
+
+        let deserialized: WebRTCStatsReport = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, report);
+    }
+}
@@ -0,0 +1,402 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Validates the caps string syntax at compile time. This follows the parser of
+// `gst_caps_from_string()` but can't know about types that are only registered at runtime, so
+// values with such a type cast are only checked syntactically.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::{Error, LitStr, Result};
+
+use crate::pipeline::crate_path;
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Number {
+    Int,
+    Float,
+    Fraction,
+}
+
+fn is_string_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"_-+/:.".contains(&c)
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, msg: impl std::fmt::Display) -> std::result::Result<T, String> {
+        Err(format!("{msg} at offset {}", self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> std::result::Result<(), String> {
+        self.skip_spaces();
+        if self.peek() != Some(c) {
+            return self.error(format!("Expected `{}`", c as char));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn read_simple_string(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(is_string_char) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.s[start..self.pos]).unwrap()
+    }
+
+    fn read_quoted_string(&mut self) -> std::result::Result<(), String> {
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                None => return self.error("Unterminated string"),
+                Some(b'"') => break,
+                Some(b'\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse_caps(&mut self) -> std::result::Result<(), String> {
+        self.skip_spaces();
+        let start = self.pos;
+        let name = self.read_simple_string();
+        self.skip_spaces();
+        if matches!(name, "ANY" | "EMPTY" | "NONE") && self.peek().is_none() {
+            return Ok(());
+        }
+        self.pos = start;
+
+        loop {
+            self.parse_structure()?;
+            self.skip_spaces();
+            match self.peek() {
+                None => return Ok(()),
+                Some(b';') => {
+                    self.pos += 1;
+                    self.skip_spaces();
+                    if self.peek().is_none() {
+                        return Ok(());
+                    }
+                }
+                Some(_) => return self.error("Expected `;` or `,`"),
+            }
+        }
+    }
+
+    fn parse_structure(&mut self) -> std::result::Result<(), String> {
+        self.skip_spaces();
+        let name = self.read_simple_string();
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return self.error("Expected structure name starting with a letter");
+        }
+
+        if self.peek() == Some(b'(') {
+            self.pos += 1;
+            self.parse_features()?;
+        }
+
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some(b',') {
+                return Ok(());
+            }
+            self.pos += 1;
+            self.skip_spaces();
+
+            let field = self.read_simple_string();
+            if !field.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return self.error("Expected field name starting with a letter");
+            }
+            self.expect(b'=')?;
+            self.parse_value()?;
+        }
+    }
+
+    fn parse_features(&mut self) -> std::result::Result<(), String> {
+        loop {
+            self.skip_spaces();
+            let feature = self.read_simple_string();
+            if feature.is_empty() {
+                return self.error("Expected caps feature");
+            }
+            self.skip_spaces();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b')') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return self.error("Expected `,` or `)` in caps features"),
+            }
+        }
+    }
+
+    fn parse_type(&mut self) -> std::result::Result<Option<&'a str>, String> {
+        self.skip_spaces();
+        if self.peek() != Some(b'(') {
+            return Ok(None);
+        }
+        self.pos += 1;
+        self.skip_spaces();
+        let type_name = self.read_simple_string();
+        if type_name.is_empty() {
+            return self.error("Expected type name");
+        }
+        self.expect(b')')?;
+        Ok(Some(type_name))
+    }
+
+    fn parse_value(&mut self) -> std::result::Result<(), String> {
+        let type_name = self.parse_type()?;
+        self.skip_spaces();
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                self.parse_range(type_name)
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                self.parse_list(b'}', type_name)
+            }
+            Some(b'<') => {
+                self.pos += 1;
+                self.parse_list(b'>', type_name)
+            }
+            _ => self.parse_simple_value(type_name),
+        }
+    }
+
+    fn parse_simple_value(&mut self, type_name: Option<&str>) -> std::result::Result<(), String> {
+        self.skip_spaces();
+        let start = self.pos;
+        if self.peek() == Some(b'"') {
+            self.read_quoted_string()?;
+            return match type_name {
+                None | Some("string" | "str" | "s") => Ok(()),
+                Some(t) if is_builtin_type(t) => {
+                    self.pos = start;
+                    self.error(format!("Expected value of type `{t}`"))
+                }
+                Some(_) => Ok(()),
+            };
+        }
+
+        let value = self.read_simple_string();
+        if value.is_empty() {
+            return self.error("Expected value");
+        }
+
+        let valid = match type_name {
+            Some("int" | "i" | "uint" | "u" | "int64" | "uint64") => {
+                number(value) == Some(Number::Int)
+            }
+            Some("float" | "f" | "double" | "d") => {
+                matches!(number(value), Some(Number::Int | Number::Float))
+            }
+            Some("fraction") => matches!(number(value), Some(Number::Int | Number::Fraction)),
+            Some("boolean" | "bool" | "b") => matches!(
+                value.to_ascii_lowercase().as_str(),
+                "true" | "false" | "yes" | "no" | "t" | "f" | "1" | "0"
+            ),
+            _ => true,
+        };
+        if !valid {
+            self.pos = start;
+            return self.error(format!(
+                "Expected value of type `{}`",
+                type_name.unwrap_or_default()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn parse_range(&mut self, type_name: Option<&str>) -> std::result::Result<(), String> {
+        let mut kinds = Vec::new();
+        loop {
+            let inner_type = self.parse_type()?.or(type_name);
+            self.skip_spaces();
+            let start = self.pos;
+            self.parse_simple_value(inner_type)?;
+            let value = std::str::from_utf8(&self.s[start..self.pos]).unwrap();
+            match number(value) {
+                Some(kind) => kinds.push(kind),
+                None => {
+                    self.pos = start;
+                    return self.error("Expected number or fraction in range");
+                }
+            }
+
+            self.skip_spaces();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return self.error("Expected `,` or `]` in range"),
+            }
+        }
+
+        let is_fraction = kinds.contains(&Number::Fraction);
+        match kinds.len() {
+            2 => Ok(()),
+            3 if !is_fraction && !kinds.contains(&Number::Float) => Ok(()),
+            3 => self.error("Only integer ranges can have a step"),
+            _ => self.error("Ranges must have a minimum and a maximum"),
+        }
+    }
+
+    fn parse_list(&mut self, end: u8, type_name: Option<&str>) -> std::result::Result<(), String> {
+        self.skip_spaces();
+        if self.peek() == Some(end) {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        loop {
+            let inner_type = self.parse_type()?.or(type_name);
+            self.skip_spaces();
+            match self.peek() {
+                Some(b'[') => {
+                    self.pos += 1;
+                    self.parse_range(inner_type)?;
+                }
+                Some(b'{') => {
+                    self.pos += 1;
+                    self.parse_list(b'}', inner_type)?;
+                }
+                Some(b'<') => {
+                    self.pos += 1;
+                    self.parse_list(b'>', inner_type)?;
+                }
+                _ => self.parse_simple_value(inner_type)?,
+            }
+
+            self.skip_spaces();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(c) if c == end => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return self.error(format!("Expected `,` or `{}`", end as char)),
+            }
+        }
+    }
+}
+
+fn is_builtin_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "int"
+            | "i"
+            | "uint"
+            | "u"
+            | "int64"
+            | "uint64"
+            | "float"
+            | "f"
+            | "double"
+            | "d"
+            | "fraction"
+            | "boolean"
+            | "bool"
+            | "b"
+    )
+}
+
+fn number(value: &str) -> Option<Number> {
+    fn is_int(s: &str) -> bool {
+        let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            !hex.is_empty() && hex.bytes().all(|c| c.is_ascii_hexdigit())
+        } else {
+            !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit())
+        }
+    }
+
+    if is_int(value) {
+        Some(Number::Int)
+    } else if let Some((numer, denom)) = value.split_once('/') {
+        (is_int(numer) && is_int(denom)).then_some(Number::Fraction)
+    } else if value.parse::<f64>().is_ok() && !value.contains(['i', 'I', 'n', 'N']) {
+        Some(Number::Float)
+    } else {
+        None
+    }
+}
+
+pub fn validate(caps: &str) -> std::result::Result<(), String> {
+    Parser {
+        s: caps.as_bytes(),
+        pos: 0,
+    }
+    .parse_caps()
+}
+
+pub fn impl_caps(input: TokenStream) -> Result<TokenStream> {
+    let lit = syn::parse2::<LitStr>(input)?;
+    let caps = lit.value();
+
+    validate(&caps).map_err(|err| Error::new(lit.span(), format!("Invalid caps: {err}")))?;
+
+    let gst = crate_path();
+    Ok(quote_spanned! {lit.span()=>
+        #gst::macro_support::caps_from_static_str(#lit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn test_validate() {
+        for caps in [
+            "ANY",
+            "EMPTY",
+            "video/x-raw",
+            "video/x-raw, format=(string){I420,NV12}, width=[1,4096]",
+            "video/x-raw(memory:GLMemory), framerate=(fraction)[0/1, 2147483647/1]",
+            "audio/x-raw, rate=(int)48000, channels=2; audio/x-raw, rate=44100",
+            "application/x-rtp, encoding-name=\"H264\", payload=(int)[96,127,1]",
+            "video/x-raw, views=<1, 2>, flags=(GstVideoMultiviewFlagsSet)0:ffffffff",
+            "audio/x-raw, layout=(string)interleaved, mask=(bitmask)0x3;",
+        ] {
+            validate(caps).unwrap_or_else(|err| panic!("{caps}: {err}"));
+        }
+
+        for caps in [
+            "",
+            "video/x-raw,",
+            "video/x-raw, width",
+            "video/x-raw, width=(int)abc",
+            "video/x-raw, framerate=(fraction)1.5",
+            "video/x-raw, width=[1]",
+            "video/x-raw, framerate=[0/1, 30/1, 1/1]",
+            "video/x-raw, format={I420, NV12",
+            "video/x-raw, format=\"I420",
+            "video/x-raw(memory:GLMemory",
+            "1video/x-raw",
+        ] {
+            assert!(validate(caps).is_err(), "{caps}");
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+mod caps;
+mod pipeline;
+
+use proc_macro::TokenStream;
+
+/// Builds a [`gst::Pipeline`] from a `gst-launch-1.0`-like description that is checked at
+/// compile time.
+///
+/// The description is parsed when the crate is compiled and syntax errors, unknown element
+/// references or duplicated element names are reported at the place in the description where
+/// they happen. The expansion creates each element like
+/// `gst::ElementFactory::make(..).property(..).build()`, adds all of them to a new
+/// [`gst::Pipeline`] and links them. Unknown properties and values that can't be used for a
+/// property result in an error instead of a panic.
+///
+/// The syntax follows `gst-launch-1.0`:
+///
+/// * `factory prop=value ...` creates a new element. Values are deserialized from strings at
+///   runtime like in `gst-launch-1.0`, string literals can be used for values containing
+///   whitespace.
+/// * `a ! b` links two elements, `a ! media/type,field=value ! b` links them with a caps filter.
+/// * `name.` and `name.pad` refer to an element by its `name` property, optionally to a specific
+///   pad of it.
+/// * `{expr}` interpolates a Rust expression. As a property or caps field value, it must be
+///   convertible into a [`glib::Value`]. In place of caps between two `!`, it must evaluate to a
+///   [`gst::Caps`] or `&gst::Caps`.
+///
+/// Links from elements with sometimes pads, like demuxers, are delayed until a matching pad is
+/// added.
+///
+/// The macro evaluates to a `Result<_, glib::BoolError>` of a struct that has a `pipeline` field
+/// with the [`gst::Pipeline`] and one [`gst::Element`] field per element whose `name` is a valid
+/// Rust identifier.
+///
+/// ```no_run
+/// use gst::prelude::*;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// gst::init()?;
+///
+/// let width = 320i32;
+/// let p = gst::pipeline!(
+///     videotestsrc name=src num-buffers=100 is-live={true}
+///         ! video/x-raw, width={width}, height=240, framerate=30/1
+///         ! videoconvert ! x264enc tune=zerolatency ! mux.
+///     audiotestsrc wave=ticks ! audioconvert ! avenc_aac ! mux.
+///     mp4mux name=mux ! filesink name=sink location="out.mp4"
+/// )?;
+///
+/// p.sink.set_property("location", "other.mp4");
+/// p.pipeline.set_state(gst::State::Playing)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Caps between two elements are validated at compile time like with [`caps!`], so the
+/// following fails to compile:
+///
+/// ```compile_fail
+/// # gst::init().unwrap();
+/// let p = gst::pipeline!(videotestsrc ! video/x-raw, width=(int)abc ! fakesink);
+/// ```
+///
+/// ```compile_fail
+/// # gst::init().unwrap();
+/// let p = gst::pipeline!(videotestsrc ! video/x-raw, framerate=(fraction)1.5 ! fakesink);
+/// ```
+///
+/// [`gst::Pipeline`]: https://gstreamer.freedesktop.org/documentation/rust/stable/latest/docs/gstreamer/struct.Pipeline.html
+/// [`gst::Element`]: https://gstreamer.freedesktop.org/documentation/rust/stable/latest/docs/gstreamer/struct.Element.html
+/// [`gst::Caps`]: https://gstreamer.freedesktop.org/documentation/rust/stable/latest/docs/gstreamer/struct.Caps.html
+/// [`glib::Value`]: https://gtk-rs.org/gtk-rs-core/stable/latest/docs/glib/value/struct.Value.html
+#[proc_macro]
+pub fn pipeline(input: TokenStream) -> TokenStream {
+    pipeline::impl_pipeline(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Creates a [`gst::Caps`] from a caps string that is checked at compile time.
+///
+/// The string uses the same syntax as [`gst::Caps::from_str()`] and syntax errors, malformed
+/// ranges and values that don't match their type cast, e.g. `width=(int)abc`, are reported as
+/// compile errors. Types that are only known at runtime, like enums or flags of plugins, are only
+/// checked syntactically and the macro panics if such a value can't be deserialized.
+///
+/// ```
+/// # gst::init().unwrap();
+/// let caps = gst::caps!("video/x-raw, format=(string){I420,NV12}, width=[1,4096]");
+/// assert_eq!(caps.structure(0).unwrap().name(), "video/x-raw");
+/// ```
+///
+/// ```compile_fail
+/// # gst::init().unwrap();
+/// let caps = gst::caps!("video/x-raw, width=[1]");
+/// ```
+///
+/// [`gst::Caps`]: https://gstreamer.freedesktop.org/documentation/rust/stable/latest/docs/gstreamer/struct.Caps.html
+/// [`gst::Caps::from_str()`]: https://gstreamer.freedesktop.org/documentation/rust/stable/latest/docs/gstreamer/struct.Caps.html#method.from_str
+#[proc_macro]
+pub fn caps(input: TokenStream) -> TokenStream {
+    caps::impl_caps(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,642 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Error, Lit, Result};
+
+pub(crate) fn crate_path() -> TokenStream {
+    match crate_name("gstreamer") {
+        Ok(FoundCrate::Itself) => quote!(crate),
+        Ok(FoundCrate::Name(name)) => {
+            let name = Ident::new(&name, Span::call_site());
+            quote!(::#name)
+        }
+        Err(_) => quote!(::gstreamer),
+    }
+}
+
+enum Value {
+    // Deserialized from the string at runtime, like gst-launch does.
+    Text(String),
+    Expr(TokenStream),
+}
+
+struct Property {
+    name: String,
+    span: Span,
+    value: Value,
+}
+
+struct Element {
+    factory: String,
+    span: Span,
+    name: Option<String>,
+    properties: Vec<Property>,
+    handle: Option<Ident>,
+}
+
+enum Caps {
+    Description {
+        text: String,
+        fields: Vec<(String, TokenStream)>,
+        span: Span,
+    },
+    Expr(TokenStream, Span),
+}
+
+impl Caps {
+    fn span(&self) -> Span {
+        match self {
+            Caps::Description { span, .. } | Caps::Expr(_, span) => *span,
+        }
+    }
+}
+
+enum Item {
+    Element(usize),
+    Reference {
+        name: String,
+        pad: Option<String>,
+        span: Span,
+    },
+    Caps(Caps),
+}
+
+struct Endpoint {
+    element: usize,
+    pad: Option<String>,
+}
+
+struct Link {
+    src: Endpoint,
+    sink: Endpoint,
+    caps: Option<Caps>,
+}
+
+#[derive(Clone)]
+enum PendingEndpoint {
+    Element(usize),
+    Reference {
+        name: String,
+        pad: Option<String>,
+        span: Span,
+    },
+}
+
+struct Parser {
+    tokens: Vec<TokenTree>,
+    pos: usize,
+    elements: Vec<Element>,
+    links: Vec<(PendingEndpoint, PendingEndpoint, Option<Caps>)>,
+}
+
+fn flatten(stream: TokenStream, tokens: &mut Vec<TokenTree>) {
+    for tt in stream {
+        match tt {
+            // Invisible groups are created when passing tokens through `macro_rules!`
+            TokenTree::Group(ref g) if g.delimiter() == Delimiter::None => {
+                flatten(g.stream(), tokens)
+            }
+            tt => tokens.push(tt),
+        }
+    }
+}
+
+// Whitespace is significant in the pipeline description, e.g. `mux. queue` is not the same as
+// `mux.queue`, so look at the source locations of the tokens.
+fn adjacent(a: &TokenTree, b: &TokenTree) -> bool {
+    let a = a.span().unwrap().end();
+    let b = b.span().unwrap().start();
+
+    a.line() == b.line() && a.column() == b.column()
+}
+
+fn is_punct(tt: Option<&TokenTree>, c: char) -> bool {
+    matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == c)
+}
+
+fn is_brace_group(tt: Option<&TokenTree>) -> bool {
+    matches!(tt, Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace)
+}
+
+fn string_literal(tt: &TokenTree) -> Option<String> {
+    match tt {
+        TokenTree::Literal(lit) => match Lit::new(lit.clone()) {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_name_start(tt: Option<&TokenTree>) -> bool {
+    match tt {
+        Some(TokenTree::Ident(_)) => true,
+        Some(tt @ TokenTree::Literal(_)) => string_literal(tt).is_none(),
+        _ => false,
+    }
+}
+
+fn is_valid_handle(name: &str) -> bool {
+    syn::parse_str::<Ident>(name).is_ok()
+}
+
+impl Parser {
+    fn new(input: TokenStream) -> Self {
+        let mut tokens = Vec::new();
+        flatten(input, &mut tokens);
+
+        Parser {
+            tokens,
+            pos: 0,
+            elements: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenTree> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<TokenTree> {
+        let tt = self.tokens.get(self.pos).cloned();
+        if tt.is_some() {
+            self.pos += 1;
+        }
+        tt
+    }
+
+    // Returns the next token if it directly follows the previous one without whitespace.
+    fn peek_adjacent(&self) -> Option<&TokenTree> {
+        let prev = self.tokens.get(self.pos.checked_sub(1)?)?;
+        self.peek().filter(|tt| adjacent(prev, tt))
+    }
+
+    fn current_span(&self) -> Span {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map(|tt| tt.span())
+            .unwrap_or_else(Span::call_site)
+    }
+
+    // Reads a name like `num-buffers`, `x264enc` or `sink_%u` that might consist of multiple
+    // tokens as long as there is no whitespace between them.
+    fn read_name(&mut self, what: &str) -> Result<(String, Span)> {
+        if !is_name_start(self.peek()) {
+            return Err(Error::new(self.current_span(), format!("Expected {what}")));
+        }
+
+        let first = self.next().unwrap();
+        let span = first.span();
+        let mut name = first.to_string();
+
+        loop {
+            match self.peek_adjacent() {
+                Some(tt @ (TokenTree::Ident(_) | TokenTree::Literal(_)))
+                    if string_literal(tt).is_none() =>
+                {
+                    name.push_str(&tt.to_string());
+                }
+                // Pad templates like `sink_%u`
+                Some(TokenTree::Punct(p)) if p.as_char() == '%' => name.push('%'),
+                // Only part of the name if followed by another part, e.g. `num-buffers`
+                Some(TokenTree::Punct(p))
+                    if p.as_char() == '-'
+                        && self.tokens.get(self.pos + 1).is_some_and(|next| {
+                            adjacent(&self.tokens[self.pos], next) && is_name_start(Some(next))
+                        }) =>
+                {
+                    name.push('-')
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+
+        Ok((name, span))
+    }
+
+    // Reads a value up to the next whitespace. Values inside caps additionally end at `,`.
+    fn read_value(&mut self, in_caps: bool) -> Result<(Value, Span)> {
+        let span = self.current_span();
+
+        match self.peek() {
+            None => return Err(Error::new(span, "Expected value")),
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                let stream = g.stream();
+                self.pos += 1;
+                if stream.is_empty() {
+                    return Err(Error::new(span, "Expected expression inside `{}`"));
+                }
+                return Ok((Value::Expr(stream), span));
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' || (in_caps && p.as_char() == ',') => {
+                return Err(Error::new(span, "Expected value"));
+            }
+            _ => (),
+        }
+
+        let mut text = String::new();
+        let mut tt = self.next();
+        while let Some(cur) = tt {
+            match string_literal(&cur) {
+                // Caps are deserialized as a whole, so keep the quotes there.
+                Some(s) if !in_caps => text.push_str(&s),
+                _ => text.push_str(&cur.to_string()),
+            }
+
+            tt = match self.peek_adjacent() {
+                Some(TokenTree::Punct(p))
+                    if p.as_char() == '!' || (in_caps && p.as_char() == ',') =>
+                {
+                    None
+                }
+                next if is_brace_group(next) => {
+                    return Err(Error::new(
+                        next.unwrap().span(),
+                        "Interpolated expressions can't be combined with other values",
+                    ));
+                }
+                Some(_) => self.next(),
+                None => None,
+            };
+        }
+
+        Ok((Value::Text(text), span))
+    }
+
+    fn parse(&mut self) -> Result<()> {
+        if self.tokens.is_empty() {
+            return Err(Error::new(Span::call_site(), "Empty pipeline description"));
+        }
+
+        while self.peek().is_some() {
+            self.parse_chain()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_chain(&mut self) -> Result<()> {
+        let mut prev = match self.parse_item()? {
+            Item::Caps(caps) => {
+                return Err(Error::new(
+                    caps.span(),
+                    "Caps must be placed between two elements",
+                ));
+            }
+            item => Self::endpoint(item),
+        };
+
+        while is_punct(self.peek(), '!') {
+            self.pos += 1;
+
+            let (caps, item) = match self.parse_item()? {
+                Item::Caps(caps) => {
+                    if !is_punct(self.peek(), '!') {
+                        return Err(Error::new(
+                            caps.span(),
+                            "Caps must be followed by `!` and an element",
+                        ));
+                    }
+                    self.pos += 1;
+
+                    match self.parse_item()? {
+                        Item::Caps(second) => {
+                            return Err(Error::new(
+                                second.span(),
+                                "Only one caps filter is allowed between two elements",
+                            ));
+                        }
+                        item => (Some(caps), item),
+                    }
+                }
+                item => (None, item),
+            };
+
+            let sink = Self::endpoint(item);
+            let src = std::mem::replace(&mut prev, sink.clone());
+            self.links.push((src, sink, caps));
+        }
+
+        Ok(())
+    }
+
+    fn endpoint(item: Item) -> PendingEndpoint {
+        match item {
+            Item::Element(idx) => PendingEndpoint::Element(idx),
+            Item::Reference { name, pad, span } => PendingEndpoint::Reference { name, pad, span },
+            Item::Caps(_) => unreachable!(),
+        }
+    }
+
+    fn parse_item(&mut self) -> Result<Item> {
+        let span = self.current_span();
+
+        match self.peek() {
+            None => Err(Error::new(span, "Expected element after `!`")),
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                let stream = g.stream();
+                self.pos += 1;
+                if stream.is_empty() {
+                    return Err(Error::new(span, "Expected caps expression inside `{}`"));
+                }
+                Ok(Item::Caps(Caps::Expr(stream, span)))
+            }
+            tt if is_name_start(tt) => {
+                let (name, span) = self.read_name("element")?;
+
+                if is_punct(self.peek_adjacent(), '.') {
+                    self.pos += 1;
+                    let pad = if is_name_start(self.peek_adjacent()) {
+                        Some(self.read_name("pad name")?.0)
+                    } else {
+                        None
+                    };
+                    Ok(Item::Reference { name, pad, span })
+                } else if is_punct(self.peek_adjacent(), '/') {
+                    self.pos += 1;
+                    self.parse_caps(name, span)
+                } else {
+                    self.parse_element(name, span)
+                }
+            }
+            Some(_) => Err(Error::new(
+                span,
+                "Expected element, element reference or caps",
+            )),
+        }
+    }
+
+    fn parse_caps(&mut self, media_type: String, span: Span) -> Result<Item> {
+        if self.peek_adjacent().is_none() {
+            return Err(Error::new(self.current_span(), "Expected media type"));
+        }
+        let (subtype, _) = self.read_name("media type")?;
+
+        let mut text = format!("{media_type}/{subtype}");
+        if let Some(TokenTree::Group(g)) = self.peek_adjacent()
+            && g.delimiter() == Delimiter::Parenthesis
+        {
+            let features = g.to_string();
+            text.extend(features.chars().filter(|c| !c.is_whitespace()));
+            self.pos += 1;
+        }
+
+        let mut fields = Vec::new();
+        while is_punct(self.peek(), ',') {
+            self.pos += 1;
+
+            let (field, field_span) = self.read_name("caps field name")?;
+            if !is_punct(self.peek(), '=') {
+                return Err(Error::new(
+                    field_span,
+                    format!("Expected `=` after caps field `{field}`"),
+                ));
+            }
+            self.pos += 1;
+
+            match self.read_value(true)?.0 {
+                Value::Text(value) => {
+                    text.push(',');
+                    text.push_str(&field);
+                    text.push('=');
+                    text.push_str(&value);
+                }
+                Value::Expr(expr) => fields.push((field, expr)),
+            }
+        }
+
+        // Interpolated fields are set on the deserialized caps, so are not part of the text.
+        crate::caps::validate(&text)
+            .map_err(|err| Error::new(span, format!("Invalid caps `{text}`: {err}")))?;
+
+        Ok(Item::Caps(Caps::Description { text, fields, span }))
+    }
+
+    fn parse_element(&mut self, factory: String, span: Span) -> Result<Item> {
+        let mut element = Element {
+            factory,
+            span,
+            name: None,
+            properties: Vec::new(),
+            handle: None,
+        };
+
+        while is_name_start(self.peek()) {
+            // Not a property but the start of the next chain
+            let checkpoint = self.pos;
+            let (name, name_span) = self.read_name("property name")?;
+            if !is_punct(self.peek(), '=') {
+                self.pos = checkpoint;
+                break;
+            }
+            self.pos += 1;
+
+            let (value, value_span) = self.read_value(false)?;
+
+            if element.properties.iter().any(|p| p.name == name) {
+                return Err(Error::new(
+                    name_span,
+                    format!("Property `{name}` is set multiple times"),
+                ));
+            }
+
+            if name == "name"
+                && let Value::Text(ref element_name) = value
+            {
+                if element_name == "pipeline" {
+                    return Err(Error::new(
+                        value_span,
+                        "Element name `pipeline` is reserved for the pipeline itself",
+                    ));
+                }
+                if self
+                    .elements
+                    .iter()
+                    .any(|e| e.name.as_ref() == Some(element_name))
+                {
+                    return Err(Error::new(
+                        value_span,
+                        format!("Duplicate element name `{element_name}`"),
+                    ));
+                }
+                if is_valid_handle(element_name) {
+                    element.handle = Some(Ident::new(element_name, value_span));
+                }
+                element.name = Some(element_name.clone());
+            }
+
+            element.properties.push(Property {
+                name,
+                span: name_span,
+                value,
+            });
+        }
+
+        self.elements.push(element);
+        Ok(Item::Element(self.elements.len() - 1))
+    }
+
+    fn resolve(&self, endpoint: PendingEndpoint) -> Result<Endpoint> {
+        match endpoint {
+            PendingEndpoint::Element(element) => Ok(Endpoint { element, pad: None }),
+            PendingEndpoint::Reference { name, pad, span } => self
+                .elements
+                .iter()
+                .position(|e| e.name.as_ref() == Some(&name))
+                .map(|element| Endpoint { element, pad })
+                .ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!("No element named `{name}` in the pipeline description"),
+                    )
+                }),
+        }
+    }
+
+    fn finish(mut self) -> Result<(Vec<Element>, Vec<Link>)> {
+        let links = std::mem::take(&mut self.links)
+            .into_iter()
+            .map(|(src, sink, caps)| {
+                Ok(Link {
+                    src: self.resolve(src)?,
+                    sink: self.resolve(sink)?,
+                    caps,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((self.elements, links))
+    }
+}
+
+fn element_var(idx: usize) -> Ident {
+    format_ident!("element_{}", idx, span = Span::mixed_site())
+}
+
+pub(crate) fn impl_pipeline(input: TokenStream) -> Result<TokenStream> {
+    let mut parser = Parser::new(input);
+    parser.parse()?;
+    let (elements, links) = parser.finish()?;
+
+    let gst = crate_path();
+    let pipeline = Ident::new("pipeline", Span::mixed_site());
+
+    let builds = elements.iter().enumerate().map(|(idx, element)| {
+        let var = element_var(idx);
+        let factory = &element.factory;
+        let properties = element.properties.iter().map(|p| {
+            let name = &p.name;
+            match p.value {
+                Value::Text(ref value) => quote_spanned! {p.span=>
+                    (#name, #gst::macro_support::PropertyValue::Str(#value))
+                },
+                Value::Expr(ref expr) => quote_spanned! {p.span=>
+                    (
+                        #name,
+                        #gst::macro_support::PropertyValue::Value(
+                            ::std::convert::Into::into(#expr),
+                        ),
+                    )
+                },
+            }
+        });
+
+        quote_spanned! {element.span=>
+            let #var = #gst::macro_support::make_element(
+                #factory,
+                ::std::vec![#(#properties),*],
+            )?;
+        }
+    });
+
+    let vars = (0..elements.len()).map(element_var).collect::<Vec<_>>();
+
+    let link_calls = links.iter().enumerate().map(|(idx, link)| {
+        let src = element_var(link.src.element);
+        let sink = element_var(link.sink.element);
+        let src_pad = match link.src.pad {
+            Some(ref pad) => quote!(::std::option::Option::Some(#pad)),
+            None => quote!(::std::option::Option::None),
+        };
+        let sink_pad = match link.sink.pad {
+            Some(ref pad) => quote!(::std::option::Option::Some(#pad)),
+            None => quote!(::std::option::Option::None),
+        };
+
+        let caps_var = format_ident!("caps_{}", idx, span = Span::mixed_site());
+        let (caps, filter) = match link.caps {
+            None => (quote!(), quote!(::std::option::Option::None)),
+            Some(Caps::Expr(ref expr, span)) => (
+                quote_spanned! {span=>
+                    let #caps_var = #gst::Caps::clone(&(#expr));
+                },
+                quote!(::std::option::Option::Some(&#caps_var)),
+            ),
+            Some(Caps::Description {
+                ref text,
+                ref fields,
+                span,
+            }) => {
+                let caps = Ident::new("caps", Span::mixed_site());
+                let fields = fields.iter().map(|(name, expr)| {
+                    quote! {
+                        #caps.set(#name, #expr);
+                    }
+                });
+                (
+                    quote_spanned! {span=>
+                        #[allow(unused_mut)]
+                        let mut #caps_var = #gst::macro_support::caps_from_str(#text)?;
+                        {
+                            #[allow(unused_variables)]
+                            let #caps = #caps_var.make_mut();
+                            #(#fields)*
+                        }
+                    },
+                    quote!(::std::option::Option::Some(&#caps_var)),
+                )
+            }
+        };
+
+        quote! {
+            #caps
+            #gst::macro_support::link(&#src, #src_pad, &#sink, #sink_pad, #filter)?;
+        }
+    });
+
+    let handles = elements
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, e)| e.handle.as_ref().map(|handle| (element_var(idx), handle)))
+        .collect::<Vec<_>>();
+    let handle_fields = handles.iter().map(|(_, handle)| handle);
+    let handle_inits = handles.iter().map(|(var, handle)| quote!(#handle: #var));
+
+    Ok(quote! {
+        (|| -> ::std::result::Result<_, #gst::glib::BoolError> {
+            #[allow(unused_imports)]
+            use #gst::prelude::*;
+
+            #[allow(dead_code)]
+            struct GstPipelineElements {
+                pub pipeline: #gst::Pipeline,
+                #(pub #handle_fields: #gst::Element,)*
+            }
+
+            #(#builds)*
+
+            let #pipeline = #gst::Pipeline::new();
+            #pipeline.add_many([#(&#vars),*])?;
+
+            #(#link_calls)*
+
+            ::std::result::Result::Ok(GstPipelineElements {
+                pipeline: #pipeline,
+                #(#handle_inits,)*
+            })
+        })()
+    })
+}
@@ -0,0 +1,32 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[test]
+fn test_caps() {
+    gst::init().unwrap();
+
+    let caps = gst::caps!("video/x-raw, format=(string){I420,NV12}, width=[1,4096]");
+    let s = caps.structure(0).unwrap();
+    assert_eq!(s.name(), "video/x-raw");
+    assert_eq!(
+        s.get::<gst::IntRange<i32>>("width").unwrap(),
+        gst::IntRange::new(1, 4096)
+    );
+    assert_eq!(
+        caps,
+        gst::Caps::builder("video/x-raw")
+            .field("format", gst::List::new(["I420", "NV12"]))
+            .field("width", gst::IntRange::new(1, 4096))
+            .build()
+    );
+
+    let caps = gst::caps!("audio/x-raw(memory:SystemMemory), rate=48000; audio/x-raw, rate=44100");
+    assert_eq!(caps.size(), 2);
+    assert!(
+        caps.features(0)
+            .unwrap()
+            .contains(gst::CAPS_FEATURE_MEMORY_SYSTEM_MEMORY)
+    );
+
+    assert!(gst::caps!("ANY").is_any());
+    assert!(gst::caps!("EMPTY").is_empty());
+}
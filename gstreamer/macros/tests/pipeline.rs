@@ -0,0 +1,71 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gst::prelude::*;
+
+#[test]
+fn test_pipeline() {
+    gst::init().unwrap();
+
+    let num_buffers = 5i32;
+    let p = gst::pipeline!(
+        fakesrc name=src num-buffers={num_buffers} sizetype=fixed sizemax=16
+            ! application/x-test, foo=(int)1, bar={2i32}
+            ! tee name=t
+        t. ! queue ! fakesink name=sink1 sync=false
+        t.src_%u ! queue name="queue" max-size-buffers=1 ! fakesink name=sink-2
+    )
+    .unwrap();
+
+    assert_eq!(p.src.property::<i32>("num-buffers"), 5);
+    assert_eq!(p.src.parent().as_ref(), Some(p.pipeline.upcast_ref()));
+    assert!(!p.sink1.property::<bool>("sync"));
+    assert_eq!(p.queue.property::<u32>("max-size-buffers"), 1);
+    assert_eq!(p.pipeline.children().len(), 7);
+    assert!(p.pipeline.by_name("sink-2").is_some());
+
+    let caps = p
+        .src
+        .static_pad("src")
+        .unwrap()
+        .peer()
+        .unwrap()
+        .parent_element()
+        .unwrap();
+    assert_eq!(caps.factory().unwrap().name(), "capsfilter");
+}
+
+#[test]
+fn test_pipeline_caps_expr() {
+    gst::init().unwrap();
+
+    let caps = gst::Caps::builder("application/x-test").build();
+    let p = gst::pipeline!(fakesrc num-buffers=1 ! {caps} ! fakesink name=sink).unwrap();
+
+    p.pipeline.set_state(gst::State::Playing).unwrap();
+    let bus = p.pipeline.bus().unwrap();
+    let msg = bus
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(5),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .unwrap();
+    assert_eq!(msg.type_(), gst::MessageType::Eos);
+    p.pipeline.set_state(gst::State::Null).unwrap();
+}
+
+#[test]
+fn test_pipeline_invalid_element() {
+    gst::init().unwrap();
+
+    assert!(gst::pipeline!(nonexistentelement ! fakesink).is_err());
+}
+
+#[test]
+fn test_pipeline_invalid_property() {
+    gst::init().unwrap();
+
+    assert!(gst::pipeline!(fakesrc nonexistent-property=1 ! fakesink).is_err());
+    assert!(gst::pipeline!(fakesrc num-buffers=abc ! fakesink).is_err());
+    assert!(gst::pipeline!(fakesrc num-buffers={"abc"} ! fakesink).is_err());
+    assert!(gst::pipeline!(fakesrc num-buffers={1u32} ! fakesink).is_ok());
+}
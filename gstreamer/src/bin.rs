@@ -9,7 +9,7 @@ use glib::{
     translate::*,
 };
 
-use crate::{Bin, BinFlags, Element, LoggableError, ffi, prelude::*};
+use crate::{Bin, BinFlags, Element, LoggableError, Pad, ffi, prelude::*};
 
 impl Bin {
     // rustdoc-stripper-ignore-next
@@ -209,6 +209,59 @@ pub trait GstBinExtManual: IsA<Bin> + 'static {
             from_glib((*ptr).flags)
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Inserts `element` between `src_pad` and its peer while the pipeline is running.
+    ///
+    /// `element` must have exactly one sink and one source pad and is added to this bin if it
+    /// has no parent yet. `src_pad` is blocked while relinking and the state of `element` is synced
+    /// with its parent.
+    ///
+    /// The returned future resolves once data flows through `element`.
+    fn insert_between(
+        &self,
+        src_pad: &impl IsA<Pad>,
+        element: &impl IsA<Element>,
+    ) -> Result<crate::LiveEditFuture, glib::BoolError> {
+        crate::live_edit::insert_between(self.as_ref(), src_pad.as_ref(), element.as_ref())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Replaces the child `old` with `new` while the pipeline is running.
+    ///
+    /// Both elements must have exactly one sink and one source pad. `new` is added to this bin if
+    /// it has no parent yet and `old` is shut down and removed from it.
+    ///
+    /// If `drain` is `true`, an EOS event is sent through `old` first so that data queued inside
+    /// it is forwarded downstream before it is replaced. Upstream stays blocked until `old`
+    /// forwards the EOS event, so this must only be used for elements that do so. Otherwise `old`
+    /// is replaced as soon as no data is flowing into it, which is only safe for elements without
+    /// their own streaming thread.
+    ///
+    /// The returned future resolves once data flows through `new`.
+    fn replace_element_live(
+        &self,
+        old: &impl IsA<Element>,
+        new: &impl IsA<Element>,
+        drain: bool,
+    ) -> Result<crate::LiveEditFuture, glib::BoolError> {
+        crate::live_edit::replace_element(self.as_ref(), old.as_ref(), Some(new.as_ref()), drain)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the child `element` while the pipeline is running and links its upstream and
+    /// downstream peers directly.
+    ///
+    /// See [`Self::replace_element_live()`] for the meaning of `drain`.
+    ///
+    /// The returned future resolves once data flows from the upstream to the downstream peer.
+    fn remove_element_live(
+        &self,
+        element: &impl IsA<Element>,
+        drain: bool,
+    ) -> Result<crate::LiveEditFuture, glib::BoolError> {
+        crate::live_edit::replace_element(self.as_ref(), element.as_ref(), None, drain)
+    }
 }
 
 impl<O: IsA<Bin>> GstBinExtManual for O {}
@@ -3,7 +3,7 @@
 use std::{fmt, io, marker::PhantomData, mem, ptr};
 
 use crate::{
-    Buffer, BufferRef,
+    Buffer, BufferList, BufferRef,
     buffer::{Readable, Writable},
     ffi,
 };
@@ -425,6 +425,145 @@ impl<'a> BufferRefCursor<&'a mut BufferRef> {
 unsafe impl<T> Send for BufferRefCursor<T> {}
 unsafe impl<T> Sync for BufferRefCursor<T> {}
 
+// rustdoc-stripper-ignore-next
+/// Readable cursor over a sequence of buffers, e.g. the content of a [`BufferList`].
+///
+/// The buffers are handled as one contiguous stream and reads transparently continue
+/// across buffer and memory boundaries.
+#[derive(Debug)]
+pub struct BufferListCursor {
+    buffers: Vec<Buffer>,
+    // Offset of the first byte of each buffer in the stream
+    offsets: Vec<u64>,
+    size: u64,
+    cur_idx: usize,
+    cur_offset: u64,
+    cur_cursor: Option<BufferCursor<Readable>>,
+}
+
+impl BufferListCursor {
+    pub fn new(buffers: impl IntoIterator<Item = Buffer>) -> Self {
+        skip_assert_initialized!();
+
+        let mut size = 0;
+        let mut offsets = Vec::new();
+        let buffers = buffers
+            .into_iter()
+            .filter(|buffer| buffer.size() > 0)
+            .inspect(|buffer| {
+                offsets.push(size);
+                size += buffer.size() as u64;
+            })
+            .collect::<Vec<_>>();
+
+        BufferListCursor {
+            buffers,
+            offsets,
+            size,
+            cur_idx: 0,
+            cur_offset: 0,
+            cur_cursor: None,
+        }
+    }
+
+    pub fn stream_len(&mut self) -> Result<u64, io::Error> {
+        Ok(self.size)
+    }
+
+    pub fn stream_position(&mut self) -> Result<u64, io::Error> {
+        Ok(self.cur_offset)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all buffers of the stream, without any empty buffers.
+    pub fn buffers(&self) -> &[Buffer] {
+        &self.buffers
+    }
+
+    pub fn into_buffer_list(self) -> BufferList {
+        self.buffers.into_iter().collect()
+    }
+}
+
+impl From<BufferList> for BufferListCursor {
+    fn from(list: BufferList) -> Self {
+        skip_assert_initialized!();
+        Self::new(list.iter_owned())
+    }
+}
+
+impl io::Read for BufferListCursor {
+    fn read(&mut self, mut data: &mut [u8]) -> Result<usize, io::Error> {
+        let mut copied = 0;
+
+        while !data.is_empty() && self.cur_idx < self.buffers.len() {
+            if self.cur_cursor.is_none() {
+                let mut cursor = self.buffers[self.cur_idx].clone().into_cursor_readable();
+                io::Seek::seek(
+                    &mut cursor,
+                    io::SeekFrom::Start(self.cur_offset - self.offsets[self.cur_idx]),
+                )?;
+                self.cur_cursor = Some(cursor);
+            }
+            let cursor = self.cur_cursor.as_mut().unwrap();
+
+            let read = io::Read::read(cursor, data)?;
+            copied += read;
+            self.cur_offset += read as u64;
+            data = &mut data[read..];
+
+            // If we're at the end of the current buffer, advance to the next one
+            if self.cur_offset == self.offsets[self.cur_idx] + cursor.size {
+                self.cur_cursor = None;
+                self.cur_idx += 1;
+            }
+        }
+
+        Ok(copied)
+    }
+}
+
+impl io::Seek for BufferListCursor {
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64, io::Error> {
+        let err = || io::Error::new(io::ErrorKind::InvalidInput, "Seek before start of buffer");
+
+        // Same semantics as for the single buffer cursors
+        let offset = match pos {
+            io::SeekFrom::Start(off) => std::cmp::min(self.size, off),
+            io::SeekFrom::End(off) if off <= 0 => self.size,
+            io::SeekFrom::End(off) => self.size.checked_sub(off as u64).ok_or_else(err)?,
+            io::SeekFrom::Current(off) if off <= 0 => self
+                .cur_offset
+                .checked_sub(off.unsigned_abs())
+                .ok_or_else(err)?,
+            io::SeekFrom::Current(off) => std::cmp::min(
+                self.size,
+                self.cur_offset.checked_add(off as u64).unwrap_or(self.size),
+            ),
+        };
+
+        let idx = self
+            .offsets
+            .partition_point(|&o| o <= offset)
+            .saturating_sub(1);
+        let idx = if offset == self.size {
+            self.buffers.len()
+        } else {
+            idx
+        };
+
+        if idx != self.cur_idx {
+            self.cur_cursor = None;
+        } else if let Some(ref mut cursor) = self.cur_cursor {
+            io::Seek::seek(cursor, io::SeekFrom::Start(offset - self.offsets[idx]))?;
+        }
+        self.cur_idx = idx;
+        self.cur_offset = offset;
+
+        Ok(self.cur_offset)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -614,4 +753,61 @@ mod tests {
         cursor.read_exact(&mut data[0..1]).unwrap();
         assert_eq!(&data[0..1], b"D");
     }
+
+    #[test]
+    fn test_buffer_list_cursor() {
+        use std::io::{self, Read, Seek};
+
+        crate::init().unwrap();
+
+        let mut buffer = Buffer::new();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            buffer.append_memory(crate::Memory::from_slice(b"01234"));
+            buffer.append_memory(crate::Memory::from_slice(b"56789"));
+        }
+        let list = BufferList::from([
+            Buffer::from_slice(b"abc"),
+            Buffer::new(),
+            buffer,
+            Buffer::from_slice(b"defg"),
+        ]);
+
+        let mut cursor = BufferListCursor::from(list);
+        assert_eq!(cursor.buffers().len(), 3);
+        assert_eq!(cursor.stream_len().unwrap(), 17);
+
+        let mut data = [0; 17];
+        cursor.read_exact(&mut data[0..5]).unwrap();
+        assert_eq!(&data[0..5], b"abc01");
+        assert_eq!(cursor.stream_position().unwrap(), 5);
+        cursor.read_exact(&mut data[0..10]).unwrap();
+        assert_eq!(&data[0..10], b"23456789de");
+        assert_eq!(cursor.read(&mut data).unwrap(), 2);
+        assert_eq!(&data[0..2], b"fg");
+        assert_eq!(cursor.read(&mut data).unwrap(), 0);
+
+        assert_eq!(cursor.seek(io::SeekFrom::Start(2)).unwrap(), 2);
+        cursor.read_exact(&mut data[0..2]).unwrap();
+        assert_eq!(&data[0..2], b"c0");
+
+        assert_eq!(cursor.seek(io::SeekFrom::Current(4)).unwrap(), 8);
+        cursor.read_exact(&mut data[0..1]).unwrap();
+        assert_eq!(&data[0..1], b"5");
+
+        assert_eq!(cursor.seek(io::SeekFrom::Current(-2)).unwrap(), 7);
+        cursor.read_exact(&mut data[0..1]).unwrap();
+        assert_eq!(&data[0..1], b"4");
+
+        assert_eq!(cursor.seek(io::SeekFrom::End(4)).unwrap(), 13);
+        cursor.read_exact(&mut data[0..4]).unwrap();
+        assert_eq!(&data[0..4], b"defg");
+
+        assert!(cursor.seek(io::SeekFrom::Current(-18)).is_err());
+        assert_eq!(cursor.stream_position().unwrap(), 17);
+
+        let list = cursor.into_buffer_list();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.calculate_size(), 17);
+    }
 }
@@ -8,7 +8,7 @@ use std::{
 
 use glib::translate::*;
 
-use crate::{Buffer, BufferRef, ffi};
+use crate::{Buffer, BufferListCursor, BufferRef, ffi};
 
 mini_object_wrapper!(BufferList, BufferListRef, ffi::GstBufferList, || {
     ffi::gst_buffer_list_get_type()
@@ -26,6 +26,10 @@ impl BufferList {
         assert_initialized_main_thread!();
         unsafe { from_glib_full(ffi::gst_buffer_list_new_sized(u32::try_from(size).unwrap())) }
     }
+
+    pub fn into_cursor_readable(self) -> BufferListCursor {
+        BufferListCursor::from(self)
+    }
 }
 
 impl BufferListRef {
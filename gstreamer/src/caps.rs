@@ -1,6 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{fmt, marker::PhantomData, ptr, str};
+use std::{cmp, fmt, marker::PhantomData, ops, ptr, str};
 
 use cfg_if::cfg_if;
 use glib::{
@@ -1265,6 +1265,103 @@ impl PartialEq for CapsRef {
 
 impl Eq for CapsRef {}
 
+// rustdoc-stripper-ignore-next
+/// Caps are ordered by the subset relation: `a <= b` if all media formats of `a` are also
+/// contained in `b`. Caps where neither is a subset of the other are not comparable.
+impl PartialOrd for CapsRef {
+    fn partial_cmp(&self, other: &CapsRef) -> Option<cmp::Ordering> {
+        match (self.is_subset(other), other.is_subset(self)) {
+            (true, true) => Some(cmp::Ordering::Equal),
+            (true, false) => Some(cmp::Ordering::Less),
+            (false, true) => Some(cmp::Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl PartialOrd for Caps {
+    fn partial_cmp(&self, other: &Caps) -> Option<cmp::Ordering> {
+        CapsRef::partial_cmp(self, other)
+    }
+}
+
+impl PartialOrd<CapsRef> for Caps {
+    fn partial_cmp(&self, other: &CapsRef) -> Option<cmp::Ordering> {
+        CapsRef::partial_cmp(self, other)
+    }
+}
+
+impl PartialOrd<Caps> for CapsRef {
+    fn partial_cmp(&self, other: &Caps) -> Option<cmp::Ordering> {
+        CapsRef::partial_cmp(self, other)
+    }
+}
+
+fn caps_union(caps: &CapsRef, other: &CapsRef) -> Caps {
+    let mut caps = caps.to_owned();
+    caps.merge(other.to_owned());
+    caps
+}
+
+macro_rules! impl_caps_binop(
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $func:path) => {
+        impl ops::$trait<&CapsRef> for &CapsRef {
+            type Output = Caps;
+
+            fn $method(self, rhs: &CapsRef) -> Caps {
+                $func(self, rhs)
+            }
+        }
+
+        impl ops::$trait<&Caps> for &CapsRef {
+            type Output = Caps;
+
+            fn $method(self, rhs: &Caps) -> Caps {
+                $func(self, rhs)
+            }
+        }
+
+        impl ops::$trait<&CapsRef> for &Caps {
+            type Output = Caps;
+
+            fn $method(self, rhs: &CapsRef) -> Caps {
+                $func(self, rhs)
+            }
+        }
+
+        impl ops::$trait<&Caps> for &Caps {
+            type Output = Caps;
+
+            fn $method(self, rhs: &Caps) -> Caps {
+                $func(self, rhs)
+            }
+        }
+
+        impl ops::$assign_trait<&CapsRef> for Caps {
+            fn $assign_method(&mut self, rhs: &CapsRef) {
+                *self = $func(self, rhs);
+            }
+        }
+
+        impl ops::$assign_trait<&Caps> for Caps {
+            fn $assign_method(&mut self, rhs: &Caps) {
+                *self = $func(self, rhs);
+            }
+        }
+    };
+);
+
+// Intersection, union and difference of the media formats described by the caps.
+impl_caps_binop!(
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    CapsRef::intersect
+);
+impl_caps_binop!(BitOr, bitor, BitOrAssign, bitor_assign, caps_union);
+impl_caps_binop!(Sub, sub, SubAssign, sub_assign, CapsRef::subtract);
+
 #[cfg(feature = "v1_28")]
 impl std::hash::Hash for CapsRef {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -1858,4 +1955,45 @@ mod tests {
         assert_eq!(bh.hash_one(&caps_b), bh.hash_one(&caps_b));
         assert_ne!(bh.hash_one(&caps_a), bh.hash_one(&caps_b));
     }
+
+    #[test]
+    fn test_set_operations() {
+        crate::init().unwrap();
+
+        let caps = Caps::builder("video/x-raw")
+            .field("format", crate::List::new(["I420", "NV12"]))
+            .field("width", crate::IntRange::new(1, 4096))
+            .build();
+        let i420 = Caps::builder("video/x-raw")
+            .field("format", "I420")
+            .field("width", crate::IntRange::new(1, 4096))
+            .build();
+        let nv12 = Caps::builder("video/x-raw")
+            .field("format", "NV12")
+            .field("width", crate::IntRange::new(1, 4096))
+            .build();
+        let audio = Caps::new_empty_simple("audio/x-raw");
+
+        assert_eq!(&caps & &i420, i420);
+        assert!((&i420 & &audio).is_empty());
+        assert_eq!(&caps - &nv12, i420);
+        assert_eq!(&i420 | &nv12, caps);
+        assert!((&caps - &i420).is_subset(&nv12));
+
+        assert!(i420 < caps);
+        assert!(caps > nv12);
+        assert!(caps <= caps.clone());
+        assert_eq!(i420.partial_cmp(&nv12), None);
+        assert_eq!(i420.partial_cmp(&audio), None);
+        assert!(Caps::new_empty() < i420);
+        assert!(caps < Caps::new_any());
+
+        let mut c = caps.clone();
+        c -= &i420;
+        assert_eq!(c, nv12);
+        c |= &audio;
+        assert_eq!(c.size(), 2);
+        c &= caps.as_ref();
+        assert_eq!(c, nv12);
+    }
 }
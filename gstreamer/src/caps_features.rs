@@ -572,6 +572,22 @@ impl CapsFeaturesRef {
         Iter::new(self)
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Checks if all features are also contained in `other`.
+    ///
+    /// Any features are only a subset of any features, and every set of features is a subset of
+    /// any features.
+    pub fn is_subset(&self, other: &CapsFeaturesRef) -> bool {
+        if other.is_any() {
+            return true;
+        }
+        if self.is_any() {
+            return false;
+        }
+
+        self.iter().all(|feature| other.contains(feature))
+    }
+
     // This is not an equivalence relation with regards to ANY. Everything is equal to ANY
     #[doc(alias = "gst_caps_features_is_equal")]
     pub fn is_equal(&self, other: &CapsFeaturesRef) -> bool {
@@ -1054,6 +1070,20 @@ mod tests {
         assert!(!cf.contains("memory:GLMemory"));
     }
 
+    #[test]
+    fn test_is_subset() {
+        crate::init().unwrap();
+
+        let dma_buf = CapsFeatures::from("memory:DMABuf");
+        let both = CapsFeatures::from(["memory:DMABuf", "meta:GstVideoOverlayComposition"]);
+
+        assert!(dma_buf.is_subset(&both));
+        assert!(!both.is_subset(&dma_buf));
+        assert!(CapsFeatures::new_empty().is_subset(&dma_buf));
+        assert!(both.is_subset(&CapsFeatures::new_any()));
+        assert!(!CapsFeatures::new_any().is_subset(&both));
+    }
+
     #[test]
     fn test_hash() {
         crate::init().unwrap();
@@ -0,0 +1,664 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{num::NonZeroU32, ptr};
+
+use glib::{prelude::*, translate::*};
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{self, Serialize, Serializer},
+};
+
+use crate::{
+    Buffer, ClockTime, Event, EventRef, EventType, GenericFormattedValue, GroupId, QOSType,
+    SeekFlags, SeekType, Segment, Seqnum, StreamFlags, Structure, TagList, Toc, event,
+    event::EventView, ffi,
+};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum EventViewSerde {
+    FlushStart,
+    FlushStop {
+        reset_time: bool,
+    },
+    StreamStart {
+        stream_id: String,
+        flags: StreamFlags,
+        group_id: Option<u32>,
+    },
+    Caps {
+        caps: crate::Caps,
+    },
+    Segment {
+        segment: Segment,
+    },
+    Tag {
+        tags: TagList,
+    },
+    Buffersize {
+        min_size: GenericFormattedValue,
+        max_size: GenericFormattedValue,
+        r#async: bool,
+    },
+    StreamGroupDone {
+        group_id: u32,
+    },
+    Eos,
+    Toc {
+        toc: Toc,
+        updated: bool,
+    },
+    Protection {
+        system_id: String,
+        data: Buffer,
+        origin: Option<String>,
+    },
+    SegmentDone {
+        position: GenericFormattedValue,
+    },
+    Gap {
+        timestamp: ClockTime,
+        duration: Option<ClockTime>,
+    },
+    #[cfg(feature = "v1_18")]
+    InstantRateChange {
+        multiplier: f64,
+        new_flags: crate::SegmentFlags,
+    },
+    Qos {
+        qos_type: QOSType,
+        proportion: f64,
+        diff: i64,
+        timestamp: Option<ClockTime>,
+    },
+    Seek {
+        rate: f64,
+        flags: SeekFlags,
+        start_type: SeekType,
+        start: GenericFormattedValue,
+        stop_type: SeekType,
+        stop: GenericFormattedValue,
+    },
+    Navigation {
+        structure: Structure,
+    },
+    Latency {
+        latency: ClockTime,
+    },
+    Step {
+        amount: GenericFormattedValue,
+        rate: f64,
+        flush: bool,
+        intermediate: bool,
+    },
+    Reconfigure,
+    TocSelect {
+        uid: String,
+    },
+    SelectStreams {
+        streams: Vec<String>,
+    },
+    #[cfg(feature = "v1_18")]
+    InstantRateSyncTime {
+        rate_multiplier: f64,
+        running_time: ClockTime,
+        upstream_running_time: ClockTime,
+    },
+    CustomUpstream {
+        structure: Structure,
+    },
+    CustomDownstream {
+        structure: Structure,
+    },
+    CustomDownstreamOob {
+        structure: Structure,
+    },
+    CustomDownstreamSticky {
+        structure: Structure,
+    },
+    CustomBoth {
+        structure: Structure,
+    },
+    CustomBothOob {
+        structure: Structure,
+    },
+    Other {
+        event_type: String,
+        structure: Option<Structure>,
+    },
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EventSerde {
+    seqnum: u32,
+    running_time_offset: i64,
+    view: EventViewSerde,
+}
+
+fn owned_structure<E: ser::Error>(event: &EventRef) -> Result<Structure, E> {
+    event
+        .structure()
+        .map(|s| s.to_owned())
+        .ok_or_else(|| E::custom(format!("{:?} event without structure", event.type_())))
+}
+
+impl Serialize for EventRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let view = match self.view() {
+            EventView::FlushStart(_) => EventViewSerde::FlushStart,
+            EventView::FlushStop(e) => EventViewSerde::FlushStop {
+                reset_time: e.resets_time(),
+            },
+            EventView::StreamStart(e) => EventViewSerde::StreamStart {
+                stream_id: e.stream_id().to_owned(),
+                flags: e.stream_flags(),
+                group_id: e.group_id().map(|group_id| group_id.0.get()),
+            },
+            EventView::Caps(e) => EventViewSerde::Caps {
+                caps: e.caps_owned(),
+            },
+            EventView::Segment(e) => EventViewSerde::Segment {
+                segment: e.segment().clone(),
+            },
+            EventView::Tag(e) => EventViewSerde::Tag {
+                tags: e.tag_owned(),
+            },
+            EventView::Buffersize(e) => {
+                let (min_size, max_size, r#async) = e.get();
+                EventViewSerde::Buffersize {
+                    min_size,
+                    max_size,
+                    r#async,
+                }
+            }
+            EventView::StreamGroupDone(e) => EventViewSerde::StreamGroupDone {
+                group_id: e.group_id().0.get(),
+            },
+            EventView::Eos(_) => EventViewSerde::Eos,
+            EventView::Toc(e) => {
+                let (toc, updated) = e.toc_owned();
+                EventViewSerde::Toc { toc, updated }
+            }
+            EventView::Protection(e) => {
+                let (system_id, data, origin) = e.get_owned();
+                EventViewSerde::Protection {
+                    system_id: system_id.to_owned(),
+                    data,
+                    origin: origin.map(ToOwned::to_owned),
+                }
+            }
+            EventView::SegmentDone(e) => EventViewSerde::SegmentDone { position: e.get() },
+            EventView::Gap(e) => {
+                let (timestamp, duration) = e.get();
+                EventViewSerde::Gap {
+                    timestamp,
+                    duration,
+                }
+            }
+            #[cfg(feature = "v1_18")]
+            EventView::InstantRateChange(e) => {
+                let (multiplier, new_flags) = e.get();
+                EventViewSerde::InstantRateChange {
+                    multiplier,
+                    new_flags,
+                }
+            }
+            EventView::Qos(e) => {
+                let (qos_type, proportion, diff, timestamp) = e.get();
+                EventViewSerde::Qos {
+                    qos_type,
+                    proportion,
+                    diff,
+                    timestamp,
+                }
+            }
+            EventView::Seek(e) => {
+                let (rate, flags, start_type, start, stop_type, stop) = e.get();
+                EventViewSerde::Seek {
+                    rate,
+                    flags,
+                    start_type,
+                    start,
+                    stop_type,
+                    stop,
+                }
+            }
+            EventView::Navigation(_) => EventViewSerde::Navigation {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::Latency(e) => EventViewSerde::Latency {
+                latency: e.latency(),
+            },
+            EventView::Step(e) => {
+                let (amount, rate, flush, intermediate) = e.get();
+                EventViewSerde::Step {
+                    amount,
+                    rate,
+                    flush,
+                    intermediate,
+                }
+            }
+            EventView::Reconfigure(_) => EventViewSerde::Reconfigure,
+            EventView::TocSelect(e) => EventViewSerde::TocSelect {
+                uid: e.uid().to_owned(),
+            },
+            EventView::SelectStreams(e) => EventViewSerde::SelectStreams {
+                streams: e.streams().iter().map(|s| s.to_string()).collect(),
+            },
+            #[cfg(feature = "v1_18")]
+            EventView::InstantRateSyncTime(e) => {
+                let (rate_multiplier, running_time, upstream_running_time) = e.get();
+                EventViewSerde::InstantRateSyncTime {
+                    rate_multiplier,
+                    running_time,
+                    upstream_running_time,
+                }
+            }
+            EventView::CustomUpstream(_) => EventViewSerde::CustomUpstream {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::CustomDownstream(_) => EventViewSerde::CustomDownstream {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::CustomDownstreamOob(_) => EventViewSerde::CustomDownstreamOob {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::CustomDownstreamSticky(_) => EventViewSerde::CustomDownstreamSticky {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::CustomBoth(_) => EventViewSerde::CustomBoth {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            EventView::CustomBothOob(_) => EventViewSerde::CustomBothOob {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            _ => EventViewSerde::Other {
+                event_type: self.type_().name().to_string(),
+                structure: self.structure().map(|s| s.to_owned()),
+            },
+        };
+
+        EventSerde {
+            seqnum: self.seqnum().0.get(),
+            running_time_offset: self.running_time_offset(),
+            view,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+fn check_format<E: de::Error>(
+    start: GenericFormattedValue,
+    stop: GenericFormattedValue,
+) -> Result<(), E> {
+    if start.format() != stop.format() {
+        return Err(E::custom(format!(
+            "mismatched formats {:?} and {:?}",
+            start.format(),
+            stop.format()
+        )));
+    }
+
+    Ok(())
+}
+
+// The C constructors return NULL if these are violated.
+fn check_rate<E: de::Error>(rate: f64) -> Result<(), E> {
+    if rate == 0.0 || rate.is_nan() {
+        return Err(E::custom(format!("invalid rate {rate}")));
+    }
+
+    Ok(())
+}
+
+fn check_defined_format<E: de::Error>(value: GenericFormattedValue) -> Result<(), E> {
+    if value.format() == crate::Format::Undefined {
+        return Err(E::custom("undefined format"));
+    }
+
+    Ok(())
+}
+
+fn event_type_from_nick<E: de::Error>(nick: &str) -> Result<EventType, E> {
+    glib::EnumClass::with_type(EventType::static_type())
+        .and_then(|class| class.value_by_nick(nick).map(|v| v.value()))
+        .map(|v| unsafe { from_glib(v) })
+        .ok_or_else(|| E::custom(format!("unknown event type {nick}")))
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+
+        let EventSerde {
+            seqnum,
+            running_time_offset,
+            view,
+        } = EventSerde::deserialize(deserializer)?;
+        let seqnum = NonZeroU32::new(seqnum).map(Seqnum);
+
+        macro_rules! build {
+            ($builder:expr) => {
+                $builder
+                    .seqnum_if_some(seqnum)
+                    .running_time_offset(running_time_offset)
+                    .build()
+            };
+        }
+
+        let event = match view {
+            EventViewSerde::FlushStart => build!(event::FlushStart::builder()),
+            EventViewSerde::FlushStop { reset_time } => {
+                build!(event::FlushStop::builder(reset_time))
+            }
+            EventViewSerde::StreamStart {
+                stream_id,
+                flags,
+                group_id,
+            } => build!(
+                event::StreamStart::builder(&stream_id)
+                    .flags(flags)
+                    .group_id_if_some(group_id.and_then(NonZeroU32::new).map(GroupId))
+            ),
+            EventViewSerde::Caps { caps } => {
+                if !caps.is_fixed() {
+                    return Err(de::Error::custom(format!("caps {caps} are not fixed")));
+                }
+                build!(event::Caps::builder(&caps))
+            }
+            EventViewSerde::Segment { segment } => {
+                check_rate::<D::Error>(segment.rate())?;
+                check_rate::<D::Error>(segment.applied_rate())?;
+                if segment.format() == crate::Format::Undefined {
+                    return Err(de::Error::custom("undefined segment format"));
+                }
+                build!(event::Segment::builder(&segment))
+            }
+            EventViewSerde::Tag { tags } => build!(event::Tag::builder(tags)),
+            EventViewSerde::Buffersize {
+                min_size,
+                max_size,
+                r#async,
+            } => {
+                check_format::<D::Error>(min_size, max_size)?;
+                build!(event::Buffersize::builder(min_size, max_size, r#async))
+            }
+            EventViewSerde::StreamGroupDone { group_id } => {
+                let group_id = NonZeroU32::new(group_id)
+                    .map(GroupId)
+                    .ok_or_else(|| <D::Error as de::Error>::custom("invalid group id 0"))?;
+                build!(event::StreamGroupDone::builder(group_id))
+            }
+            EventViewSerde::Eos => build!(event::Eos::builder()),
+            EventViewSerde::Toc { toc, updated } => build!(event::Toc::builder(&toc, updated)),
+            EventViewSerde::Protection {
+                system_id,
+                data,
+                origin,
+            } => build!(
+                event::Protection::builder(&system_id, &data).origin_if_some(origin.as_deref())
+            ),
+            EventViewSerde::SegmentDone { position } => {
+                build!(event::SegmentDone::builder(position))
+            }
+            EventViewSerde::Gap {
+                timestamp,
+                duration,
+            } => build!(event::Gap::builder(timestamp).duration(duration)),
+            #[cfg(feature = "v1_18")]
+            EventViewSerde::InstantRateChange {
+                multiplier,
+                new_flags,
+            } => {
+                check_rate::<D::Error>(multiplier)?;
+                build!(event::InstantRateChange::builder(multiplier, new_flags))
+            }
+            EventViewSerde::Qos {
+                qos_type,
+                proportion,
+                diff,
+                timestamp,
+            } => {
+                // The timestamp adjusted by `diff` must not be negative
+                if diff < 0 && timestamp.is_some_and(|ts| diff.unsigned_abs() > ts.nseconds()) {
+                    return Err(de::Error::custom(format!(
+                        "diff {diff} is larger than the timestamp"
+                    )));
+                }
+                build!(event::Qos::builder(qos_type, proportion, diff).timestamp(timestamp))
+            }
+            EventViewSerde::Seek {
+                rate,
+                flags,
+                start_type,
+                start,
+                stop_type,
+                stop,
+            } => {
+                check_format::<D::Error>(start, stop)?;
+                check_rate::<D::Error>(rate)?;
+                build!(event::Seek::builder(
+                    rate, flags, start_type, start, stop_type, stop
+                ))
+            }
+            EventViewSerde::Navigation { structure } => {
+                build!(event::Navigation::builder(structure))
+            }
+            EventViewSerde::Latency { latency } => build!(event::Latency::builder(latency)),
+            EventViewSerde::Step {
+                amount,
+                rate,
+                flush,
+                intermediate,
+            } => {
+                check_defined_format::<D::Error>(amount)?;
+                if rate <= 0.0 || rate.is_nan() {
+                    return Err(de::Error::custom(format!("invalid step rate {rate}")));
+                }
+                build!(event::Step::builder(amount, rate, flush, intermediate))
+            }
+            EventViewSerde::Reconfigure => build!(event::Reconfigure::builder()),
+            EventViewSerde::TocSelect { uid } => build!(event::TocSelect::builder(&uid)),
+            EventViewSerde::SelectStreams { streams } => build!(event::SelectStreams::builder(
+                streams.iter().map(String::as_str)
+            )),
+            #[cfg(feature = "v1_18")]
+            EventViewSerde::InstantRateSyncTime {
+                rate_multiplier,
+                running_time,
+                upstream_running_time,
+            } => {
+                check_rate::<D::Error>(rate_multiplier)?;
+                build!(event::InstantRateSyncTime::builder(
+                    rate_multiplier,
+                    running_time,
+                    upstream_running_time
+                ))
+            }
+            EventViewSerde::CustomUpstream { structure } => {
+                build!(event::CustomUpstream::builder(structure))
+            }
+            EventViewSerde::CustomDownstream { structure } => {
+                build!(event::CustomDownstream::builder(structure))
+            }
+            EventViewSerde::CustomDownstreamOob { structure } => {
+                build!(event::CustomDownstreamOob::builder(structure))
+            }
+            EventViewSerde::CustomDownstreamSticky { structure } => {
+                build!(event::CustomDownstreamSticky::builder(structure))
+            }
+            EventViewSerde::CustomBoth { structure } => {
+                build!(event::CustomBoth::builder(structure))
+            }
+            EventViewSerde::CustomBothOob { structure } => {
+                build!(event::CustomBothOob::builder(structure))
+            }
+            EventViewSerde::Other {
+                event_type,
+                structure,
+            } => {
+                let event_type = event_type_from_nick::<D::Error>(&event_type)?;
+                let mut event = unsafe {
+                    Option::<Event>::from_glib_full(ffi::gst_event_new_custom(
+                        event_type.into_glib(),
+                        structure
+                            .map(|s| s.into_glib_ptr())
+                            .unwrap_or(ptr::null_mut()),
+                    ))
+                    .ok_or_else(|| {
+                        <D::Error as de::Error>::custom(format!(
+                            "can't create {event_type:?} event"
+                        ))
+                    })?
+                };
+
+                {
+                    let event = event.get_mut().unwrap();
+                    if let Some(seqnum) = seqnum {
+                        event.set_seqnum(seqnum);
+                    }
+                    event.set_running_time_offset(running_time_offset);
+                }
+
+                event
+            }
+        };
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ClockTime, Event, GenericFormattedValue, SeekFlags, SeekType, Structure,
+        event::{self, EventView},
+    };
+
+    #[test]
+    fn test_serialize() {
+        crate::init().unwrap();
+
+        let event = event::FlushStop::builder(true)
+            .seqnum(crate::Seqnum::next())
+            .running_time_offset(42)
+            .build();
+
+        let pretty_config = ron::ser::PrettyConfig::new().new_line("".to_string());
+
+        let res = ron::ser::to_string_pretty(&event, pretty_config);
+        let seqnum = event.seqnum().0.get();
+        assert_eq!(
+            Ok(format!(
+                concat!(
+                    "(",
+                    "    seqnum: {},",
+                    "    running_time_offset: 42,",
+                    "    view: FlushStop(",
+                    "        reset_time: true,",
+                    "    ),",
+                    ")"
+                ),
+                seqnum
+            )),
+            res,
+        );
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        crate::init().unwrap();
+
+        let seek = event::Seek::builder(
+            1.5,
+            SeekFlags::FLUSH | SeekFlags::ACCURATE,
+            SeekType::Set,
+            ClockTime::from_seconds(1),
+            SeekType::End,
+            ClockTime::NONE,
+        )
+        .running_time_offset(-10)
+        .build();
+        let res = ron::ser::to_string(&seek).unwrap();
+        let seek_de: Event = ron::de::from_str(&res).unwrap();
+        assert_eq!(seek_de.seqnum(), seek.seqnum());
+        assert_eq!(seek_de.running_time_offset(), -10);
+        match seek_de.view() {
+            EventView::Seek(s) => {
+                let (rate, flags, start_type, start, stop_type, stop) = s.get();
+                assert_eq!(rate, 1.5);
+                assert_eq!(flags, SeekFlags::FLUSH | SeekFlags::ACCURATE);
+                assert_eq!(start_type, SeekType::Set);
+                assert_eq!(
+                    start,
+                    GenericFormattedValue::from(ClockTime::from_seconds(1))
+                );
+                assert_eq!(stop_type, SeekType::End);
+                assert_eq!(stop, GenericFormattedValue::from(ClockTime::NONE));
+            }
+            _ => panic!("unexpected event {seek_de:?}"),
+        }
+
+        let stream_start = event::StreamStart::builder("stream-id")
+            .group_id(crate::GroupId::next())
+            .build();
+        let res = ron::ser::to_string(&stream_start).unwrap();
+        let stream_start_de: Event = ron::de::from_str(&res).unwrap();
+        match (stream_start.view(), stream_start_de.view()) {
+            (EventView::StreamStart(a), EventView::StreamStart(b)) => {
+                assert_eq!(a.stream_id(), b.stream_id());
+                assert_eq!(a.stream_flags(), b.stream_flags());
+                assert_eq!(a.group_id(), b.group_id());
+            }
+            _ => panic!("unexpected event {stream_start_de:?}"),
+        }
+
+        let custom = event::CustomDownstream::builder(
+            Structure::builder("custom").field("foo", 42i32).build(),
+        )
+        .build();
+        let res = ron::ser::to_string(&custom).unwrap();
+        let custom_de: Event = ron::de::from_str(&res).unwrap();
+        assert_eq!(custom_de.type_(), custom.type_());
+        assert_eq!(custom_de.structure(), custom.structure());
+        assert_eq!(custom_de.seqnum(), custom.seqnum());
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        crate::init().unwrap();
+
+        let seek = event::Seek::new(
+            1.5,
+            SeekFlags::FLUSH,
+            SeekType::Set,
+            ClockTime::ZERO,
+            SeekType::None,
+            ClockTime::NONE,
+        );
+        let res = ron::ser::to_string(&seek).unwrap();
+        assert!(res.contains("rate:1.5"));
+        let res = res.replace("rate:1.5", "rate:0.0");
+        assert!(ron::de::from_str::<Event>(&res).is_err());
+
+        let step = event::Step::new(crate::format::Buffers::from_u64(1), 1.5, true, false);
+        let res = ron::ser::to_string(&step).unwrap();
+        assert!(ron::de::from_str::<Event>(&res).is_ok());
+        assert!(ron::de::from_str::<Event>(&res.replace("rate:1.5", "rate:-1.5")).is_err());
+
+        let unfixed = super::EventSerde {
+            seqnum: 0,
+            running_time_offset: 0,
+            view: super::EventViewSerde::Caps {
+                caps: crate::Caps::new_any(),
+            },
+        };
+        let res = ron::ser::to_string(&unfixed).unwrap();
+        assert!(ron::de::from_str::<Event>(&res).is_err());
+    }
+}
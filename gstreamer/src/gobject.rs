@@ -70,7 +70,7 @@ pub enum GObjectError {
     },
 }
 
-fn value_from_property_str(
+pub(crate) fn value_from_property_str(
     pspec: glib::ParamSpec,
     value: &str,
 ) -> Result<glib::Value, GObjectError> {
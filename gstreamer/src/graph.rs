@@ -0,0 +1,880 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Structured snapshots of the topology of a [`Bin`].
+//!
+//! Unlike [`debug_to_dot_data()`](crate::prelude::GstBinExtManual::debug_to_dot_data), a
+//! [`PipelineGraph`] keeps the elements, pads, links and their negotiated caps as data. It can be
+//! exported as DOT, Mermaid or JSON, serialized with the `serde` feature, and two snapshots can be
+//! compared with [`PipelineGraph::diff()`], e.g. to highlight what changed after a renegotiation.
+//!
+//! Elements are identified by their path of names relative to the snapshotted bin, e.g.
+//! `pipeline0/bin0/queue0`, and pads by the path of their element followed by `.` and the pad
+//! name, e.g. `pipeline0/bin0/queue0.src`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use crate::{
+    Bin, Element, GhostPad, Pad, PadDirection, PadPresence, State, prelude::*,
+    utils::write_json_string,
+};
+
+// rustdoc-stripper-ignore-next
+/// Snapshot of a bin, all its children and the links between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineGraph {
+    pub root: ElementNode,
+    pub links: Vec<Link>,
+}
+
+// rustdoc-stripper-ignore-next
+/// An element of a [`PipelineGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementNode {
+    pub name: String,
+    pub path: String,
+    pub factory_name: Option<String>,
+    pub type_name: String,
+    pub state: State,
+    pub pending_state: State,
+    // rustdoc-stripper-ignore-next
+    /// Serialized values of all readable and writable properties that differ from their default
+    /// value.
+    pub properties: BTreeMap<String, String>,
+    pub pads: Vec<PadNode>,
+    // rustdoc-stripper-ignore-next
+    /// The child elements if this element is a bin.
+    pub children: Option<Vec<ElementNode>>,
+}
+
+// rustdoc-stripper-ignore-next
+/// A pad of an [`ElementNode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PadNode {
+    pub name: String,
+    pub path: String,
+    pub direction: PadDirection,
+    pub presence: Option<PadPresence>,
+    pub caps: Option<String>,
+    // rustdoc-stripper-ignore-next
+    /// The path of the target pad if this is a ghost pad.
+    pub ghost_target: Option<String>,
+}
+
+// rustdoc-stripper-ignore-next
+/// A link between a source and a sink pad of a [`PipelineGraph`].
+///
+/// Links between ghost pads and their targets are not included here but are available via
+/// [`PadNode::ghost_target`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Link {
+    pub src: String,
+    pub sink: String,
+    pub caps: Option<String>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Differences between two [`PipelineGraph`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphDiff {
+    pub added_elements: Vec<String>,
+    pub removed_elements: Vec<String>,
+    pub added_pads: Vec<String>,
+    pub removed_pads: Vec<String>,
+    pub added_links: Vec<Link>,
+    pub removed_links: Vec<Link>,
+    pub state_changes: Vec<StateChange>,
+    pub caps_changes: Vec<CapsChange>,
+    pub property_changes: Vec<PropertyChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateChange {
+    pub element: String,
+    pub old: State,
+    pub new: State,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CapsChange {
+    pub pad: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyChange {
+    pub element: String,
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self == &GraphDiff::default()
+    }
+}
+
+impl PipelineGraph {
+    // rustdoc-stripper-ignore-next
+    /// Takes a snapshot of `bin` and all elements inside it.
+    pub fn from_bin(bin: &impl IsA<Bin>) -> Self {
+        let root = bin.upcast_ref::<Element>();
+        let mut links = Vec::new();
+        let root_node = element_node(root, root, String::from(root.name().as_str()), &mut links);
+        links.sort();
+
+        PipelineGraph {
+            root: root_node,
+            links,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Iterates over all elements of the graph, including the root bin, in depth-first order.
+    pub fn elements(&self) -> impl Iterator<Item = &ElementNode> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            if let Some(ref children) = node.children {
+                stack.extend(children.iter().rev());
+            }
+            Some(node)
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the element with the given path.
+    pub fn element(&self, path: &str) -> Option<&ElementNode> {
+        self.elements().find(|e| e.path == path)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the pad with the given path.
+    pub fn pad(&self, path: &str) -> Option<&PadNode> {
+        self.elements()
+            .flat_map(|e| e.pads.iter())
+            .find(|p| p.path == path)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Computes the changes from `self` to `other`.
+    pub fn diff(&self, other: &PipelineGraph) -> GraphDiff {
+        let old_elements = self
+            .elements()
+            .map(|e| (e.path.as_str(), e))
+            .collect::<BTreeMap<_, _>>();
+        let new_elements = other
+            .elements()
+            .map(|e| (e.path.as_str(), e))
+            .collect::<BTreeMap<_, _>>();
+
+        let old_pads = old_elements
+            .values()
+            .flat_map(|e| e.pads.iter())
+            .map(|p| (p.path.as_str(), p))
+            .collect::<BTreeMap<_, _>>();
+        let new_pads = new_elements
+            .values()
+            .flat_map(|e| e.pads.iter())
+            .map(|p| (p.path.as_str(), p))
+            .collect::<BTreeMap<_, _>>();
+
+        let old_links = self
+            .links
+            .iter()
+            .map(|l| (l.src.as_str(), l.sink.as_str()))
+            .collect::<BTreeSet<_>>();
+        let new_links = other
+            .links
+            .iter()
+            .map(|l| (l.src.as_str(), l.sink.as_str()))
+            .collect::<BTreeSet<_>>();
+
+        let mut diff = GraphDiff {
+            added_elements: added(&old_elements, &new_elements),
+            removed_elements: added(&new_elements, &old_elements),
+            added_pads: added(&old_pads, &new_pads),
+            removed_pads: added(&new_pads, &old_pads),
+            added_links: other
+                .links
+                .iter()
+                .filter(|l| !old_links.contains(&(l.src.as_str(), l.sink.as_str())))
+                .cloned()
+                .collect(),
+            removed_links: self
+                .links
+                .iter()
+                .filter(|l| !new_links.contains(&(l.src.as_str(), l.sink.as_str())))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        for (path, old) in &old_elements {
+            let Some(new) = new_elements.get(path) else {
+                continue;
+            };
+
+            if old.state != new.state {
+                diff.state_changes.push(StateChange {
+                    element: path.to_string(),
+                    old: old.state,
+                    new: new.state,
+                });
+            }
+
+            let names = old
+                .properties
+                .keys()
+                .chain(new.properties.keys())
+                .collect::<BTreeSet<_>>();
+            for name in names {
+                let old_value = old.properties.get(name);
+                let new_value = new.properties.get(name);
+                if old_value != new_value {
+                    diff.property_changes.push(PropertyChange {
+                        element: path.to_string(),
+                        name: name.clone(),
+                        old: old_value.cloned(),
+                        new: new_value.cloned(),
+                    });
+                }
+            }
+        }
+
+        for (path, old) in &old_pads {
+            if let Some(new) = new_pads.get(path)
+                && old.caps != new.caps
+            {
+                diff.caps_changes.push(CapsChange {
+                    pad: path.to_string(),
+                    old: old.caps.clone(),
+                    new: new.caps.clone(),
+                });
+            }
+        }
+
+        diff
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Exports the graph in the DOT format of graphviz.
+    ///
+    /// Bins are drawn as clusters with their ghost pads as nodes inside them, the mapping of ghost
+    /// pads to their targets is drawn with dashed edges.
+    pub fn to_dot(&self) -> String {
+        let ids = node_ids(self);
+        let mut dot = String::from("digraph pipeline {\n  rankdir=LR;\n  node [shape=box];\n");
+
+        fn write_node(dot: &mut String, ids: &NodeIds, node: &ElementNode, indent: usize) {
+            let pad = "  ".repeat(indent);
+            match node.children {
+                Some(ref children) => {
+                    let _ = writeln!(dot, "{pad}subgraph cluster_{} {{", ids.elements[&node.path]);
+                    let _ = writeln!(
+                        dot,
+                        "{pad}  label=\"{}\";",
+                        dot_escape(&element_label(node))
+                    );
+                    for ghost in node.pads.iter().filter(|p| p.ghost_target.is_some()) {
+                        let _ = writeln!(
+                            dot,
+                            "{pad}  {} [label=\"{}\", shape=ellipse];",
+                            ids.pads[&ghost.path],
+                            dot_escape(&ghost.name)
+                        );
+                    }
+                    for child in children {
+                        write_node(dot, ids, child, indent + 1);
+                    }
+                    let _ = writeln!(dot, "{pad}}}");
+                }
+                None => {
+                    let _ = writeln!(
+                        dot,
+                        "{pad}{} [label=\"{}\"];",
+                        ids.elements[&node.path],
+                        dot_escape(&element_label(node))
+                    );
+                }
+            }
+        }
+        write_node(&mut dot, &ids, &self.root, 1);
+
+        for (src, sink, label) in ghost_edges(self, &ids) {
+            let _ = writeln!(
+                dot,
+                "  {src} -> {sink} [label=\"{}\", style=dashed];",
+                dot_escape(&label)
+            );
+        }
+        for (src, sink, label) in link_edges(self, &ids) {
+            let _ = writeln!(dot, "  {src} -> {sink} [label=\"{}\"];", dot_escape(&label));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Exports the graph as a Mermaid flowchart.
+    ///
+    /// Bins are drawn as subgraphs with their ghost pads as nodes inside them, the mapping of
+    /// ghost pads to their targets is drawn with dotted edges.
+    pub fn to_mermaid(&self) -> String {
+        let ids = node_ids(self);
+        let mut mermaid = String::from("flowchart LR\n");
+
+        fn write_node(mermaid: &mut String, ids: &NodeIds, node: &ElementNode, indent: usize) {
+            let pad = "    ".repeat(indent);
+            match node.children {
+                Some(ref children) => {
+                    let _ = writeln!(
+                        mermaid,
+                        "{pad}subgraph {}[\"{}\"]",
+                        ids.elements[&node.path],
+                        mermaid_escape(&element_label(node))
+                    );
+                    for ghost in node.pads.iter().filter(|p| p.ghost_target.is_some()) {
+                        let _ = writeln!(
+                            mermaid,
+                            "{pad}    {}((\"{}\"))",
+                            ids.pads[&ghost.path],
+                            mermaid_escape(&ghost.name)
+                        );
+                    }
+                    for child in children {
+                        write_node(mermaid, ids, child, indent + 1);
+                    }
+                    let _ = writeln!(mermaid, "{pad}end");
+                }
+                None => {
+                    let _ = writeln!(
+                        mermaid,
+                        "{pad}{}[\"{}\"]",
+                        ids.elements[&node.path],
+                        mermaid_escape(&element_label(node))
+                    );
+                }
+            }
+        }
+        write_node(&mut mermaid, &ids, &self.root, 1);
+
+        for (src, sink, label) in ghost_edges(self, &ids) {
+            let _ = writeln!(
+                mermaid,
+                "    {src} -.->|\"{}\"| {sink}",
+                mermaid_escape(&label)
+            );
+        }
+        for (src, sink, label) in link_edges(self, &ids) {
+            let _ = writeln!(
+                mermaid,
+                "    {src} -->|\"{}\"| {sink}",
+                mermaid_escape(&label)
+            );
+        }
+
+        mermaid
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Exports the graph as JSON.
+    ///
+    /// The output has the same structure as the serialization of the graph with `serde`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\"root\":");
+        element_json(&mut json, &self.root);
+        json.push_str(",\"links\":[");
+        for (idx, link) in self.links.iter().enumerate() {
+            if idx > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"src\":");
+            write_json_string(&mut json, &link.src);
+            json.push_str(",\"sink\":");
+            write_json_string(&mut json, &link.sink);
+            json.push_str(",\"caps\":");
+            json_option(&mut json, link.caps.as_deref());
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+fn added<'a, T>(old: &BTreeMap<&'a str, T>, new: &BTreeMap<&'a str, T>) -> Vec<String> {
+    new.keys()
+        .filter(|path| !old.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect()
+}
+
+// Returns the path of `element` relative to `root`, or `None` if it's not inside `root`.
+fn element_path(root: &Element, element: &Element) -> Option<String> {
+    let mut names = vec![element.name()];
+    let mut current = element.clone();
+    while &current != root {
+        current = current.parent()?.downcast::<Element>().ok()?;
+        names.push(current.name());
+    }
+
+    Some(
+        names
+            .iter()
+            .rev()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+fn pad_path(root: &Element, pad: &Pad) -> Option<String> {
+    let element = pad.parent_element()?;
+    Some(format!("{}.{}", element_path(root, &element)?, pad.name()))
+}
+
+fn element_node(
+    root: &Element,
+    element: &Element,
+    path: String,
+    links: &mut Vec<Link>,
+) -> ElementNode {
+    let pads = element
+        .pads()
+        .iter()
+        .map(|pad| {
+            let pad_path = format!("{path}.{}", pad.name());
+
+            if pad.direction() == PadDirection::Src
+                && let Some(peer) = pad.peer()
+                && let Some(peer_path) = pad_path_of_peer(root, &peer)
+            {
+                links.push(Link {
+                    src: pad_path.clone(),
+                    sink: peer_path,
+                    caps: pad.current_caps().map(|caps| caps.to_string()),
+                });
+            }
+
+            PadNode {
+                name: pad.name().into(),
+                direction: pad.direction(),
+                presence: pad.pad_template().map(|templ| templ.presence()),
+                caps: pad.current_caps().map(|caps| caps.to_string()),
+                ghost_target: pad
+                    .downcast_ref::<GhostPad>()
+                    .and_then(|ghost| ghost.target())
+                    .and_then(|target| pad_path(root, &target)),
+                path: pad_path,
+            }
+        })
+        .collect();
+
+    let children = element.downcast_ref::<Bin>().map(|bin| {
+        // Children are stored in reverse order of addition.
+        bin.children()
+            .iter()
+            .rev()
+            .map(|child| {
+                let child_path = format!("{path}/{}", child.name());
+                element_node(root, child, child_path, links)
+            })
+            .collect()
+    });
+
+    ElementNode {
+        name: element.name().into(),
+        factory_name: element.factory().map(|factory| factory.name().into()),
+        type_name: element.type_().name().to_owned(),
+        state: element.current_state(),
+        pending_state: element.pending_state(),
+        properties: crate::utils::non_default_properties(element),
+        pads,
+        children,
+        path,
+    }
+}
+
+// Links to the internal pad of a ghost pad are represented by the ghost pad's target instead.
+fn pad_path_of_peer(root: &Element, peer: &Pad) -> Option<String> {
+    if peer.parent_element().is_none() {
+        return None;
+    }
+
+    pad_path(root, peer)
+}
+
+fn element_label(node: &ElementNode) -> String {
+    let mut label = node.name.clone();
+    if let Some(ref factory_name) = node.factory_name
+        && node.children.is_none()
+    {
+        let _ = write!(label, "\n{factory_name}");
+    }
+    let _ = write!(label, "\n[{:?}]", node.state);
+    label
+}
+
+struct NodeIds {
+    elements: BTreeMap<String, String>,
+    pads: BTreeMap<String, String>,
+}
+
+// Node identifiers can't contain arbitrary characters so assign a generated one to each element
+// and ghost pad.
+fn node_ids(graph: &PipelineGraph) -> NodeIds {
+    let mut ids = NodeIds {
+        elements: BTreeMap::new(),
+        pads: BTreeMap::new(),
+    };
+
+    for (idx, element) in graph.elements().enumerate() {
+        ids.elements
+            .insert(element.path.clone(), format!("element{idx}"));
+    }
+    for (idx, pad) in graph
+        .elements()
+        .flat_map(|e| e.pads.iter())
+        .filter(|p| p.ghost_target.is_some())
+        .enumerate()
+    {
+        ids.pads.insert(pad.path.clone(), format!("ghostpad{idx}"));
+    }
+
+    ids
+}
+
+// The node that represents a pad: the pad itself if it is a ghost pad, otherwise its element.
+fn pad_node_id<'a>(ids: &'a NodeIds, pad_path: &str) -> Option<&'a str> {
+    if let Some(id) = ids.pads.get(pad_path) {
+        return Some(id);
+    }
+
+    let (element_path, _) = pad_path.rsplit_once('.')?;
+    ids.elements.get(element_path).map(String::as_str)
+}
+
+fn ghost_edges(graph: &PipelineGraph, ids: &NodeIds) -> Vec<(String, String, String)> {
+    graph
+        .elements()
+        .flat_map(|e| e.pads.iter())
+        .filter_map(|pad| {
+            let target = pad.ghost_target.as_deref()?;
+            let ghost_id = ids.pads.get(&pad.path)?.clone();
+            let target_id = pad_node_id(ids, target)?.to_owned();
+            let target_name = target.rsplit_once('.').map_or(target, |(_, name)| name);
+
+            Some(if pad.direction == PadDirection::Src {
+                (target_id, ghost_id, target_name.to_owned())
+            } else {
+                (ghost_id, target_id, target_name.to_owned())
+            })
+        })
+        .collect()
+}
+
+fn link_edges(graph: &PipelineGraph, ids: &NodeIds) -> Vec<(String, String, String)> {
+    graph
+        .links
+        .iter()
+        .filter_map(|link| {
+            let src = pad_node_id(ids, &link.src)?.to_owned();
+            let sink = pad_node_id(ids, &link.sink)?.to_owned();
+
+            let src_name = link.src.rsplit_once('.').map_or("", |(_, name)| name);
+            let sink_name = link.sink.rsplit_once('.').map_or("", |(_, name)| name);
+            let mut label = format!("{src_name} -> {sink_name}");
+            if let Some(ref caps) = link.caps {
+                let _ = write!(label, "\n{caps}");
+            }
+
+            Some((src, sink, label))
+        })
+        .collect()
+}
+
+fn dot_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn mermaid_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn json_option(json: &mut String, s: Option<&str>) {
+    match s {
+        Some(s) => write_json_string(json, s),
+        None => json.push_str("null"),
+    }
+}
+
+fn element_json(json: &mut String, node: &ElementNode) {
+    json.push_str("{\"name\":");
+    write_json_string(json, &node.name);
+    json.push_str(",\"path\":");
+    write_json_string(json, &node.path);
+    json.push_str(",\"factory_name\":");
+    json_option(json, node.factory_name.as_deref());
+    json.push_str(",\"type_name\":");
+    write_json_string(json, &node.type_name);
+    json.push_str(",\"state\":");
+    write_json_string(json, &format!("{:?}", node.state));
+    json.push_str(",\"pending_state\":");
+    write_json_string(json, &format!("{:?}", node.pending_state));
+
+    json.push_str(",\"properties\":{");
+    for (idx, (name, value)) in node.properties.iter().enumerate() {
+        if idx > 0 {
+            json.push(',');
+        }
+        write_json_string(json, name);
+        json.push(':');
+        write_json_string(json, value);
+    }
+
+    json.push_str("},\"pads\":[");
+    for (idx, pad) in node.pads.iter().enumerate() {
+        if idx > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"name\":");
+        write_json_string(json, &pad.name);
+        json.push_str(",\"path\":");
+        write_json_string(json, &pad.path);
+        json.push_str(",\"direction\":");
+        write_json_string(json, &format!("{:?}", pad.direction));
+        json.push_str(",\"presence\":");
+        json_option(json, pad.presence.map(|p| format!("{p:?}")).as_deref());
+        json.push_str(",\"caps\":");
+        json_option(json, pad.caps.as_deref());
+        json.push_str(",\"ghost_target\":");
+        json_option(json, pad.ghost_target.as_deref());
+        json.push('}');
+    }
+
+    json.push_str("],\"children\":");
+    match node.children {
+        Some(ref children) => {
+            json.push('[');
+            for (idx, child) in children.iter().enumerate() {
+                if idx > 0 {
+                    json.push(',');
+                }
+                element_json(json, child);
+            }
+            json.push(']');
+        }
+        None => json.push_str("null"),
+    }
+    json.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline() -> crate::Pipeline {
+        let pipeline = crate::Pipeline::with_name("pipeline");
+        let src = crate::ElementFactory::make("fakesrc")
+            .name("src")
+            .property("num-buffers", 1i32)
+            .build()
+            .unwrap();
+        let sink = crate::ElementFactory::make("fakesink")
+            .name("sink")
+            .property("sync", false)
+            .build()
+            .unwrap();
+
+        let bin = Bin::with_name("bin");
+        let identity = crate::ElementFactory::make("identity")
+            .name("id")
+            .build()
+            .unwrap();
+        let queue = crate::ElementFactory::make("queue")
+            .name("queue")
+            .build()
+            .unwrap();
+        bin.add_many([&identity, &queue]).unwrap();
+        identity.link(&queue).unwrap();
+        bin.add_pad(
+            &GhostPad::builder_with_target(&identity.static_pad("sink").unwrap())
+                .unwrap()
+                .name("sink")
+                .build(),
+        )
+        .unwrap();
+        bin.add_pad(
+            &GhostPad::builder_with_target(&queue.static_pad("src").unwrap())
+                .unwrap()
+                .name("src")
+                .build(),
+        )
+        .unwrap();
+
+        pipeline.add_many([&src, bin.upcast_ref(), &sink]).unwrap();
+        Element::link_many([&src, bin.upcast_ref(), &sink]).unwrap();
+
+        pipeline
+    }
+
+    #[test]
+    fn test_from_bin() {
+        crate::init().unwrap();
+
+        let pipeline = pipeline();
+        let root = pipeline.name();
+        let graph = PipelineGraph::from_bin(&pipeline);
+
+        assert_eq!(graph.root.path, root.as_str());
+        assert_eq!(graph.root.state, State::Null);
+
+        let src = graph.element(&format!("{root}/src")).unwrap();
+        assert_eq!(src.factory_name.as_deref(), Some("fakesrc"));
+        assert_eq!(
+            src.properties.get("num-buffers").map(String::as_str),
+            Some("1")
+        );
+        assert!(src.children.is_none());
+
+        let bin = graph.element(&format!("{root}/bin")).unwrap();
+        assert_eq!(bin.children.as_ref().unwrap().len(), 2);
+        assert!(graph.element(&format!("{root}/bin/queue")).is_some());
+
+        let ghost = bin
+            .pads
+            .iter()
+            .find(|p| p.direction == PadDirection::Sink)
+            .unwrap();
+        assert_eq!(
+            ghost.ghost_target.as_deref(),
+            Some(format!("{root}/bin/id.sink").as_str())
+        );
+
+        let links = graph
+            .links
+            .iter()
+            .map(|l| (l.src.as_str(), l.sink.as_str()))
+            .collect::<Vec<_>>();
+        assert!(links.contains(&(
+            format!("{root}/bin/id.src").as_str(),
+            format!("{root}/bin/queue.sink").as_str()
+        )));
+        assert!(links.contains(&(
+            format!("{root}/src.src").as_str(),
+            format!("{root}/bin.sink").as_str()
+        )));
+        assert_eq!(links.len(), 3);
+    }
+
+    #[test]
+    fn test_exporters() {
+        crate::init().unwrap();
+
+        let graph = PipelineGraph::from_bin(&pipeline());
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph pipeline {"));
+        assert!(dot.contains("subgraph cluster_"));
+        assert!(dot.contains("style=dashed"));
+        assert_eq!(dot.matches(" -> ").count(), 3 + 2 + 3);
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert_eq!(mermaid.matches(" -.->").count(), 2);
+        assert_eq!(mermaid.matches(" -->").count(), 3);
+
+        let json = graph.to_json();
+        assert!(json.starts_with("{\"root\":{\"name\":"));
+        assert!(json.contains("\"factory_name\":\"fakesrc\""));
+        assert!(json.contains("\"state\":\"Null\""));
+    }
+
+    #[test]
+    fn test_diff() {
+        crate::init().unwrap();
+
+        let pipeline = pipeline();
+        let root = pipeline.name();
+        let before = PipelineGraph::from_bin(&pipeline);
+        assert!(before.diff(&before).is_empty());
+
+        let src = pipeline.by_name("src").unwrap();
+        src.set_property("num-buffers", 2i32);
+        let extra = crate::ElementFactory::make("fakesink")
+            .name("extra")
+            .build()
+            .unwrap();
+        pipeline.add(&extra).unwrap();
+        pipeline.set_state(State::Ready).unwrap();
+
+        let after = PipelineGraph::from_bin(&pipeline);
+        let diff = before.diff(&after);
+        pipeline.set_state(State::Null).unwrap();
+
+        assert_eq!(diff.added_elements, [format!("{root}/extra")]);
+        assert!(diff.removed_elements.is_empty());
+        assert_eq!(diff.added_pads, [format!("{root}/extra.sink")]);
+        assert!(diff.added_links.is_empty());
+        assert!(diff.removed_links.is_empty());
+        assert_eq!(
+            diff.property_changes,
+            [PropertyChange {
+                element: format!("{root}/src"),
+                name: String::from("num-buffers"),
+                old: Some(String::from("1")),
+                new: Some(String::from("2")),
+            }]
+        );
+        assert!(
+            diff.state_changes
+                .iter()
+                .any(|c| c.element == root.as_str() && c.new == State::Ready)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        crate::init().unwrap();
+
+        let graph = PipelineGraph::from_bin(&pipeline());
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(json, graph.to_json());
+
+        let graph_de: PipelineGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph_de, graph);
+    }
+}
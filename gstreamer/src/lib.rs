@@ -13,6 +13,8 @@ pub use gstreamer_sys as ffi;
 pub use pastey as paste;
 pub use pastey;
 
+pub use gstreamer_macros::{caps, pipeline};
+
 #[doc(hidden)]
 pub static INITIALIZED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
 
@@ -104,6 +106,8 @@ mod flag_serde;
 
 pub mod message;
 pub use crate::message::{Message, MessageErrorDomain, MessageRef, MessageView, MessageViewMut};
+#[cfg(feature = "serde")]
+mod message_serde;
 
 pub mod structure;
 pub use crate::structure::{Structure, StructureRef};
@@ -144,7 +148,7 @@ pub use crate::buffer::{
     BUFFER_COPY_ALL, BUFFER_COPY_METADATA, Buffer, BufferMap, BufferRef, MappedBuffer,
 };
 mod buffer_cursor;
-pub use crate::buffer_cursor::{BufferCursor, BufferRefCursor};
+pub use crate::buffer_cursor::{BufferCursor, BufferListCursor, BufferRefCursor};
 pub mod memory;
 mod memory_wrapped;
 #[cfg(feature = "v1_26")]
@@ -168,8 +172,12 @@ mod bufferlist_serde;
 
 pub mod query;
 pub use crate::query::{Query, QueryRef, QueryView, QueryViewMut};
+#[cfg(feature = "serde")]
+mod query_serde;
 pub mod event;
 pub use crate::event::{Event, EventRef, EventView, EventViewMut, GroupId, Seqnum};
+#[cfg(feature = "serde")]
+mod event_serde;
 pub mod context;
 #[cfg(feature = "v1_28")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_28")))]
@@ -193,6 +201,8 @@ mod dynamic_type_factory;
 
 mod bin;
 pub use bin::BinBuilder;
+mod live_edit;
+pub use live_edit::LiveEditFuture;
 
 mod pipeline;
 pub use pipeline::PipelineBuilder;
@@ -234,6 +244,8 @@ mod control_binding;
 mod control_source;
 mod parse_context;
 mod preset;
+mod property_preset;
+pub use crate::property_preset::{PropertyPreset, PropertyPresetChange, PropertyPresetError};
 mod proxy_pad;
 mod registry;
 mod system_clock;
@@ -259,6 +271,7 @@ pub use crate::{
 mod plugin_feature;
 
 mod plugin;
+pub use crate::plugin::StaticPluginBuilder;
 pub mod stream;
 pub mod stream_collection;
 
@@ -307,6 +320,11 @@ pub use crate::utils::ObjectLockGuard;
 
 pub mod parse;
 
+pub mod graph;
+
+#[doc(hidden)]
+pub mod macro_support;
+
 #[cfg(feature = "v1_28")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_28")))]
 pub mod cpuid;
@@ -0,0 +1,383 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Helpers for changing the elements of a running pipeline.
+//
+// All of them follow the same scheme: the source pad upstream of the edited part of the pipeline
+// is blocked with an IDLE probe, the element to be removed is optionally drained by sending an
+// EOS event through it, then the pads are relinked and the states of the new elements are synced
+// with their parent before the upstream pad is unblocked again.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use futures_channel::oneshot;
+
+use crate::{
+    Bin, Element, EventType, Pad, PadDirection, PadProbeReturn, PadProbeType, State, prelude::*,
+};
+
+type Sender = Arc<Mutex<Option<oneshot::Sender<Result<(), glib::BoolError>>>>>;
+
+// rustdoc-stripper-ignore-next
+/// Future returned by the live editing functions of [`GstBinExtManual`].
+///
+/// Resolves once the first buffer after relinking has passed through the edited part of the
+/// pipeline, or with an error if relinking failed. If no data is flowing anymore, e.g. because
+/// the pipeline was shut down in the meantime, the future never resolves.
+///
+/// When draining, upstream stays blocked until the removed element forwards the EOS event. If it
+/// never does, e.g. because it is a sink or drops EOS, the edit never completes and the future
+/// never resolves.
+///
+/// Dropping the future does not cancel the edit.
+///
+/// [`GstBinExtManual`]: crate::prelude::GstBinExtManual
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LiveEditFuture(oneshot::Receiver<Result<(), glib::BoolError>>);
+
+impl Future for LiveEditFuture {
+    type Output = Result<(), glib::BoolError>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.0).poll(context) {
+            Poll::Ready(Ok(res)) => Poll::Ready(res),
+            Poll::Ready(Err(_)) => Poll::Ready(Err(glib::bool_error!(
+                "Edited elements were disposed before data flowed through them"
+            ))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl futures_core::future::FusedFuture for LiveEditFuture {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+fn send(sender: &Sender, res: Result<(), glib::BoolError>) {
+    if let Some(sender) = sender.lock().unwrap().take() {
+        let _ = sender.send(res);
+    }
+}
+
+fn notify_on_data(pad: &Pad, sender: Sender) {
+    pad.add_probe(
+        PadProbeType::BUFFER | PadProbeType::BUFFER_LIST,
+        move |_, _| {
+            send(&sender, Ok(()));
+            PadProbeReturn::Remove
+        },
+    );
+}
+
+fn single_pad(element: &Element, direction: PadDirection) -> Result<Pad, glib::BoolError> {
+    let mut pads = match direction {
+        PadDirection::Src => element.src_pads(),
+        _ => element.sink_pads(),
+    };
+
+    if pads.len() != 1 {
+        return Err(glib::bool_error!(
+            "Element '{}' does not have exactly one {:?} pad",
+            element.name(),
+            direction
+        ));
+    }
+
+    Ok(pads.pop().unwrap())
+}
+
+fn peer(pad: &Pad) -> Result<Pad, glib::BoolError> {
+    pad.peer()
+        .ok_or_else(|| glib::bool_error!("Pad '{}' is not linked", pad.name()))
+}
+
+fn link(src: &Pad, sink: &Pad) -> Result<(), glib::BoolError> {
+    src.link(sink).map(|_| ()).map_err(|err| {
+        glib::bool_error!(
+            "Failed to link pads '{}' and '{}': {}",
+            src.name(),
+            sink.name(),
+            err
+        )
+    })
+}
+
+fn add_to_bin(bin: &Bin, element: &Element) -> Result<(), glib::BoolError> {
+    match element.parent() {
+        None => bin.add(element),
+        Some(parent) if parent == *bin.upcast_ref::<crate::Object>() => Ok(()),
+        Some(_) => Err(glib::bool_error!(
+            "Element '{}' already has a different parent",
+            element.name()
+        )),
+    }
+}
+
+struct Edit {
+    bin: Bin,
+    upstream: Pad,
+    downstream: Pad,
+    old: Option<(Element, Pad, Pad)>,
+    new: Option<(Element, Pad, Pad)>,
+    sender: Sender,
+}
+
+impl Edit {
+    fn relink(&self) -> Result<(), glib::BoolError> {
+        if let Some((ref old, ref old_sink, ref old_src)) = self.old {
+            self.upstream.unlink(old_sink)?;
+            old.set_state(State::Null)
+                .map_err(|_| glib::bool_error!("Failed to shut down element '{}'", old.name()))?;
+            old_src.unlink(&self.downstream)?;
+            self.bin.remove(old)?;
+        } else {
+            self.upstream.unlink(&self.downstream)?;
+        }
+
+        if let Some((ref new, ref new_sink, ref new_src)) = self.new {
+            new.sync_state_with_parent()?;
+            notify_on_data(new_src, self.sender.clone());
+            link(&self.upstream, new_sink)?;
+            link(new_src, &self.downstream)?;
+        } else {
+            notify_on_data(&self.upstream, self.sender.clone());
+            link(&self.upstream, &self.downstream)?;
+        }
+
+        Ok(())
+    }
+
+    // Relinks on another thread as elements can't be shut down from the streaming thread that is
+    // blocked by the probe, and unblocks upstream afterwards.
+    fn finish(self: Arc<Self>, block_id: crate::PadProbeId) {
+        let bin = self.bin.clone();
+        bin.call_async(move |_| {
+            if let Err(err) = self.relink() {
+                send(&self.sender, Err(err));
+            }
+            self.upstream.remove_probe(block_id);
+        });
+    }
+
+    fn start(self, drain: bool) -> LiveEditFuture {
+        let (sender, receiver) = oneshot::channel();
+        *self.sender.lock().unwrap() = Some(sender);
+
+        let edit = Arc::new(self);
+        let upstream = edit.upstream.clone();
+        let started = AtomicBool::new(false);
+        upstream.add_probe(PadProbeType::IDLE, move |_, info| {
+            // Called again while the pad is still blocked and the edit is in progress
+            if started.swap(true, Ordering::SeqCst) {
+                return PadProbeReturn::Ok;
+            }
+            let block_id = info.id.take().unwrap();
+
+            // Keep upstream blocked until the pads are relinked
+            let Some((old, old_sink, old_src)) = edit.old.as_ref().filter(|_| drain) else {
+                edit.clone().finish(block_id);
+                return PadProbeReturn::Ok;
+            };
+
+            // and before that until the old element has forwarded all its data
+            let block_id = Mutex::new(Some(block_id));
+            let drain_edit = edit.clone();
+            let eos_id = old_src.add_probe(PadProbeType::EVENT_DOWNSTREAM, move |pad, info| {
+                if info.event().map(|event| event.type_()) != Some(EventType::Eos) {
+                    return PadProbeReturn::Ok;
+                }
+                let Some(block_id) = block_id.lock().unwrap().take() else {
+                    return PadProbeReturn::Drop;
+                };
+                if let Some(eos_id) = info.id.take() {
+                    pad.remove_probe(eos_id);
+                }
+
+                drain_edit.clone().finish(block_id);
+
+                PadProbeReturn::Drop
+            });
+
+            if !old_sink.send_event(crate::event::Eos::new()) {
+                if let Some(eos_id) = eos_id {
+                    old_src.remove_probe(eos_id);
+                }
+                send(
+                    &edit.sender,
+                    Err(glib::bool_error!(
+                        "Failed to drain element '{}'",
+                        old.name()
+                    )),
+                );
+                return PadProbeReturn::Remove;
+            }
+
+            PadProbeReturn::Ok
+        });
+
+        LiveEditFuture(receiver)
+    }
+}
+
+pub(crate) fn insert_between(
+    bin: &Bin,
+    src_pad: &Pad,
+    element: &Element,
+) -> Result<LiveEditFuture, glib::BoolError> {
+    if src_pad.direction() != PadDirection::Src {
+        return Err(glib::bool_error!(
+            "Pad '{}' is not a source pad",
+            src_pad.name()
+        ));
+    }
+
+    let downstream = peer(src_pad)?;
+    let new = (
+        element.clone(),
+        single_pad(element, PadDirection::Sink)?,
+        single_pad(element, PadDirection::Src)?,
+    );
+    add_to_bin(bin, element)?;
+
+    Ok(Edit {
+        bin: bin.clone(),
+        upstream: src_pad.clone(),
+        downstream,
+        old: None,
+        new: Some(new),
+        sender: Default::default(),
+    }
+    .start(false))
+}
+
+pub(crate) fn replace_element(
+    bin: &Bin,
+    old: &Element,
+    new: Option<&Element>,
+    drain: bool,
+) -> Result<LiveEditFuture, glib::BoolError> {
+    if old.parent().as_ref() != Some(bin.upcast_ref::<crate::Object>()) {
+        return Err(glib::bool_error!(
+            "Element '{}' is not a child of bin '{}'",
+            old.name(),
+            bin.name()
+        ));
+    }
+
+    let old_sink = single_pad(old, PadDirection::Sink)?;
+    let old_src = single_pad(old, PadDirection::Src)?;
+    let upstream = peer(&old_sink)?;
+    let downstream = peer(&old_src)?;
+
+    let new = new
+        .map(|new| {
+            Ok::<_, glib::BoolError>((
+                new.clone(),
+                single_pad(new, PadDirection::Sink)?,
+                single_pad(new, PadDirection::Src)?,
+            ))
+        })
+        .transpose()?;
+    if let Some((ref new, _, _)) = new {
+        add_to_bin(bin, new)?;
+    }
+
+    Ok(Edit {
+        bin: bin.clone(),
+        upstream,
+        downstream,
+        old: Some((old.clone(), old_sink, old_src)),
+        new,
+        sender: Default::default(),
+    }
+    .start(drain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElementFactory;
+
+    fn pipeline() -> (crate::Pipeline, Element, Element) {
+        let pipeline = crate::Pipeline::new();
+        let src = ElementFactory::make("fakesrc")
+            .property("is-live", true)
+            .build()
+            .unwrap();
+        let identity = ElementFactory::make("identity").build().unwrap();
+        let sink = ElementFactory::make("fakesink").build().unwrap();
+        pipeline.add_many([&src, &identity, &sink]).unwrap();
+        Element::link_many([&src, &identity, &sink]).unwrap();
+
+        pipeline.set_state(State::Playing).unwrap();
+
+        (pipeline, src, identity)
+    }
+
+    #[test]
+    fn test_insert_between() {
+        crate::init().unwrap();
+
+        let (pipeline, src, _) = pipeline();
+        let queue = ElementFactory::make("queue").build().unwrap();
+
+        let future = pipeline
+            .insert_between(&src.static_pad("src").unwrap(), &queue)
+            .unwrap();
+        futures_executor::block_on(future).unwrap();
+
+        assert_eq!(
+            queue.parent().as_ref(),
+            Some(pipeline.upcast_ref::<crate::Object>())
+        );
+        assert_eq!(queue.current_state(), State::Playing);
+        assert_eq!(
+            src.static_pad("src").unwrap().peer(),
+            queue.static_pad("sink")
+        );
+
+        pipeline.set_state(State::Null).unwrap();
+    }
+
+    #[test]
+    fn test_replace_and_remove() {
+        crate::init().unwrap();
+
+        let (pipeline, src, identity) = pipeline();
+        let src_pad = src.static_pad("src").unwrap();
+        let downstream_element = || src_pad.peer().unwrap().parent_element().unwrap();
+
+        for drain in [false, true] {
+            let old = downstream_element();
+            let new = ElementFactory::make("identity").build().unwrap();
+
+            let future = pipeline.replace_element_live(&old, &new, drain).unwrap();
+            futures_executor::block_on(future).unwrap();
+
+            assert!(old.parent().is_none());
+            assert_eq!(old.current_state(), State::Null);
+            assert_eq!(new.current_state(), State::Playing);
+            assert_eq!(src_pad.peer(), new.static_pad("sink"));
+        }
+
+        let old = downstream_element();
+        let future = pipeline.remove_element_live(&old, true).unwrap();
+        futures_executor::block_on(future).unwrap();
+        assert!(old.parent().is_none());
+        assert_eq!(downstream_element().factory().unwrap().name(), "fakesink");
+
+        assert!(pipeline.remove_element_live(&identity, false).is_err());
+
+        pipeline.set_state(State::Null).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Runtime support for the code generated by the `pipeline!` and `caps!` macros.
+
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{Caps, Element, ElementFactory, PadDirection, PadPresence, prelude::*};
+
+// A property value from a `pipeline!` description.
+pub enum PropertyValue {
+    // Deserialized from the string like in `gst-launch-1.0`.
+    Str(&'static str),
+    Value(glib::Value),
+}
+
+// Creates the element like `ElementFactory::make(..).property(..).build()` but returns an error
+// instead of panicking if the element has no such property or a value can't be used for it.
+pub fn make_element(
+    factory_name: &str,
+    properties: Vec<(&'static str, PropertyValue)>,
+) -> Result<Element, glib::BoolError> {
+    let factory = ElementFactory::find(factory_name)
+        .ok_or_else(|| glib::bool_error!("No element factory '{}'", factory_name))?
+        .load()?;
+    let klass = glib::object::ObjectClass::from_type(factory.element_type()).ok_or_else(|| {
+        glib::bool_error!("Failed to create element from factory '{}'", factory_name)
+    })?;
+
+    let mut builder = factory.create();
+    for (name, value) in properties {
+        let pspec = klass.find_property(name).ok_or_else(|| {
+            glib::bool_error!(
+                "Element factory '{}' has no property '{}'",
+                factory_name,
+                name
+            )
+        })?;
+        if !pspec.flags().contains(glib::ParamFlags::WRITABLE) {
+            return Err(glib::bool_error!(
+                "Property '{}' of element factory '{}' is not writable",
+                name,
+                factory_name
+            ));
+        }
+
+        let value = match value {
+            PropertyValue::Str(value) => crate::gobject::value_from_property_str(pspec, value)
+                .map_err(|_| {
+                    glib::bool_error!(
+                        "Invalid value '{}' for property '{}' of element factory '{}'",
+                        value,
+                        name,
+                        factory_name
+                    )
+                })?,
+            PropertyValue::Value(value) => {
+                value.transform_with_type(pspec.value_type()).map_err(|_| {
+                    glib::bool_error!(
+                        "Invalid type {} for property '{}' of element factory '{}'",
+                        value.type_(),
+                        name,
+                        factory_name
+                    )
+                })?
+            }
+        };
+        builder = builder.property(name, value);
+    }
+
+    builder.build()
+}
+
+pub fn caps_from_str(caps: &str) -> Result<Caps, glib::BoolError> {
+    Caps::from_str(caps).map_err(|_| glib::bool_error!("Invalid caps '{}'", caps))
+}
+
+// The syntax of the caps was already checked by the `caps!` macro, so this can only fail for
+// values of types that are unknown at runtime.
+pub fn caps_from_static_str(caps: &'static str) -> Caps {
+    Caps::from_str(caps).unwrap_or_else(|_| panic!("Invalid caps '{caps}'"))
+}
+
+fn link_pads(
+    src: &Element,
+    src_pad: Option<&str>,
+    sink: &Element,
+    sink_pad: Option<&str>,
+    filter: Option<&Caps>,
+) -> Result<(), glib::BoolError> {
+    match filter {
+        Some(filter) => src.link_pads_filtered(src_pad, sink, sink_pad, filter),
+        None => src.link_pads(src_pad, sink, sink_pad),
+    }
+}
+
+// Links the two elements like gst-launch does: if the source element has sometimes pads and
+// linking fails now, the link is retried once a matching pad is added.
+pub fn link(
+    src: &Element,
+    src_pad: Option<&str>,
+    sink: &Element,
+    sink_pad: Option<&str>,
+    filter: Option<&Caps>,
+) -> Result<(), glib::BoolError> {
+    if link_pads(src, src_pad, sink, sink_pad, filter).is_ok() {
+        return Ok(());
+    }
+
+    let has_sometimes_pads = src.pad_template_list().iter().any(|templ| {
+        templ.direction() == PadDirection::Src && templ.presence() == PadPresence::Sometimes
+    });
+    if !has_sometimes_pads {
+        return Err(glib::bool_error!(
+            "Failed to link {}{}{} to {}{}{}",
+            src.name(),
+            if src_pad.is_some() { "." } else { "" },
+            src_pad.unwrap_or(""),
+            sink.name(),
+            if sink_pad.is_some() { "." } else { "" },
+            sink_pad.unwrap_or(""),
+        ));
+    }
+
+    let sink = sink.downgrade();
+    let src_pad = src_pad.map(String::from);
+    let sink_pad = sink_pad.map(String::from);
+    let filter = filter.cloned();
+    let linked = AtomicBool::new(false);
+
+    src.connect_pad_added(move |src, pad| {
+        if pad.direction() != PadDirection::Src || linked.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let Some(sink) = sink.upgrade() else {
+            return;
+        };
+
+        if let Some(ref src_pad) = src_pad
+            && pad.name().as_str() != src_pad
+            && !pad
+                .pad_template()
+                .is_some_and(|templ| templ.name_template() == src_pad)
+        {
+            return;
+        }
+
+        if link_pads(
+            src,
+            Some(pad.name().as_str()),
+            &sink,
+            sink_pad.as_deref(),
+            filter.as_ref(),
+        )
+        .is_ok()
+        {
+            linked.store(true, Ordering::SeqCst);
+        }
+    });
+
+    Ok(())
+}
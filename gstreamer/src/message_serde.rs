@@ -0,0 +1,674 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{num::NonZeroU32, ptr};
+
+use glib::{prelude::*, translate::*};
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{self, Serialize, Serializer},
+};
+
+use crate::{
+    BufferingMode, ClockTime, GenericFormattedValue, GroupId, Message, MessageRef, MessageType,
+    ProgressType, Seqnum, State, Structure, TagList, Toc, ffi, message, message::MessageView,
+};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ErrorSerde {
+    domain: String,
+    code: i32,
+    message: String,
+}
+
+impl From<&glib::Error> for ErrorSerde {
+    fn from(error: &glib::Error) -> Self {
+        skip_assert_initialized!();
+        let error_ptr: *const glib::ffi::GError = error.to_glib_none().0;
+
+        ErrorSerde {
+            domain: error.domain().as_str().to_owned(),
+            code: unsafe { (*error_ptr).code },
+            message: error.message().to_owned(),
+        }
+    }
+}
+
+impl ErrorSerde {
+    fn into_error<E: de::Error>(self) -> Result<glib::Error, E> {
+        use crate::{CoreError, LibraryError, ResourceError, StreamError};
+
+        let domain = glib::Quark::from_str(&self.domain);
+        if ![
+            CoreError::domain(),
+            ResourceError::domain(),
+            StreamError::domain(),
+            LibraryError::domain(),
+        ]
+        .contains(&domain)
+        {
+            return Err(E::custom(format!(
+                "unsupported error domain {}",
+                self.domain
+            )));
+        }
+
+        unsafe {
+            Ok(from_glib_full(glib::ffi::g_error_new_literal(
+                domain.into_glib(),
+                self.code,
+                self.message.to_glib_none().0,
+            )))
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum MessageViewSerde {
+    Eos,
+    Error {
+        error: ErrorSerde,
+        debug: Option<String>,
+        details: Option<Structure>,
+    },
+    Warning {
+        error: ErrorSerde,
+        debug: Option<String>,
+        details: Option<Structure>,
+    },
+    Info {
+        error: ErrorSerde,
+        debug: Option<String>,
+        details: Option<Structure>,
+    },
+    Tag {
+        tags: TagList,
+    },
+    Buffering {
+        percent: i32,
+        mode: BufferingMode,
+        avg_in: i32,
+        avg_out: i32,
+        buffering_left: i64,
+    },
+    StateChanged {
+        old: State,
+        current: State,
+        pending: State,
+    },
+    StateDirty,
+    StepDone {
+        amount: GenericFormattedValue,
+        rate: f64,
+        flush: bool,
+        intermediate: bool,
+        duration: Option<ClockTime>,
+        eos: bool,
+    },
+    Application {
+        structure: Structure,
+    },
+    Element {
+        structure: Structure,
+    },
+    SegmentStart {
+        position: GenericFormattedValue,
+    },
+    SegmentDone {
+        position: GenericFormattedValue,
+    },
+    DurationChanged,
+    Latency,
+    AsyncStart,
+    AsyncDone {
+        running_time: Option<ClockTime>,
+    },
+    RequestState {
+        state: State,
+    },
+    StepStart {
+        active: bool,
+        amount: GenericFormattedValue,
+        rate: f64,
+        flush: bool,
+        intermediate: bool,
+    },
+    Qos {
+        live: bool,
+        running_time: Option<ClockTime>,
+        stream_time: Option<ClockTime>,
+        timestamp: Option<ClockTime>,
+        duration: Option<ClockTime>,
+        jitter: i64,
+        proportion: f64,
+        quality: i32,
+        processed: GenericFormattedValue,
+        dropped: GenericFormattedValue,
+    },
+    Progress {
+        progress_type: ProgressType,
+        code: String,
+        text: String,
+    },
+    Toc {
+        toc: Toc,
+        updated: bool,
+    },
+    ResetTime {
+        running_time: ClockTime,
+    },
+    StreamStart {
+        group_id: Option<u32>,
+    },
+    NeedContext {
+        context_type: String,
+    },
+    #[cfg(feature = "v1_18")]
+    InstantRateRequest {
+        rate_multiplier: f64,
+    },
+    Other {
+        message_type: String,
+        structure: Option<Structure>,
+    },
+}
+
+// The source object can't be restored when deserializing, its path is only kept for
+// information purposes.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MessageSerde {
+    seqnum: u32,
+    src: Option<String>,
+    view: MessageViewSerde,
+}
+
+fn message_type_nick(message_type: MessageType) -> String {
+    skip_assert_initialized!();
+    glib::FlagsClass::with_type(MessageType::static_type())
+        .and_then(|class| {
+            class
+                .value(message_type.bits())
+                .map(|v| v.nick().to_owned())
+        })
+        .unwrap_or_else(|| format!("{:#x}", message_type.bits()))
+}
+
+fn message_type_from_nick<E: de::Error>(nick: &str) -> Result<MessageType, E> {
+    glib::FlagsClass::with_type(MessageType::static_type())
+        .and_then(|class| class.value_by_nick(nick).map(|v| v.value()))
+        .map(MessageType::from_bits_truncate)
+        .ok_or_else(|| E::custom(format!("unknown message type {nick}")))
+}
+
+fn owned_structure<E: ser::Error>(message: &MessageRef) -> Result<Structure, E> {
+    message
+        .structure()
+        .map(|s| s.to_owned())
+        .ok_or_else(|| E::custom(format!("{:?} message without structure", message.type_())))
+}
+
+impl Serialize for MessageRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let view = match self.view() {
+            MessageView::Eos(_) => MessageViewSerde::Eos,
+            MessageView::Error(m) => MessageViewSerde::Error {
+                error: ErrorSerde::from(&m.error()),
+                debug: m.debug().map(Into::into),
+                details: m.details().map(|s| s.to_owned()),
+            },
+            MessageView::Warning(m) => MessageViewSerde::Warning {
+                error: ErrorSerde::from(&m.error()),
+                debug: m.debug().map(Into::into),
+                details: m.details().map(|s| s.to_owned()),
+            },
+            MessageView::Info(m) => MessageViewSerde::Info {
+                error: ErrorSerde::from(&m.error()),
+                debug: m.debug().map(Into::into),
+                details: m.details().map(|s| s.to_owned()),
+            },
+            MessageView::Tag(m) => MessageViewSerde::Tag { tags: m.tags() },
+            MessageView::Buffering(m) => {
+                let (mode, avg_in, avg_out, buffering_left) = m.buffering_stats();
+                MessageViewSerde::Buffering {
+                    percent: m.percent(),
+                    mode,
+                    avg_in,
+                    avg_out,
+                    buffering_left,
+                }
+            }
+            MessageView::StateChanged(m) => MessageViewSerde::StateChanged {
+                old: m.old(),
+                current: m.current(),
+                pending: m.pending(),
+            },
+            MessageView::StateDirty(_) => MessageViewSerde::StateDirty,
+            MessageView::StepDone(m) => {
+                let (amount, rate, flush, intermediate, duration, eos) = m.get();
+                MessageViewSerde::StepDone {
+                    amount,
+                    rate,
+                    flush,
+                    intermediate,
+                    duration,
+                    eos,
+                }
+            }
+            MessageView::Application(_) => MessageViewSerde::Application {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            MessageView::Element(_) => MessageViewSerde::Element {
+                structure: owned_structure::<S::Error>(self)?,
+            },
+            MessageView::SegmentStart(m) => MessageViewSerde::SegmentStart { position: m.get() },
+            MessageView::SegmentDone(m) => MessageViewSerde::SegmentDone { position: m.get() },
+            MessageView::DurationChanged(_) => MessageViewSerde::DurationChanged,
+            MessageView::Latency(_) => MessageViewSerde::Latency,
+            MessageView::AsyncStart(_) => MessageViewSerde::AsyncStart,
+            MessageView::AsyncDone(m) => MessageViewSerde::AsyncDone {
+                running_time: m.running_time(),
+            },
+            MessageView::RequestState(m) => MessageViewSerde::RequestState {
+                state: m.requested_state(),
+            },
+            MessageView::StepStart(m) => {
+                let (active, amount, rate, flush, intermediate) = m.get();
+                MessageViewSerde::StepStart {
+                    active,
+                    amount,
+                    rate,
+                    flush,
+                    intermediate,
+                }
+            }
+            MessageView::Qos(m) => {
+                let (live, running_time, stream_time, timestamp, duration) = m.get();
+                let (jitter, proportion, quality) = m.values();
+                let (processed, dropped) = m.stats();
+                MessageViewSerde::Qos {
+                    live,
+                    running_time,
+                    stream_time,
+                    timestamp,
+                    duration,
+                    jitter,
+                    proportion,
+                    quality,
+                    processed,
+                    dropped,
+                }
+            }
+            MessageView::Progress(m) => {
+                let (progress_type, code, text) = m.get();
+                MessageViewSerde::Progress {
+                    progress_type,
+                    code: code.to_owned(),
+                    text: text.to_owned(),
+                }
+            }
+            MessageView::Toc(m) => {
+                let (toc, updated) = m.toc();
+                MessageViewSerde::Toc { toc, updated }
+            }
+            MessageView::ResetTime(m) => MessageViewSerde::ResetTime {
+                running_time: m.running_time(),
+            },
+            MessageView::StreamStart(m) => MessageViewSerde::StreamStart {
+                group_id: m.group_id().map(|group_id| group_id.0.get()),
+            },
+            MessageView::NeedContext(m) => MessageViewSerde::NeedContext {
+                context_type: m.context_type().to_owned(),
+            },
+            #[cfg(feature = "v1_18")]
+            MessageView::InstantRateRequest(m) => MessageViewSerde::InstantRateRequest {
+                rate_multiplier: m.rate_multiplier(),
+            },
+            _ => MessageViewSerde::Other {
+                message_type: message_type_nick(self.type_()),
+                structure: self.structure().map(|s| s.to_owned()),
+            },
+        };
+
+        MessageSerde {
+            seqnum: self.seqnum().0.get(),
+            src: self.src().map(|src| src.path_string().into()),
+            view,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+
+        let MessageSerde { seqnum, view, .. } = MessageSerde::deserialize(deserializer)?;
+        let seqnum = NonZeroU32::new(seqnum).map(Seqnum);
+
+        macro_rules! build {
+            ($builder:expr) => {
+                $builder.seqnum_if_some(seqnum).build()
+            };
+        }
+
+        let message = match view {
+            MessageViewSerde::Eos => build!(message::Eos::builder()),
+            MessageViewSerde::Error {
+                error,
+                debug,
+                details,
+            } => build!(
+                message::Error::builder_from_error(error.into_error::<D::Error>()?)
+                    .debug_if_some(debug.as_deref())
+                    .details_if_some(details)
+            ),
+            MessageViewSerde::Warning {
+                error,
+                debug,
+                details,
+            } => build!(
+                message::Warning::builder_from_error(error.into_error::<D::Error>()?)
+                    .debug_if_some(debug.as_deref())
+                    .details_if_some(details)
+            ),
+            MessageViewSerde::Info {
+                error,
+                debug,
+                details,
+            } => build!(
+                message::Info::builder_from_error(error.into_error::<D::Error>()?)
+                    .debug_if_some(debug.as_deref())
+                    .details_if_some(details)
+            ),
+            MessageViewSerde::Tag { tags } => build!(message::Tag::builder(tags)),
+            MessageViewSerde::Buffering {
+                percent,
+                mode,
+                avg_in,
+                avg_out,
+                buffering_left,
+            } => {
+                // `gst_message_new_buffering()` returns NULL otherwise
+                if !(0..=100).contains(&percent) {
+                    return Err(de::Error::custom(format!(
+                        "invalid buffering percent {percent}"
+                    )));
+                }
+                build!(message::Buffering::builder(percent).stats(
+                    mode,
+                    avg_in,
+                    avg_out,
+                    buffering_left
+                ))
+            }
+            MessageViewSerde::StateChanged {
+                old,
+                current,
+                pending,
+            } => build!(message::StateChanged::builder(old, current, pending)),
+            MessageViewSerde::StateDirty => build!(message::StateDirty::builder()),
+            MessageViewSerde::StepDone {
+                amount,
+                rate,
+                flush,
+                intermediate,
+                duration,
+                eos,
+            } => build!(message::StepDone::builder(
+                amount,
+                rate,
+                flush,
+                intermediate,
+                duration,
+                eos
+            )),
+            MessageViewSerde::Application { structure } => {
+                build!(message::Application::builder(structure))
+            }
+            MessageViewSerde::Element { structure } => {
+                build!(message::Element::builder(structure))
+            }
+            MessageViewSerde::SegmentStart { position } => {
+                build!(message::SegmentStart::builder(position))
+            }
+            MessageViewSerde::SegmentDone { position } => {
+                build!(message::SegmentDone::builder(position))
+            }
+            MessageViewSerde::DurationChanged => build!(message::DurationChanged::builder()),
+            MessageViewSerde::Latency => build!(message::Latency::builder()),
+            MessageViewSerde::AsyncStart => build!(message::AsyncStart::builder()),
+            MessageViewSerde::AsyncDone { running_time } => {
+                build!(message::AsyncDone::builder().running_time(running_time))
+            }
+            MessageViewSerde::RequestState { state } => {
+                build!(message::RequestState::builder(state))
+            }
+            MessageViewSerde::StepStart {
+                active,
+                amount,
+                rate,
+                flush,
+                intermediate,
+            } => build!(message::StepStart::builder(
+                active,
+                amount,
+                rate,
+                flush,
+                intermediate
+            )),
+            MessageViewSerde::Qos {
+                live,
+                running_time,
+                stream_time,
+                timestamp,
+                duration,
+                jitter,
+                proportion,
+                quality,
+                processed,
+                dropped,
+            } => {
+                if processed.format() != dropped.format() {
+                    return Err(de::Error::custom(format!(
+                        "mismatched formats {:?} and {:?}",
+                        processed.format(),
+                        dropped.format()
+                    )));
+                }
+
+                build!(
+                    message::Qos::builder(live)
+                        .running_time(running_time)
+                        .stream_time(stream_time)
+                        .timestamp(timestamp)
+                        .duration(duration)
+                        .values(jitter, proportion, quality)
+                        .stats(processed, dropped)
+                )
+            }
+            MessageViewSerde::Progress {
+                progress_type,
+                code,
+                text,
+            } => build!(message::Progress::builder(progress_type, &code, &text)),
+            MessageViewSerde::Toc { toc, updated } => {
+                build!(message::Toc::builder(&toc, updated))
+            }
+            MessageViewSerde::ResetTime { running_time } => {
+                build!(message::ResetTime::builder(running_time))
+            }
+            MessageViewSerde::StreamStart { group_id } => build!(
+                message::StreamStart::builder()
+                    .group_id_if_some(group_id.and_then(NonZeroU32::new).map(GroupId))
+            ),
+            MessageViewSerde::NeedContext { context_type } => {
+                build!(message::NeedContext::builder(&context_type))
+            }
+            #[cfg(feature = "v1_18")]
+            MessageViewSerde::InstantRateRequest { rate_multiplier } => {
+                if rate_multiplier == 0.0 || rate_multiplier.is_nan() {
+                    return Err(de::Error::custom(format!(
+                        "invalid rate multiplier {rate_multiplier}"
+                    )));
+                }
+                build!(message::InstantRateRequest::builder(rate_multiplier))
+            }
+            MessageViewSerde::Other {
+                message_type,
+                structure,
+            } => {
+                let message_type = message_type_from_nick::<D::Error>(&message_type)?;
+                let mut message = unsafe {
+                    Option::<Message>::from_glib_full(ffi::gst_message_new_custom(
+                        message_type.into_glib(),
+                        ptr::null_mut(),
+                        structure
+                            .map(|s| s.into_glib_ptr())
+                            .unwrap_or(ptr::null_mut()),
+                    ))
+                    .ok_or_else(|| {
+                        <D::Error as de::Error>::custom(format!(
+                            "can't create {message_type:?} message"
+                        ))
+                    })?
+                };
+
+                if let Some(seqnum) = seqnum {
+                    message.get_mut().unwrap().set_seqnum(seqnum);
+                }
+
+                message
+            }
+        };
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::translate::*;
+
+    use crate::{
+        ClockTime, Format, GenericFormattedValue, Message, State, Structure,
+        message::{self, MessageView},
+        prelude::*,
+    };
+
+    #[test]
+    fn test_serialize() {
+        crate::init().unwrap();
+
+        let pipeline = crate::Pipeline::with_name("pipeline0");
+        let msg = message::StateChanged::builder(State::Ready, State::Paused, State::Playing)
+            .src(&pipeline)
+            .build();
+
+        let pretty_config = ron::ser::PrettyConfig::new().new_line("".to_string());
+
+        let res = ron::ser::to_string_pretty(&msg, pretty_config);
+        assert_eq!(
+            Ok(format!(
+                concat!(
+                    "(",
+                    "    seqnum: {},",
+                    "    src: Some(\"/pipeline0\"),",
+                    "    view: StateChanged(",
+                    "        old: Ready,",
+                    "        current: Paused,",
+                    "        pending: Playing,",
+                    "    ),",
+                    ")"
+                ),
+                msg.seqnum().0.get()
+            )),
+            res,
+        );
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        crate::init().unwrap();
+
+        let error = message::Error::builder(crate::CoreError::Failed, "something failed")
+            .debug("some debug info")
+            .details(Structure::builder("details").field("foo", 42i32).build())
+            .build();
+        let res = ron::ser::to_string(&error).unwrap();
+        let error_de: Message = ron::de::from_str(&res).unwrap();
+        assert_eq!(error_de.seqnum(), error.seqnum());
+        match (error.view(), error_de.view()) {
+            (MessageView::Error(a), MessageView::Error(b)) => {
+                assert_eq!(
+                    b.error().kind::<crate::CoreError>(),
+                    Some(crate::CoreError::Failed)
+                );
+                assert_eq!(a.error().message(), b.error().message());
+                assert_eq!(a.debug(), b.debug());
+                assert_eq!(a.details(), b.details());
+            }
+            _ => panic!("unexpected message {error_de:?}"),
+        }
+
+        let qos = message::Qos::builder(true)
+            .running_time(ClockTime::from_seconds(1))
+            .values(-100, 0.5, 1_000_000)
+            .stats(
+                crate::format::Buffers::from_u64(10),
+                crate::format::Buffers::from_u64(2),
+            )
+            .build();
+        let res = ron::ser::to_string(&qos).unwrap();
+        let qos_de: Message = ron::de::from_str(&res).unwrap();
+        match (qos.view(), qos_de.view()) {
+            (MessageView::Qos(a), MessageView::Qos(b)) => {
+                assert_eq!(a.get(), b.get());
+                assert_eq!(a.values(), b.values());
+                assert_eq!(a.stats(), b.stats());
+                assert_eq!(
+                    b.processed(),
+                    GenericFormattedValue::new(Format::Buffers, 10)
+                );
+            }
+            _ => panic!("unexpected message {qos_de:?}"),
+        }
+
+        let res = ron::ser::to_string(&message::Eos::new()).unwrap();
+        let eos_de: Message = ron::de::from_str(&res).unwrap();
+        assert!(matches!(eos_de.view(), MessageView::Eos(_)));
+    }
+
+    #[test]
+    fn test_serialize_without_structure() {
+        crate::init().unwrap();
+
+        for message_type in [crate::MessageType::Application, crate::MessageType::Element] {
+            let msg = unsafe {
+                Message::from_glib_full(crate::ffi::gst_message_new_custom(
+                    message_type.into_glib(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                ))
+            };
+            assert!(msg.structure().is_none());
+            assert!(ron::ser::to_string(&msg).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        crate::init().unwrap();
+
+        let buffering = message::Buffering::new(50);
+        let res = ron::ser::to_string(&buffering).unwrap();
+        assert!(res.contains("percent:50"));
+        assert!(ron::de::from_str::<Message>(&res).is_ok());
+        let res = res.replace("percent:50", "percent:101");
+        assert!(ron::de::from_str::<Message>(&res).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::ptr;
+use std::{borrow::Cow, fmt::Write, ptr};
 
 use glib::{prelude::*, translate::*};
 
-use crate::{Bin, Element, Object, ParseContext, ParseFlags, ffi};
+use crate::{
+    Bin, Caps, Element, GhostPad, Object, Pad, ParseContext, ParseFlags, Pipeline, ProxyPad, ffi,
+    prelude::*, utils::write_json_string,
+};
 
 pub use crate::auto::functions::parse_bin_from_description as bin_from_description;
 pub use crate::auto::functions::parse_launch as launch;
@@ -117,6 +120,352 @@ pub fn launchv_full(
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Output format of [`to_description_full()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DescriptionFormat {
+    // rustdoc-stripper-ignore-next
+    /// `gst-launch-1.0` syntax that can be passed to [`launch()`].
+    #[default]
+    Launch,
+    // rustdoc-stripper-ignore-next
+    /// JSON representation of the elements, their properties, ghost pads and links.
+    Json,
+}
+
+// rustdoc-stripper-ignore-next
+/// Serializes `bin` into `gst-launch-1.0` syntax.
+///
+/// This is the inverse of [`launch()`] and can be used for debugging or to reproduce a
+/// pipeline that was built programmatically. See [`to_description_full()`] for details.
+pub fn to_description(bin: &impl IsA<Bin>) -> String {
+    skip_assert_initialized!();
+    to_description_full(bin, DescriptionFormat::Launch, false)
+}
+
+// rustdoc-stripper-ignore-next
+/// Serializes `bin` in the given `format`.
+///
+/// All elements of `bin` are serialized with their factory name, their name and all
+/// properties that have a value different from the default value of their `ParamSpec`.
+/// Children of plain [`Bin`]s and [`Pipeline`]s are serialized recursively, while other bins,
+/// e.g. `decodebin`, are handled like any other element because their children are created
+/// internally.
+///
+/// Links are serialized between the actual elements by following ghost pads of nested bins and
+/// refer to the element and pad names, including names of request pads. If
+/// `with_negotiated_caps` is `true`, the currently negotiated caps are added as caps filter to
+/// each link.
+///
+/// Properties of `bin` itself and its ghost pads can only be represented in
+/// [`DescriptionFormat::Json`].
+pub fn to_description_full(
+    bin: &impl IsA<Bin>,
+    format: DescriptionFormat,
+    with_negotiated_caps: bool,
+) -> String {
+    skip_assert_initialized!();
+
+    let bin = bin.as_ref();
+    let node = DescriptionNode::new(bin.upcast_ref(), true);
+
+    let mut links = Vec::new();
+    node.collect_links(bin, with_negotiated_caps, &mut links);
+
+    let mut out = String::new();
+    match format {
+        DescriptionFormat::Launch => {
+            for child in &node.children {
+                child.write_launch(&mut out);
+            }
+            for link in &links {
+                link.write_launch(&mut out);
+            }
+            out.truncate(out.trim_end().len());
+        }
+        DescriptionFormat::Json => {
+            node.write_json(Some(&links), &mut out);
+        }
+    }
+
+    out
+}
+
+struct DescriptionNode {
+    element: Element,
+    factory: String,
+    properties: Vec<(String, String)>,
+    children: Vec<DescriptionNode>,
+    ghost_pads: Vec<GhostPad>,
+}
+
+struct DescriptionLink {
+    src: Pad,
+    sink: Pad,
+    caps: Option<Caps>,
+}
+
+fn is_container(element: &Element) -> bool {
+    let type_ = element.type_();
+    type_ == Bin::static_type() || type_ == Pipeline::static_type()
+}
+
+fn is_inner_container(element: &Element, root: &Bin) -> bool {
+    is_container(element) && element.has_as_ancestor(root)
+}
+
+// Follows ghost pads of nested bins to the actual pad the given pad is linked to.
+fn resolve_peer(pad: &Pad, root: &Bin) -> Option<Pad> {
+    let mut peer = pad.peer()?;
+
+    loop {
+        if let Some(ghost) = peer.downcast_ref::<GhostPad>()
+            && ghost
+                .parent_element()
+                .is_some_and(|e| is_inner_container(&e, root))
+        {
+            peer = ghost.target()?;
+        } else if let Some(ghost) = peer
+            .downcast_ref::<ProxyPad>()
+            .and_then(|proxy| proxy.internal())
+            .and_then(|internal| internal.downcast::<GhostPad>().ok())
+            && ghost
+                .parent_element()
+                .is_some_and(|e| is_inner_container(&e, root))
+        {
+            peer = ghost.peer()?;
+        } else {
+            return Some(peer);
+        }
+    }
+}
+
+fn pad_ref(pad: &Pad) -> String {
+    match pad.parent_element() {
+        Some(element) => format!("{}.{}", element.name(), pad.name()),
+        None => pad.name().to_string(),
+    }
+}
+
+fn launch_value(value: &str) -> Cow<'_, str> {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_+.:/".contains(c))
+    {
+        return Cow::Borrowed(value);
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    Cow::Owned(quoted)
+}
+
+// Caps between links can't contain unquoted whitespace.
+fn launch_caps(caps: &Caps) -> String {
+    let caps = caps.to_string();
+    let mut out = String::with_capacity(caps.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in caps.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_whitespace() {
+            continue;
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+impl DescriptionNode {
+    fn new(element: &Element, recurse: bool) -> Self {
+        let (children, ghost_pads) = match element.downcast_ref::<Bin>() {
+            Some(bin) if recurse => (
+                bin.children()
+                    .iter()
+                    .rev()
+                    .map(|child| DescriptionNode::new(child, is_container(child)))
+                    .collect(),
+                element
+                    .pads()
+                    .into_iter()
+                    .filter_map(|pad| pad.downcast::<GhostPad>().ok())
+                    .collect(),
+            ),
+            _ => (Vec::new(), Vec::new()),
+        };
+
+        DescriptionNode {
+            element: element.clone(),
+            factory: element
+                .factory()
+                .map(|factory| factory.name().to_string())
+                .unwrap_or_else(|| element.type_().name().to_owned()),
+            properties: crate::utils::non_default_properties(element),
+            children,
+            ghost_pads,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        !is_container(&self.element)
+    }
+
+    fn collect_links(&self, root: &Bin, with_caps: bool, links: &mut Vec<DescriptionLink>) {
+        for child in &self.children {
+            if !child.is_leaf() {
+                child.collect_links(root, with_caps, links);
+                continue;
+            }
+
+            for src in child.element.src_pads() {
+                let Some(sink) = resolve_peer(&src, root) else {
+                    continue;
+                };
+                if !sink
+                    .parent_element()
+                    .is_some_and(|e| e.has_as_ancestor(root))
+                {
+                    continue;
+                }
+
+                let caps = if with_caps { src.current_caps() } else { None };
+                links.push(DescriptionLink { src, sink, caps });
+            }
+        }
+    }
+
+    fn write_launch(&self, out: &mut String) {
+        if self.is_leaf() {
+            out.push_str(&self.factory);
+        } else {
+            out.push_str(&self.factory);
+            out.push_str(".(");
+        }
+
+        let _ = write!(out, " name={}", launch_value(&self.element.name()));
+        for (name, value) in &self.properties {
+            let _ = write!(out, " {name}={}", launch_value(value));
+        }
+        out.push(' ');
+
+        if !self.is_leaf() {
+            for child in &self.children {
+                child.write_launch(out);
+            }
+            out.push_str(") ");
+        }
+    }
+
+    fn write_json(&self, links: Option<&[DescriptionLink]>, out: &mut String) {
+        out.push_str("{\"factory\":");
+        write_json_string(out, &self.factory);
+        out.push_str(",\"name\":");
+        write_json_string(out, &self.element.name());
+
+        out.push_str(",\"properties\":{");
+        for (idx, (name, value)) in self.properties.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            write_json_string(out, name);
+            out.push(':');
+            write_json_string(out, value);
+        }
+        out.push('}');
+
+        if !self.children.is_empty() || !self.is_leaf() {
+            out.push_str(",\"elements\":[");
+            for (idx, child) in self.children.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                child.write_json(None, out);
+            }
+            out.push(']');
+
+            out.push_str(",\"ghost-pads\":[");
+            for (idx, ghost) in self.ghost_pads.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str("{\"name\":");
+                write_json_string(out, &ghost.name());
+                out.push_str(",\"direction\":");
+                write_json_string(
+                    out,
+                    match ghost.direction() {
+                        crate::PadDirection::Src => "src",
+                        crate::PadDirection::Sink => "sink",
+                        _ => "unknown",
+                    },
+                );
+                out.push_str(",\"target\":");
+                match ghost.target() {
+                    Some(target) => write_json_string(out, &pad_ref(&target)),
+                    None => out.push_str("null"),
+                }
+                out.push('}');
+            }
+            out.push(']');
+        }
+
+        if let Some(links) = links {
+            out.push_str(",\"links\":[");
+            for (idx, link) in links.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str("{\"src\":");
+                write_json_string(out, &pad_ref(&link.src));
+                out.push_str(",\"sink\":");
+                write_json_string(out, &pad_ref(&link.sink));
+                if let Some(ref caps) = link.caps {
+                    out.push_str(",\"caps\":");
+                    write_json_string(out, &caps.to_string());
+                }
+                out.push('}');
+            }
+            out.push(']');
+        }
+
+        out.push('}');
+    }
+}
+
+impl DescriptionLink {
+    fn write_launch(&self, out: &mut String) {
+        out.push_str(&pad_ref(&self.src));
+        out.push_str(" ! ");
+        if let Some(ref caps) = self.caps {
+            out.push_str(&launch_caps(caps));
+            out.push_str(" ! ");
+        }
+        out.push_str(&pad_ref(&self.sink));
+        out.push(' ');
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +483,93 @@ mod tests {
         let name = bin.name();
         assert_ne!(name, "");
     }
+
+    #[test]
+    fn test_to_description() {
+        crate::init().unwrap();
+
+        let pipeline = crate::Pipeline::new();
+        let src = crate::ElementFactory::make("fakesrc")
+            .name("src")
+            .property("num-buffers", 5i32)
+            .build()
+            .unwrap();
+        let tee = crate::ElementFactory::make("tee")
+            .name("t")
+            .build()
+            .unwrap();
+        let sink = crate::ElementFactory::make("fakesink")
+            .name("sink1")
+            .property("sync", false)
+            .build()
+            .unwrap();
+
+        let bin = crate::Bin::with_name("inner");
+        let queue = crate::ElementFactory::make("queue")
+            .name("q")
+            .build()
+            .unwrap();
+        let sink2 = crate::ElementFactory::make("fakesink")
+            .name("sink2")
+            .build()
+            .unwrap();
+        bin.add_many([&queue, &sink2]).unwrap();
+        queue.link(&sink2).unwrap();
+        let ghost = crate::GhostPad::with_target(&queue.static_pad("sink").unwrap()).unwrap();
+        bin.add_pad(&ghost).unwrap();
+
+        pipeline
+            .add_many([&src, &tee, &sink, bin.upcast_ref()])
+            .unwrap();
+        src.link(&tee).unwrap();
+        tee.link(&sink).unwrap();
+        tee.link(&bin).unwrap();
+
+        let description = to_description(&pipeline);
+        assert_eq!(
+            description,
+            "fakesrc name=src num-buffers=5 tee name=t fakesink name=sink1 sync=false \
+             bin.( name=inner queue name=q fakesink name=sink2 ) \
+             src.src ! t.sink t.src_0 ! sink1.sink t.src_1 ! q.sink q.src ! sink2.sink"
+        );
+
+        let relaunched = launch(&description)
+            .unwrap()
+            .downcast::<crate::Pipeline>()
+            .unwrap();
+        assert_eq!(to_description(&relaunched), description);
+
+        let json = to_description_full(&pipeline, DescriptionFormat::Json, false);
+        assert!(json.starts_with("{\"factory\":\"pipeline\",\"name\":"));
+        assert!(json.contains(
+            "{\"factory\":\"fakesrc\",\"name\":\"src\",\"properties\":{\"num-buffers\":\"5\"}}"
+        ));
+        assert!(json.contains(
+            "\"ghost-pads\":[{\"name\":\"sink\",\"direction\":\"sink\",\"target\":\"q.sink\"}]"
+        ));
+        assert!(json.contains("{\"src\":\"t.src_1\",\"sink\":\"q.sink\"}"));
+    }
+
+    #[test]
+    fn test_to_description_json_escaping() {
+        crate::init().unwrap();
+
+        let location = "dir/\"quoted\"\\file\n\t\u{1}.bin";
+        let pipeline = crate::Pipeline::new();
+        let src = crate::ElementFactory::make("filesrc")
+            .name("src")
+            .property("location", location)
+            .build()
+            .unwrap();
+        pipeline.add(&src).unwrap();
+
+        let json = to_description_full(&pipeline, DescriptionFormat::Json, false);
+        let serialized = location.to_value().serialize().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["elements"][0]["properties"]["location"],
+            serde_json::Value::from(serialized.as_str())
+        );
+        assert!(json.contains(&serde_json::to_string(serialized.as_str()).unwrap()));
+    }
 }
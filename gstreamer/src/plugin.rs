@@ -2,7 +2,10 @@
 
 use glib::{prelude::*, translate::*};
 
-use crate::{Plugin, PluginFlags, StructureRef, ffi, prelude::*};
+use crate::{
+    Caps, DeviceProvider, Element, Plugin, PluginFlags, Rank, StructureRef, Tracer, TypeFind, ffi,
+    prelude::*,
+};
 
 impl Plugin {
     #[doc(alias = "get_cache_data")]
@@ -26,4 +29,313 @@ impl Plugin {
             from_glib((*ptr).flags)
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for registering an in-process plugin.
+    ///
+    /// This allows applications that embed their elements, typefinders, device providers and
+    /// tracers to register them as part of a static plugin without a plugin shared library. The
+    /// registered features are visible via the registry like those of any other plugin, e.g.
+    /// via [`Registry::features_by_plugin()`](crate::Registry::features_by_plugin).
+    pub fn static_builder(name: &str, description: &str) -> StaticPluginBuilder {
+        assert_initialized_main_thread!();
+        StaticPluginBuilder {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            version: String::from("0.0.0"),
+            license: String::from("unknown"),
+            source: name.to_owned(),
+            package: name.to_owned(),
+            origin: String::new(),
+            features: Vec::new(),
+        }
+    }
+}
+
+type FeatureRegisterFn = Box<dyn FnOnce(&Plugin) -> Result<(), glib::BoolError> + 'static>;
+
+// rustdoc-stripper-ignore-next
+/// Builder for registering a static plugin.
+///
+/// See [`Plugin::static_builder()`].
+#[must_use = "The builder must be registered to be used"]
+pub struct StaticPluginBuilder {
+    name: String,
+    description: String,
+    version: String,
+    license: String,
+    source: String,
+    package: String,
+    origin: String,
+    features: Vec<FeatureRegisterFn>,
+}
+
+impl StaticPluginBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Sets the version of the plugin.
+    ///
+    /// Defaults to `0.0.0`.
+    pub fn version(self, version: &str) -> Self {
+        Self {
+            version: version.to_owned(),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the license of the plugin.
+    ///
+    /// This must be one of the licenses known to GStreamer, e.g. `LGPL` or `MIT/X11`, otherwise
+    /// registration fails. Defaults to `unknown`.
+    pub fn license(self, license: &str) -> Self {
+        Self {
+            license: license.to_owned(),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the source module the plugin belongs to.
+    ///
+    /// Defaults to the plugin name.
+    pub fn source(self, source: &str) -> Self {
+        Self {
+            source: source.to_owned(),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the name of the package the plugin is shipped in.
+    ///
+    /// Defaults to the plugin name.
+    pub fn package(self, package: &str) -> Self {
+        Self {
+            package: package.to_owned(),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the URL of the origin of the plugin.
+    ///
+    /// Defaults to an empty string.
+    pub fn origin(self, origin: &str) -> Self {
+        Self {
+            origin: origin.to_owned(),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers an element factory for `type_` with the plugin.
+    pub fn element(self, name: &str, rank: Rank, type_: glib::Type) -> Self {
+        assert!(type_.is_a(Element::static_type()));
+        let name = name.to_owned();
+        self.feature(move |plugin| Element::register(Some(plugin), &name, rank, type_))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers a typefind function with the plugin.
+    pub fn typefind<F>(
+        self,
+        name: &str,
+        rank: Rank,
+        extensions: Option<&str>,
+        possible_caps: Option<&Caps>,
+        func: F,
+    ) -> Self
+    where
+        F: Fn(&mut TypeFind) + Send + Sync + 'static,
+    {
+        let name = name.to_owned();
+        let extensions = extensions.map(ToOwned::to_owned);
+        let possible_caps = possible_caps.cloned();
+        self.feature(move |plugin| {
+            TypeFind::register(
+                Some(plugin),
+                &name,
+                rank,
+                extensions.as_deref(),
+                possible_caps.as_ref(),
+                func,
+            )
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers a device provider factory for `type_` with the plugin.
+    pub fn device_provider(self, name: &str, rank: Rank, type_: glib::Type) -> Self {
+        assert!(type_.is_a(DeviceProvider::static_type()));
+        let name = name.to_owned();
+        self.feature(move |plugin| DeviceProvider::register(Some(plugin), &name, rank, type_))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers a tracer factory for `type_` with the plugin.
+    pub fn tracer(self, name: &str, type_: glib::Type) -> Self {
+        assert!(type_.is_a(Tracer::static_type()));
+        let name = name.to_owned();
+        self.feature(move |plugin| Tracer::register(Some(plugin), &name, type_))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Runs `func` with the plugin during registration.
+    ///
+    /// This can be used for registering any other features or for initializing the plugin in
+    /// other ways. Functions and features are run in the order they were added to the builder.
+    pub fn feature<F>(mut self, func: F) -> Self
+    where
+        F: FnOnce(&Plugin) -> Result<(), glib::BoolError> + 'static,
+    {
+        self.features.push(Box::new(func));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers the plugin and all its features with the default registry.
+    ///
+    /// Registration fails if any of the features fails to register.
+    #[doc(alias = "gst_plugin_register_static_full")]
+    pub fn register(self) -> Result<Plugin, glib::BoolError> {
+        struct InitData {
+            features: Vec<FeatureRegisterFn>,
+            plugin: Option<Plugin>,
+            error: Option<glib::BoolError>,
+        }
+
+        unsafe extern "C" fn plugin_init_trampoline(
+            plugin: *mut ffi::GstPlugin,
+            user_data: glib::ffi::gpointer,
+        ) -> glib::ffi::gboolean {
+            unsafe {
+                let data = &mut *(user_data as *mut InitData);
+                let plugin = from_glib_borrow::<_, Plugin>(plugin);
+
+                let features = std::mem::take(&mut data.features);
+                let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    features.into_iter().try_for_each(|func| func(&*plugin))
+                }));
+
+                match res {
+                    Ok(Ok(())) => {
+                        data.plugin = Some((*plugin).clone());
+                        glib::ffi::GTRUE
+                    }
+                    Ok(Err(err)) => {
+                        crate::error!(
+                            crate::CAT_PLUGIN_LOADING,
+                            "Failed to register plugin: {}",
+                            err
+                        );
+                        data.error = Some(err);
+                        glib::ffi::GFALSE
+                    }
+                    Err(_) => {
+                        crate::error!(
+                            crate::CAT_PLUGIN_LOADING,
+                            "Failed to initialize plugin due to panic"
+                        );
+                        data.error = Some(glib::bool_error!(
+                            "Failed to initialize plugin due to panic"
+                        ));
+                        glib::ffi::GFALSE
+                    }
+                }
+            }
+        }
+
+        let mut data = InitData {
+            features: self.features,
+            plugin: None,
+            error: None,
+        };
+
+        unsafe {
+            let res = glib::result_from_gboolean!(
+                ffi::gst_plugin_register_static_full(
+                    crate::subclass::MAJOR_VERSION,
+                    crate::subclass::MINOR_VERSION,
+                    self.name.to_glib_none().0,
+                    self.description.to_glib_none().0,
+                    Some(plugin_init_trampoline),
+                    self.version.to_glib_none().0,
+                    self.license.to_glib_none().0,
+                    self.source.to_glib_none().0,
+                    self.package.to_glib_none().0,
+                    self.origin.to_glib_none().0,
+                    &mut data as *mut InitData as glib::ffi::gpointer,
+                ),
+                "Failed to register the plugin"
+            );
+
+            if let Some(err) = data.error {
+                return Err(err);
+            }
+            res?;
+
+            data.plugin
+                .ok_or_else(|| glib::bool_error!("Failed to register the plugin"))
+        }
+    }
+}
+
+impl std::fmt::Debug for StaticPluginBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("StaticPluginBuilder")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("version", &self.version)
+            .field("license", &self.license)
+            .field("source", &self.source)
+            .field("package", &self.package)
+            .field("origin", &self.origin)
+            .field("features", &self.features.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_builder() {
+        crate::init().unwrap();
+
+        let plugin = Plugin::static_builder("rsstaticplugintest", "Static plugin test")
+            .version("1.2.3")
+            .license("MIT/X11")
+            .element("rsstaticbin", Rank::NONE, crate::Bin::static_type())
+            .typefind(
+                "rsstatictypefind",
+                Rank::NONE,
+                Some("rsst"),
+                None,
+                |_typefind| {},
+            )
+            .register()
+            .unwrap();
+
+        assert_eq!(plugin.plugin_name(), "rsstaticplugintest");
+        assert_eq!(plugin.version(), "1.2.3");
+        assert_eq!(plugin.license(), "MIT/X11");
+
+        let registry = crate::Registry::get();
+        let mut features = registry
+            .features_by_plugin("rsstaticplugintest")
+            .into_iter()
+            .map(|f| f.name().to_string())
+            .collect::<Vec<_>>();
+        features.sort();
+        assert_eq!(features, ["rsstaticbin", "rsstatictypefind"]);
+
+        let element = crate::ElementFactory::make("rsstaticbin").build().unwrap();
+        assert!(element.is::<crate::Bin>());
+
+        let res = Plugin::static_builder("rsstaticpluginfailtest", "Static plugin test")
+            .feature(|_plugin| Err(glib::bool_error!("Failure")))
+            .register();
+        assert!(res.is_err());
+    }
 }
@@ -0,0 +1,388 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use glib::{gobject_ffi, translate::*};
+use thiserror::Error;
+
+use crate::{Structure, StructureRef, prelude::*};
+
+// rustdoc-stripper-ignore-next
+/// A set of property values that can be stored and applied to objects.
+///
+/// Unlike [`Preset`](crate::Preset), which is implemented by elements and stores named presets in
+/// the user's data directory, this is a plain in-memory map from property names to their values,
+/// serialized like in `gst-launch-1.0` pipeline descriptions. It can be exported from any object,
+/// validated against and applied to objects with compatible properties, compared with another
+/// preset, and saved to and loaded from files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PropertyPreset {
+    properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PropertyPresetError {
+    #[error("Object has no property '{0}'")]
+    UnknownProperty(String),
+    #[error("Property '{0}' is not writable")]
+    NotWritable(String),
+    #[error("Invalid value '{value}' for property '{name}'")]
+    InvalidValue { name: String, value: String },
+    #[error("Failed to parse preset: {0}")]
+    Parse(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+// rustdoc-stripper-ignore-next
+/// A property whose value differs between two [`PropertyPreset`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyPresetChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+// Properties that can be set after construction and are not only about the object hierarchy.
+fn is_preset_property(pspec: &glib::ParamSpec) -> bool {
+    crate::utils::is_configuration_property(pspec)
+        && !pspec.flags().contains(glib::ParamFlags::CONSTRUCT_ONLY)
+}
+
+fn deserialize_value(
+    pspec: &glib::ParamSpec,
+    value: &str,
+) -> Result<glib::Value, PropertyPresetError> {
+    #[cfg(feature = "v1_20")]
+    let res = glib::Value::deserialize_with_pspec(value, pspec);
+    #[cfg(not(feature = "v1_20"))]
+    let res = glib::Value::deserialize(value, pspec.value_type());
+
+    let invalid_value = || PropertyPresetError::InvalidValue {
+        name: pspec.name().to_owned(),
+        value: value.to_owned(),
+    };
+
+    let mut value = res.map_err(|_| invalid_value())?;
+    // Returns true if the value had to be modified to fit into the range of the property
+    let modified: bool = unsafe {
+        from_glib(gobject_ffi::g_param_value_validate(
+            pspec.to_glib_none().0,
+            value.to_glib_none_mut().0,
+        ))
+    };
+    if modified {
+        return Err(invalid_value());
+    }
+
+    Ok(value)
+}
+
+impl PropertyPreset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Exports the current values of all writable properties of `object`.
+    ///
+    /// Construct-only and deprecated properties, the properties of [`Object`](crate::Object) and
+    /// properties whose values can't be serialized are skipped.
+    pub fn from_object(object: &impl IsA<glib::Object>) -> Self {
+        let object = object.upcast_ref::<glib::Object>();
+        let properties = object
+            .list_properties()
+            .iter()
+            .filter(|pspec| is_preset_property(pspec))
+            .filter_map(|pspec| {
+                let value = object.property_value(pspec.name()).serialize().ok()?;
+                Some((pspec.name().to_owned(), value.into()))
+            })
+            .collect();
+
+        PropertyPreset { properties }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(String::as_str)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the serialized `value` of the property `name`.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.properties.insert(name.to_owned(), value.to_owned());
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes `value` and sets it for the property `name`.
+    pub fn set_value(
+        &mut self,
+        name: &str,
+        value: impl Into<glib::Value>,
+    ) -> Result<(), glib::BoolError> {
+        let value = value.into().serialize()?;
+        self.properties.insert(name.to_owned(), value.into());
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.properties.remove(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks that `object` has all properties of the preset, that they are writable after
+    /// construction and that the values are valid for them.
+    ///
+    /// Returns the deserialized values.
+    pub fn validate(
+        &self,
+        object: &impl IsA<glib::Object>,
+    ) -> Result<Vec<(glib::ParamSpec, glib::Value)>, PropertyPresetError> {
+        let object = object.upcast_ref::<glib::Object>();
+
+        self.properties
+            .iter()
+            .map(|(name, value)| {
+                let pspec = object
+                    .find_property(name)
+                    .ok_or_else(|| PropertyPresetError::UnknownProperty(name.clone()))?;
+                if !is_preset_property(&pspec) {
+                    return Err(PropertyPresetError::NotWritable(name.clone()));
+                }
+
+                let value = deserialize_value(&pspec, value)?;
+                Ok((pspec, value))
+            })
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Applies the preset to `object`.
+    ///
+    /// All values are validated before any property is set, so either all or none of the
+    /// properties are changed. Property change notifications are only emitted once all
+    /// properties are set.
+    pub fn apply(&self, object: &impl IsA<glib::Object>) -> Result<(), PropertyPresetError> {
+        let values = self.validate(object)?;
+
+        let object = object.upcast_ref::<glib::Object>();
+        let _guard = object.freeze_notify();
+        for (pspec, value) in values {
+            object.set_property_from_value(pspec.name(), &value);
+        }
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all properties whose values differ between `self` and `other`, sorted by name.
+    pub fn diff(&self, other: &PropertyPreset) -> Vec<PropertyPresetChange> {
+        let mut names = self
+            .properties
+            .keys()
+            .chain(other.properties.keys())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let old = self.properties.get(name);
+                let new = other.properties.get(name);
+                (old != new).then(|| PropertyPresetChange {
+                    name: name.clone(),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                })
+            })
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts the preset into a [`Structure`] named `name` with one string field per property.
+    pub fn to_structure(&self, name: &str) -> Structure {
+        let mut s = Structure::new_empty(name);
+        for (name, value) in &self.properties {
+            s.set(name.as_str(), value.as_str());
+        }
+        s
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a preset from a [`Structure`] created by [`Self::to_structure()`].
+    pub fn from_structure(s: &StructureRef) -> Result<Self, PropertyPresetError> {
+        let properties = s
+            .iter()
+            .map(|(name, value)| {
+                let value = value.get::<String>().map_err(|_| {
+                    PropertyPresetError::Parse(format!("Field '{name}' is not a string"))
+                })?;
+                Ok((name.to_string(), value))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(PropertyPreset { properties })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Saves the preset to a file in the [`Structure`] string format.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PropertyPresetError> {
+        fs::write(path, format!("{}\n", self.to_structure("preset")))?;
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Loads a preset from a file written by [`Self::save()`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PropertyPresetError> {
+        let contents = fs::read_to_string(path)?;
+        let s = Structure::from_str(contents.trim())
+            .map_err(|err| PropertyPresetError::Parse(err.to_string()))?;
+        Self::from_structure(&s)
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for PropertyPreset {
+    fn from_iter<T: IntoIterator<Item = (&'a str, &'a str)>>(iter: T) -> Self {
+        PropertyPreset {
+            properties: iter
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_object_and_apply() {
+        crate::init().unwrap();
+
+        let src = crate::ElementFactory::make("fakesrc")
+            .property("num-buffers", 10i32)
+            .property_from_str("sizetype", "fixed")
+            .build()
+            .unwrap();
+
+        let preset = PropertyPreset::from_object(&src);
+        assert_eq!(preset.get("num-buffers"), Some("10"));
+        assert_eq!(preset.get("sizetype"), Some("fixed"));
+        assert!(preset.get("name").is_none());
+        assert!(preset.get("parent").is_none());
+
+        let other = crate::ElementFactory::make("fakesrc").build().unwrap();
+        assert_ne!(PropertyPreset::from_object(&other), preset);
+        preset.apply(&other).unwrap();
+        assert_eq!(other.property::<i32>("num-buffers"), 10);
+        assert_eq!(PropertyPreset::from_object(&other), preset);
+    }
+
+    #[test]
+    fn test_validate() {
+        crate::init().unwrap();
+
+        let src = crate::ElementFactory::make("fakesrc").build().unwrap();
+
+        let mut preset = PropertyPreset::new();
+        preset.set_value("num-buffers", 5i32).unwrap();
+        preset.set("is-live", "true");
+        assert_eq!(preset.validate(&src).unwrap().len(), 2);
+
+        preset.set("num-buffers", "abc");
+        assert!(matches!(
+            preset.apply(&src),
+            Err(PropertyPresetError::InvalidValue { .. })
+        ));
+        // Nothing was applied because of the invalid value
+        assert!(!src.property::<bool>("is-live"));
+
+        preset.set("num-buffers", "-5");
+        assert!(matches!(
+            preset.validate(&src),
+            Err(PropertyPresetError::InvalidValue { .. })
+        ));
+
+        let mut preset = PropertyPreset::new();
+        preset.set("does-not-exist", "1");
+        assert!(matches!(
+            preset.validate(&src),
+            Err(PropertyPresetError::UnknownProperty(_))
+        ));
+
+        let mut preset = PropertyPreset::new();
+        preset.set("name", "foo");
+        assert!(matches!(
+            preset.validate(&src),
+            Err(PropertyPresetError::NotWritable(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = PropertyPreset::from_iter([("bitrate", "1000"), ("tune", "zerolatency")]);
+        let b = PropertyPreset::from_iter([("bitrate", "2000"), ("key-int-max", "60")]);
+
+        assert!(a.diff(&a).is_empty());
+        assert_eq!(
+            a.diff(&b),
+            [
+                PropertyPresetChange {
+                    name: String::from("bitrate"),
+                    old: Some(String::from("1000")),
+                    new: Some(String::from("2000")),
+                },
+                PropertyPresetChange {
+                    name: String::from("key-int-max"),
+                    old: None,
+                    new: Some(String::from("60")),
+                },
+                PropertyPresetChange {
+                    name: String::from("tune"),
+                    old: Some(String::from("zerolatency")),
+                    new: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_load() {
+        crate::init().unwrap();
+
+        let preset =
+            PropertyPreset::from_iter([("num-buffers", "10"), ("format", "\"quoted, string\"")]);
+
+        let path = std::env::temp_dir().join(format!(
+            "gstreamer-rs-property-preset-{}.txt",
+            std::process::id()
+        ));
+        preset.save(&path).unwrap();
+        let loaded = PropertyPreset::load(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.unwrap(), preset);
+    }
+}
@@ -0,0 +1,491 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{prelude::*, translate::*};
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    ser::{self, Serialize, Serializer},
+};
+
+use crate::{
+    BufferingMode, ClockTime, Format, GenericFormattedValue, PadMode, Query, QueryRef, QueryType,
+    SchedulingFlags, Structure, ffi, query, query::QueryView,
+};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum QueryViewSerde {
+    Position {
+        format: Format,
+        result: GenericFormattedValue,
+    },
+    Duration {
+        format: Format,
+        result: GenericFormattedValue,
+    },
+    Latency {
+        live: bool,
+        min: ClockTime,
+        max: Option<ClockTime>,
+    },
+    Seeking {
+        format: Format,
+        seekable: bool,
+        start: GenericFormattedValue,
+        end: GenericFormattedValue,
+    },
+    Segment {
+        format: Format,
+        rate: f64,
+        start: GenericFormattedValue,
+        stop: GenericFormattedValue,
+    },
+    Convert {
+        src: GenericFormattedValue,
+        dest: GenericFormattedValue,
+    },
+    Formats {
+        formats: Vec<Format>,
+    },
+    Buffering {
+        format: Format,
+        busy: bool,
+        percent: i32,
+        start: GenericFormattedValue,
+        stop: GenericFormattedValue,
+        estimated_total: i64,
+        mode: BufferingMode,
+        avg_in: i32,
+        avg_out: i32,
+        buffering_left: i64,
+        ranges: Vec<(GenericFormattedValue, GenericFormattedValue)>,
+    },
+    Custom {
+        structure: Structure,
+    },
+    Uri {
+        uri: Option<String>,
+        redirection: Option<String>,
+        permanent: bool,
+    },
+    // The allocation params, pools and metas can't be serialized and are dropped.
+    Allocation {
+        caps: Option<crate::Caps>,
+        need_pool: bool,
+    },
+    Scheduling {
+        flags: SchedulingFlags,
+        minsize: i32,
+        maxsize: i32,
+        align: i32,
+        modes: Vec<PadMode>,
+    },
+    AcceptCaps {
+        caps: crate::Caps,
+        result: bool,
+    },
+    Caps {
+        filter: Option<crate::Caps>,
+        result: Option<crate::Caps>,
+    },
+    Drain,
+    Context {
+        context_type: String,
+    },
+    #[cfg(feature = "v1_16")]
+    Bitrate {
+        bitrate: u32,
+    },
+    #[cfg(feature = "v1_22")]
+    Selectable {
+        selectable: bool,
+    },
+    Other {
+        query_type: String,
+        structure: Option<Structure>,
+    },
+}
+
+impl Serialize for QueryRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let view = match self.view() {
+            QueryView::Position(q) => QueryViewSerde::Position {
+                format: q.format(),
+                result: q.result(),
+            },
+            QueryView::Duration(q) => QueryViewSerde::Duration {
+                format: q.format(),
+                result: q.result(),
+            },
+            QueryView::Latency(q) => {
+                let (live, min, max) = q.result();
+                QueryViewSerde::Latency { live, min, max }
+            }
+            QueryView::Seeking(q) => {
+                let (seekable, start, end) = q.result();
+                QueryViewSerde::Seeking {
+                    format: q.format(),
+                    seekable,
+                    start,
+                    end,
+                }
+            }
+            QueryView::Segment(q) => {
+                let (rate, start, stop) = q.result();
+                QueryViewSerde::Segment {
+                    format: q.format(),
+                    rate,
+                    start,
+                    stop,
+                }
+            }
+            QueryView::Convert(q) => {
+                let (src, dest) = q.result();
+                QueryViewSerde::Convert { src, dest }
+            }
+            QueryView::Formats(q) => QueryViewSerde::Formats {
+                formats: q.result().collect(),
+            },
+            QueryView::Buffering(q) => {
+                let (busy, percent) = q.percent();
+                let (start, stop, estimated_total) = q.range();
+                let (mode, avg_in, avg_out, buffering_left) = q.stats();
+                QueryViewSerde::Buffering {
+                    format: q.format(),
+                    busy,
+                    percent,
+                    start,
+                    stop,
+                    estimated_total,
+                    mode,
+                    avg_in,
+                    avg_out,
+                    buffering_left,
+                    ranges: q.ranges().collect(),
+                }
+            }
+            QueryView::Custom(_) => QueryViewSerde::Custom {
+                structure: self.structure().map(|s| s.to_owned()).ok_or_else(|| {
+                    <S::Error as ser::Error>::custom("custom query without structure")
+                })?,
+            },
+            QueryView::Uri(q) => {
+                let (redirection, permanent) = q.redirection();
+                QueryViewSerde::Uri {
+                    uri: q.uri().map(Into::into),
+                    redirection: redirection.map(Into::into),
+                    permanent,
+                }
+            }
+            QueryView::Allocation(q) => {
+                let (caps, need_pool) = q.get_owned();
+                QueryViewSerde::Allocation { caps, need_pool }
+            }
+            QueryView::Scheduling(q) => {
+                let (flags, minsize, maxsize, align) = q.result();
+                QueryViewSerde::Scheduling {
+                    flags,
+                    minsize,
+                    maxsize,
+                    align,
+                    modes: q.scheduling_modes().collect(),
+                }
+            }
+            QueryView::AcceptCaps(q) => QueryViewSerde::AcceptCaps {
+                caps: q.caps_owned(),
+                result: q.result(),
+            },
+            QueryView::Caps(q) => QueryViewSerde::Caps {
+                filter: q.filter_owned(),
+                result: q.result_owned(),
+            },
+            QueryView::Drain(_) => QueryViewSerde::Drain,
+            QueryView::Context(q) => QueryViewSerde::Context {
+                context_type: q.context_type().to_owned(),
+            },
+            #[cfg(feature = "v1_16")]
+            QueryView::Bitrate(q) => QueryViewSerde::Bitrate {
+                bitrate: q.bitrate(),
+            },
+            #[cfg(feature = "v1_22")]
+            QueryView::Selectable(q) => QueryViewSerde::Selectable {
+                selectable: q.selectable(),
+            },
+            _ => QueryViewSerde::Other {
+                query_type: self.type_().name().to_string(),
+                structure: self.structure().map(|s| s.to_owned()),
+            },
+        };
+
+        view.serialize(serializer)
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+fn check_format<E: de::Error>(format: Format, value: GenericFormattedValue) -> Result<(), E> {
+    if format != value.format() {
+        return Err(E::custom(format!(
+            "mismatched formats {:?} and {:?}",
+            format,
+            value.format()
+        )));
+    }
+
+    Ok(())
+}
+
+fn query_type_from_nick<E: de::Error>(nick: &str) -> Result<QueryType, E> {
+    glib::EnumClass::with_type(QueryType::static_type())
+        .and_then(|class| class.value_by_nick(nick).map(|v| v.value()))
+        .map(|v| unsafe { from_glib(v) })
+        .ok_or_else(|| E::custom(format!("unknown query type {nick}")))
+}
+
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+
+        let query = match QueryViewSerde::deserialize(deserializer)? {
+            QueryViewSerde::Position { format, result } => {
+                check_format::<D::Error>(format, result)?;
+                let mut q = query::Position::new(format);
+                q.set(result);
+                q.into()
+            }
+            QueryViewSerde::Duration { format, result } => {
+                check_format::<D::Error>(format, result)?;
+                let mut q = query::Duration::new(format);
+                q.set(result);
+                q.into()
+            }
+            QueryViewSerde::Latency { live, min, max } => {
+                let mut q = query::Latency::new();
+                q.set(live, min, max);
+                q.into()
+            }
+            QueryViewSerde::Seeking {
+                format,
+                seekable,
+                start,
+                end,
+            } => {
+                check_format::<D::Error>(format, start)?;
+                check_format::<D::Error>(format, end)?;
+                let mut q = query::Seeking::new(format);
+                q.set(seekable, start, end);
+                q.into()
+            }
+            QueryViewSerde::Segment {
+                format,
+                rate,
+                start,
+                stop,
+            } => {
+                check_format::<D::Error>(format, start)?;
+                check_format::<D::Error>(format, stop)?;
+                let mut q = query::Segment::new(format);
+                q.set(rate, start, stop);
+                q.into()
+            }
+            QueryViewSerde::Convert { src, dest } => {
+                let mut q = query::Convert::new(src, dest.format());
+                q.set(src, dest);
+                q.into()
+            }
+            QueryViewSerde::Formats { formats } => {
+                let mut q = query::Formats::new();
+                q.set(formats);
+                q.into()
+            }
+            QueryViewSerde::Buffering {
+                format,
+                busy,
+                percent,
+                start,
+                stop,
+                estimated_total,
+                mode,
+                avg_in,
+                avg_out,
+                buffering_left,
+                ranges,
+            } => {
+                check_format::<D::Error>(format, start)?;
+                check_format::<D::Error>(format, stop)?;
+                for (range_start, range_stop) in &ranges {
+                    check_format::<D::Error>(format, *range_start)?;
+                    check_format::<D::Error>(format, *range_stop)?;
+                }
+
+                let mut q = query::Buffering::new(format);
+                q.set_percent(busy, percent);
+                q.set_range(start, stop, estimated_total);
+                q.set_stats(mode, avg_in, avg_out, buffering_left);
+                q.add_buffering_ranges(ranges);
+                q.into()
+            }
+            QueryViewSerde::Custom { structure } => query::Custom::new(structure).into(),
+            QueryViewSerde::Uri {
+                uri,
+                redirection,
+                permanent,
+            } => {
+                let mut q = query::Uri::new();
+                q.set_uri(uri.as_deref());
+                if redirection.is_some() || permanent {
+                    q.set_redirection(redirection.as_deref(), permanent);
+                }
+                q.into()
+            }
+            QueryViewSerde::Allocation { caps, need_pool } => {
+                query::Allocation::new(caps.as_ref(), need_pool).into()
+            }
+            QueryViewSerde::Scheduling {
+                flags,
+                minsize,
+                maxsize,
+                align,
+                modes,
+            } => {
+                let mut q = query::Scheduling::new();
+                q.set(flags, minsize, maxsize, align);
+                q.add_scheduling_modes(modes);
+                q.into()
+            }
+            QueryViewSerde::AcceptCaps { caps, result } => {
+                let mut q = query::AcceptCaps::new(&caps);
+                q.set_result(result);
+                q.into()
+            }
+            QueryViewSerde::Caps { filter, result } => {
+                let mut q = query::Caps::new(filter.as_ref());
+                q.set_result(result.as_ref());
+                q.into()
+            }
+            QueryViewSerde::Drain => query::Drain::new().into(),
+            QueryViewSerde::Context { context_type } => query::Context::new(&context_type).into(),
+            #[cfg(feature = "v1_16")]
+            QueryViewSerde::Bitrate { bitrate } => {
+                let mut q = query::Bitrate::new();
+                q.set_bitrate(bitrate);
+                q.into()
+            }
+            #[cfg(feature = "v1_22")]
+            QueryViewSerde::Selectable { selectable } => {
+                let mut q = query::Selectable::new();
+                q.set_selectable(selectable);
+                q.into()
+            }
+            QueryViewSerde::Other {
+                query_type,
+                structure,
+            } => {
+                let query_type = query_type_from_nick::<D::Error>(&query_type)?;
+                unsafe {
+                    Option::<Query>::from_glib_full(ffi::gst_query_new_custom(
+                        query_type.into_glib(),
+                        structure
+                            .map(|s| s.into_glib_ptr())
+                            .unwrap_or(ptr::null_mut()),
+                    ))
+                    .ok_or_else(|| {
+                        <D::Error as de::Error>::custom(format!(
+                            "can't create {query_type:?} query"
+                        ))
+                    })?
+                }
+            }
+        };
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ClockTime, Format, GenericFormattedValue, PadMode, Query, SchedulingFlags,
+        query::{self, QueryView},
+    };
+
+    #[test]
+    fn test_serialize() {
+        crate::init().unwrap();
+
+        let mut q = query::Position::new(Format::Time);
+        q.set(ClockTime::from_nseconds(42));
+
+        let pretty_config = ron::ser::PrettyConfig::new().new_line("".to_string());
+
+        let res = ron::ser::to_string_pretty(&q.query(), pretty_config);
+        assert_eq!(
+            Ok(concat!(
+                "Position(",
+                "    format: Time,",
+                "    result: Time(Some(42)),",
+                ")"
+            )
+            .to_owned()),
+            res,
+        );
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        crate::init().unwrap();
+
+        let mut q = query::Position::new(Format::Time);
+        q.set(ClockTime::from_seconds(1));
+        let res = ron::ser::to_string(&q.query()).unwrap();
+        let q_de: Query = ron::de::from_str(&res).unwrap();
+        match q_de.view() {
+            QueryView::Position(p) => {
+                assert_eq!(p.format(), Format::Time);
+                assert_eq!(
+                    p.result(),
+                    GenericFormattedValue::from(ClockTime::from_seconds(1))
+                );
+            }
+            _ => panic!("unexpected query {q_de:?}"),
+        }
+
+        let mut q = query::Scheduling::new();
+        q.set(SchedulingFlags::SEEKABLE, 1, -1, 0);
+        q.add_scheduling_modes([PadMode::Push, PadMode::Pull]);
+        let res = ron::ser::to_string(&q.query()).unwrap();
+        let q_de: Query = ron::de::from_str(&res).unwrap();
+        match q_de.view() {
+            QueryView::Scheduling(s) => {
+                assert_eq!(s.result(), (SchedulingFlags::SEEKABLE, 1, -1, 0));
+                assert_eq!(
+                    s.scheduling_modes().collect::<Vec<_>>(),
+                    [PadMode::Push, PadMode::Pull]
+                );
+            }
+            _ => panic!("unexpected query {q_de:?}"),
+        }
+
+        let mut q = query::Uri::new();
+        q.set_uri("file:///foo");
+        q.set_redirection("file:///bar", true);
+        let res = ron::ser::to_string(&q.query()).unwrap();
+        let q_de: Query = ron::de::from_str(&res).unwrap();
+        match q_de.view() {
+            QueryView::Uri(u) => {
+                assert_eq!(u.uri().as_deref(), Some("file:///foo"));
+                assert_eq!(u.uri_redirection().as_deref(), Some("file:///bar"));
+                assert!(u.uri_redirection_permanent());
+            }
+            _ => panic!("unexpected query {q_de:?}"),
+        }
+
+        // Mismatching formats are rejected instead of panicking
+        let res = ron::de::from_str::<Query>("Position(format: Bytes, result: Time(Some(1)))");
+        assert!(res.is_err());
+    }
+}
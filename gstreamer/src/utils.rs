@@ -182,3 +182,62 @@ macro_rules! define_fixed_size_iter(
 );
 
 pub(crate) use define_fixed_size_iter;
+
+// Properties that make up the configuration of an object: readable and writable, not deprecated
+// and not inherited from `GstObject`, i.e. not `name` or `parent`.
+pub(crate) fn is_configuration_property(pspec: &glib::ParamSpec) -> bool {
+    let flags = pspec.flags();
+    flags.contains(glib::ParamFlags::READWRITE)
+        && !flags.contains(glib::ParamFlags::DEPRECATED)
+        && pspec.owner_type() != crate::Object::static_type()
+}
+
+// Serialized values of all configuration properties of `object` that differ from the default
+// value of their `ParamSpec`.
+pub(crate) fn non_default_properties<C: FromIterator<(String, String)>>(
+    object: &impl IsA<glib::Object>,
+) -> C {
+    use crate::value::GstValueExt;
+
+    let object = object.upcast_ref::<glib::Object>();
+    object
+        .list_properties()
+        .iter()
+        .filter(|pspec| is_configuration_property(pspec))
+        .filter_map(|pspec| {
+            let value = object.property_value(pspec.name()).serialize().ok()?;
+            if pspec
+                .default_value()
+                .serialize()
+                .is_ok_and(|default| default == value)
+            {
+                return None;
+            }
+
+            Some((pspec.name().to_owned(), value.into()))
+        })
+        .collect()
+}
+
+// Appends `s` as JSON string literal, escaped the same way as by `serde_json`.
+pub(crate) fn write_json_string(out: &mut String, s: &str) {
+    use std::fmt::Write;
+
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
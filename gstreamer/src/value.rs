@@ -493,6 +493,15 @@ impl From<(i64, i64, i64)> for IntRange<i64> {
     }
 }
 
+impl<T: IntRangeType> From<ops::RangeInclusive<T>> for IntRange<T> {
+    #[inline]
+    fn from(range: ops::RangeInclusive<T>) -> Self {
+        skip_assert_initialized!();
+        let (min, max) = range.into_inner();
+        Self::new(min, max)
+    }
+}
+
 impl glib::types::StaticType for IntRange<i32> {
     #[inline]
     fn static_type() -> glib::types::Type {
@@ -645,6 +654,16 @@ impl From<(Fraction, Fraction)> for FractionRange {
     }
 }
 
+impl From<ops::RangeInclusive<Fraction>> for FractionRange {
+    #[inline]
+    fn from(range: ops::RangeInclusive<Fraction>) -> Self {
+        skip_assert_initialized!();
+
+        let (min, max) = range.into_inner();
+        Self::new(min, max)
+    }
+}
+
 impl glib::types::StaticType for FractionRange {
     #[inline]
     fn static_type() -> glib::types::Type {
@@ -914,6 +933,13 @@ impl AsRef<[glib::SendValue]> for Array {
     }
 }
 
+impl<T: Into<glib::Value> + Send, const N: usize> From<[T; N]> for Array {
+    fn from(values: [T; N]) -> Self {
+        skip_assert_initialized!();
+        Self::new(values)
+    }
+}
+
 impl std::iter::FromIterator<glib::SendValue> for Array {
     fn from_iter<T: IntoIterator<Item = glib::SendValue>>(iter: T) -> Self {
         skip_assert_initialized!();
@@ -1145,6 +1171,13 @@ impl AsRef<[glib::SendValue]> for List {
     }
 }
 
+impl<T: Into<glib::Value> + Send, const N: usize> From<[T; N]> for List {
+    fn from(values: [T; N]) -> Self {
+        skip_assert_initialized!();
+        Self::new(values)
+    }
+}
+
 impl std::iter::FromIterator<glib::SendValue> for List {
     fn from_iter<T: IntoIterator<Item = glib::SendValue>>(iter: T) -> Self {
         skip_assert_initialized!();
@@ -2196,6 +2229,31 @@ mod tests {
         assert_eq!(f3, crate::Fraction::new(2, 27));
     }
 
+    #[test]
+    fn test_from_conversions() {
+        crate::init().unwrap();
+
+        assert_eq!(IntRange::from(1..=4096), IntRange::new(1, 4096));
+        assert_eq!(IntRange::from(-5i64..=5), IntRange::<i64>::new(-5, 5));
+
+        let range = FractionRange::from(Fraction::new(0, 1)..=Fraction::new(30, 1));
+        assert_eq!(range.min(), Fraction::new(0, 1));
+        assert_eq!(range.max(), Fraction::new(30, 1));
+
+        let list = List::from(["I420", "NV12"]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[1].get::<&str>().unwrap(), "NV12");
+
+        let array = Array::from([1i32, 2, 3]);
+        assert_eq!(
+            array
+                .iter()
+                .map(|v| v.get::<i32>().unwrap())
+                .collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
     #[test]
     fn test_int_range_constructor() {
         crate::init().unwrap();